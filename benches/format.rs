@@ -0,0 +1,125 @@
+//! Parse and format throughput.
+//!
+//! Run with `cargo bench`. With `--features bench_hooks` the allocations
+//! each path makes per value are printed before the timings, to compare
+//! across upgrades.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ssfmt::{FormatOptions, NumberFormat};
+use std::hint::black_box;
+
+#[cfg(feature = "bench_hooks")]
+#[global_allocator]
+static ALLOC: ssfmt::bench_hooks::CountingAllocator = ssfmt::bench_hooks::CountingAllocator::new();
+
+const CODES: &[(&str, &str)] = &[
+    ("general", "General"),
+    ("integer", "0"),
+    (
+        "accounting",
+        "_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)",
+    ),
+    ("percent", "0.00%"),
+    ("scientific", "0.00E+00"),
+    ("date", "yyyy-mm-dd"),
+    ("datetime", "m/d/yyyy h:mm:ss AM/PM"),
+    ("elapsed", "[h]:mm:ss.000"),
+    ("fraction", "# ?/?"),
+    ("fraction_3", "# ???/???"),
+];
+
+/// Values spread across magnitudes and signs, valid as dates.
+fn values() -> Vec<f64> {
+    (1..=1000)
+        .map(|n| n as f64 * 47.123_456_7 * if n % 5 == 0 { -1.0 } else { 1.0 })
+        .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, code) in CODES {
+        group.bench_with_input(BenchmarkId::from_parameter(name), code, |b, code| {
+            b.iter(|| NumberFormat::parse(black_box(code)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_format(c: &mut Criterion) {
+    let opts = FormatOptions::default();
+    let values = values();
+    let mut group = c.benchmark_group("format");
+    group.throughput(Throughput::Elements(values.len() as u64));
+    for (name, code) in CODES {
+        let fmt = NumberFormat::parse(code).unwrap();
+        report_allocations(name, &fmt, &values, &opts);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &fmt, |b, fmt| {
+            b.iter(|| {
+                for &value in &values {
+                    black_box(fmt.format(black_box(value), &opts));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_cached(c: &mut Criterion) {
+    let opts = FormatOptions::default();
+    let values = values();
+    let mut group = c.benchmark_group("cached_format");
+    group.throughput(Throughput::Elements(values.len() as u64));
+    for (name, code) in CODES {
+        group.bench_with_input(BenchmarkId::from_parameter(name), code, |b, code| {
+            b.iter(|| {
+                for &value in &values {
+                    black_box(ssfmt::format(black_box(value), code, &opts).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_format_to(c: &mut Criterion) {
+    let opts = FormatOptions::default();
+    let values = values();
+    let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    let mut out = String::with_capacity(64 * values.len());
+    let mut group = c.benchmark_group("format_to");
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("number", |b| {
+        b.iter(|| {
+            out.clear();
+            for &value in &values {
+                fmt.format_to(black_box(value), &opts, &mut out).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+#[cfg(feature = "bench_hooks")]
+fn report_allocations(name: &str, fmt: &NumberFormat, values: &[f64], opts: &FormatOptions) {
+    let (_, counts) = ssfmt::bench_hooks::count_allocations(|| {
+        for &value in values {
+            black_box(fmt.format(value, opts));
+        }
+    });
+    println!(
+        "format/{name}: {:.2} allocations per value",
+        counts.allocations as f64 / values.len() as f64
+    );
+}
+
+#[cfg(not(feature = "bench_hooks"))]
+fn report_allocations(_: &str, _: &NumberFormat, _: &[f64], _: &FormatOptions) {}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_format,
+    bench_cached,
+    bench_format_to
+);
+criterion_main!(benches);
@@ -0,0 +1,268 @@
+//! Build script: embeds CLDR locale data when the `cldr` feature is enabled.
+//!
+//! The data is read from the JSON packages of the `cldr-json` repository,
+//! found in the directory named by `SSFMT_CLDR_DIR`:
+//!
+//! - `cldr-core/supplemental/{likelySubtags,currencyData,numberingSystems}.json`
+//! - `cldr-numbers-full/main/<tag>/{numbers,currencies}.json`
+//! - `cldr-dates-full/main/<tag>/ca-gregorian.json`
+//!
+//! Every locale with both number and date data becomes one entry of the
+//! generated `cldr_locales.rs`, sorted by lowercase tag.
+//!
+//! Without `SSFMT_CLDR_DIR` (as in `--all-features` builds) the small data
+//! set in `tests/fixtures/cldr` is used, or no locales at all if it is
+//! missing, with a build warning either way.
+
+fn main() {
+    #[cfg(feature = "cldr")]
+    cldr::generate();
+}
+
+#[cfg(feature = "cldr")]
+mod cldr {
+    use std::fmt::Write as _;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use serde_json::Value;
+
+    const MONTHS: [&str; 12] = ["1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12"];
+    const DAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+    /// One locale's data, ready to be written out as Rust.
+    struct Entry {
+        tag: String,
+        decimal: char,
+        group: char,
+        date_separator: char,
+        time_separator: char,
+        currency: String,
+        am: String,
+        pm: String,
+        months_short: Vec<String>,
+        months_full: Vec<String>,
+        days_short: Vec<String>,
+        days_full: Vec<String>,
+        native_zero: Option<char>,
+    }
+
+    /// The supplemental tables shared by all locales.
+    struct Supplemental {
+        likely_subtags: Value,
+        currency_data: Value,
+        numbering_systems: Value,
+    }
+
+    pub(crate) fn generate() {
+        println!("cargo:rerun-if-env-changed=SSFMT_CLDR_DIR");
+        let out = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR"));
+        let root = match std::env::var_os("SSFMT_CLDR_DIR") {
+            Some(root) => PathBuf::from(root),
+            None => {
+                let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cldr");
+                if !fixtures.is_dir() {
+                    println!(
+                        "cargo:warning=SSFMT_CLDR_DIR is not set; the `cldr` feature has no locales"
+                    );
+                    fs::write(out.join("cldr_locales.rs"), render(&[]))
+                        .expect("write cldr_locales.rs");
+                    return;
+                }
+                println!(
+                    "cargo:warning=SSFMT_CLDR_DIR is not set; using the test fixtures in {}",
+                    fixtures.display()
+                );
+                fixtures
+            }
+        };
+        println!("cargo:rerun-if-changed={}", root.display());
+
+        let supplemental_dir = root.join("cldr-core/supplemental");
+        let supplemental = Supplemental {
+            likely_subtags: read_json(&supplemental_dir.join("likelySubtags.json")),
+            currency_data: read_json(&supplemental_dir.join("currencyData.json")),
+            numbering_systems: read_json(&supplemental_dir.join("numberingSystems.json")),
+        };
+
+        let numbers_dir = root.join("cldr-numbers-full/main");
+        let dates_dir = root.join("cldr-dates-full/main");
+        let mut entries = Vec::new();
+        let dirs = fs::read_dir(&numbers_dir)
+            .unwrap_or_else(|err| panic!("reading {}: {err}", numbers_dir.display()));
+        for dir in dirs {
+            let tag = dir.expect("locale directory").file_name();
+            let tag = tag.to_string_lossy();
+            if let Some(entry) = read_locale(&tag, &numbers_dir, &dates_dir, &supplemental) {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by_key(|entry| entry.tag.to_ascii_lowercase());
+
+        fs::write(out.join("cldr_locales.rs"), render(&entries)).expect("write cldr_locales.rs");
+    }
+
+    fn read_json(path: &Path) -> Value {
+        let text =
+            fs::read_to_string(path).unwrap_or_else(|err| panic!("reading {}: {err}", path.display()));
+        serde_json::from_str(&text).unwrap_or_else(|err| panic!("parsing {}: {err}", path.display()))
+    }
+
+    /// Read one locale, or `None` if any of its data is missing.
+    fn read_locale(
+        tag: &str,
+        numbers_dir: &Path,
+        dates_dir: &Path,
+        supplemental: &Supplemental,
+    ) -> Option<Entry> {
+        let numbers_path = numbers_dir.join(tag).join("numbers.json");
+        let dates_path = dates_dir.join(tag).join("ca-gregorian.json");
+        if !numbers_path.exists() || !dates_path.exists() {
+            return None;
+        }
+        let numbers = read_json(&numbers_path);
+        let numbers = &numbers["main"][tag]["numbers"];
+        let dates = read_json(&dates_path);
+        let gregorian = &dates["main"][tag]["dates"]["calendars"]["gregorian"];
+
+        let system = numbers["defaultNumberingSystem"].as_str().unwrap_or("latn");
+        let symbols = Some(&numbers[format!("symbols-numberSystem-{system}")])
+            .filter(|symbols| symbols.is_object())
+            .unwrap_or(&numbers["symbols-numberSystem-latn"]);
+        let native_zero = (system != "latn")
+            .then(|| {
+                supplemental.numbering_systems["supplemental"]["numberingSystems"][system]
+                    ["_digits"]
+                    .as_str()
+            })
+            .flatten()
+            .and_then(|digits| digits.chars().next());
+
+        let names = |value: &Value, keys: &[&str]| -> Option<Vec<String>> {
+            keys.iter()
+                .map(|key| value[key].as_str().map(str::to_string))
+                .collect()
+        };
+        let months = &gregorian["months"]["format"];
+        let days = &gregorian["days"]["format"];
+        let periods = &gregorian["dayPeriods"]["format"]["abbreviated"];
+
+        Some(Entry {
+            tag: tag.to_string(),
+            decimal: single_char(&symbols["decimal"])?,
+            group: single_char(&symbols["group"])?,
+            date_separator: gregorian["dateFormats"]["short"]
+                .as_str()
+                .and_then(date_separator)
+                .unwrap_or('/'),
+            time_separator: single_char(&symbols["timeSeparator"]).unwrap_or(':'),
+            currency: currency_symbol(tag, numbers_dir, supplemental),
+            am: periods["am"].as_str()?.to_string(),
+            pm: periods["pm"].as_str()?.to_string(),
+            months_short: names(&months["abbreviated"], &MONTHS)?,
+            months_full: names(&months["wide"], &MONTHS)?,
+            days_short: names(&days["abbreviated"], &DAYS)?,
+            days_full: names(&days["wide"], &DAYS)?,
+            native_zero,
+        })
+    }
+
+    /// The separator, if it is a single character. Some locales use a
+    /// narrow no-break space plus a mark, which `Locale` cannot hold.
+    fn single_char(value: &Value) -> Option<char> {
+        let mut chars = value.as_str()?.chars();
+        let c = chars.next()?;
+        chars.next().is_none().then_some(c)
+    }
+
+    /// The character between the first two fields of a short date pattern
+    /// such as `d.M.yy`, past any direction marks, unless it is a space or
+    /// quoted text.
+    fn date_separator(pattern: &str) -> Option<char> {
+        let c = pattern
+            .chars()
+            .find(|c| !c.is_ascii_alphabetic() && !matches!(c, '\u{200e}' | '\u{200f}' | '\u{61c}'))?;
+        (!c.is_whitespace() && c != '\'').then_some(c)
+    }
+
+    /// The symbol of the current currency of the locale's region, falling
+    /// back to the ISO code, or the generic currency sign when the region
+    /// has no currency.
+    fn currency_symbol(tag: &str, numbers_dir: &Path, supplemental: &Supplemental) -> String {
+        let Some(code) = region(tag, supplemental).and_then(|r| current_currency(&r, supplemental))
+        else {
+            return "¤".to_string();
+        };
+        let path = numbers_dir.join(tag).join("currencies.json");
+        if path.exists() {
+            let currencies = read_json(&path);
+            let symbol = &currencies["main"][tag]["numbers"]["currencies"][code.as_str()]["symbol"];
+            if let Some(symbol) = symbol.as_str() {
+                return symbol.to_string();
+            }
+        }
+        code
+    }
+
+    /// The region subtag of `tag`, or of its likely subtags.
+    fn region(tag: &str, supplemental: &Supplemental) -> Option<String> {
+        let is_region = |s: &&str| {
+            (s.len() == 2 && s.chars().all(|c| c.is_ascii_uppercase()))
+                || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()))
+        };
+        if let Some(region) = tag.split('-').skip(1).find(is_region) {
+            return Some(region.to_string());
+        }
+        let likely = supplemental.likely_subtags["supplemental"]["likelySubtags"][tag].as_str()?;
+        likely.split('-').skip(1).find(is_region).map(str::to_string)
+    }
+
+    /// The ISO code of the currency a region uses today.
+    fn current_currency(region: &str, supplemental: &Supplemental) -> Option<String> {
+        let entries = supplemental.currency_data["supplemental"]["currencyData"]["region"][region]
+            .as_array()?;
+        entries.iter().rev().find_map(|entry| {
+            let (code, info) = entry.as_object()?.iter().next()?;
+            let current = info.get("_to").is_none()
+                && info.get("_tender").and_then(Value::as_str) != Some("false");
+            current.then(|| code.clone())
+        })
+    }
+
+    fn render(entries: &[Entry]) -> String {
+        let mut out = String::from("// Generated by build.rs from CLDR data. Do not edit.\n\n");
+        out.push_str("pub(crate) static CLDR_LOCALES: &[CldrLocale] = &[\n");
+        for entry in entries {
+            let list = |names: &[String]| {
+                names.iter().map(|n| format!("{n:?}")).collect::<Vec<_>>().join(", ")
+            };
+            writeln!(
+                out,
+                "    CldrLocale {{\n        tag: {:?},\n        locale: Locale {{\n            \
+                 decimal_separator: {:?},\n            thousands_separator: {:?},\n            \
+                 date_separator: {:?},\n            time_separator: {:?},\n            \
+                 currency_symbol: {:?},\n            am_string: {:?},\n            \
+                 pm_string: {:?},\n            true_string: \"TRUE\",\n            \
+                 false_string: \"FALSE\",\n            month_names_short: [{}],\n            \
+                 month_names_full: [{}],\n            day_names_short: [{}],\n            \
+                 day_names_full: [{}],\n        }},\n        native_zero: {:?},\n    }},",
+                entry.tag.to_ascii_lowercase(),
+                entry.decimal,
+                entry.group,
+                entry.date_separator,
+                entry.time_separator,
+                entry.currency,
+                entry.am,
+                entry.pm,
+                list(&entry.months_short),
+                list(&entry.months_full),
+                list(&entry.days_short),
+                list(&entry.days_full),
+                entry.native_zero,
+            )
+            .expect("write to string");
+        }
+        out.push_str("];\n");
+        out
+    }
+}
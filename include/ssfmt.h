@@ -0,0 +1,95 @@
+#ifndef SSFMT_H
+#define SSFMT_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// A required pointer argument was null.
+#define SSFMT_ERR_NULL -1
+
+// A string argument was not valid UTF-8.
+#define SSFMT_ERR_UTF8 -2
+
+// The format code could not be parsed, or the format ID is unknown.
+#define SSFMT_ERR_PARSE -3
+
+// The library panicked; the message is available from [`ssfmt_last_error`].
+#define SSFMT_ERR_PANIC -4
+
+// A parsed format, created by [`ssfmt_parse`] and released with
+// [`ssfmt_free`].
+typedef struct SsfmtFormat SsfmtFormat;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Format `value` with the format code `code`.
+//
+// Returns the length of the formatted text, or a negative `SSFMT_ERR_*`
+// code. Pass a null `out_buf` to only measure the text.
+//
+// # Safety
+// `code` must point to a NUL-terminated string, and `out_buf` must be
+// null or valid for writes of `out_len` bytes.
+ptrdiff_t ssfmt_format(double value, const char *code, char *out_buf, size_t out_len);
+
+// Format `value` with a built-in format ID, such as 14 for `m/d/yy`.
+//
+// Returns the length of the formatted text, [`SSFMT_ERR_PARSE`] for an
+// unknown ID, or [`SSFMT_ERR_PANIC`].
+//
+// # Safety
+// `out_buf` must be null or valid for writes of `out_len` bytes.
+ptrdiff_t ssfmt_format_with_id(double value, uint32_t format_id, char *out_buf, size_t out_len);
+
+// Parse a format code once for formatting many values.
+//
+// Returns null if the code cannot be parsed or parsing panicked. The
+// handle may be used from any thread and must be released with
+// [`ssfmt_free`].
+//
+// # Safety
+// `code` must point to a NUL-terminated string.
+struct SsfmtFormat *ssfmt_parse(const char *code);
+
+// Format `value` with a format returned by [`ssfmt_parse`].
+//
+// Returns the length of the formatted text, [`SSFMT_ERR_NULL`] if
+// `format` is null, or [`SSFMT_ERR_PANIC`].
+//
+// # Safety
+// `format` must be null or a live handle from [`ssfmt_parse`], and
+// `out_buf` must be null or valid for writes of `out_len` bytes.
+ptrdiff_t ssfmt_format_compiled(const struct SsfmtFormat *format,
+                                double value,
+                                char *out_buf,
+                                size_t out_len);
+
+// Release a format returned by [`ssfmt_parse`]. Null is ignored.
+//
+// # Safety
+// `format` must be null or a handle from [`ssfmt_parse`] that has not
+// been freed.
+void ssfmt_free(struct SsfmtFormat *format);
+
+// Copy the error message of the last call on this thread into `out_buf`.
+//
+// Every other function except [`ssfmt_free`] clears the message when it
+// starts, so this returns the length of the message, which is 0 if the
+// last call succeeded.
+//
+// # Safety
+// `out_buf` must be null or valid for writes of `out_len` bytes.
+ptrdiff_t ssfmt_last_error(char *out_buf, size_t out_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SSFMT_H */
@@ -0,0 +1,55 @@
+//! Formatting Arrow arrays (requires `arrow` feature).
+//!
+//! Engines that export numeric columns to display strings, for CSV or a
+//! text column in Parquet, format whole arrays at once. The values are
+//! written straight into the string array's buffer, so no `String` is
+//! allocated per cell.
+
+use arrow_array::builder::StringBuilder;
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{PrimitiveArray, StringArray};
+
+use crate::ast::NumberFormat;
+use crate::options::FormatOptions;
+
+impl NumberFormat {
+    /// Format a numeric Arrow array into a string array (requires `arrow`
+    /// feature).
+    ///
+    /// Works for every primitive type that converts to `f64` without loss
+    /// (floats and integers up to 32 bits). Null slots stay null.
+    ///
+    /// # Examples
+    /// ```
+    /// use arrow_array::{Array, Float64Array, Int32Array};
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    /// let opts = FormatOptions::default();
+    ///
+    /// let strings = fmt.format_arrow(&Float64Array::from(vec![Some(1234.5), None]), &opts);
+    /// assert_eq!(strings.value(0), "1,234.50");
+    /// assert!(strings.is_null(1));
+    ///
+    /// let strings = fmt.format_arrow(&Int32Array::from(vec![-7]), &opts);
+    /// assert_eq!(strings.value(0), "-7.00");
+    /// ```
+    pub fn format_arrow<T>(&self, values: &PrimitiveArray<T>, opts: &FormatOptions) -> StringArray
+    where
+        T: ArrowPrimitiveType,
+        T::Native: Into<f64>,
+    {
+        let mut builder = StringBuilder::with_capacity(values.len(), values.len() * 8);
+        for value in values.iter() {
+            match value {
+                Some(value) => {
+                    // Writing into the builder cannot fail
+                    let _ = self.format_to(value.into(), opts, &mut builder);
+                    builder.append_value("");
+                }
+                None => builder.append_null(),
+            }
+        }
+        builder.finish()
+    }
+}
@@ -1,10 +1,12 @@
 //! AST types for parsed format codes.
 
-use crate::error::ParseError;
+use crate::error::{ParseError, ParseWarning};
+use smallvec::SmallVec;
 use std::str::FromStr;
 
 /// Named colors supported in format codes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NamedColor {
     Black,
     Blue,
@@ -36,6 +38,7 @@ impl FromStr for NamedColor {
 
 /// Color specification in a format section.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color {
     Named(NamedColor),
     Indexed(u8),
@@ -43,6 +46,7 @@ pub enum Color {
 
 /// Conditional expression for section selection.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Condition {
     GreaterThan(f64),
     LessThan(f64),
@@ -82,6 +86,7 @@ impl Condition {
 
 /// Digit placeholder type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DigitPlaceholder {
     /// `0` - Display digit or zero
     Zero,
@@ -109,12 +114,14 @@ impl DigitPlaceholder {
 
 /// Date/time format parts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DatePart {
-    /// `yy` - Two-digit year
+    /// `y` or `yy` - Two-digit year
     Year2,
-    /// `yyy` - Three-digit year (minimum 3 digits, shows full year)
+    /// `yyy` - Year with at least 3 digits: like `yyyy` for Gregorian
+    /// years, but an era year such as the ROC year 113 is not padded to 4
     Year3,
-    /// `yyyy` - Four-digit year
+    /// `yyyy` or longer - Four-digit year
     Year4,
     /// `m` - Month as number without leading zero (1-12)
     Month,
@@ -132,7 +139,7 @@ pub enum DatePart {
     Day2,
     /// `ddd` - Day of week as abbreviated name (Sun, Mon, etc.)
     DayAbbr,
-    /// `dddd` - Day of week as full name (Sunday, Monday, etc.)
+    /// `dddd` or longer - Day of week as full name (Sunday, Monday, etc.)
     DayFull,
     /// `h` - Hour without leading zero (0-23 or 1-12 with AM/PM)
     Hour,
@@ -156,14 +163,25 @@ pub enum DatePart {
     BuddhistYear4Alt,
     /// `B2yy` - Alternative Buddhist calendar era, last 2 digits (Gregorian - 582)
     BuddhistYear2Alt,
+    /// `g` - Japanese era as a Latin letter (R for Reiwa)
+    EraLetter,
+    /// `gg` - Japanese era as the first character of its name (令)
+    EraAbbr,
+    /// `ggg` - Japanese era full name (令和)
+    EraFull,
+    /// `e` - Year within the Japanese era (Gregorian year in other locales)
+    EraYear,
+    /// `ee` or `r` - Year within the Japanese era, two digits
+    EraYear2,
 }
 
 /// AM/PM format style.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AmPmStyle {
-    /// `AM/PM` - Uppercase AM or PM
+    /// `AM/PM` - The locale's AM or PM designator (AM or PM in English)
     Upper,
-    /// `am/pm` - Lowercase am or pm
+    /// `am/pm` - Same as `AM/PM`; Excel ignores the case
     Lower,
     /// `A/P` - Uppercase single letter A or P
     ShortUpper,
@@ -173,11 +191,18 @@ pub enum AmPmStyle {
     MalformedUpper,
     /// `am/p` - Malformed lowercase pattern (outputs a0/p or a1/p)
     MalformedLower,
+    /// `上午/下午` - Chinese 上午 or 下午 whatever the locale
+    Chinese,
 }
 
 /// Elapsed time format part (for durations).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElapsedPart {
+    /// `[d]` - Total elapsed days without padding
+    Days,
+    /// `[dd]` - Total elapsed days with zero-padding to 2 digits
+    Days2,
     /// `[h]` - Total elapsed hours without padding
     Hours,
     /// `[hh]` - Total elapsed hours with zero-padding to 2 digits
@@ -194,6 +219,7 @@ pub enum ElapsedPart {
 
 /// Fraction denominator specification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FractionDenom {
     UpToDigits(u8),
     Fixed(u32),
@@ -201,6 +227,7 @@ pub enum FractionDenom {
 
 /// Locale code from format string.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LocaleCode {
     /// Currency symbol to display (e.g., "$", "€", "£")
     pub currency: Option<String>,
@@ -210,11 +237,15 @@ pub struct LocaleCode {
 
 /// A single part of a format section.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FormatPart {
-    /// Literal text to display as-is (from unescaped characters or quoted strings)
+    /// Literal text to display as-is (from unescaped characters)
     Literal(String),
     /// Escaped literal character (e.g., `\r`, `\#`) - does not get minus sign for negative values
     EscapedLiteral(String),
+    /// Quoted literal text (e.g., `"kg"`) - displays like `Literal`, kept apart so the
+    /// code is written back with its quotes
+    QuotedLiteral(String),
     /// Digit placeholder (0, #, or ?)
     Digit(DigitPlaceholder),
     /// Decimal point separator
@@ -229,11 +260,17 @@ pub enum FormatPart {
         upper: bool,
         /// True to always show sign, false for minus only
         show_plus: bool,
+        /// Digit placeholders for the exponent (`00` in `E+00`)
+        exponent_digits: Vec<DigitPlaceholder>,
     },
     /// Fraction format (e.g., # #/# or # ??/??)
     Fraction {
         /// Digit placeholders for integer part
         integer_digits: Vec<DigitPlaceholder>,
+        /// Text between the integer part and the numerator: a space in
+        /// `# ?/?`, `" and "` in `0" and "?/?`, empty without an integer part
+        #[cfg_attr(feature = "serde", serde(default))]
+        integer_separator: String,
         /// Digit placeholders for numerator
         numerator_digits: Vec<DigitPlaceholder>,
         /// Denominator specification (fixed or up to N digits)
@@ -259,6 +296,10 @@ pub enum FormatPart {
     Locale(LocaleCode),
     /// General number formatting (used when "General" keyword appears with additional format parts)
     GeneralNumber,
+    /// `[DBNum1]`-`[DBNum3]` - East Asian numeral style for digits
+    DbNum(u8),
+    /// `[NatNum0]`-`[NatNum11]` - Native numeral style for digits
+    NatNum(u8),
 }
 
 impl FormatPart {
@@ -287,6 +328,7 @@ impl FormatPart {
 /// Smallest time unit displayed in a format (used for pre-rounding).
 /// Based on SSF's `bt` variable in bits/82_eval.js
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeUnit {
     /// No time components in format
     None,
@@ -302,6 +344,7 @@ pub enum TimeUnit {
 
 /// Type of format for optimization and dispatch
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FormatType {
     /// General number format or mixed
     General,
@@ -315,8 +358,53 @@ pub enum FormatType {
     Text,
 }
 
+/// Analysis of a format section's numeric structure.
+///
+/// Computed by the parser for number sections and stored in
+/// [`SectionMetadata::analysis`], so formatting doesn't rescan the parts.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatAnalysis {
+    /// Number of integer digit placeholders
+    pub integer_placeholders: Vec<DigitPlaceholder>,
+    /// Number of decimal digit placeholders
+    pub decimal_placeholders: Vec<DigitPlaceholder>,
+    /// Whether the format has a thousands separator
+    pub has_thousands_separator: bool,
+    /// Number of percent signs (each multiplies by 100)
+    pub percent_count: usize,
+    /// Thousands scaling factor (trailing commas divide by 1000 each)
+    pub thousands_scale: usize,
+    /// The integer digit run as written: digit placeholders, thousands
+    /// separators and the literals between them, in format order
+    pub integer_parts: Vec<FormatPart>,
+    /// Literals that appear inline with decimal digits (position -> literal)
+    /// Position is counted from the left (0 = first decimal place, 1 = second, etc.)
+    pub decimal_inline_literals: Vec<(usize, String)>,
+    /// Parts before the number (literals, etc.)
+    pub prefix_parts: Vec<FormatPart>,
+    /// Parts after the number (literals, percent, etc.)
+    pub suffix_parts: Vec<FormatPart>,
+}
+
+impl FormatAnalysis {
+    /// Get the number of required decimal places
+    pub fn decimal_places(&self) -> usize {
+        self.decimal_placeholders.len()
+    }
+
+    /// Get the minimum integer digits (count of Zero placeholders)
+    pub fn min_integer_digits(&self) -> usize {
+        self.integer_placeholders
+            .iter()
+            .filter(|p| p.is_required())
+            .count()
+    }
+}
+
 /// Pre-computed metadata about a section to avoid repeated scanning
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SectionMetadata {
     /// True if format contains AM/PM indicator
     pub has_ampm: bool,
@@ -330,6 +418,15 @@ pub struct SectionMetadata {
     pub smallest_time_unit: TimeUnit,
     /// Primary format type
     pub format_type: FormatType,
+    /// LCID from the first `[$-xxx]` locale tag, if any
+    pub lcid: Option<u32>,
+    /// Numeral style from the first `[DBNumN]` modifier, if any
+    pub dbnum: Option<u8>,
+    /// Numeral style from the first `[NatNumN]` modifier, if any
+    pub natnum: Option<u8>,
+    /// Numeric layout for number sections, `None` for date, text, fraction
+    /// and scientific sections
+    pub analysis: Option<FormatAnalysis>,
 }
 
 impl Default for SectionMetadata {
@@ -341,6 +438,10 @@ impl Default for SectionMetadata {
             has_elapsed_time: false,
             smallest_time_unit: TimeUnit::None,
             format_type: FormatType::General,
+            lcid: None,
+            dbnum: None,
+            natnum: None,
+            analysis: None,
         }
     }
 }
@@ -353,6 +454,7 @@ impl Default for SectionMetadata {
 /// 3. Zero
 /// 4. Text
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Section {
     /// Optional condition for this section (e.g., [>100])
     pub condition: Option<Condition>,
@@ -365,6 +467,20 @@ pub struct Section {
 }
 
 impl Section {
+    /// Returns true if this section's condition holds for `value`, or if it
+    /// has no condition.
+    ///
+    /// Sections without conditions are chosen by the sign of the value and
+    /// their position; use [`NumberFormat::section_for`] to get the section
+    /// a value is formatted with.
+    ///
+    /// [`NumberFormat::section_for`]: crate::NumberFormat::section_for
+    pub fn matches(&self, value: f64) -> bool {
+        self.condition
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(value))
+    }
+
     /// Returns true if this section contains any date/time parts.
     pub fn has_date_parts(&self) -> bool {
         self.parts.iter().any(|p| p.is_date_part())
@@ -377,6 +493,14 @@ impl Section {
             .any(|p| matches!(p, FormatPart::TextPlaceholder))
     }
 
+    /// Returns true if this section formats like General: it has no parts
+    /// or only the `General` keyword.
+    pub fn is_general(&self) -> bool {
+        self.parts
+            .iter()
+            .all(|p| matches!(p, FormatPart::GeneralNumber))
+    }
+
     /// Returns true if this section contains a percent sign.
     pub fn has_percent(&self) -> bool {
         self.parts.iter().any(|p| matches!(p, FormatPart::Percent))
@@ -388,20 +512,25 @@ impl Section {
 /// This is the main type returned by parsing. It can be reused to format
 /// multiple values efficiently.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NumberFormat {
-    sections: Vec<Section>,
+    /// Inline for the common one- and two-section codes
+    sections: SmallVec<[Section; 2]>,
 }
 
 impl NumberFormat {
     /// Create a NumberFormat from parsed sections.
     /// Limits to 4 sections maximum per Excel spec.
     pub fn from_sections(sections: Vec<Section>) -> Self {
-        let sections = if sections.len() > 4 {
-            sections.into_iter().take(4).collect()
-        } else {
-            sections
-        };
-        NumberFormat { sections }
+        Self::from_section_iter(sections)
+    }
+
+    /// Like [`from_sections`](Self::from_sections), without requiring a
+    /// `Vec`.
+    pub(crate) fn from_section_iter(sections: impl IntoIterator<Item = Section>) -> Self {
+        NumberFormat {
+            sections: sections.into_iter().take(4).collect(),
+        }
     }
 
     /// Get the sections of this format.
@@ -409,11 +538,33 @@ impl NumberFormat {
         &self.sections
     }
 
+    pub(crate) fn sections_mut(&mut self) -> &mut [Section] {
+        &mut self.sections
+    }
+
     /// Returns true if this format contains date/time parts.
     pub fn is_date_format(&self) -> bool {
         self.sections.iter().any(|s| s.has_date_parts())
     }
 
+    /// Returns true if this format shows elapsed time (`[h]`, `[mm]`, `[ss]`).
+    ///
+    /// Elapsed-time formats are also date formats, but their values are
+    /// durations rather than points in time.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// assert!(NumberFormat::parse("[h]:mm:ss").unwrap().is_duration_format());
+    /// assert!(!NumberFormat::parse("h:mm:ss").unwrap().is_duration_format());
+    /// ```
+    pub fn is_duration_format(&self) -> bool {
+        self.sections
+            .iter()
+            .any(|s| s.parts.iter().any(|p| matches!(p, FormatPart::Elapsed(_))))
+    }
+
     /// Returns true if this is a text-only format.
     pub fn is_text_format(&self) -> bool {
         self.sections.len() == 1 && self.sections[0].has_text_placeholder()
@@ -438,4 +589,83 @@ impl NumberFormat {
     pub fn parse(format_code: &str) -> Result<NumberFormat, ParseError> {
         crate::parser::parse(format_code)
     }
+
+    /// Parse a format code with the given options.
+    ///
+    /// With [`ExtraSections::Error`](crate::ExtraSections::Error), a code
+    /// with more than four sections fails instead of losing the extra ones.
+    /// Codes beyond the [`ParseLimits`](crate::ParseLimits) fail with
+    /// [`ParseError::FormatTooComplex`]; [`parse`](Self::parse) uses the
+    /// default limits.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{ExtraSections, NumberFormat, ParseError, ParseOptions};
+    ///
+    /// let code = "0;-0;0;@;0.00";
+    /// assert_eq!(NumberFormat::parse(code).unwrap().sections().len(), 4);
+    ///
+    /// let strict = ParseOptions {
+    ///     extra_sections: ExtraSections::Error,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     NumberFormat::parse_with_options(code, &strict),
+    ///     Err(ParseError::TooManySections)
+    /// );
+    /// ```
+    pub fn parse_with_options(
+        format_code: &str,
+        options: &crate::options::ParseOptions,
+    ) -> Result<NumberFormat, ParseError> {
+        crate::parser::parse_with_options(format_code, options)
+    }
+
+    /// Parse a format code, repairing recoverable problems instead of failing.
+    ///
+    /// Like Excel, this quietly accepts malformed codes: an unterminated
+    /// bracket or quoted string is closed at the end of the code, a stray
+    /// `]` is shown as text, unknown bracket content and sections after the
+    /// fourth are ignored, and an empty code is treated as General. Each
+    /// repair is reported as a [`ParseWarning`].
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat, ParseWarning};
+    ///
+    /// let (fmt, warnings) = NumberFormat::parse_lenient("0.00\" kg");
+    /// assert_eq!(fmt.format(2.5, &FormatOptions::default()), "2.50 kg");
+    /// assert!(matches!(warnings[..], [ParseWarning::UnterminatedQuote { .. }]));
+    ///
+    /// let (_, warnings) = NumberFormat::parse_lenient("#,##0");
+    /// assert!(warnings.is_empty());
+    /// ```
+    pub fn parse_lenient(format_code: &str) -> (NumberFormat, Vec<ParseWarning>) {
+        crate::parser::parse_lenient(format_code)
+    }
+}
+
+impl FromStr for NumberFormat {
+    type Err = ParseError;
+
+    /// Same as [`NumberFormat::parse`], for `str::parse` and generic code.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt: NumberFormat = "#,##0.00".parse().unwrap();
+    /// assert_eq!(fmt.format(1234.5, &FormatOptions::default()), "1,234.50");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NumberFormat::parse(s)
+    }
+}
+
+impl TryFrom<&str> for NumberFormat {
+    type Error = ParseError;
+
+    fn try_from(format_code: &str) -> Result<Self, Self::Error> {
+        NumberFormat::parse(format_code)
+    }
 }
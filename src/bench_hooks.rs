@@ -0,0 +1,106 @@
+//! Allocation counters for performance regression tests (requires
+//! `bench_hooks` feature).
+//!
+//! Install [`CountingAllocator`] as the global allocator of a benchmark or
+//! test binary, then wrap the code to measure in [`count_allocations`]:
+//!
+//! ```
+//! use ssfmt::bench_hooks::{count_allocations, CountingAllocator};
+//! use ssfmt::{FormatOptions, NumberFormat};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator::new();
+//!
+//! let fmt = NumberFormat::parse("#,##0.00").unwrap();
+//! let opts = FormatOptions::default();
+//! let (text, counts) = count_allocations(|| fmt.format(1234.5, &opts));
+//! assert_eq!(text, "1,234.50");
+//! assert!(counts.allocations >= 1);
+//! ```
+//!
+//! Counts are kept per thread, so allocations made by other test threads
+//! do not show up. Without the allocator installed every count is zero.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static COUNTS: Cell<AllocationCounts> = const { Cell::new(AllocationCounts::ZERO) };
+}
+
+/// Allocations made on one thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocationCounts {
+    /// Calls to `alloc`, `alloc_zeroed` and `realloc`
+    pub allocations: u64,
+    /// Bytes requested by those calls
+    pub bytes: u64,
+}
+
+impl AllocationCounts {
+    const ZERO: AllocationCounts = AllocationCounts {
+        allocations: 0,
+        bytes: 0,
+    };
+}
+
+/// A global allocator that counts allocations on each thread and hands
+/// them to the system allocator.
+#[derive(Debug, Default)]
+pub struct CountingAllocator(System);
+
+impl CountingAllocator {
+    /// Create the allocator, for use in a `#[global_allocator]` static.
+    pub const fn new() -> Self {
+        CountingAllocator(System)
+    }
+}
+
+fn record(size: usize) {
+    // The thread-local may already be gone while a thread shuts down
+    let _ = COUNTS.try_with(|counts| {
+        let mut c = counts.get();
+        c.allocations += 1;
+        c.bytes += size as u64;
+        counts.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        self.0.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        self.0.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+}
+
+/// The allocations made on this thread so far.
+pub fn allocation_counts() -> AllocationCounts {
+    COUNTS.with(Cell::get)
+}
+
+/// Run `f` and return its result with the allocations it made on this
+/// thread.
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, AllocationCounts) {
+    let before = allocation_counts();
+    let result = f();
+    let after = allocation_counts();
+    let counts = AllocationCounts {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+    };
+    (result, counts)
+}
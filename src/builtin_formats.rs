@@ -2,11 +2,20 @@
 //!
 //! Excel uses numeric format IDs (0-49 and others) for built-in formats.
 //! These IDs are stored in .xlsx files but the actual format codes are implied.
-//! This module provides the mapping from format IDs to format code strings.
+//! This module provides the mapping from format IDs to format code strings,
+//! including the East Asian and Thai IDs and the short dates whose codes
+//! depend on the locale.
+//!
+//! [`builtin_format`] keeps the IDs 0-49 parsed, so formatting by ID needs
+//! neither a parse nor a cache lookup.
 //!
 //! Based on ECMA-376 and Excel's actual implementation, matching the behavior
 //! from SheetJS's ssf library.
 
+use std::sync::OnceLock;
+
+use crate::ast::NumberFormat;
+
 /// Get the format code string for a built-in format ID.
 ///
 /// Excel stores format IDs in .xlsx files (numFmtId attribute), but the actual
@@ -53,17 +62,53 @@ pub fn format_code_from_id(id: u32) -> Option<&'static str> {
         38 => Some("#,##0 ;[Red](#,##0)"),
         39 => Some("#,##0.00;(#,##0.00)"),
         40 => Some("#,##0.00;[Red](#,##0.00)"),
+        // Accounting: skips and a fill line up the digits and the currency
+        // symbol, and zero shows as a dash under the units digit
+        41 => Some(r#"_(* #,##0_);_(* \(#,##0\);_(* "-"_);_(@_)"#),
+        42 => Some(r#"_("$"* #,##0_);_("$"* \(#,##0\);_("$"* "-"_);_(@_)"#),
+        43 => Some(r#"_(* #,##0.00_);_(* \(#,##0.00\);_(* "-"??_);_(@_)"#),
+        44 => Some(r#"_("$"* #,##0.00_);_("$"* \(#,##0.00\);_("$"* "-"??_);_(@_)"#),
         45 => Some("mm:ss"),
         46 => Some("[h]:mm:ss"),
         47 => Some("mmss.0"),
         48 => Some("##0.0E+0"),
         49 => Some("@"),
-        // Note: IDs 5-8, 23-36, 41-44, 50+ are not defined as built-in formats
+        // Note: IDs 5-8, 23-36, 50+ are not defined as built-in formats
         // Custom formats typically start at 164
         _ => None,
     }
 }
 
+/// The parsed format for a built-in format ID, in the en-US codes of
+/// [`format_code_from_id`].
+///
+/// Every built-in code is parsed together the first time this is called,
+/// then shared for the life of the program.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{builtin_format, FormatOptions};
+///
+/// let fmt = builtin_format(4).unwrap();
+/// assert_eq!(fmt.format(1234.5, &FormatOptions::default()), "1,234.50");
+/// assert!(builtin_format(164).is_none());
+/// ```
+pub fn builtin_format(id: u32) -> Option<&'static NumberFormat> {
+    static BUILTINS: OnceLock<Vec<Option<NumberFormat>>> = OnceLock::new();
+    let builtins = BUILTINS.get_or_init(|| {
+        (0..BUILTIN_ID_COUNT)
+            .map(|id| {
+                format_code_from_id(id)
+                    .map(|code| NumberFormat::parse(code).expect("built-in format code parses"))
+            })
+            .collect()
+    });
+    builtins.get(usize::try_from(id).ok()?)?.as_ref()
+}
+
+/// The built-in IDs run from 0 to 49.
+const BUILTIN_ID_COUNT: u32 = 50;
+
 /// Check if a format ID is a built-in format.
 ///
 /// Built-in formats are those in the range 0-49 that have predefined format codes.
@@ -81,6 +126,245 @@ pub fn is_builtin_format_id(id: u32) -> bool {
     format_code_from_id(id).is_some()
 }
 
+/// Get the format code for a built-in format ID as shown in a given locale.
+///
+/// IDs 27-36 and 50-58 are East Asian date and time formats whose codes
+/// depend on the locale (zh-TW, zh-CN, ja-JP, ko-KR), and IDs 59-81 are
+/// Thai formats. These resolve by the language of `lcid` and are `None` for
+/// other locales. All other IDs resolve as [`format_code_from_id`] does.
+///
+/// The Thai codes use the Latin date letters with a Thai-digit locale tag
+/// (`[$-D00041E]d/m/yyyy`), which render like the Thai-letter codes
+/// (`ว/ด/ปปปป`) in the specification.
+///
+/// Use [`Locale::lcid`](crate::Locale::lcid) to get the LCID of a built-in
+/// locale.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{format_code_from_id_with_locale, Locale};
+///
+/// assert_eq!(format_code_from_id_with_locale(31, 0x804), Some("yyyy\"年\"m\"月\"d\"日\""));
+/// assert_eq!(format_code_from_id_with_locale(31, 0x412), Some("yyyy\"년\" mm\"월\" dd\"일\""));
+/// assert_eq!(format_code_from_id_with_locale(31, 0x409), None);
+/// assert_eq!(format_code_from_id_with_locale(14, 0x411), Some("m/d/yy"));
+///
+/// let ja = Locale::from_tag("ja-JP").unwrap();
+/// let lcid = ja.lcid().unwrap();
+/// assert_eq!(format_code_from_id_with_locale(32, lcid), Some("h\"時\"mm\"分\""));
+/// ```
+pub fn format_code_from_id_with_locale(id: u32, lcid: u32) -> Option<&'static str> {
+    let table = match (lcid & 0x3FF, lcid & 0xFFFF) {
+        (0x04, 0x0404 | 0x0C04 | 0x1404) => &ZH_TW,
+        (0x04, _) => &ZH_CN,
+        (0x11, _) => &JA_JP,
+        (0x12, _) => &KO_KR,
+        (0x1E, _) => return thai_format_code(id).or_else(|| format_code_from_id(id)),
+        _ => return format_code_from_id(id),
+    };
+    match id {
+        27..=36 => Some(table[id as usize - 27]),
+        50..=58 => Some(table[id as usize - 40]),
+        _ => format_code_from_id(id),
+    }
+}
+
+/// Get the format code Excel shows for a built-in format ID in a locale.
+///
+/// IDs 14 and 22 are stored without a code and shown in the short date of
+/// the regional settings, so they differ by locale: `dd/mm/yyyy` in
+/// en-GB, `yyyy/m/d` in ja-JP. Regions without their own short date use
+/// their language's. Every other ID resolves as
+/// [`format_code_from_id_with_locale`] does.
+///
+/// # Examples
+/// ```
+/// use ssfmt::localized_format_code;
+///
+/// assert_eq!(localized_format_code(14, 0x809), Some("dd/mm/yyyy"));
+/// assert_eq!(localized_format_code(22, 0x411), Some("yyyy/m/d h:mm"));
+/// assert_eq!(localized_format_code(14, 0x409), Some("m/d/yy"));
+/// assert_eq!(localized_format_code(31, 0x411), Some("yyyy\"年\"m\"月\"d\"日\""));
+/// assert_eq!(localized_format_code(15, 0x809), Some("d-mmm-yy"));
+/// ```
+pub fn localized_format_code(id: u32, lcid: u32) -> Option<&'static str> {
+    let region = lcid & 0xFFFF;
+    let short_date = SHORT_DATES
+        .iter()
+        .find(|(entry, ..)| *entry == region)
+        .or_else(|| {
+            SHORT_DATES
+                .iter()
+                .find(|(entry, ..)| entry & 0x3FF == lcid & 0x3FF)
+        });
+    match (id, short_date) {
+        (14, Some((_, date, _))) => Some(date),
+        (22, Some((_, _, date_time))) => Some(date_time),
+        _ => format_code_from_id_with_locale(id, lcid),
+    }
+}
+
+/// Codes for IDs 14 and 22 by LCID, from the Windows short dates. A
+/// language's first entry stands for its other regions. Dots are escaped,
+/// since a bare `.` shows the decimal separator.
+const SHORT_DATES: &[(u32, &str, &str)] = &[
+    (0x0409, "m/d/yy", "m/d/yy h:mm"),                   // en-US
+    (0x0809, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // en-GB
+    (0x0C09, "d/mm/yyyy", "d/mm/yyyy h:mm"),             // en-AU
+    (0x1009, "yyyy-mm-dd", "yyyy-mm-dd h:mm"),           // en-CA
+    (0x0407, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // de-DE
+    (0x0C07, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // de-AT
+    (0x0807, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // de-CH
+    (0x040C, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // fr-FR
+    (0x0C0C, "yyyy-mm-dd", "yyyy-mm-dd hh:mm"),          // fr-CA
+    (0x100C, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // fr-CH
+    (0x0C0A, "dd/mm/yyyy", "dd/mm/yyyy h:mm"),           // es-ES
+    (0x080A, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // es-MX
+    (0x0410, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // it-IT
+    (0x0416, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // pt-BR
+    (0x0816, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // pt-PT
+    (0x0413, "d-m-yyyy", "d-m-yyyy hh:mm"),              // nl-NL
+    (0x0415, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // pl-PL
+    (0x0419, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy h:mm"),     // ru-RU
+    (0x0422, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy h:mm"),     // uk-UA
+    (0x0405, r"d\.m\.yyyy", r"d\.m\.yyyy h:mm"),         // cs-CZ
+    (0x041D, "yyyy-mm-dd", "yyyy-mm-dd hh:mm"),          // sv-SE
+    (0x0406, "dd-mm-yyyy", "dd-mm-yyyy hh:mm"),          // da-DK
+    (0x0414, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // nb-NO
+    (0x040B, r"d\.m\.yyyy", r"d\.m\.yyyy h:mm"),         // fi-FI
+    (0x041F, r"dd\.mm\.yyyy", r"dd\.mm\.yyyy hh:mm"),    // tr-TR
+    (0x0408, "d/m/yyyy", "d/m/yyyy h:mm"),               // el-GR
+    (0x040E, r"yyyy\.mm\.dd\.", r"yyyy\.mm\.dd\. h:mm"), // hu-HU
+    (0x0411, "yyyy/m/d", "yyyy/m/d h:mm"),               // ja-JP
+    (0x0804, "yyyy/m/d", "yyyy/m/d h:mm"),               // zh-CN
+    (0x0404, "yyyy/m/d", "yyyy/m/d h:mm"),               // zh-TW
+    (0x0C04, "d/m/yyyy", "d/m/yyyy h:mm"),               // zh-HK
+    (0x0412, "yyyy-mm-dd", "yyyy-mm-dd h:mm"),           // ko-KR
+    (0x0401, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // ar-SA
+    (0x040D, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // he-IL
+    (0x041E, "d/m/yyyy", "d/m/yyyy h:mm"),               // th-TH
+    (0x0439, "dd-mm-yyyy", "dd-mm-yyyy hh:mm"),          // hi-IN
+    (0x0421, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // id-ID
+    (0x042A, "dd/mm/yyyy", "dd/mm/yyyy hh:mm"),          // vi-VN
+];
+
+/// Codes for IDs 27-36 followed by 50-58.
+type EastAsianTable = [&'static str; 19];
+
+const ZH_TW: EastAsianTable = [
+    "[$-404]e/m/d",
+    "[$-404]e\"年\"m\"月\"d\"日\"",
+    "[$-404]e\"年\"m\"月\"d\"日\"",
+    "m/d/yy",
+    "yyyy\"年\"m\"月\"d\"日\"",
+    "hh\"時\"mm\"分\"",
+    "hh\"時\"mm\"分\"ss\"秒\"",
+    "上午/下午hh\"時\"mm\"分\"",
+    "上午/下午hh\"時\"mm\"分\"ss\"秒\"",
+    "[$-404]e/m/d",
+    "[$-404]e/m/d",
+    "[$-404]e\"年\"m\"月\"d\"日\"",
+    "上午/下午hh\"時\"mm\"分\"",
+    "上午/下午hh\"時\"mm\"分\"ss\"秒\"",
+    "[$-404]e\"年\"m\"月\"d\"日\"",
+    "上午/下午hh\"時\"mm\"分\"",
+    "上午/下午hh\"時\"mm\"分\"ss\"秒\"",
+    "[$-404]e/m/d",
+    "[$-404]e\"年\"m\"月\"d\"日\"",
+];
+
+const ZH_CN: EastAsianTable = [
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+    "m\"月\"d\"日\"",
+    "m-d-yy",
+    "yyyy\"年\"m\"月\"d\"日\"",
+    "h\"时\"mm\"分\"",
+    "h\"时\"mm\"分\"ss\"秒\"",
+    "上午/下午h\"时\"mm\"分\"",
+    "上午/下午h\"时\"mm\"分\"ss\"秒\"",
+    "yyyy\"年\"m\"月\"",
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+    "m\"月\"d\"日\"",
+    "上午/下午h\"时\"mm\"分\"",
+    "上午/下午h\"时\"mm\"分\"ss\"秒\"",
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+];
+
+const JA_JP: EastAsianTable = [
+    "[$-411]ge.m.d",
+    "[$-411]ggge\"年\"m\"月\"d\"日\"",
+    "[$-411]ggge\"年\"m\"月\"d\"日\"",
+    "m/d/yy",
+    "yyyy\"年\"m\"月\"d\"日\"",
+    "h\"時\"mm\"分\"",
+    "h\"時\"mm\"分\"ss\"秒\"",
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+    "[$-411]ge.m.d",
+    "[$-411]ge.m.d",
+    "[$-411]ggge\"年\"m\"月\"d\"日\"",
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+    "[$-411]ggge\"年\"m\"月\"d\"日\"",
+    "yyyy\"年\"m\"月\"",
+    "m\"月\"d\"日\"",
+    "[$-411]ge.m.d",
+    "[$-411]ggge\"年\"m\"月\"d\"日\"",
+];
+
+const KO_KR: EastAsianTable = [
+    "yyyy\"年\" mm\"月\" dd\"日\"",
+    "mm-dd",
+    "mm-dd",
+    "mm-dd-yy",
+    "yyyy\"년\" mm\"월\" dd\"일\"",
+    "h\"시\" mm\"분\"",
+    "h\"시\" mm\"분\" ss\"초\"",
+    "yyyy-mm-dd",
+    "yyyy-mm-dd",
+    "yyyy\"年\" mm\"月\" dd\"日\"",
+    "yyyy\"年\" mm\"月\" dd\"日\"",
+    "mm-dd",
+    "yyyy-mm-dd",
+    "yyyy-mm-dd",
+    "mm-dd",
+    "yyyy-mm-dd",
+    "yyyy-mm-dd",
+    "yyyy\"年\" mm\"月\" dd\"日\"",
+    "mm-dd",
+];
+
+/// Thai formats (IDs 59-81).
+fn thai_format_code(id: u32) -> Option<&'static str> {
+    match id {
+        59 => Some("[$-D00041E]0"),
+        60 => Some("[$-D00041E]0.00"),
+        61 => Some("[$-D00041E]#,##0"),
+        62 => Some("[$-D00041E]#,##0.00"),
+        67 => Some("[$-D00041E]0%"),
+        68 => Some("[$-D00041E]0.00%"),
+        69 => Some("[$-D00041E]# ?/?"),
+        70 => Some("[$-D00041E]# ??/??"),
+        71 => Some("[$-D00041E]d/m/yyyy"),
+        72 => Some("[$-D00041E]d-mmm-yy"),
+        73 => Some("[$-D00041E]d-mmm"),
+        74 => Some("[$-D00041E]mmm-yy"),
+        75 => Some("[$-D00041E]h:mm"),
+        76 => Some("[$-D00041E]h:mm:ss"),
+        77 => Some("[$-D00041E]d/m/yyyy h:mm"),
+        78 => Some("[$-D00041E]mm:ss"),
+        79 => Some("[$-D00041E][h]:mm:ss"),
+        80 => Some("[$-D00041E]mm:ss.0"),
+        81 => Some("d/m/bb"),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +416,10 @@ mod tests {
         assert_eq!(format_code_from_id(38), Some("#,##0 ;[Red](#,##0)"));
         assert_eq!(format_code_from_id(39), Some("#,##0.00;(#,##0.00)"));
         assert_eq!(format_code_from_id(40), Some("#,##0.00;[Red](#,##0.00)"));
+        assert_eq!(
+            format_code_from_id(44),
+            Some(r#"_("$"* #,##0.00_);_("$"* \(#,##0.00\);_("$"* "-"??_);_(@_)"#)
+        );
     }
 
     #[test]
@@ -1,29 +1,270 @@
 //! Format code caching.
 
 use lru::LruCache;
+use smallvec::SmallVec;
 use std::num::NonZeroUsize;
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use crate::ast::NumberFormat;
+use crate::compiled::CompiledFormat;
 use crate::error::ParseError;
+use crate::options::ParseOptions;
 
-/// Global cache for parsed format codes.
-static CACHE: Mutex<Option<LruCache<String, NumberFormat>>> = Mutex::new(None);
+/// Capacity of a default [`FormatCache`] and of the global cache.
+pub const DEFAULT_CACHE_CAPACITY: usize = 100;
 
-const CACHE_SIZE: usize = 100;
+/// Global cache used by the convenience functions such as [`format`](crate::format).
+static GLOBAL: FormatCache = FormatCache::new(DEFAULT_CACHE_CAPACITY);
 
-/// Get or parse a format code, using the cache.
-pub fn get_or_parse(format_code: &str) -> Result<NumberFormat, ParseError> {
-    let mut cache_guard = CACHE.lock().unwrap();
+/// The cache behind [`format`](crate::format) and
+/// [`parse_value`](crate::parse_value), and behind
+/// [`format_with_id`](crate::format_with_id) for localized codes; the en-US
+/// built-in codes are kept parsed by [`builtin_format`](crate::builtin_format).
+///
+/// Call `global_cache().set_capacity(0)` to opt out of caching there.
+pub fn global_cache() -> &'static FormatCache {
+    &GLOBAL
+}
+
+/// A least-recently-used cache of parsed format codes.
+///
+/// One global cache backs the convenience functions; create a separate
+/// `FormatCache` per thread or per workbook to keep their format codes from
+/// evicting each other. A capacity of 0 disables caching.
+///
+/// # Cache keys
+///
+/// A cached format is found by its format code together with the
+/// [`ParseOptions`] it was parsed with, so the same code parsed with
+/// different options never returns the other's result. Every input that
+/// changes what a code parses to belongs in `ParseOptions`; the
+/// [`FormatOptions`](crate::FormatOptions), locale included, only apply
+/// when formatting and are not part of the key. The variants of one code
+/// share its entry, so the capacity and [`CacheStats::len`] count codes;
+/// each code keeps its four most recently parsed variants.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{FormatCache, FormatOptions};
+///
+/// let cache = FormatCache::new(16);
+/// let opts = FormatOptions::default();
+/// assert_eq!(cache.format(1234.5, "#,##0.00", &opts).unwrap(), "1,234.50");
+/// assert_eq!(cache.format(99.0, "#,##0.00", &opts).unwrap(), "99.00");
+///
+/// let stats = cache.stats();
+/// assert_eq!((stats.hits, stats.misses, stats.len), (1, 1, 1));
+/// ```
+#[derive(Debug)]
+pub struct FormatCache {
+    state: Mutex<CacheState>,
+}
+
+/// Most parses kept for one format code; the oldest is dropped for a new one.
+const MAX_VARIANTS: usize = 4;
+
+/// The parses of one format code, by the options they were parsed with.
+type Variants = SmallVec<[(ParseOptions, CompiledFormat); 1]>;
+
+#[derive(Debug)]
+struct CacheState {
+    /// Created on first use so `FormatCache::new` can be `const`
+    entries: Option<LruCache<String, Variants>>,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+/// Hit and miss counts for a [`FormatCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to parse the format code
+    pub misses: u64,
+    /// Format codes currently cached
+    pub len: usize,
+    /// Maximum number of cached format codes
+    pub capacity: usize,
+}
+
+impl FormatCache {
+    /// Create a cache holding up to `capacity` format codes.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(CacheState {
+                entries: None,
+                capacity,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    /// Create a cache that stores nothing; every lookup parses.
+    pub const fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    /// Get the parsed format for a code, parsing and caching it if needed.
+    ///
+    /// Cached formats are shared, not copied: repeated lookups return
+    /// handles to the same parsed format.
+    pub fn get_or_parse(&self, format_code: &str) -> Result<CompiledFormat, ParseError> {
+        self.get_or_parse_with_options(format_code, &ParseOptions::default())
+    }
+
+    /// Get the parsed format for a code and parse options, parsing and
+    /// caching it if needed.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{ExtraSections, FormatCache, ParseError, ParseOptions};
+    ///
+    /// let cache = FormatCache::new(16);
+    /// let strict = ParseOptions {
+    ///     extra_sections: ExtraSections::Error,
+    ///     ..Default::default()
+    /// };
+    /// let code = "0;-0;0;@;0.00";
+    /// assert!(cache.get_or_parse(code).is_ok());
+    /// assert_eq!(
+    ///     cache.get_or_parse_with_options(code, &strict).unwrap_err(),
+    ///     ParseError::TooManySections
+    /// );
+    /// ```
+    pub fn get_or_parse_with_options(
+        &self,
+        format_code: &str,
+        options: &ParseOptions,
+    ) -> Result<CompiledFormat, ParseError> {
+        let parse =
+            || NumberFormat::parse_with_options(format_code, options).map(CompiledFormat::from);
+        let mut state = self.lock();
+        if state.capacity == 0 {
+            state.misses += 1;
+            drop(state);
+            return parse();
+        }
+
+        let capacity = state.capacity;
+        let cached = state
+            .entries_mut(capacity)
+            .get(format_code)
+            .and_then(|variants| variants.iter().find(|(o, _)| o == options))
+            .map(|(_, fmt)| fmt.clone());
+        if let Some(fmt) = cached {
+            state.hits += 1;
+            return Ok(fmt);
+        }
+
+        state.misses += 1;
+        // Parse without the lock so other lookups aren't held up
+        drop(state);
+        let fmt = parse()?;
+
+        let mut state = self.lock();
+        let capacity = state.capacity;
+        if capacity == 0 {
+            return Ok(fmt);
+        }
+        let entries = state.entries_mut(capacity);
+        match entries.get_mut(format_code) {
+            Some(variants) => {
+                // Another thread may have parsed it meanwhile; share its handle
+                if let Some((_, cached)) = variants.iter().find(|(o, _)| o == options) {
+                    return Ok(cached.clone());
+                }
+                if variants.len() == MAX_VARIANTS {
+                    variants.remove(0);
+                }
+                variants.push((*options, fmt.clone()));
+            }
+            None => {
+                let variants = smallvec::smallvec![(*options, fmt.clone())];
+                entries.put(format_code.to_string(), variants);
+            }
+        }
+        Ok(fmt)
+    }
+
+    /// Parse (or look up) a format code and format a value with it.
+    pub fn format(
+        &self,
+        value: f64,
+        format_code: &str,
+        opts: &crate::FormatOptions,
+    ) -> Result<String, ParseError> {
+        Ok(self.get_or_parse(format_code)?.format(value, opts))
+    }
+
+    /// Remove every cached format. Statistics are kept.
+    pub fn clear(&self) {
+        if let Some(entries) = &mut self.lock().entries {
+            entries.clear();
+        }
+    }
 
-    let cache =
-        cache_guard.get_or_insert_with(|| LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap()));
+    /// Reset the hit and miss counts to zero.
+    pub fn reset_stats(&self) {
+        let mut state = self.lock();
+        state.hits = 0;
+        state.misses = 0;
+    }
+
+    /// Current hit/miss counts and size.
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            len: state.entries.as_ref().map_or(0, LruCache::len),
+            capacity: state.capacity,
+        }
+    }
+
+    /// Maximum number of cached format codes.
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
 
-    if let Some(fmt) = cache.get(format_code) {
-        return Ok(fmt.clone());
+    /// Change the capacity, evicting the least recently used entries if the
+    /// cache shrinks. A capacity of 0 disables caching.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut state = self.lock();
+        state.capacity = capacity;
+        match NonZeroUsize::new(capacity) {
+            Some(capacity) => {
+                if let Some(entries) = &mut state.entries {
+                    entries.resize(capacity);
+                }
+            }
+            None => state.entries = None,
+        }
     }
 
-    let fmt = NumberFormat::parse(format_code)?;
-    cache.put(format_code.to_string(), fmt.clone());
-    Ok(fmt)
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        // A panic while holding the lock can't leave the LRU inconsistent
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for FormatCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+impl CacheState {
+    /// The entries, created on first use. `capacity` must be non-zero.
+    fn entries_mut(&mut self, capacity: usize) -> &mut LruCache<String, Variants> {
+        self.entries.get_or_insert_with(|| {
+            LruCache::new(NonZeroUsize::new(capacity).expect("capacity checked by caller"))
+        })
+    }
+}
+
+/// Get or parse a format code, using the global cache.
+pub(crate) fn get_or_parse(format_code: &str) -> Result<CompiledFormat, ParseError> {
+    GLOBAL.get_or_parse(format_code)
 }
@@ -0,0 +1,198 @@
+//! Calendar systems for date sections
+//!
+//! A section's calendar comes from the `B2` prefix (Hijri) or from the
+//! calendar type in bits 16–23 of its locale tag, numbered like Windows'
+//! `CAL_*` constants: `[$-30411]` is Japanese eras, `[$-40404]` the
+//! Republic of China, `[$-7041E]` Thai Buddhist and `[$-8040D]` Hebrew.
+//! The Japanese, ROC and Thai calendars only renumber the year; the Hijri
+//! and Hebrew calendars have their own months and days.
+//!
+//! Without a calendar type, the era codes `g` and `e` still follow the
+//! section's language: Japanese eras under `[$-411]`, ROC years under
+//! `[$-404]` and Buddhist years under `[$-41E]`.
+
+use crate::era::Era;
+
+/// ROC era names for `g`, `gg` and `ggg`
+const ROC_ERA_NAMES: [&str; 3] = ["民國", "民國", "中華民國"];
+
+/// Buddhist Era names for `g`, `gg` and `ggg`
+const BUDDHIST_ERA_NAMES: [&str; 3] = ["พ.ศ.", "พ.ศ.", "พุทธศักราช"];
+
+/// A calendar dates can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CalendarSystem {
+    Gregorian,
+    /// Japanese imperial eras (calendar type 03)
+    Japanese,
+    /// Republic of China (Minguo) years, counted from 1912 (calendar type 04)
+    Roc,
+    /// Hijri, from `B2` or calendar type 06
+    Hijri,
+    /// Thai solar calendar with Buddhist Era years, Gregorian + 543
+    /// (calendar type 07)
+    ThaiBuddhist,
+    /// Hebrew lunisolar calendar (calendar type 08)
+    Hebrew,
+}
+
+/// A year counted within an era, with the era's names for `g`, `gg` and
+/// `ggg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EraYear {
+    pub year: i32,
+    pub names: [&'static str; 3],
+}
+
+impl CalendarSystem {
+    /// The calendar named by an LCID's calendar type, if it names one we
+    /// support.
+    pub fn from_calendar_type(lcid: u32) -> Option<Self> {
+        match (lcid >> 16) & 0xFF {
+            0x03 => Some(CalendarSystem::Japanese),
+            0x04 => Some(CalendarSystem::Roc),
+            0x06 => Some(CalendarSystem::Hijri),
+            0x07 => Some(CalendarSystem::ThaiBuddhist),
+            0x08 => Some(CalendarSystem::Hebrew),
+            _ => None,
+        }
+    }
+
+    /// The calendar whose eras `g` and `e` show for an LCID: its calendar
+    /// type if that has eras, otherwise the one its language uses.
+    pub fn for_eras(lcid: u32) -> Option<Self> {
+        match Self::from_calendar_type(lcid) {
+            Some(calendar) if calendar.has_eras() => Some(calendar),
+            _ if crate::era::is_japanese_lcid(lcid) => Some(CalendarSystem::Japanese),
+            // Chinese (Taiwan) only; other Chinese locales are Gregorian
+            _ if lcid & 0xFFFF == 0x0404 => Some(CalendarSystem::Roc),
+            _ if lcid & 0x3FF == 0x1E => Some(CalendarSystem::ThaiBuddhist),
+            _ => None,
+        }
+    }
+
+    /// Whether the calendar counts years within eras.
+    pub fn has_eras(self) -> bool {
+        matches!(
+            self,
+            CalendarSystem::Japanese | CalendarSystem::Roc | CalendarSystem::ThaiBuddhist
+        )
+    }
+
+    /// The era year of a Gregorian date, or `None` for calendars without
+    /// eras and dates before the calendar's first era.
+    pub fn era_year(self, year: i32, month: u32, day: u32) -> Option<EraYear> {
+        match self {
+            CalendarSystem::Japanese => {
+                crate::era::japanese_era(year, month, day).map(|(era, era_year)| EraYear {
+                    year: era_year,
+                    names: [era.letter, era.abbr, era.name],
+                })
+            }
+            CalendarSystem::Roc if year >= 1912 => Some(EraYear {
+                year: year - 1911,
+                names: ROC_ERA_NAMES,
+            }),
+            CalendarSystem::ThaiBuddhist => Some(EraYear {
+                year: year + 543,
+                names: BUDDHIST_ERA_NAMES,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The era names of calendars with a single era.
+    pub fn single_era_names(self) -> Option<[&'static str; 3]> {
+        match self {
+            CalendarSystem::Roc => Some(ROC_ERA_NAMES),
+            CalendarSystem::ThaiBuddhist => Some(BUDDHIST_ERA_NAMES),
+            _ => None,
+        }
+    }
+
+    /// The Gregorian year for an era year read back from text. Japanese
+    /// years need the era they count from.
+    pub fn gregorian_year(self, era_year: i32, japanese: Option<&Era>) -> Option<i32> {
+        match self {
+            CalendarSystem::Japanese => japanese.map(|era| era.start.0 + era_year - 1),
+            CalendarSystem::Roc => Some(era_year + 1911),
+            CalendarSystem::ThaiBuddhist => Some(era_year - 543),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_types() {
+        assert_eq!(
+            CalendarSystem::from_calendar_type(0x7041E),
+            Some(CalendarSystem::ThaiBuddhist)
+        );
+        assert_eq!(
+            CalendarSystem::from_calendar_type(0x40404),
+            Some(CalendarSystem::Roc)
+        );
+        assert_eq!(
+            CalendarSystem::from_calendar_type(0x8040D),
+            Some(CalendarSystem::Hebrew)
+        );
+        assert_eq!(CalendarSystem::from_calendar_type(0x404), None);
+        // The numeral system byte doesn't affect the calendar
+        assert_eq!(
+            CalendarSystem::from_calendar_type(0x0D07_041E),
+            Some(CalendarSystem::ThaiBuddhist)
+        );
+    }
+
+    #[test]
+    fn test_eras_follow_language() {
+        assert_eq!(
+            CalendarSystem::for_eras(0x411),
+            Some(CalendarSystem::Japanese)
+        );
+        assert_eq!(CalendarSystem::for_eras(0x404), Some(CalendarSystem::Roc));
+        assert_eq!(
+            CalendarSystem::for_eras(0x41E),
+            Some(CalendarSystem::ThaiBuddhist)
+        );
+        assert_eq!(CalendarSystem::for_eras(0x804), None);
+        assert_eq!(CalendarSystem::for_eras(0x409), None);
+        // A calendar type with eras wins over the language
+        assert_eq!(
+            CalendarSystem::for_eras(0x70409),
+            Some(CalendarSystem::ThaiBuddhist)
+        );
+        // One without eras leaves it to the language
+        assert_eq!(
+            CalendarSystem::for_eras(0x80411),
+            Some(CalendarSystem::Japanese)
+        );
+    }
+
+    #[test]
+    fn test_era_years() {
+        let roc = CalendarSystem::Roc.era_year(2024, 6, 15).unwrap();
+        assert_eq!(roc.year, 113);
+        assert_eq!(CalendarSystem::Roc.era_year(1912, 1, 1).unwrap().year, 1);
+        assert!(CalendarSystem::Roc.era_year(1911, 12, 31).is_none());
+        let thai = CalendarSystem::ThaiBuddhist.era_year(2024, 6, 15).unwrap();
+        assert_eq!(thai.year, 2567);
+        let japanese = CalendarSystem::Japanese.era_year(2024, 6, 15).unwrap();
+        assert_eq!((japanese.year, japanese.names[2]), (6, "令和"));
+        assert!(CalendarSystem::Hebrew.era_year(2024, 6, 15).is_none());
+
+        assert_eq!(CalendarSystem::Roc.gregorian_year(113, None), Some(2024));
+        assert_eq!(
+            CalendarSystem::ThaiBuddhist.gregorian_year(2567, None),
+            Some(2024)
+        );
+        assert_eq!(
+            CalendarSystem::Japanese.gregorian_year(6, crate::era::era_named("令和")),
+            Some(2024)
+        );
+    }
+}
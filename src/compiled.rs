@@ -0,0 +1,64 @@
+//! Shared handle to a parsed format.
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::ast::NumberFormat;
+use crate::error::ParseError;
+
+/// A parsed format behind an [`Arc`], cheap to clone and share across threads.
+///
+/// Dereferences to [`NumberFormat`], so all formatting methods are available.
+/// The per-section number analysis is computed at parse time, so every
+/// clone formats without further setup. [`FormatCache`](crate::FormatCache)
+/// hands these out, so a pipeline can look a code up once and give each
+/// worker a clone instead of going back to the cache for every cell.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{CompiledFormat, FormatOptions};
+///
+/// let fmt = CompiledFormat::parse("#,##0.00").unwrap();
+/// let handles: Vec<_> = (1..=3)
+///     .map(|i| {
+///         let fmt = fmt.clone();
+///         std::thread::spawn(move || fmt.format(i as f64 * 1000.0, &FormatOptions::default()))
+///     })
+///     .collect();
+/// let results: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+/// assert_eq!(results, ["1,000.00", "2,000.00", "3,000.00"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledFormat(Arc<NumberFormat>);
+
+impl CompiledFormat {
+    /// Parse a format code into a shareable handle.
+    pub fn parse(format_code: &str) -> Result<Self, ParseError> {
+        NumberFormat::parse(format_code).map(Self::from)
+    }
+
+    /// Whether two handles share the same parsed format.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl From<NumberFormat> for CompiledFormat {
+    fn from(format: NumberFormat) -> Self {
+        Self(Arc::new(format))
+    }
+}
+
+impl Deref for CompiledFormat {
+    type Target = NumberFormat;
+
+    fn deref(&self) -> &NumberFormat {
+        &self.0
+    }
+}
+
+impl AsRef<NumberFormat> for CompiledFormat {
+    fn as_ref(&self) -> &NumberFormat {
+        &self.0
+    }
+}
@@ -22,7 +22,8 @@ use crate::options::DateSystem;
 
 /// Convert an Excel serial number to a date (year, month, day).
 ///
-/// Returns `None` if the serial number is invalid (negative or zero for some systems).
+/// Returns `None` if the serial number is invalid: below 1 in the 1900
+/// system, or negative in the 1904 system (where 0 is January 1, 1904).
 ///
 /// # Arguments
 /// * `serial` - The Excel serial number (integer part is the date)
@@ -38,13 +39,10 @@ use crate::options::DateSystem;
 pub fn serial_to_date(serial: f64, system: DateSystem) -> Option<(i32, u32, u32)> {
     let days = serial.floor() as i64;
 
-    if days < 1 {
-        return None;
-    }
-
     match system {
-        DateSystem::Date1900 => serial_to_date_1900(days),
-        DateSystem::Date1904 => serial_to_date_1904(days),
+        DateSystem::Date1900 if days >= 1 => serial_to_date_1900(days),
+        DateSystem::Date1904 if days >= 0 => serial_to_date_1904(days),
+        _ => None,
     }
 }
 
@@ -219,6 +217,54 @@ fn date_to_serial_1904(year: i32, month: u32, day: u32) -> f64 {
     date_to_serial_1900(year, month, day) - 1462.0
 }
 
+/// Convert a serial number from one date system to another.
+///
+/// Dates in the 1904 system are 1462 days lower than in the 1900 system;
+/// the time of day is unchanged. Use this when copying date values between
+/// workbooks with different date systems. Durations such as `[h]:mm` values
+/// do not depend on the date system and should not be converted.
+///
+/// Dates before 1904 have no 1904-system serial, so converting them gives a
+/// negative serial, which Excel cannot display.
+///
+/// # Examples
+/// ```
+/// use ssfmt::date_serial::convert_system;
+/// use ssfmt::DateSystem;
+///
+/// // January 1, 2024 at noon
+/// assert_eq!(convert_system(45292.5, DateSystem::Date1900, DateSystem::Date1904), 43830.5);
+/// assert_eq!(convert_system(43830.5, DateSystem::Date1904, DateSystem::Date1900), 45292.5);
+/// assert_eq!(convert_system(100.0, DateSystem::Date1900, DateSystem::Date1900), 100.0);
+/// ```
+pub fn convert_system(serial: f64, from: DateSystem, to: DateSystem) -> f64 {
+    match (from, to) {
+        (DateSystem::Date1900, DateSystem::Date1904) => serial - 1462.0,
+        (DateSystem::Date1904, DateSystem::Date1900) => serial + 1462.0,
+        _ => serial,
+    }
+}
+
+/// Convert a duration to a serial number: a day fraction, with whole days
+/// in the integer part.
+///
+/// Elapsed-time formats such as `[h]:mm:ss` show the result as a duration;
+/// durations do not depend on the date system.
+///
+/// # Examples
+/// ```
+/// use ssfmt::date_serial::duration_to_serial;
+/// use std::time::Duration;
+///
+/// assert_eq!(duration_to_serial(Duration::from_secs(6 * 3600)), 0.25);
+/// assert_eq!(duration_to_serial(Duration::from_secs(36 * 3600)), 1.5);
+/// ```
+pub fn duration_to_serial(duration: std::time::Duration) -> f64 {
+    let days = (duration.as_secs() / 86_400) as f64;
+    let rest = (duration.as_secs() % 86_400) as f64 + f64::from(duration.subsec_nanos()) / 1e9;
+    days + rest / 86_400.0
+}
+
 /// Get the day of the week from a serial number.
 ///
 /// # Arguments
@@ -248,6 +294,228 @@ pub fn serial_to_weekday(serial: f64, system: DateSystem) -> u32 {
     }
 }
 
+/// Milliseconds in a day.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// Split a serial into a calendar date and the millisecond of the day,
+/// rounding to the nearest millisecond.
+///
+/// Serial 0 in the 1900 system ("January 0, 1900") becomes December 31,
+/// 1899. Returns `None` for negative or non-finite serials.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn split_serial(serial: f64, system: DateSystem) -> Option<((i32, u32, u32), u32)> {
+    if !serial.is_finite() || serial < 0.0 {
+        return None;
+    }
+
+    let mut days = serial.floor() as i64;
+    let mut ms = ((serial - serial.floor()) * MS_PER_DAY as f64).round() as i64;
+    if ms >= MS_PER_DAY {
+        days += 1;
+        ms -= MS_PER_DAY;
+    }
+
+    let date = if days == 0 && system == DateSystem::Date1900 {
+        (1899, 12, 31)
+    } else {
+        serial_to_date(days as f64, system)?
+    };
+    Some((date, ms as u32))
+}
+
+/// Fraction of a day for a time of day.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn day_fraction(seconds_from_midnight: u32, nanosecond: u32) -> f64 {
+    (seconds_from_midnight as f64 + nanosecond as f64 / 1e9) / 86_400.0
+}
+
+/// Convert an Excel serial number to a date and time (requires `chrono` feature).
+///
+/// The time of day is rounded to the nearest millisecond, which absorbs the
+/// floating point error in stored serials (so `0.5` is exactly noon).
+///
+/// Returns `None` for serials with no real date: negative serials, and in
+/// the 1900 system serial 60, Excel's phantom February 29, 1900. Serial 0
+/// in the 1900 system ("January 0, 1900") is December 31, 1899, so pure
+/// time values keep their time of day.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use ssfmt::date_serial::serial_to_naive_datetime;
+/// use ssfmt::DateSystem;
+///
+/// let dt = serial_to_naive_datetime(45292.75, DateSystem::Date1900).unwrap();
+/// assert_eq!(dt, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(18, 0, 0).unwrap());
+/// assert_eq!(serial_to_naive_datetime(60.0, DateSystem::Date1900), None);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn serial_to_naive_datetime(serial: f64, system: DateSystem) -> Option<chrono::NaiveDateTime> {
+    let ((year, month, day), ms) = split_serial(serial, system)?;
+    // The phantom February 29, 1900 has no chrono date, so gives None
+    let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+    let time =
+        chrono::NaiveTime::from_num_seconds_from_midnight_opt(ms / 1000, ms % 1000 * 1_000_000)?;
+    Some(date.and_time(time))
+}
+
+/// Convert a date and time to an Excel serial number (requires `chrono` feature).
+///
+/// The inverse of [`serial_to_naive_datetime`]: dates from March 1, 1900
+/// on account for the phantom leap day in the 1900 system. Dates before
+/// the system's first day give zero or negative serials, which Excel cannot
+/// display.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use ssfmt::date_serial::naive_datetime_to_serial;
+/// use ssfmt::DateSystem;
+///
+/// let dt = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(18, 0, 0).unwrap();
+/// assert_eq!(naive_datetime_to_serial(dt, DateSystem::Date1900), 45292.75);
+/// assert_eq!(naive_datetime_to_serial(dt, DateSystem::Date1904), 43830.75);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn naive_datetime_to_serial(dt: chrono::NaiveDateTime, system: DateSystem) -> f64 {
+    use chrono::{Datelike, Timelike};
+
+    let date = dt.date();
+    let time = dt.time();
+    date_to_serial(date.year(), date.month(), date.day(), system)
+        + day_fraction(time.num_seconds_from_midnight(), time.nanosecond())
+}
+
+/// Fraction of a day for a `chrono::NaiveTime`.
+#[cfg(feature = "chrono")]
+pub(crate) fn naive_time_to_day_fraction(time: chrono::NaiveTime) -> f64 {
+    use chrono::Timelike;
+
+    day_fraction(time.num_seconds_from_midnight(), time.nanosecond())
+}
+
+/// Convert an Excel serial number to a date and time (requires `time` feature).
+///
+/// Works like [`serial_to_naive_datetime`] for the `time` crate: the time
+/// of day is rounded to the nearest millisecond, and negative serials and
+/// the phantom February 29, 1900 (serial 60) give `None`.
+///
+/// # Examples
+/// ```
+/// use ssfmt::date_serial::serial_to_primitive_datetime;
+/// use ssfmt::DateSystem;
+/// use time::macros::datetime;
+///
+/// assert_eq!(
+///     serial_to_primitive_datetime(45292.75, DateSystem::Date1900),
+///     Some(datetime!(2024-01-01 18:00))
+/// );
+/// ```
+#[cfg(feature = "time")]
+pub fn serial_to_primitive_datetime(
+    serial: f64,
+    system: DateSystem,
+) -> Option<time::PrimitiveDateTime> {
+    let ((year, month, day), ms) = split_serial(serial, system)?;
+    let month = time::Month::try_from(month as u8).ok()?;
+    let date = time::Date::from_calendar_date(year, month, day as u8).ok()?;
+    let time = time::Time::from_hms_milli(
+        (ms / 3_600_000) as u8,
+        (ms / 60_000 % 60) as u8,
+        (ms / 1000 % 60) as u8,
+        (ms % 1000) as u16,
+    )
+    .ok()?;
+    Some(time::PrimitiveDateTime::new(date, time))
+}
+
+/// Convert a date and time to an Excel serial number (requires `time` feature).
+///
+/// The inverse of [`serial_to_primitive_datetime`].
+///
+/// # Examples
+/// ```
+/// use ssfmt::date_serial::primitive_datetime_to_serial;
+/// use ssfmt::DateSystem;
+/// use time::macros::datetime;
+///
+/// let dt = datetime!(2024-01-01 18:00);
+/// assert_eq!(primitive_datetime_to_serial(dt, DateSystem::Date1900), 45292.75);
+/// assert_eq!(primitive_datetime_to_serial(dt, DateSystem::Date1904), 43830.75);
+/// ```
+#[cfg(feature = "time")]
+pub fn primitive_datetime_to_serial(dt: time::PrimitiveDateTime, system: DateSystem) -> f64 {
+    date_to_serial(dt.year(), dt.month() as u32, dt.day() as u32, system)
+        + time_to_day_fraction(dt.time())
+}
+
+/// Fraction of a day for a `time::Time`.
+#[cfg(feature = "time")]
+pub(crate) fn time_to_day_fraction(time: time::Time) -> f64 {
+    let seconds = time.hour() as u32 * 3600 + time.minute() as u32 * 60 + time.second() as u32;
+    day_fraction(seconds, time.nanosecond())
+}
+
+/// Convert an Excel serial number to a civil date and time (requires `jiff` feature).
+///
+/// Works like [`serial_to_naive_datetime`] for `jiff`: the time of day is
+/// rounded to the nearest millisecond, and negative serials and the phantom
+/// February 29, 1900 (serial 60) give `None`.
+///
+/// # Examples
+/// ```
+/// use jiff::civil::date;
+/// use ssfmt::date_serial::serial_to_civil_datetime;
+/// use ssfmt::DateSystem;
+///
+/// assert_eq!(
+///     serial_to_civil_datetime(45292.75, DateSystem::Date1900),
+///     Some(date(2024, 1, 1).at(18, 0, 0, 0))
+/// );
+/// ```
+#[cfg(feature = "jiff")]
+pub fn serial_to_civil_datetime(serial: f64, system: DateSystem) -> Option<jiff::civil::DateTime> {
+    let ((year, month, day), ms) = split_serial(serial, system)?;
+    jiff::civil::DateTime::new(
+        i16::try_from(year).ok()?,
+        month as i8,
+        day as i8,
+        (ms / 3_600_000) as i8,
+        (ms / 60_000 % 60) as i8,
+        (ms / 1000 % 60) as i8,
+        (ms % 1000 * 1_000_000) as i32,
+    )
+    .ok()
+}
+
+/// Convert a civil date and time to an Excel serial number (requires `jiff` feature).
+///
+/// The inverse of [`serial_to_civil_datetime`].
+///
+/// # Examples
+/// ```
+/// use jiff::civil::date;
+/// use ssfmt::date_serial::civil_datetime_to_serial;
+/// use ssfmt::DateSystem;
+///
+/// let dt = date(2024, 1, 1).at(18, 0, 0, 0);
+/// assert_eq!(civil_datetime_to_serial(dt, DateSystem::Date1900), 45292.75);
+/// assert_eq!(civil_datetime_to_serial(dt, DateSystem::Date1904), 43830.75);
+/// ```
+#[cfg(feature = "jiff")]
+pub fn civil_datetime_to_serial(dt: jiff::civil::DateTime, system: DateSystem) -> f64 {
+    date_to_serial(dt.year() as i32, dt.month() as u32, dt.day() as u32, system)
+        + civil_time_to_day_fraction(dt.time())
+}
+
+/// Fraction of a day for a `jiff::civil::Time`.
+#[cfg(feature = "jiff")]
+pub(crate) fn civil_time_to_day_fraction(time: jiff::civil::Time) -> f64 {
+    let seconds = time.hour() as u32 * 3600 + time.minute() as u32 * 60 + time.second() as u32;
+    day_fraction(seconds, time.subsec_nanosecond() as u32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
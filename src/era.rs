@@ -0,0 +1,100 @@
+//! Japanese imperial eras
+//!
+//! Excel's `g`/`gg`/`ggg` and `e` date codes show the era and the year
+//! within it when the section has a Japanese `[$-411]` locale. Each era
+//! starts at the accession date Excel uses, and its first year is year 1.
+
+/// A Japanese imperial era.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Era {
+    /// First day of the era as (year, month, day)
+    pub start: (i32, u32, u32),
+    /// Latin letter shown for `g` (R for Reiwa)
+    pub letter: &'static str,
+    /// First character of the name, shown for `gg` (令)
+    pub abbr: &'static str,
+    /// Full name, shown for `ggg` (令和)
+    pub name: &'static str,
+}
+
+/// Eras from Meiji on, oldest first.
+const ERAS: [Era; 5] = [
+    Era {
+        start: (1868, 9, 8),
+        letter: "M",
+        abbr: "明",
+        name: "明治",
+    },
+    Era {
+        start: (1912, 7, 30),
+        letter: "T",
+        abbr: "大",
+        name: "大正",
+    },
+    Era {
+        start: (1926, 12, 25),
+        letter: "S",
+        abbr: "昭",
+        name: "昭和",
+    },
+    Era {
+        start: (1989, 1, 8),
+        letter: "H",
+        abbr: "平",
+        name: "平成",
+    },
+    Era {
+        start: (2019, 5, 1),
+        letter: "R",
+        abbr: "令",
+        name: "令和",
+    },
+];
+
+/// The era a Gregorian date falls in and the year within that era.
+///
+/// Dates before Meiji have no era and return `None`.
+pub(crate) fn japanese_era(year: i32, month: u32, day: u32) -> Option<(&'static Era, i32)> {
+    ERAS.iter()
+        .rev()
+        .find(|era| (year, month, day) >= era.start)
+        .map(|era| (era, year - era.start.0 + 1))
+}
+
+/// The era shown as `text` by any of `g`, `gg` or `ggg`.
+pub(crate) fn era_named(text: &str) -> Option<&'static Era> {
+    ERAS.iter().find(|era| {
+        text.eq_ignore_ascii_case(era.letter) || text == era.abbr || text == era.name
+    })
+}
+
+/// Returns true if an LCID selects a Japanese locale.
+pub(crate) fn is_japanese_lcid(lcid: u32) -> bool {
+    lcid & 0x3FF == 0x11
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_era_boundaries() {
+        let (era, year) = japanese_era(2019, 4, 30).unwrap();
+        assert_eq!((era.name, year), ("平成", 31));
+        let (era, year) = japanese_era(2019, 5, 1).unwrap();
+        assert_eq!((era.name, year), ("令和", 1));
+        let (era, year) = japanese_era(1989, 1, 7).unwrap();
+        assert_eq!((era.name, year), ("昭和", 64));
+        let (era, year) = japanese_era(1900, 1, 1).unwrap();
+        assert_eq!((era.letter, year), ("M", 33));
+        assert!(japanese_era(1868, 9, 7).is_none());
+    }
+
+    #[test]
+    fn test_japanese_lcid() {
+        assert!(is_japanese_lcid(0x411));
+        assert!(is_japanese_lcid(0x0003_0411));
+        assert!(!is_japanese_lcid(0x409));
+        assert!(!is_japanese_lcid(0x404));
+    }
+}
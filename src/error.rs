@@ -1,21 +1,39 @@
 //! Error types for parsing and formatting.
 
+use std::fmt;
+use std::ops::Range;
+
 use thiserror::Error;
 
 /// Errors that can occur when parsing a format code.
+///
+/// Errors at a location in the format code carry the byte span and the
+/// offending text, so an editor can underline exactly what broke the
+/// format; [`render_diagnostic`](Self::render_diagnostic) prints a
+/// caret-annotated message.
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum ParseError {
-    #[error("unexpected token at position {position}: found '{found}'")]
-    UnexpectedToken { position: usize, found: char },
+    #[error("unexpected token at position {}: found '{found}'", span.start)]
+    UnexpectedToken { span: Range<usize>, found: String },
+
+    #[error("unterminated bracket at position {}", span.start)]
+    UnterminatedBracket { span: Range<usize>, text: String },
+
+    #[error("unterminated quoted string at position {}", span.start)]
+    UnterminatedQuote { span: Range<usize>, text: String },
 
-    #[error("unterminated bracket at position {position}")]
-    UnterminatedBracket { position: usize },
+    #[error("backslash at position {} escapes nothing", span.start)]
+    DanglingEscape { span: Range<usize> },
 
-    #[error("invalid condition at position {position}: {reason}")]
-    InvalidCondition { position: usize, reason: String },
+    #[error("invalid condition at position {}: {reason}", span.start)]
+    InvalidCondition {
+        span: Range<usize>,
+        text: String,
+        reason: String,
+    },
 
-    #[error("invalid locale code at position {position}")]
-    InvalidLocaleCode { position: usize },
+    #[error("invalid locale code at position {}", span.start)]
+    InvalidLocaleCode { span: Range<usize>, text: String },
 
     #[error("too many sections (maximum 4 allowed)")]
     TooManySections,
@@ -25,6 +43,232 @@ pub enum ParseError {
 
     #[error("invalid format ID: {0} is not a recognized built-in format")]
     InvalidFormatId(u32),
+
+    #[error("format code too complex at position {}: more than {max} {limit}", span.start)]
+    FormatTooComplex {
+        span: Range<usize>,
+        limit: &'static str,
+        max: usize,
+    },
+
+    /// A valid construct this crate does not implement yet, such as
+    /// `[DBNum4]`. Callers may show the raw number instead; malformed codes
+    /// get the other variants.
+    #[error("unsupported feature: {feature}")]
+    Unsupported { feature: &'static str },
+}
+
+/// Identifies the kind of a [`ParseError`], independent of its details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    UnexpectedToken,
+    UnterminatedBracket,
+    UnterminatedQuote,
+    DanglingEscape,
+    InvalidCondition,
+    InvalidLocaleCode,
+    TooManySections,
+    EmptyFormat,
+    InvalidFormatId,
+    FormatTooComplex,
+    Unsupported,
+}
+
+impl DiagnosticCode {
+    /// Stable kebab-case name, e.g. `"unterminated-bracket"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::UnexpectedToken => "unexpected-token",
+            DiagnosticCode::UnterminatedBracket => "unterminated-bracket",
+            DiagnosticCode::UnterminatedQuote => "unterminated-quote",
+            DiagnosticCode::DanglingEscape => "dangling-escape",
+            DiagnosticCode::InvalidCondition => "invalid-condition",
+            DiagnosticCode::InvalidLocaleCode => "invalid-locale-code",
+            DiagnosticCode::TooManySections => "too-many-sections",
+            DiagnosticCode::EmptyFormat => "empty-format",
+            DiagnosticCode::InvalidFormatId => "invalid-format-id",
+            DiagnosticCode::FormatTooComplex => "format-too-complex",
+            DiagnosticCode::Unsupported => "unsupported",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ParseError {
+    /// The kind of this error.
+    pub fn code(&self) -> DiagnosticCode {
+        match self {
+            ParseError::UnexpectedToken { .. } => DiagnosticCode::UnexpectedToken,
+            ParseError::UnterminatedBracket { .. } => DiagnosticCode::UnterminatedBracket,
+            ParseError::UnterminatedQuote { .. } => DiagnosticCode::UnterminatedQuote,
+            ParseError::DanglingEscape { .. } => DiagnosticCode::DanglingEscape,
+            ParseError::InvalidCondition { .. } => DiagnosticCode::InvalidCondition,
+            ParseError::InvalidLocaleCode { .. } => DiagnosticCode::InvalidLocaleCode,
+            ParseError::TooManySections => DiagnosticCode::TooManySections,
+            ParseError::EmptyFormat => DiagnosticCode::EmptyFormat,
+            ParseError::InvalidFormatId(_) => DiagnosticCode::InvalidFormatId,
+            ParseError::FormatTooComplex { .. } => DiagnosticCode::FormatTooComplex,
+            ParseError::Unsupported { .. } => DiagnosticCode::Unsupported,
+        }
+    }
+
+    /// Byte range of the offending text in the format code, if the error
+    /// has a location.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            ParseError::UnexpectedToken { span, .. }
+            | ParseError::UnterminatedBracket { span, .. }
+            | ParseError::UnterminatedQuote { span, .. }
+            | ParseError::DanglingEscape { span }
+            | ParseError::InvalidCondition { span, .. }
+            | ParseError::InvalidLocaleCode { span, .. }
+            | ParseError::FormatTooComplex { span, .. } => Some(span.clone()),
+            ParseError::TooManySections
+            | ParseError::EmptyFormat
+            | ParseError::InvalidFormatId(_)
+            | ParseError::Unsupported { .. } => None,
+        }
+    }
+
+    /// The offending text, if the error has a location. Errors about an
+    /// oversized format code only have a span, to avoid copying it.
+    pub fn offending_text(&self) -> Option<&str> {
+        match self {
+            ParseError::UnexpectedToken { found: text, .. }
+            | ParseError::UnterminatedBracket { text, .. }
+            | ParseError::UnterminatedQuote { text, .. }
+            | ParseError::InvalidCondition { text, .. }
+            | ParseError::InvalidLocaleCode { text, .. } => Some(text),
+            ParseError::DanglingEscape { .. } => Some("\\"),
+            ParseError::FormatTooComplex { .. }
+            | ParseError::TooManySections
+            | ParseError::EmptyFormat
+            | ParseError::InvalidFormatId(_)
+            | ParseError::Unsupported { .. } => None,
+        }
+    }
+
+    /// Render the error with the format code and a caret under the
+    /// offending text.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// let code = "0.00\" kg";
+    /// let err = NumberFormat::parse(code).unwrap_err();
+    /// assert_eq!(
+    ///     err.render_diagnostic(code),
+    ///     "error[unterminated-quote]: unterminated quoted string at position 4\n  \
+    ///      |\n  \
+    ///      | 0.00\" kg\n  \
+    ///      |     ^^^^"
+    /// );
+    /// ```
+    pub fn render_diagnostic(&self, format_code: &str) -> String {
+        let mut out = format!("error[{}]: {}", self.code(), self);
+        let Some(span) = self.span() else {
+            return out;
+        };
+        let (Some(before), Some(text)) = (format_code.get(..span.start), format_code.get(span))
+        else {
+            return out;
+        };
+        let indent = before.chars().count();
+        let carets = text.chars().count().max(1);
+        out.push_str("\n  |\n  | ");
+        out.push_str(format_code);
+        out.push_str("\n  | ");
+        out.extend(std::iter::repeat_n(' ', indent));
+        out.extend(std::iter::repeat_n('^', carets));
+        out
+    }
+}
+
+/// A recoverable problem found by [`NumberFormat::parse_lenient`](crate::NumberFormat::parse_lenient).
+///
+/// Each warning describes how the format code was repaired.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ParseWarning {
+    #[error("empty format code; treated as General")]
+    EmptyFormat,
+
+    #[error("unterminated bracket at position {}; closed at end of format", span.start)]
+    UnterminatedBracket { span: Range<usize>, text: String },
+
+    #[error("unterminated quoted string at position {}; closed at end of format", span.start)]
+    UnterminatedQuote { span: Range<usize>, text: String },
+
+    #[error("backslash at position {} escapes nothing; ignored", span.start)]
+    DanglingEscape { span: Range<usize> },
+
+    #[error("unmatched ']' at position {}; shown as text", span.start)]
+    StrayCloseBracket { span: Range<usize> },
+
+    #[error("unknown bracket content '{text}' at position {}; ignored", span.start)]
+    UnknownBracket { span: Range<usize>, text: String },
+
+    #[error("{ignored} section(s) after the fourth ignored, starting at position {}", span.start)]
+    TooManySections { span: Range<usize>, ignored: usize },
+
+    #[error("format code too complex at position {}: more than {max} {limit}; treated as General", span.start)]
+    FormatTooComplex {
+        span: Range<usize>,
+        limit: &'static str,
+        max: usize,
+    },
+
+    #[error("unsupported feature {feature} at position {}; ignored", span.start)]
+    Unsupported {
+        span: Range<usize>,
+        feature: &'static str,
+    },
+}
+
+impl ParseWarning {
+    /// Byte range of the text the warning is about, if any.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            ParseWarning::EmptyFormat => None,
+            ParseWarning::UnterminatedBracket { span, .. }
+            | ParseWarning::UnterminatedQuote { span, .. }
+            | ParseWarning::DanglingEscape { span }
+            | ParseWarning::StrayCloseBracket { span }
+            | ParseWarning::UnknownBracket { span, .. }
+            | ParseWarning::TooManySections { span, .. }
+            | ParseWarning::FormatTooComplex { span, .. }
+            | ParseWarning::Unsupported { span, .. } => Some(span.clone()),
+        }
+    }
+}
+
+/// A construct Excel refuses, reported by [`validate`](crate::validate).
+///
+/// Sections are numbered from 0.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ValidationError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error("too many sections (maximum 4 allowed); the fifth starts at position {}", span.start)]
+    TooManySections { span: Range<usize> },
+
+    #[error("section {section} mixes the text placeholder @ with number or date codes")]
+    TextMixedWithNumber { section: usize },
+
+    #[error("section {section} has a condition; only the first two sections may")]
+    ConditionNotAllowed { section: usize },
+
+    #[error("{count} sections have conditions; at most two may")]
+    TooManyConditions { count: usize },
+
+    #[error("unknown bracket content '{text}' at position {}", span.start)]
+    UnknownBracket { span: Range<usize>, text: String },
 }
 
 /// Errors that can occur when formatting a value.
@@ -41,4 +285,12 @@ pub enum FormatError {
 
     #[error("invalid serial number: {value}")]
     InvalidSerialNumber { value: f64 },
+
+    #[error("value needs {needed} columns but the cell is {width} wide")]
+    CellOverflow { needed: usize, width: usize },
+
+    /// The format uses a feature this crate does not implement yet;
+    /// `format` shows the raw number instead.
+    #[error("unsupported feature: {feature}")]
+    Unsupported { feature: &'static str },
 }
@@ -0,0 +1,253 @@
+//! C ABI (requires `ffi` feature).
+//!
+//! `extern "C"` functions for C, Python `ctypes` and other runtimes that can
+//! call a shared library. The declarations are in `include/ssfmt.h`,
+//! generated from this module with `cbindgen --config cbindgen.toml
+//! --output include/ssfmt.h`. Build the library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or
+//! `staticlib`).
+//!
+//! Strings are NUL-terminated UTF-8. Formatted text is written into a
+//! caller-owned buffer the way `snprintf` does it: the return value is the
+//! full length of the text, without the NUL, and if that is not less than
+//! `out_len` the text was truncated. Negative return values are errors,
+//! with the message available from [`ssfmt_last_error`] until the next call
+//! on the same thread. Values are formatted with default options (1900 date
+//! system, en-US locale).
+//!
+//! A panic never unwinds into the caller: it is reported as
+//! [`SSFMT_ERR_PANIC`] (or a null handle from [`ssfmt_parse`]) with the
+//! panic message as the last error.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::compiled::CompiledFormat;
+use crate::options::FormatOptions;
+
+/// A required pointer argument was null.
+pub const SSFMT_ERR_NULL: isize = -1;
+/// A string argument was not valid UTF-8.
+pub const SSFMT_ERR_UTF8: isize = -2;
+/// The format code could not be parsed, or the format ID is unknown.
+pub const SSFMT_ERR_PARSE: isize = -3;
+/// The library panicked; the message is available from [`ssfmt_last_error`].
+pub const SSFMT_ERR_PANIC: isize = -4;
+
+/// A parsed format, created by [`ssfmt_parse`] and released with
+/// [`ssfmt_free`].
+pub struct SsfmtFormat(CompiledFormat);
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// Run the body of an `extern "C"` function: clear the last error, then run
+/// `body`, turning a panic into `on_panic` instead of unwinding across the
+/// C ABI.
+fn ffi_entry<T>(on_panic: T, body: impl FnOnce() -> T) -> T {
+    LAST_ERROR.with(|last| last.borrow_mut().clear());
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        set_last_error(format!("panic: {}", panic_message(&*payload)));
+        on_panic
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Read a NUL-terminated UTF-8 argument.
+///
+/// # Safety
+/// `s` must be null or point to a NUL-terminated string.
+unsafe fn read_str<'a>(s: *const c_char) -> Result<&'a str, isize> {
+    if s.is_null() {
+        set_last_error("null pointer argument".to_string());
+        return Err(SSFMT_ERR_NULL);
+    }
+    CStr::from_ptr(s).to_str().map_err(|err| {
+        set_last_error(err.to_string());
+        SSFMT_ERR_UTF8
+    })
+}
+
+/// Copy `text` into `out_buf` like `snprintf`, returning its full length.
+///
+/// # Safety
+/// `out_buf` must be null or valid for writes of `out_len` bytes.
+unsafe fn write_out(text: &str, out_buf: *mut c_char, out_len: usize) -> isize {
+    if !out_buf.is_null() && out_len > 0 {
+        let n = truncate_len(text, out_len - 1);
+        ptr::copy_nonoverlapping(text.as_ptr(), out_buf.cast::<u8>(), n);
+        *out_buf.add(n) = 0;
+    }
+    text.len() as isize
+}
+
+/// The longest prefix of `text` that fits in `max` bytes without splitting
+/// a character.
+fn truncate_len(text: &str, max: usize) -> usize {
+    if text.len() <= max {
+        return text.len();
+    }
+    (0..=max)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+fn parse_result<T>(result: Result<T, crate::ParseError>) -> Result<T, isize> {
+    result.map_err(|err| {
+        set_last_error(err.to_string());
+        SSFMT_ERR_PARSE
+    })
+}
+
+/// Format `value` with the format code `code`.
+///
+/// Returns the length of the formatted text, or a negative `SSFMT_ERR_*`
+/// code. Pass a null `out_buf` to only measure the text.
+///
+/// # Safety
+/// `code` must point to a NUL-terminated string, and `out_buf` must be
+/// null or valid for writes of `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssfmt_format(
+    value: f64,
+    code: *const c_char,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> isize {
+    ffi_entry(SSFMT_ERR_PANIC, || {
+        let text = read_str(code).and_then(|code| parse_result(crate::format_default(value, code)));
+        match text {
+            Ok(text) => write_out(&text, out_buf, out_len),
+            Err(code) => code,
+        }
+    })
+}
+
+/// Format `value` with a built-in format ID, such as 14 for `m/d/yy`.
+///
+/// Returns the length of the formatted text, [`SSFMT_ERR_PARSE`] for an
+/// unknown ID, or [`SSFMT_ERR_PANIC`].
+///
+/// # Safety
+/// `out_buf` must be null or valid for writes of `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssfmt_format_with_id(
+    value: f64,
+    format_id: u32,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> isize {
+    ffi_entry(SSFMT_ERR_PANIC, || {
+        match parse_result(crate::format_with_id_default(value, format_id)) {
+            Ok(text) => write_out(&text, out_buf, out_len),
+            Err(code) => code,
+        }
+    })
+}
+
+/// Parse a format code once for formatting many values.
+///
+/// Returns null if the code cannot be parsed or parsing panicked. The
+/// handle may be used from any thread and must be released with
+/// [`ssfmt_free`].
+///
+/// # Safety
+/// `code` must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ssfmt_parse(code: *const c_char) -> *mut SsfmtFormat {
+    ffi_entry(ptr::null_mut(), || {
+        match read_str(code).and_then(|code| parse_result(CompiledFormat::parse(code))) {
+            Ok(format) => Box::into_raw(Box::new(SsfmtFormat(format))),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Format `value` with a format returned by [`ssfmt_parse`].
+///
+/// Returns the length of the formatted text, [`SSFMT_ERR_NULL`] if
+/// `format` is null, or [`SSFMT_ERR_PANIC`].
+///
+/// # Safety
+/// `format` must be null or a live handle from [`ssfmt_parse`], and
+/// `out_buf` must be null or valid for writes of `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssfmt_format_compiled(
+    format: *const SsfmtFormat,
+    value: f64,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> isize {
+    ffi_entry(SSFMT_ERR_PANIC, || {
+        let Some(format) = format.as_ref() else {
+            set_last_error("null pointer argument".to_string());
+            return SSFMT_ERR_NULL;
+        };
+        let text = format.0.format(value, &FormatOptions::default());
+        write_out(&text, out_buf, out_len)
+    })
+}
+
+/// Release a format returned by [`ssfmt_parse`]. Null is ignored.
+///
+/// # Safety
+/// `format` must be null or a handle from [`ssfmt_parse`] that has not
+/// been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ssfmt_free(format: *mut SsfmtFormat) {
+    if !format.is_null() {
+        drop(Box::from_raw(format));
+    }
+}
+
+/// Copy the error message of the last call on this thread into `out_buf`.
+///
+/// Every other function except [`ssfmt_free`] clears the message when it
+/// starts, so this returns the length of the message, which is 0 if the
+/// last call succeeded.
+///
+/// # Safety
+/// `out_buf` must be null or valid for writes of `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ssfmt_last_error(out_buf: *mut c_char, out_len: usize) -> isize {
+    LAST_ERROR.with(|last| write_out(&last.borrow(), out_buf, out_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_entry_sets_last_error() {
+        let result = ffi_entry(SSFMT_ERR_PANIC, || -> isize { panic!("boom") });
+        assert_eq!(result, SSFMT_ERR_PANIC);
+        LAST_ERROR.with(|last| assert_eq!(*last.borrow(), "panic: boom"));
+
+        let handle = ffi_entry(ptr::null_mut(), || -> *mut SsfmtFormat {
+            panic!("{} failed", "parse")
+        });
+        assert!(handle.is_null());
+        LAST_ERROR.with(|last| assert_eq!(*last.borrow(), "panic: parse failed"));
+
+        assert_eq!(ffi_entry(SSFMT_ERR_PANIC, || 3), 3);
+        LAST_ERROR.with(|last| assert!(last.borrow().is_empty()));
+    }
+}
@@ -0,0 +1,156 @@
+//! Standard date, time and fraction formats, ready to use.
+//!
+//! Each format is a static that is parsed the first time it is used and
+//! then shared, so it dereferences to a [`NumberFormat`]. The `to_iso_*`
+//! helpers format a serial number directly, and [`fraction_of`] builds a
+//! fraction format for any fixed denominator.
+//!
+//! Serial numbers carry no time zone. [`RFC3339`] labels the time as UTC
+//! with a `Z` suffix, so it only suits serials that already hold UTC.
+//!
+//! # Examples
+//! ```
+//! use ssfmt::formats;
+//! use ssfmt::{DateSystem, FormatOptions};
+//!
+//! let opts = FormatOptions::default();
+//! assert_eq!(formats::ISO_DATETIME.format(45306.5, &opts), "2024-01-15T12:00:00");
+//! assert_eq!(formats::ISO_DATE.code(), "yyyy-mm-dd");
+//! assert_eq!(
+//!     formats::to_iso_date(45306.0, DateSystem::Date1900).as_deref(),
+//!     Some("2024-01-15")
+//! );
+//! ```
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use crate::ast::NumberFormat;
+use crate::options::{DateSystem, FormatOptions, OverflowBehavior};
+
+/// A format code that is parsed on first use.
+pub struct StaticFormat {
+    code: &'static str,
+    parsed: OnceLock<NumberFormat>,
+}
+
+impl StaticFormat {
+    /// A format for `code`, which must parse without errors.
+    pub const fn new(code: &'static str) -> Self {
+        Self {
+            code,
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// The format code.
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+}
+
+impl Deref for StaticFormat {
+    type Target = NumberFormat;
+
+    fn deref(&self) -> &NumberFormat {
+        self.parsed
+            .get_or_init(|| NumberFormat::parse(self.code).expect("static format code parses"))
+    }
+}
+
+impl fmt::Debug for StaticFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StaticFormat").field(&self.code).finish()
+    }
+}
+
+/// ISO 8601 calendar date: `2024-01-15`
+pub static ISO_DATE: StaticFormat = StaticFormat::new("yyyy-mm-dd");
+
+/// ISO 8601 local date and time: `2024-01-15T12:30:00`
+pub static ISO_DATETIME: StaticFormat = StaticFormat::new("yyyy-mm-dd\"T\"hh:mm:ss");
+
+/// RFC 3339 timestamp in UTC with milliseconds: `2024-01-15T12:30:00.000Z`
+pub static RFC3339: StaticFormat = StaticFormat::new("yyyy-mm-dd\"T\"hh:mm:ss.000\"Z\"");
+
+/// 24-hour time of day: `12:30:00`
+pub static TIME_HMS: StaticFormat = StaticFormat::new("hh:mm:ss");
+
+/// Fraction in halves, as in Excel's "As halves" format: `1 1/2`
+pub static AS_HALVES: StaticFormat = StaticFormat::new("# ?/2");
+
+/// Fraction in quarters: `1 3/4`
+pub static AS_QUARTERS: StaticFormat = StaticFormat::new("# ?/4");
+
+/// Fraction in eighths: `1 5/8`
+pub static AS_EIGHTHS: StaticFormat = StaticFormat::new("# ?/8");
+
+/// Fraction in sixteenths, with a two-digit numerator: `1  5/16`
+pub static AS_SIXTEENTHS: StaticFormat = StaticFormat::new("# ??/16");
+
+/// Fraction in tenths: `1 3/10`
+pub static AS_TENTHS: StaticFormat = StaticFormat::new("# ?/10");
+
+/// Fraction in hundredths: `1 25/100`
+pub static AS_HUNDREDTHS: StaticFormat = StaticFormat::new("# ??/100");
+
+/// A mixed fraction format with a fixed `denominator`, such as `# ??/32`.
+///
+/// The numerator gets as many `?` placeholders as the denominator has
+/// digits, so numerators line up in a column. Exact values show the whole
+/// number followed by spaces where the fraction would be.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{formats, FormatOptions};
+///
+/// let opts = FormatOptions::default();
+/// let fmt = formats::fraction_of(32);
+/// assert_eq!(fmt.format(2.125, &opts), "2  4/32");
+/// assert_eq!(fmt.format(3.0, &opts), "3      ");
+/// ```
+///
+/// # Panics
+/// Panics if `denominator` is zero.
+pub fn fraction_of(denominator: u32) -> NumberFormat {
+    assert!(denominator > 0, "fraction denominator must be positive");
+    let width = denominator.to_string().len();
+    let code = format!("# {}/{}", "?".repeat(width), denominator);
+    NumberFormat::parse(&code).expect("fraction format code parses")
+}
+
+/// Format `serial` as an ISO 8601 date, or `None` if it is outside the
+/// date system's range.
+pub fn to_iso_date(serial: f64, date_system: DateSystem) -> Option<String> {
+    format_serial(&ISO_DATE, serial, date_system)
+}
+
+/// Format `serial` as an ISO 8601 date and time, or `None` if it is
+/// outside the date system's range.
+pub fn to_iso_datetime(serial: f64, date_system: DateSystem) -> Option<String> {
+    format_serial(&ISO_DATETIME, serial, date_system)
+}
+
+/// Format `serial` as an RFC 3339 UTC timestamp, or `None` if it is
+/// outside the date system's range.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{formats, DateSystem};
+///
+/// let stamp = formats::to_rfc3339(0.25, DateSystem::Date1904);
+/// assert_eq!(stamp.as_deref(), Some("1904-01-01T06:00:00.000Z"));
+/// assert_eq!(formats::to_rfc3339(-1.0, DateSystem::Date1900), None);
+/// ```
+pub fn to_rfc3339(serial: f64, date_system: DateSystem) -> Option<String> {
+    format_serial(&RFC3339, serial, date_system)
+}
+
+fn format_serial(format: &StaticFormat, serial: f64, date_system: DateSystem) -> Option<String> {
+    let opts = FormatOptions::builder()
+        .date_system(date_system)
+        .overflow(OverflowBehavior::Error)
+        .build();
+    format.try_format(serial, &opts).ok()
+}
@@ -0,0 +1,186 @@
+//! Formatting many values with one format.
+//!
+//! A column of cells usually shares one format code. The parsed format
+//! already carries each section's numeric layout, so the batch APIs only
+//! select a section and render each value.
+
+use std::fmt;
+
+use super::general_format;
+use super::output::Output;
+use crate::ast::NumberFormat;
+use crate::compiled::CompiledFormat;
+use crate::options::FormatOptions;
+
+impl NumberFormat {
+    /// Format a column of values, returning one string per value.
+    ///
+    /// Produces the same text as calling [`format`](Self::format) on each
+    /// value.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt = NumberFormat::parse("#,##0.00;(#,##0.00)").unwrap();
+    /// let column = fmt.format_batch(&[1234.5, -7.0, 0.0], &FormatOptions::default());
+    /// assert_eq!(column, ["1,234.50", "(7.00)", "0.00"]);
+    /// ```
+    pub fn format_batch(&self, values: &[f64], opts: &FormatOptions) -> Vec<String> {
+        self.format_iter(values.iter().copied(), opts).collect()
+    }
+
+    /// Format a column of values on the rayon thread pool (requires
+    /// `rayon` feature).
+    ///
+    /// Produces the same strings, in the same order, as
+    /// [`format_batch`](Self::format_batch). Each worker renders into its
+    /// own scratch buffer, so only the returned strings are allocated.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt = NumberFormat::parse("0.0%").unwrap();
+    /// let values: Vec<f64> = (0..1000).map(|n| n as f64 / 1000.0).collect();
+    /// let column = fmt.par_format_batch(&values, &FormatOptions::default());
+    /// assert_eq!(column[1], "0.1%");
+    /// assert_eq!(column[999], "99.9%");
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_format_batch(&self, values: &[f64], opts: &FormatOptions) -> Vec<String> {
+        use rayon::prelude::*;
+
+        values
+            .par_iter()
+            .map_init(Output::new, |buffer, &value| {
+                buffer.clear();
+                let result = self
+                    .write_value(value, opts, buffer)
+                    .and_then(|_| buffer.finish(opts));
+                match result {
+                    Ok(()) => buffer.as_str().to_owned(),
+                    Err(_) => general_format(value),
+                }
+            })
+            .collect()
+    }
+
+    /// Lazily format a sequence of values.
+    ///
+    /// Like [`format_batch`](Self::format_batch), but yields each string as
+    /// it is produced, so values can be streamed without collecting them.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt = NumberFormat::parse("0%").unwrap();
+    /// let opts = FormatOptions::default();
+    /// let mut iter = fmt.format_iter((1..=3).map(|n| n as f64 / 4.0), &opts);
+    /// assert_eq!(iter.next().as_deref(), Some("25%"));
+    /// assert_eq!(iter.collect::<Vec<_>>(), ["50%", "75%"]);
+    /// ```
+    pub fn format_iter<'a, I>(
+        &'a self,
+        values: I,
+        opts: &'a FormatOptions,
+    ) -> FormatIter<'a, I::IntoIter>
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        FormatIter {
+            format: self,
+            opts,
+            values: values.into_iter(),
+        }
+    }
+}
+
+/// Iterator returned by [`NumberFormat::format_iter`].
+#[derive(Debug)]
+pub struct FormatIter<'a, I> {
+    format: &'a NumberFormat,
+    opts: &'a FormatOptions,
+    values: I,
+}
+
+impl<I: Iterator<Item = f64>> Iterator for FormatIter<'_, I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let value = self.values.next()?;
+        let mut out = Output::new();
+        let result = self
+            .format
+            .write_value(value, self.opts, &mut out)
+            .and_then(|_| out.finish(self.opts));
+        Some(match result {
+            Ok(()) => out.into_string(),
+            Err(_) => general_format(value),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+/// Iterator returned by [`format_column`](crate::format_column).
+///
+/// Owns its format and options, so it can be handed to a writer thread,
+/// and renders every value into one buffer that is reused across items.
+#[derive(Debug)]
+pub struct ColumnIter<I> {
+    format: CompiledFormat,
+    opts: FormatOptions,
+    values: I,
+    buffer: Output,
+}
+
+impl<I: Iterator<Item = f64>> ColumnIter<I> {
+    pub(crate) fn new(format: CompiledFormat, opts: FormatOptions, values: I) -> Self {
+        Self {
+            format,
+            opts,
+            values,
+            buffer: Output::new(),
+        }
+    }
+
+    /// Format the next value straight into `out`, without allocating a
+    /// `String` for it. Returns `None` once the values run out.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{format_column, FormatOptions};
+    ///
+    /// let opts = FormatOptions::default();
+    /// let mut column = format_column([1.5, -2.0], "0.00", &opts).unwrap();
+    /// let mut csv = String::new();
+    /// while let Some(result) = column.next_to(&mut csv) {
+    ///     result.unwrap();
+    ///     csv.push('\n');
+    /// }
+    /// assert_eq!(csv, "1.50\n-2.00\n");
+    /// ```
+    pub fn next_to<W: fmt::Write>(&mut self, out: &mut W) -> Option<fmt::Result> {
+        let value = self.values.next()?;
+        self.buffer.clear();
+        Some(self.format.write_to(value, &self.opts, &mut self.buffer, out))
+    }
+}
+
+impl<I: Iterator<Item = f64>> Iterator for ColumnIter<I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let mut text = String::new();
+        self.next_to(&mut text)?.ok()?;
+        Some(text)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::ast::{FormatPart, Section};
 use crate::error::FormatError;
+use crate::formatter::number::format_integer_digits;
 use crate::options::FormatOptions;
 use num_bigint::BigInt;
 
@@ -58,27 +59,28 @@ fn format_large_bigint(
     };
 
     // Analyze the format to understand what we need to do
-    let analysis = super::number::analyze_format(section);
+    let analysis = crate::parser::analysis::section_analysis(section);
+
+    // Apply percent scaling (each % multiplies by 100)
+    let percent_value = if analysis.percent_count > 0 {
+        &abs_value * BigInt::from(100_u64).pow(analysis.percent_count as u32)
+    } else {
+        abs_value.clone()
+    };
 
     // Apply thousands scaling (trailing commas divide by 1000 each)
     let scaled_value = if analysis.thousands_scale > 0 {
         let divisor = BigInt::from(1000_u64).pow(analysis.thousands_scale as u32);
-        &abs_value / &divisor
+        &percent_value / &divisor
     } else {
-        abs_value.clone()
+        percent_value
     };
 
     // Convert to string for formatting
     let value_str = scaled_value.to_string();
 
     // Format the integer part
-    let formatted_integer = format_bigint_integer(
-        &value_str,
-        &analysis.integer_placeholders,
-        analysis.has_thousands_separator,
-        &analysis.inline_literals,
-        opts,
-    );
+    let formatted_integer = format_integer_digits(value_str.as_bytes(), &analysis, opts);
 
     // Handle decimal places (for BigInt, decimal part is always 0)
     let decimal_places = analysis.decimal_places();
@@ -96,7 +98,9 @@ fn format_large_bigint(
     let mut result = String::new();
     for part in &analysis.prefix_parts {
         match part {
-            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => result.push_str(s),
             FormatPart::Locale(locale_code) => {
                 if let Some(ref currency) = locale_code.currency {
                     result.push_str(currency);
@@ -113,7 +117,9 @@ fn format_large_bigint(
     // Build suffix
     for part in &analysis.suffix_parts {
         match part {
-            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => result.push_str(s),
             FormatPart::Locale(locale_code) => {
                 if let Some(ref currency) = locale_code.currency {
                     result.push_str(currency);
@@ -127,81 +133,6 @@ fn format_large_bigint(
     Ok(result)
 }
 
-/// Format the integer part of a BigInt as a string.
-fn format_bigint_integer(
-    value_str: &str,
-    placeholders: &[crate::ast::DigitPlaceholder],
-    use_thousands: bool,
-    inline_literals: &[(usize, String)],
-    opts: &FormatOptions,
-) -> String {
-    let value_digits: Vec<char> = value_str.chars().collect();
-
-    let min_digits = placeholders.iter().filter(|p| p.is_required()).count();
-    let output_len = value_digits.len().max(min_digits);
-
-    // Build right-to-left into Vec, then reverse once
-    let separator_count = if use_thousands { output_len / 3 } else { 0 };
-    let literal_chars: usize = inline_literals.iter().map(|(_, s)| s.len()).sum();
-    let estimated_capacity = output_len + separator_count + literal_chars;
-    let mut chars = Vec::with_capacity(estimated_capacity);
-
-    // Process from right to left (least significant first)
-    for (digit_count, pos_from_right) in (0..output_len).enumerate() {
-        let digit_index = value_digits.len() as isize - 1 - pos_from_right as isize;
-
-        // Add thousands separator if needed (but not at position 0)
-        if use_thousands && digit_count > 0 && digit_count % 3 == 0 {
-            chars.push(opts.locale.thousands_separator);
-        }
-
-        // Check if there's an inline literal at this position
-        let literals_at_pos: Vec<&str> = inline_literals
-            .iter()
-            .filter(|(pos, _)| *pos == pos_from_right)
-            .map(|(_, s)| s.as_str())
-            .collect();
-
-        for literal_str in literals_at_pos.iter().rev() {
-            for ch in literal_str.chars().rev() {
-                chars.push(ch);
-            }
-        }
-
-        if digit_index >= 0 {
-            // We have a digit from the value
-            chars.push(value_digits[digit_index as usize]);
-        } else {
-            // Use placeholder's empty character for padding
-            let placeholder_index = placeholders.len() as isize - 1 - pos_from_right as isize;
-            if placeholder_index >= 0 {
-                let placeholder = placeholders[placeholder_index as usize];
-                if let Some(c) = placeholder.empty_char() {
-                    chars.push(c);
-                }
-            }
-        }
-    }
-
-    // Handle the case where we have no digits but need at least one
-    if chars.is_empty() && placeholders.iter().any(|p| p.is_required()) {
-        chars.push('0');
-    }
-
-    // Push any inline literals that are at positions beyond what we formatted
-    for (literal_pos, literal_str) in inline_literals {
-        if *literal_pos >= output_len {
-            for ch in literal_str.chars().rev() {
-                chars.push(ch);
-            }
-        }
-    }
-
-    // Reverse and collect into String
-    chars.reverse();
-    chars.into_iter().collect()
-}
-
 /// Fallback formatting for BigInt values.
 /// Converts to string representation.
 pub fn fallback_format_bigint(value: &BigInt) -> String {
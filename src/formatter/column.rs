@@ -0,0 +1,118 @@
+//! Formatting a column of values with alignment metadata.
+//!
+//! Excel lines up a column by padding each cell: `?` placeholders leave a
+//! space for missing digits and `_x` skips reserve the width of a character,
+//! so decimal points fall in the same place when every cell uses the same
+//! format. A table renderer that lays out cells itself needs to know where
+//! that padding and the decimal point ended up; [`ColumnFormatter`] reports
+//! it alongside each formatted string.
+
+use crate::ast::{Color, NumberFormat};
+use crate::options::FormatOptions;
+
+use super::output::text_width;
+use super::rich::FormattedRun;
+
+/// Formats values that share one format and reports how each cell is laid
+/// out.
+///
+/// Widths are measured with the options' [`LayoutMode`], so they count
+/// terminal columns in [`LayoutMode::Monospace`].
+///
+/// [`LayoutMode`]: crate::LayoutMode
+/// [`LayoutMode::Monospace`]: crate::LayoutMode::Monospace
+///
+/// # Examples
+/// ```
+/// use ssfmt::{ColumnFormatter, FormatOptions, NumberFormat};
+///
+/// let fmt = NumberFormat::parse("#,##0.00_);(#,##0.00)").unwrap();
+/// let opts = FormatOptions::default();
+/// let cells = ColumnFormatter::new(&fmt, &opts).format(&[1234.5, -7.0]);
+/// assert_eq!(cells[0].text, "1,234.50 ");
+/// assert_eq!(cells[0].decimal_offset, Some(5));
+/// assert_eq!(cells[0].skip_width, 1);
+/// assert_eq!(cells[1].text, "(7.00)");
+/// assert_eq!(cells[1].decimal_offset, Some(2));
+///
+/// // Right-align the decimal points
+/// let offset = cells.iter().filter_map(|c| c.decimal_offset).max().unwrap();
+/// let lines: Vec<String> = cells
+///     .iter()
+///     .map(|c| format!("{}{}", " ".repeat(offset - c.decimal_offset.unwrap()), c.text))
+///     .collect();
+/// assert_eq!(lines, ["1,234.50 ", "   (7.00)"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnFormatter<'a> {
+    format: &'a NumberFormat,
+    opts: &'a FormatOptions,
+}
+
+/// One formatted cell and its layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnCell {
+    /// The formatted string, identical to [`NumberFormat::format`]'s output
+    pub text: String,
+    /// Color of the selected section, if any
+    pub color: Option<Color>,
+    /// Width of `text`
+    pub width: usize,
+    /// Width of the text before the decimal separator, or `None` if the
+    /// value was shown without one
+    pub decimal_offset: Option<usize>,
+    /// Width reserved by `_x` skips
+    pub skip_width: usize,
+    /// Width taken by `*` fill characters
+    pub fill_width: usize,
+}
+
+impl<'a> ColumnFormatter<'a> {
+    /// A formatter for values shown with `format` and `opts`.
+    pub fn new(format: &'a NumberFormat, opts: &'a FormatOptions) -> Self {
+        Self { format, opts }
+    }
+
+    /// Format each value, in order.
+    pub fn format(&self, values: &[f64]) -> Vec<ColumnCell> {
+        values
+            .iter()
+            .map(|&value| self.format_cell(value))
+            .collect()
+    }
+
+    /// Format a single value.
+    pub fn format_cell(&self, value: f64) -> ColumnCell {
+        let rich = self.format.format_rich(value, self.opts);
+        let layout = self.opts.layout;
+        let decimal = self.opts.locale.decimal_separator;
+
+        let mut offset = 0;
+        let mut decimal_offset = None;
+        let mut skip_width = 0;
+        let mut fill_width = 0;
+        for run in &rich.runs {
+            let width = text_width(run.as_str(), layout);
+            match run {
+                FormattedRun::Digits(s) if decimal_offset.is_none() => {
+                    if let Some(pos) = s.find(decimal) {
+                        decimal_offset = Some(offset + text_width(&s[..pos], layout));
+                    }
+                }
+                FormattedRun::Skip { .. } => skip_width += width,
+                FormattedRun::Fill { .. } => fill_width += width,
+                _ => {}
+            }
+            offset += width;
+        }
+
+        ColumnCell {
+            width: text_width(&rich.text, layout),
+            text: rich.text,
+            color: rich.color,
+            decimal_offset,
+            skip_width,
+            fill_width,
+        }
+    }
+}
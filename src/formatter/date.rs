@@ -2,20 +2,33 @@
 
 use crate::ast::{AmPmStyle, DatePart, ElapsedPart, FormatPart, Section};
 use crate::date_serial::{serial_to_date, serial_to_weekday};
+use crate::calendar::{CalendarSystem, EraYear};
 use crate::error::FormatError;
 use crate::locale::Locale;
-use crate::options::FormatOptions;
+use crate::options::{
+    DateSystem, FormatOptions, HijriAlgorithm, OverflowBehavior, RoundingMode,
+};
+
+use super::output::Output;
 
 /// Format a value as a date/time using the given section.
-pub fn format_date(
+pub(crate) fn write_date(
     value: f64,
     section: &Section,
     opts: &FormatOptions,
-) -> Result<String, FormatError> {
+    out: &mut Output,
+) -> Result<(), FormatError> {
     // SSF returns empty string for out-of-range dates (< 0 or > 2958465)
     // This matches Excel's behavior - see bits/35_datecode.js line 2
     if !(0.0..=2958465.0).contains(&value) {
-        return Ok(String::new());
+        return match opts.overflow {
+            OverflowBehavior::Empty => Ok(()),
+            OverflowBehavior::Hashes(count) => {
+                out.push_literal(&"#".repeat(opts.cell_width.unwrap_or(count)));
+                Ok(())
+            }
+            OverflowBehavior::Error => Err(FormatError::DateOutOfRange { serial: value }),
+        };
     }
 
     // Use pre-computed metadata instead of scanning parts
@@ -23,6 +36,14 @@ pub fn format_date(
     let is_hijri = section.metadata.is_hijri;
     let has_ampm = section.metadata.has_ampm;
 
+    // Month/day names and AM/PM strings follow the section's [$-xxx] LCID
+    // when we have data for it; separators always come from the options.
+    let names_locale = section
+        .metadata
+        .lcid
+        .and_then(crate::locale::registry::lookup_lcid)
+        .unwrap_or(&opts.locale);
+
     // Check if there are multiple SubSecond parts (still need to scan for this specific case)
     let has_multiple_subseconds = section
         .parts
@@ -42,7 +63,12 @@ pub fn format_date(
 
     // Get date components
     // For time-only values (serial < 1), use a default date since we only need time
-    let (mut year, mut month, mut day) = if value >= 1.0 {
+    // In the 1904 system day 0 is a real date, January 1, 1904
+    let first_day = match opts.date_system {
+        DateSystem::Date1900 => 1.0,
+        DateSystem::Date1904 => 0.0,
+    };
+    let (mut year, mut month, mut day) = if value >= first_day {
         serial_to_date(value, opts.date_system)
             .ok_or(FormatError::DateOutOfRange { serial: value })?
     } else {
@@ -51,18 +77,42 @@ pub fn format_date(
         (1900, 1, 0)
     };
 
-    // Apply Hijri calendar conversion if B2 prefix is used
-    // Use the Kuwaiti algorithm for proper date conversion
-    if is_hijri {
+    // The section's calendar comes from B2 or the locale tag's calendar type
+    let lcid = section.metadata.lcid;
+    let calendar = if is_hijri {
+        CalendarSystem::Hijri
+    } else {
+        lcid.and_then(CalendarSystem::from_calendar_type)
+            .unwrap_or(CalendarSystem::Gregorian)
+    };
+
+    // Era codes follow the calendar, or else the language; without an era
+    // `e` shows the year and `g` shows nothing
+    let era = lcid
+        .and_then(CalendarSystem::for_eras)
+        .and_then(|calendar| calendar.era_year(year, month, day));
+    // `b` always shows the Buddhist Era year
+    let buddhist_year = CalendarSystem::ThaiBuddhist
+        .era_year(year, month, day)
+        .map_or(year, |era| era.year);
+
+    if calendar.has_eras() {
+        if let Some(era) = era {
+            year = era.year;
+        }
+    }
+
+    if calendar == CalendarSystem::Hijri {
         let days = value.floor() as i64;
-        if days == 60 {
+        let tabular = opts.hijri_algorithm == HijriAlgorithm::Tabular;
+        if tabular && days == 60 {
             // Special case for Excel's fake leap day (Feb 29, 1900)
             // This date doesn't exist in the Gregorian calendar
             // SSF hardcodes this to 1317-10-29
             year = 1317;
             month = 10;
             day = 29;
-        } else if days == 0 {
+        } else if tabular && days == 0 {
             // Special case for day 0 (Dec 31, 1899 in Excel's calendar)
             // SSF hardcodes this to 1317-08-29
             year = 1317;
@@ -71,35 +121,48 @@ pub fn format_date(
         } else {
             // For all other dates, use proper Hijri calendar conversion
             let (hijri_year, hijri_month, hijri_day) =
-                crate::hijri::gregorian_to_hijri(year, month, day);
+                crate::hijri::to_hijri(opts.hijri_algorithm, year, month, day);
             year = hijri_year;
             month = hijri_month;
             day = hijri_day;
         }
     }
 
+    // Hebrew month names are in Hebrew script for Hebrew-language tags
+    let mut calendar_month_name = None;
+    if calendar == CalendarSystem::Hebrew {
+        let date = crate::hebrew::gregorian_to_hebrew(year, month, day);
+        let hebrew_script = lcid.is_some_and(|lcid| lcid & 0x3FF == 0x0D);
+        calendar_month_name = Some(date.month_name(hebrew_script));
+        (year, month, day) = (date.year, date.month, date.day);
+    }
+
     // Get time components
-    // Only round seconds when there's no subsecond display in the format
+    // Whole seconds are rounded here; with subseconds shown they come from the nanoseconds below
     let has_subseconds = section.metadata.max_subsecond_precision.is_some();
-    let (mut hour, mut minute, mut second) = crate::date_serial::serial_to_time_with_rounding(adjusted_value, !has_subseconds);
+    let (mut hour, mut minute, mut second) = crate::date_serial::serial_to_time(adjusted_value);
+    let mut subsec_nanos = 0;
 
     // Apply pre-rounding based on smallest displayed time unit
     // This ensures proper rounding behavior (e.g., 12:34:59.9 displayed as "hh:mm" shows "12:35")
     // Only apply when we have subsecond display - otherwise, serial_to_time already rounded.
     if has_subseconds {
-        let fraction = adjusted_value.fract().abs();
-        // Round to millisecond precision first (same as serial_to_time_impl) to handle
-        // floating point errors, then extract subseconds
-        let total_seconds = (fraction * 86400.0 * 1000.0).round() / 1000.0;
-        let subseconds = total_seconds - total_seconds.floor();
+        // Work in whole nanoseconds so up to nine places come out exact
+        let nanos = time_of_day_nanos(adjusted_value);
+        let total_seconds = (nanos / NANOS_PER_SECOND) as u32;
+        hour = (total_seconds / 3600) % 24;
+        minute = (total_seconds % 3600) / 60;
+        second = total_seconds % 60;
+        subsec_nanos = (nanos % NANOS_PER_SECOND) as u32;
 
         apply_time_prerounding(
             &mut hour,
             &mut minute,
             &mut second,
-            subseconds,
+            subsec_nanos,
             section.metadata.smallest_time_unit,
             section.metadata.max_subsecond_precision,
+            opts.rounding,
         );
     }
 
@@ -109,14 +172,13 @@ pub fn format_date(
     let weekday = serial_to_weekday(value, opts.date_system);
 
     // Build the formatted string
-    let mut result = String::new();
-
     for part in &section.parts {
         match part {
             FormatPart::DatePart(date_part) => {
                 let formatted = format_date_part(
                     *date_part,
                     year,
+                    buddhist_year,
                     month,
                     day,
                     hour,
@@ -124,38 +186,72 @@ pub fn format_date(
                     second,
                     weekday,
                     has_ampm,
-                    value, // Pass the original serial value for fractional seconds
+                    subsec_nanos,
                     has_multiple_subseconds,
-                    &opts.locale,
+                    opts.rounding,
+                    names_locale,
+                    era,
+                    calendar_month_name,
+                );
+                // Years read digit by digit under [DBNumN] (二〇二四年)
+                let is_year = matches!(
+                    date_part,
+                    DatePart::Year2
+                        | DatePart::Year3
+                        | DatePart::Year4
+                        | DatePart::BuddhistYear2
+                        | DatePart::BuddhistYear4
+                        | DatePart::BuddhistYear4Alt
+                        | DatePart::BuddhistYear2Alt
                 );
-                result.push_str(&formatted);
+                if is_year {
+                    out.push_digit_sequence(&formatted);
+                } else {
+                    out.push_digits(&formatted);
+                }
             }
             FormatPart::AmPm(style) => {
-                let formatted = format_ampm(*style, hour, &opts.locale);
-                result.push_str(&formatted);
+                let formatted = format_ampm(*style, hour, names_locale);
+                out.push_digits(&formatted);
             }
             FormatPart::Elapsed(elapsed_part) => {
-                let formatted = format_elapsed(*elapsed_part, adjusted_value);
-                result.push_str(&formatted);
+                let formatted = format_elapsed(
+                    *elapsed_part,
+                    adjusted_value,
+                    section.metadata.max_subsecond_precision,
+                    opts.rounding,
+                );
+                out.push_digits(&formatted);
+            }
+            FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) if opts.localize_separators => {
+                for c in s.chars() {
+                    out.push_literal_char(match c {
+                        '/' => opts.locale.date_separator,
+                        ':' => opts.locale.time_separator,
+                        c => c,
+                    });
+                }
             }
-            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => {
-                result.push_str(s);
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => {
+                out.push_literal(s);
             }
             FormatPart::Skip(c) => {
                 // Skip width of character - add a space for alignment
-                result.push(*c);
+                out.push_skip(*c, opts.layout);
             }
-            FormatPart::Fill(_) => {
+            FormatPart::Fill(c) => {
                 // Fill characters are handled at a higher level
-                // For now, just skip
+                out.push_fill(*c);
             }
             FormatPart::ThousandsSeparator => {
                 // In date formats, the thousands separator (,) is just a literal comma
-                result.push(opts.locale.thousands_separator);
+                out.push_literal_char(',');
             }
             FormatPart::DecimalPoint => {
                 // In date formats, the decimal point is just a literal
-                result.push(opts.locale.decimal_separator);
+                out.push_literal_char(opts.locale.decimal_separator);
             }
             _ => {
                 // Other parts (e.g., numeric) are not expected in date formats
@@ -164,7 +260,7 @@ pub fn format_date(
         }
     }
 
-    Ok(result)
+    Ok(())
 }
 
 /// Format a single date/time part.
@@ -172,6 +268,7 @@ pub fn format_date(
 fn format_date_part(
     part: DatePart,
     year: i32,
+    buddhist_year: i32,
     month: u32,
     day: u32,
     hour: u32,
@@ -179,9 +276,12 @@ fn format_date_part(
     second: u32,
     weekday: u32,
     has_ampm: bool,
-    serial: f64,
+    subsec_nanos: u32,
     has_multiple_subseconds: bool,
+    rounding: RoundingMode,
     locale: &Locale,
+    era: Option<EraYear>,
+    calendar_month_name: Option<&str>,
 ) -> String {
     match part {
         // Year formatting
@@ -190,16 +290,8 @@ fn format_date_part(
         DatePart::Year4 => format!("{:04}", year),
 
         // Buddhist calendar (Thai Buddhist Era)
-        DatePart::BuddhistYear2 => {
-            // Thai Buddhist calendar: Gregorian year + 543
-            let buddhist_year = year + 543;
-            format!("{:02}", buddhist_year % 100)
-        }
-        DatePart::BuddhistYear4 => {
-            // Thai Buddhist calendar: Gregorian year + 543
-            let buddhist_year = year + 543;
-            format!("{:04}", buddhist_year)
-        }
+        DatePart::BuddhistYear2 => format!("{:02}", buddhist_year % 100),
+        DatePart::BuddhistYear4 => format!("{:04}", buddhist_year),
         DatePart::BuddhistYear4Alt => {
             // Hijri calendar (B2yyyy prefix)
             // Year has already been adjusted by fix_hijri conversion above
@@ -213,14 +305,33 @@ fn format_date_part(
             format!("{:02}", year % 100)
         }
 
+        // Eras (Japanese, ROC, Thai Buddhist)
+        DatePart::EraLetter => era.map_or_else(String::new, |era| era.names[0].to_string()),
+        DatePart::EraAbbr => era.map_or_else(String::new, |era| era.names[1].to_string()),
+        DatePart::EraFull => era.map_or_else(String::new, |era| era.names[2].to_string()),
+        DatePart::EraYear => match era {
+            Some(era) => format!("{}", era.year),
+            None => format!("{:04}", year),
+        },
+        DatePart::EraYear2 => match era {
+            Some(era) => format!("{:02}", era.year),
+            None => format!("{:04}", year),
+        },
+
         // Month formatting
         DatePart::Month => format!("{}", month),
         DatePart::Month2 => format!("{:02}", month),
-        DatePart::MonthAbbr => locale.month_names_short[(month - 1) as usize].to_string(),
-        DatePart::MonthFull => locale.month_names_full[(month - 1) as usize].to_string(),
+        // The Hebrew calendar brings its own month names
+        DatePart::MonthAbbr => calendar_month_name
+            .unwrap_or_else(|| locale.month_names_short[(month - 1) as usize])
+            .to_string(),
+        DatePart::MonthFull => calendar_month_name
+            .unwrap_or_else(|| locale.month_names_full[(month - 1) as usize])
+            .to_string(),
         DatePart::MonthLetter => {
             // First letter of the month name
-            locale.month_names_full[(month - 1) as usize]
+            calendar_month_name
+                .unwrap_or_else(|| locale.month_names_full[(month - 1) as usize])
                 .chars()
                 .next()
                 .unwrap_or('?')
@@ -256,33 +367,55 @@ fn format_date_part(
 
         // Sub-second formatting
         DatePart::SubSecond(places) => {
-            // For sub-second precision, we need the fractional seconds from the original serial
-            // Calculate total seconds with fractional part
-            let time_fraction = serial.fract().abs();
-            let total_seconds = time_fraction * 86400.0; // seconds in a day
-            let subsecond_fraction = total_seconds.fract();
-
-            if places == 0 {
-                String::new()
-            } else {
-                let multiplier = 10_u32.pow(places as u32);
-                // Round to high precision first to handle floating point errors
-                let high_precision = (subsecond_fraction * 10000.0).round() / 10000.0;
-
-                // Use different rounding strategies based on whether there are multiple subsecond displays
-                let subsec = if has_multiple_subseconds {
-                    // Multiple subsecond displays: truncate for consistency
-                    (high_precision * multiplier as f64) as u32 % multiplier
-                } else {
-                    // Single subsecond display: round
-                    ((high_precision * multiplier as f64).round() as u32) % multiplier
-                };
-                format!("{:0width$}", subsec, width = places as usize)
-            }
+            // Multiple subsecond displays truncate for consistency; a
+            // single one rounds, having already carried into the seconds
+            let shown = places.min(9);
+            let digits =
+                subsecond_digits(subsec_nanos, shown, !has_multiple_subseconds, rounding)
+                    % 10_u64.pow(shown as u32);
+            // Places past the ninth are below a nanosecond
+            format!(
+                "{:0width$}{:0<pad$}",
+                digits,
+                "",
+                width = shown as usize,
+                pad = (places - shown) as usize
+            )
         }
     }
 }
 
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const NANOS_PER_DAY: u64 = 86_400 * NANOS_PER_SECOND;
+
+/// Nanoseconds since midnight in the time of day of a serial.
+///
+/// A serial only pins a time down to within its last bit, about a
+/// microsecond for a date in 2024, so the count is rounded to the power of
+/// ten just above that error. A time meant as 12:00:00.0015 then cannot
+/// come out a hair short of it and round the wrong way at `.000`.
+fn time_of_day_nanos(serial: f64) -> u64 {
+    let nanos = serial.fract().abs() * NANOS_PER_DAY as f64;
+    let error = serial.abs() * f64::EPSILON * NANOS_PER_DAY as f64;
+    let mut step = 1_u64;
+    while (step as f64) < error {
+        step *= 10;
+    }
+    (nanos / step as f64).round() as u64 * step
+}
+
+/// The first `places` digits (at most 9) of a fraction of a second given
+/// in nanoseconds, rounded or truncated. Rounding may give `10^places`,
+/// which carries into the seconds.
+fn subsecond_digits(nanos: u32, places: u8, round: bool, rounding: RoundingMode) -> u64 {
+    let divisor = 10_u64.pow(9 - places as u32);
+    if round {
+        rounding.round(nanos as f64 / divisor as f64) as u64
+    } else {
+        nanos as u64 / divisor
+    }
+}
+
 /// Convert 24-hour time to 12-hour format.
 /// 0 -> 12, 1-12 -> 1-12, 13-23 -> 1-11
 fn to_12_hour(hour: u32) -> u32 {
@@ -297,15 +430,17 @@ fn to_12_hour(hour: u32) -> u32 {
 fn format_ampm(style: AmPmStyle, hour: u32, locale: &Locale) -> String {
     let is_pm = hour >= 12;
 
-    // Excel always outputs uppercase AM/PM regardless of format case
     match style {
+        // Excel shows the locale's designator whatever the format's case,
+        // so `am/pm` still gives AM in English and a. m. in Spanish
         AmPmStyle::Upper | AmPmStyle::Lower => {
             if is_pm {
-                locale.pm_string.to_uppercase()
+                locale.pm_string.to_string()
             } else {
-                locale.am_string.to_uppercase()
+                locale.am_string.to_string()
             }
         }
+        AmPmStyle::Chinese => if is_pm { "下午" } else { "上午" }.to_string(),
         AmPmStyle::ShortUpper | AmPmStyle::ShortLower => {
             if is_pm {
                 "P".to_string()
@@ -336,12 +471,14 @@ fn apply_time_prerounding(
     hour: &mut u32,
     minute: &mut u32,
     second: &mut u32,
-    subseconds: f64,
+    subsec_nanos: u32,
     smallest_unit: crate::ast::TimeUnit,
     subsecond_precision: Option<u8>,
+    rounding: RoundingMode,
 ) {
     use crate::ast::TimeUnit;
 
+    let half_second = u64::from(subsec_nanos) >= NANOS_PER_SECOND / 2;
     match smallest_unit {
         TimeUnit::Hours => {
             // Round subseconds -> seconds -> minutes -> hours
@@ -349,7 +486,7 @@ fn apply_time_prerounding(
             let mut min = *minute as i64;
             let mut hr = *hour as i64;
 
-            if subseconds >= 0.5 {
+            if half_second {
                 sec += 1;
             }
             if sec >= 60 {
@@ -373,7 +510,7 @@ fn apply_time_prerounding(
             let mut sec = *second as i64;
             let mut min = *minute as i64;
 
-            if subseconds >= 0.5 {
+            if half_second {
                 sec += 1;
             }
             if sec >= 60 {
@@ -391,7 +528,7 @@ fn apply_time_prerounding(
             // Round subseconds -> seconds (don't carry to minutes)
             let mut sec = *second as i64;
 
-            if subseconds >= 0.5 {
+            if half_second {
                 sec += 1;
             }
             if sec >= 60 {
@@ -403,12 +540,13 @@ fn apply_time_prerounding(
         TimeUnit::Subseconds => {
             // For subsecond display, check if the subseconds would round up to the next second
             // based on the display precision.
-            // For n decimal places, threshold = 1 - 0.5 * 10^(-n)
             // e.g., .0 (1 place): 0.95 rounds to 1.0
             //       .00 (2 places): 0.995 rounds to 1.00
             if let Some(precision) = subsecond_precision {
-                let threshold = 1.0 - 0.5 * 10_f64.powi(-(precision as i32));
-                if subseconds >= threshold {
+                let precision = precision.min(9);
+                if subsecond_digits(subsec_nanos, precision, true, rounding)
+                    >= 10_u64.pow(precision as u32)
+                {
                     let mut sec = *second as i64 + 1;
                     if sec >= 60 {
                         sec %= 60;
@@ -424,7 +562,45 @@ fn apply_time_prerounding(
 }
 
 /// Format elapsed time (total hours, minutes, or seconds).
-fn format_elapsed(part: ElapsedPart, serial_value: f64) -> String {
+fn format_elapsed(
+    part: ElapsedPart,
+    serial_value: f64,
+    subsecond_precision: Option<u8>,
+    rounding: RoundingMode,
+) -> String {
+    let padded = matches!(
+        part,
+        ElapsedPart::Days2 | ElapsedPart::Hours2 | ElapsedPart::Minutes2 | ElapsedPart::Seconds2
+    );
+    let pad = |total: i64| {
+        if padded {
+            format!("{:02}", total)
+        } else {
+            format!("{}", total)
+        }
+    };
+
+    // With subseconds shown (`[ss].000`) the fraction is displayed rather
+    // than rounded away. Like the clock fields, only the seconds count
+    // carries, and only when the fraction displays as a whole second.
+    if let Some(precision) = subsecond_precision {
+        let nanos = time_of_day_nanos(serial_value);
+        let whole_seconds =
+            serial_value.floor() as i64 * 86400 + (nanos / NANOS_PER_SECOND) as i64;
+        return pad(match part {
+            ElapsedPart::Days | ElapsedPart::Days2 => whole_seconds / 86400,
+            ElapsedPart::Hours | ElapsedPart::Hours2 => whole_seconds / 3600,
+            ElapsedPart::Minutes | ElapsedPart::Minutes2 => whole_seconds / 60,
+            ElapsedPart::Seconds | ElapsedPart::Seconds2 => {
+                let precision = precision.min(9);
+                let subsec_nanos = (nanos % NANOS_PER_SECOND) as u32;
+                let carry = subsecond_digits(subsec_nanos, precision, true, rounding)
+                    >= 10_u64.pow(precision as u32);
+                whole_seconds + i64::from(carry)
+            }
+        });
+    }
+
     // SSF algorithm: parse serial into integer time components first, then calculate elapsed
     // This matches Excel's behavior exactly
 
@@ -456,6 +632,22 @@ fn format_elapsed(part: ElapsedPart, serial_value: f64) -> String {
     // SSF performs pre-rounding based on which time fields are present (lines 102-115 in 82_eval.js)
     // This ensures that when displaying [m], we round up if seconds would round to 60
     match part {
+        ElapsedPart::Days | ElapsedPart::Days2 => {
+            // Round like hours, carrying a full day into the count
+            if subseconds >= 0.5 {
+                seconds += 1;
+            }
+            if seconds >= 60 {
+                minutes += 1;
+            }
+            if minutes >= 60 {
+                hours += 1;
+            }
+            if hours >= 24 {
+                date += 1;
+            }
+            pad(date)
+        }
         ElapsedPart::Hours | ElapsedPart::Hours2 => {
             // For hours format: round subseconds, then carry over through S -> M -> H
             if subseconds >= 0.5 {
@@ -470,12 +662,7 @@ fn format_elapsed(part: ElapsedPart, serial_value: f64) -> String {
                 hours += 1;
             }
             // Total elapsed hours: D*24 + H (all integer arithmetic after rounding)
-            let total_hours = date * 24 + hours;
-            if matches!(part, ElapsedPart::Hours2) {
-                format!("{:02}", total_hours)
-            } else {
-                format!("{}", total_hours)
-            }
+            pad(date * 24 + hours)
         }
         ElapsedPart::Minutes | ElapsedPart::Minutes2 => {
             // For minutes format: round subseconds, then carry over S -> M (not to H)
@@ -487,22 +674,13 @@ fn format_elapsed(part: ElapsedPart, serial_value: f64) -> String {
                 minutes += 1;
             }
             // Total elapsed minutes: (D*24+H)*60 + M (all integer arithmetic after rounding)
-            let total_minutes = (date * 24 + hours) * 60 + minutes;
-            if matches!(part, ElapsedPart::Minutes2) {
-                format!("{:02}", total_minutes)
-            } else {
-                format!("{}", total_minutes)
-            }
+            pad((date * 24 + hours) * 60 + minutes)
         }
         ElapsedPart::Seconds | ElapsedPart::Seconds2 => {
             // For seconds format: round S+u directly, no pre-rounding
             // Total elapsed seconds: ((D*24+H)*60+M)*60 + round(S+u)
-            let total_seconds = ((date * 24 + hours) * 60 + minutes) * 60 + (seconds as f64 + subseconds).round() as i64;
-            if matches!(part, ElapsedPart::Seconds2) {
-                format!("{:02}", total_seconds)
-            } else {
-                format!("{}", total_seconds)
-            }
+            let whole_minutes = (date * 24 + hours) * 60 + minutes;
+            pad(whole_minutes * 60 + (seconds as f64 + subseconds).round() as i64)
         }
     }
 }
@@ -0,0 +1,54 @@
+//! Lazy `Display` adapter for formatted values.
+//!
+//! [`Formatted`] holds the format, value and options and renders them only
+//! when it is written, through the same per-thread buffer as
+//! [`NumberFormat::format_to`].
+
+use std::fmt;
+
+use crate::ast::NumberFormat;
+use crate::options::FormatOptions;
+
+/// A value paired with its format, rendered when displayed.
+///
+/// Created by [`NumberFormat::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct Formatted<'a> {
+    format: &'a NumberFormat,
+    value: f64,
+    opts: &'a FormatOptions,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format.format_to(self.value, self.opts, f)
+    }
+}
+
+impl NumberFormat {
+    /// Wrap a value so that it is formatted when displayed.
+    ///
+    /// Writing the result with `write!` or `format!` produces the same text
+    /// as [`format`](Self::format), without returning a `String` per value.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::fmt::Write;
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    /// let opts = FormatOptions::default();
+    /// let mut out = String::new();
+    /// for value in [1234.5, 42.0] {
+    ///     writeln!(out, "{}", fmt.display(value, &opts)).unwrap();
+    /// }
+    /// assert_eq!(out, "1,234.50\n42.00\n");
+    /// ```
+    pub fn display<'a>(&'a self, value: f64, opts: &'a FormatOptions) -> Formatted<'a> {
+        Formatted {
+            format: self,
+            value,
+            opts,
+        }
+    }
+}
@@ -5,43 +5,90 @@ use crate::error::FormatError;
 use crate::formatter::number::format_simple_with_placeholders;
 use crate::options::FormatOptions;
 
+use super::output::Output;
+
 /// Format a fraction part (numerator or denominator) with digit placeholders.
 /// Uses the unified placeholder formatting helper from number.rs.
 fn format_fraction_part(value: u64, placeholders: &[DigitPlaceholder]) -> String {
     format_simple_with_placeholders(value, placeholders)
 }
 
-/// Format a number as a fraction according to the format section.
-pub fn format_fraction(
+/// Format a number with a fraction section: the fraction itself plus the
+/// literals, currency symbols, percent signs, skips and fills around it.
+///
+/// The sign is left to the caller, as for other number sections.
+pub(crate) fn write_fraction(
     value: f64,
     section: &Section,
-    _opts: &FormatOptions,
-) -> Result<String, FormatError> {
-    // Find the fraction part in the section
-    let fraction_part = section.parts.iter().find_map(|p| {
-        if let FormatPart::Fraction {
-            integer_digits,
-            numerator_digits,
-            denominator,
-            space_before_slash,
-            space_after_slash,
-        } = p
-        {
-            Some((integer_digits, numerator_digits, denominator, space_before_slash, space_after_slash))
-        } else {
-            None
+    opts: &FormatOptions,
+    out: &mut Output,
+) -> Result<(), FormatError> {
+    let mut scaled = value.abs();
+    for _ in 0..section.parts.iter().filter(|p| matches!(p, FormatPart::Percent)).count() {
+        scaled *= 100.0;
+    }
+
+    let mut has_fraction = false;
+    for part in &section.parts {
+        match part {
+            FormatPart::Fraction {
+                integer_digits,
+                integer_separator,
+                numerator_digits,
+                denominator,
+                space_before_slash,
+                space_after_slash,
+            } => {
+                has_fraction = true;
+                let (integer, fraction) = format_fraction(
+                    scaled,
+                    integer_digits,
+                    numerator_digits,
+                    denominator,
+                    space_before_slash,
+                    space_after_slash,
+                );
+                if let Some(integer) = integer {
+                    out.push_digits(&integer);
+                    out.push_literal(integer_separator);
+                }
+                out.push_digits(&fraction);
+            }
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => out.push_literal(s),
+            FormatPart::Locale(locale) => {
+                if let Some(ref currency) = locale.currency {
+                    out.push_literal(currency);
+                }
+            }
+            FormatPart::Percent => out.push_literal("%"),
+            FormatPart::Skip(c) => out.push_skip(*c, opts.layout),
+            FormatPart::Fill(c) => out.push_fill(*c),
+            _ => {}
         }
-    });
+    }
 
-    let Some((integer_digits, numerator_digits, denominator, space_before_slash, space_after_slash)) = fraction_part else {
+    if !has_fraction {
         return Err(FormatError::TypeMismatch {
             expected: "fraction format",
             got: "no fraction part found",
         });
-    };
+    }
+    Ok(())
+}
 
+/// Format a non-negative value as a fraction. Returns the integer part of a
+/// mixed fraction, if any, and the fraction itself.
+fn format_fraction(
+    abs_value: f64,
+    integer_digits: &[DigitPlaceholder],
+    numerator_digits: &[DigitPlaceholder],
+    denominator: &FractionDenom,
+    space_before_slash: &str,
+    space_after_slash: &str,
+) -> (Option<String>, String) {
     // Separate integer and fractional parts
-    let abs_value = value.abs();
     let mut integer_part = abs_value.trunc() as i64;
     let frac_part = abs_value.fract();
 
@@ -67,28 +114,25 @@ pub fn format_fraction(
     };
 
     // Find best fraction approximation
-    let (mut num, denom) = if is_mixed {
-        // Mixed fraction: approximate the fractional part only
-        match denominator {
-            FractionDenom::UpToDigits(_) => {
-                let max_denom = 10_u32.pow(padding_width as u32) - 1;
-                find_best_fraction(frac_part, max_denom)
-            }
-            FractionDenom::Fixed(d) => {
-                let num = (frac_part * (*d as f64)).round() as u32;
-                (num, *d)
+    let (mut num, denom) = match denominator {
+        FractionDenom::UpToDigits(_) => {
+            // SSF approximates the whole value and splits off the integer
+            // part afterwards, for mixed and improper fractions alike
+            let (num, denom) = find_best_fraction(abs_value, padding_width as u32);
+            if is_mixed {
+                integer_part = (num / denom) as i64;
+                (num % denom, denom)
+            } else {
+                (num, denom)
             }
         }
-    } else {
-        // Improper fraction: approximate the entire value
-        match denominator {
-            FractionDenom::UpToDigits(_) => {
-                let max_denom = 10_u32.pow(padding_width as u32) - 1;
-                find_best_fraction(abs_value, max_denom)
-            }
-            FractionDenom::Fixed(d) => {
-                let num = (abs_value * (*d as f64)).round() as u32;
-                (num, *d)
+        FractionDenom::Fixed(d) => {
+            let d = *d as u64;
+            if is_mixed {
+                // Mixed fraction: round the fractional part only
+                ((frac_part * d as f64).round() as u64, d)
+            } else {
+                ((abs_value * d as f64).round() as u64, d)
             }
         }
     };
@@ -100,41 +144,25 @@ pub fn format_fraction(
         num %= denom;
     }
 
-    // Format the result
-    let mut result = String::new();
-
-    // Add sign for negative values
-    if value < 0.0 {
-        result.push('-');
-    }
-
     // Format integer part (mixed fractions only)
-    if is_mixed {
+    let integer = is_mixed.then(|| {
         if integer_part > 0 || num == 0 {
-            // Format integer with digit placeholders
-            let int_str = if !integer_digits.is_empty() {
-                format_fraction_part(integer_part as u64, integer_digits)
-            } else {
-                format!("{}", integer_part)
-            };
-            result.push_str(&int_str);
-        } else if !integer_digits.is_empty() {
+            format_fraction_part(integer_part as u64, integer_digits)
+        } else {
             // Zero integer with non-zero fraction: show placeholders
-            for placeholder in integer_digits {
-                // Hash shows nothing, Question shows space, Zero shows '0'
-                if let Some(c) = placeholder.empty_char() {
-                    result.push(c);
-                }
-                // Hash returns None, so nothing is added
-            }
+            // Hash shows nothing, Question shows space, Zero shows '0'
+            integer_digits.iter().filter_map(|p| p.empty_char()).collect()
         }
-        // Add space between integer and fraction
-        result.push(' ');
-    }
+    });
+
+    let mut result = String::new();
 
     // Format the fraction part
-    // For mixed fractions with no fractional part (num=0), use spaces instead of "0/X"
-    if is_mixed && num == 0 {
+    // For mixed fractions with no fractional part (num=0), use spaces instead of "0/X",
+    // unless a fixed denominator's numerator has a required `0` placeholder
+    let shows_zero_numerator = matches!(denominator, FractionDenom::Fixed(_))
+        && numerator_digits.iter().any(|p| p.is_required());
+    if is_mixed && num == 0 && !shows_zero_numerator {
         // SSF: fill(" ", 2*ri+1 + r[2].length + r[3].length)
         // This creates spaces for: numerator (ri) + slash (1) + denominator (ri) + spaces around slash
         let total_spaces = if matches!(denominator, FractionDenom::Fixed(_)) {
@@ -155,21 +183,21 @@ pub fn format_fraction(
         // Determine how to format the numerator based on fraction type
         if !integer_digits.is_empty() {
             // Mixed fraction with non-zero fractional part (e.g., "# ??/?????????" or "# ??/16")
-            // SSF uses pad_(ff[1], ri) - left-pad numerator to padding_width
-            let pad_width = if matches!(denominator, FractionDenom::UpToDigits(_)) {
-                padding_width as usize
+            if matches!(denominator, FractionDenom::UpToDigits(_)) {
+                // SSF uses pad_(ff[1], ri) - left-pad numerator to padding_width
+                for _ in 0..(padding_width as usize).saturating_sub(num_str.len()) {
+                    result.push(' ');
+                }
+                result.push_str(&num_str);
             } else {
-                // For fixed denominators, pad to numerator placeholder width
-                numerator_digits.len()
-            };
-            for _ in 0..pad_width.saturating_sub(num_str.len()) {
-                result.push(' ');
+                // For fixed denominators, the numerator placeholders pad it:
+                // `?` with spaces, `0` with zeros (e.g., "# 00/16" gives "1 04/16")
+                result.push_str(&format_fraction_part(num, numerator_digits));
             }
-            result.push_str(&num_str);
         } else {
             // Improper fraction: use numerator_digits placeholders (e.g., "#0#00??/??")
             // SSF uses write_num("n", r[1], ff[1]) - see bits/63_numflt.js line 47
-            let formatted_num = format_fraction_part(num as u64, numerator_digits);
+            let formatted_num = format_fraction_part(num, numerator_digits);
             result.push_str(&formatted_num);
         }
 
@@ -192,63 +220,44 @@ pub fn format_fraction(
         }
     }
 
-    Ok(result)
+    (integer, result)
 }
 
-/// Find the best fraction approximation for a decimal value.
-/// Uses continued fractions algorithm for best rational approximation.
-fn find_best_fraction(value: f64, max_denom: u32) -> (u32, u32) {
-    if value == 0.0 || max_denom == 0 {
-        return (0, 1);
-    }
-
-    // Handle values very close to 0
-    if value.abs() < 1e-10 {
-        return (0, 1);
-    }
-
-    // Use continued fractions algorithm
-    let mut x = value;
-    let mut a = x.floor();
-    let mut h = [a as i64, 1];
-    let mut k = [1_i64, 0];
-
-    let mut n = 0;
-    while n < 20 {
-        // Limit iterations
-        if (x - a).abs() < 1e-10 {
-            break;
-        }
-
-        x = 1.0 / (x - a);
-        a = x.floor();
-
-        let h_next = a as i64 * h[0] + h[1];
-        let k_next = a as i64 * k[0] + k[1];
-
-        // Check if denominator exceeds limit
-        if k_next > max_denom as i64 {
-            // Return previous convergent
+/// Find the best fraction approximation for a non-negative value, with a
+/// denominator of at most `digits` digits. Returns an improper fraction.
+///
+/// This is a port of SSF's `SSF_frac`, which Excel's results match: it
+/// walks the continued fraction convergents of `value` until the
+/// denominator reaches the limit, then steps back to the last convergent
+/// whose denominator fits. Arithmetic is done in `f64`, as in JavaScript,
+/// so the stopping points agree with SSF.
+fn find_best_fraction(value: f64, digits: u32) -> (u64, u64) {
+    let max_denom = 10_f64.powi(digits as i32) - 1.0;
+
+    let mut b = value;
+    let (mut p_2, mut p_1, mut p) = (0.0, 1.0, 0.0);
+    let (mut q_2, mut q_1, mut q) = (1.0, 0.0, 0.0);
+    while q_1 < max_denom {
+        let a = b.floor();
+        p = a * p_1 + p_2;
+        q = a * q_1 + q_2;
+        if b - a < 0.000_000_05 {
             break;
         }
-
-        h[1] = h[0];
-        h[0] = h_next;
-        k[1] = k[0];
-        k[0] = k_next;
-
-        n += 1;
+        b = 1.0 / (b - a);
+        (p_2, p_1) = (p_1, p);
+        (q_2, q_1) = (q_1, q);
     }
 
-    // Ensure we don't exceed max denominator
-    if k[0] > max_denom as i64 {
-        // Fall back to simple rounding
-        let denom = max_denom.min(10);
-        let num = (value * denom as f64).round() as u32;
-        return (num, denom);
+    if q > max_denom {
+        if q_1 > max_denom {
+            (q, p) = (q_2, p_2);
+        } else {
+            (q, p) = (q_1, p_1);
+        }
     }
 
-    (h[0].max(0) as u32, k[0].max(1) as u32)
+    (p as u64, (q as u64).max(1))
 }
 
 #[cfg(test)]
@@ -258,15 +267,22 @@ mod tests {
     #[test]
     fn test_find_best_fraction() {
         // Test 1/5
-        let (num, denom) = find_best_fraction(0.2, 9);
+        let (num, denom) = find_best_fraction(0.2, 1);
         assert_eq!((num, denom), (1, 5));
 
         // Test 1/3
-        let (num, denom) = find_best_fraction(0.333333, 9);
+        let (num, denom) = find_best_fraction(0.333333, 1);
         assert_eq!((num, denom), (1, 3));
 
         // Test 2/3
-        let (num, denom) = find_best_fraction(0.666666, 9);
+        let (num, denom) = find_best_fraction(0.666666, 1);
         assert_eq!((num, denom), (2, 3));
+
+        // Steps back to the last convergent that fits the digit count
+        assert_eq!(find_best_fraction(std::f64::consts::PI, 2), (22, 7));
+        assert_eq!(find_best_fraction(std::f64::consts::PI, 3), (355, 113));
+
+        // Remainders below SSF's 5e-8 threshold end the search
+        assert_eq!(find_best_fraction(1.50000001, 2), (3, 2));
     }
 }
@@ -0,0 +1,400 @@
+//! Excel's General number format
+//!
+//! A port of SSF's `SSF_general_num` (bits/40_general.js), which picks
+//! between decimal and scientific notation so the result fits in 11
+//! characters (12 for negative numbers). SSF is built on JavaScript's
+//! `toFixed`, `toPrecision` and `toExponential`; those are reproduced here,
+//! including their rounding of exact ties away from zero where Rust's
+//! formatting rounds them to even.
+
+/// Format a number the way Excel's General format shows it.
+///
+/// Integers that fit in 32 bits are shown in full. Other values keep at
+/// most 11 characters, switching to scientific notation with up to six
+/// significant digits when the decimal form doesn't fit.
+///
+/// # Examples
+/// ```
+/// use ssfmt::general_format;
+///
+/// assert_eq!(general_format(1234.5678), "1234.5678");
+/// assert_eq!(general_format(1.0 / 3.0), "0.333333333");
+/// assert_eq!(general_format(123456789012.0), "1.23457E+11");
+/// assert_eq!(general_format(0.0000123456), "1.23456E-05");
+/// ```
+pub fn general_format(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        let text = if value > 0.0 { "Infinity" } else { "-Infinity" };
+        return text.to_string();
+    }
+    if value.fract() == 0.0 && value >= i32::MIN as f64 && value <= i32::MAX as f64 {
+        return (value as i32).to_string();
+    }
+
+    // SSF computes the magnitude as ln(|v|) * log10(e), which can land one
+    // below log10 at exact powers of ten
+    let magnitude = (value.abs().ln() * std::f64::consts::LOG10_E).floor() as i32;
+    let text = if (-4..=-1).contains(&magnitude) {
+        to_precision(value, (10 + magnitude) as usize)
+    } else if magnitude.abs() <= 9 {
+        small_exp(value)
+    } else if magnitude == 10 {
+        let mut text = to_fixed(value, 10);
+        text.truncate(12);
+        text
+    } else {
+        large_exp(value)
+    };
+    strip_decimal(&normalize_exp(&text.to_uppercase()))
+}
+
+/// Format a number as General in a column `max_chars` characters wide.
+///
+/// Excel narrows General numbers to fit the column instead of letting them
+/// overflow: decimals are rounded off, and numbers too long for that are
+/// shown in scientific notation with fewer digits. Of the two, the form
+/// keeping more significant digits wins. Returns `None` if the number
+/// doesn't fit even then, where Excel fills the cell with `#`.
+///
+/// # Examples
+/// ```
+/// use ssfmt::general_format_with_width;
+///
+/// assert_eq!(general_format_with_width(1234.5678, 11).unwrap(), "1234.5678");
+/// assert_eq!(general_format_with_width(1234.5678, 6).unwrap(), "1234.6");
+/// assert_eq!(general_format_with_width(123456789.0, 8).unwrap(), "1.23E+08");
+/// assert_eq!(general_format_with_width(123456789.0, 4), None);
+/// ```
+pub fn general_format_with_width(value: f64, max_chars: usize) -> Option<String> {
+    let full = general_format(value);
+    if full.len() <= max_chars {
+        return Some(full);
+    }
+    if !value.is_finite() {
+        return None;
+    }
+    // Numbers General already shows in scientific notation stay there
+    let decimal = if full.contains('E') {
+        None
+    } else {
+        fit_decimal(value, max_chars)
+    };
+    let scientific = fit_scientific(value, max_chars);
+    match (decimal, scientific) {
+        (Some(decimal), Some(scientific))
+            if significant_digits_shown(&scientific) > significant_digits_shown(&decimal) =>
+        {
+            Some(scientific)
+        }
+        (decimal, scientific) => decimal.or(scientific),
+    }
+}
+
+/// The value rounded to as many decimals as fit.
+fn fit_decimal(value: f64, max_chars: usize) -> Option<String> {
+    let int_len = to_fixed(value, 0).len();
+    let most = max_chars.saturating_sub(int_len + 1);
+    (0..=most).rev().find_map(|decimals| {
+        let text = strip_decimal(&to_fixed(value, decimals));
+        let text = if text == "-0" { "0".to_string() } else { text };
+        (text.len() <= max_chars).then_some(text)
+    })
+}
+
+/// The value in scientific notation with as many mantissa digits as fit.
+fn fit_scientific(value: f64, max_chars: usize) -> Option<String> {
+    (0..=5).rev().find_map(|decimals| {
+        let text = normalize_exp(&to_exponential(value, decimals).to_uppercase());
+        (text.len() <= max_chars).then_some(text)
+    })
+}
+
+/// Significant digits in a General result, not counting the exponent.
+fn significant_digits_shown(text: &str) -> usize {
+    let mantissa = text.split('E').next().unwrap_or(text);
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    digits.trim_start_matches('0').len()
+}
+
+/// Widest result before switching notation, not counting a minus sign.
+fn max_width(value: f64) -> usize {
+    if value < 0.0 {
+        12
+    } else {
+        11
+    }
+}
+
+/// Values from 1E-9 up to 1E10.
+fn small_exp(value: f64) -> String {
+    let text = strip_decimal(&to_fixed(value, 12));
+    if text.len() <= max_width(value) {
+        return text;
+    }
+    let text = to_precision(value, 10);
+    if text.len() <= max_width(value) {
+        return text;
+    }
+    to_exponential(value, 5)
+}
+
+/// Values below 1E-9 or from 1E11 up.
+fn large_exp(value: f64) -> String {
+    let text = strip_decimal(&to_fixed(value, 11));
+    if text.len() > max_width(value) || text == "0" || text == "-0" {
+        to_precision(value, 6)
+    } else {
+        text
+    }
+}
+
+/// Drop trailing zeros after a decimal point, and the point if nothing is
+/// left after it.
+fn strip_decimal(text: &str) -> String {
+    match text.rfind('.') {
+        Some(dot) if text[dot + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+            let trimmed = text.trim_end_matches('0');
+            trimmed.strip_suffix('.').unwrap_or(trimmed).to_string()
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Drop trailing zeros from a mantissa and pad one-digit exponents:
+/// `1.50000E+5` becomes `1.5E+05`.
+fn normalize_exp(text: &str) -> String {
+    let Some(e) = text.find('E') else {
+        return text.to_string();
+    };
+    let (mantissa, exponent) = text.split_at(e);
+    let mantissa = strip_decimal(mantissa);
+    let (sign, digits) = exponent[1..].split_at(1);
+    if digits.len() == 1 {
+        format!("{mantissa}E{sign}0{digits}")
+    } else {
+        format!("{mantissa}{exponent}")
+    }
+}
+
+/// JavaScript's `Number.prototype.toFixed`.
+fn to_fixed(value: f64, decimals: usize) -> String {
+    let abs = value.abs();
+    if abs >= 1e21 {
+        return to_js_string(value);
+    }
+    let mut text = if fraction_digits(abs) == decimals + 1 {
+        // An exact tie, which Rust rounds to even
+        let mut exact = format!("{:.*}", decimals + 1, abs);
+        exact.pop();
+        if decimals == 0 {
+            exact.pop();
+        }
+        round_up(&mut exact);
+        exact
+    } else {
+        format!("{:.*}", decimals, abs)
+    };
+    if value < 0.0 {
+        text.insert(0, '-');
+    }
+    text
+}
+
+/// JavaScript's `Number.prototype.toPrecision`.
+fn to_precision(value: f64, precision: usize) -> String {
+    let (digits, exponent) = significant_digits(value.abs(), precision);
+    let mut text = String::new();
+    if value < 0.0 {
+        text.push('-');
+    }
+    if exponent < -6 || exponent >= precision as i32 {
+        push_exponential(&mut text, &digits, exponent);
+    } else if exponent >= 0 {
+        let (int, frac) = digits.split_at(exponent as usize + 1);
+        text.push_str(int);
+        if !frac.is_empty() {
+            text.push('.');
+            text.push_str(frac);
+        }
+    } else {
+        text.push_str("0.");
+        text.extend(std::iter::repeat_n('0', (-exponent - 1) as usize));
+        text.push_str(&digits);
+    }
+    text
+}
+
+/// JavaScript's `Number.prototype.toExponential`.
+fn to_exponential(value: f64, decimals: usize) -> String {
+    let (digits, exponent) = significant_digits(value.abs(), decimals + 1);
+    let mut text = String::new();
+    if value < 0.0 {
+        text.push('-');
+    }
+    push_exponential(&mut text, &digits, exponent);
+    text
+}
+
+/// JavaScript's `String(value)` for values from 1E21 up: the shortest
+/// digits that read back as the value, in exponential form.
+fn to_js_string(value: f64) -> String {
+    let shortest = format!("{:e}", value.abs());
+    let (mantissa, exponent) = shortest.split_once('e').unwrap_or((&shortest, "0"));
+    let digits = mantissa.replace('.', "");
+    let mut text = String::new();
+    if value < 0.0 {
+        text.push('-');
+    }
+    push_exponential(&mut text, &digits, exponent.parse().unwrap_or(0));
+    text
+}
+
+/// Append `d.ddde+x`.
+fn push_exponential(text: &mut String, digits: &str, exponent: i32) {
+    let (first, rest) = digits.split_at(1);
+    text.push_str(first);
+    if !rest.is_empty() {
+        text.push('.');
+        text.push_str(rest);
+    }
+    text.push('e');
+    text.push(if exponent < 0 { '-' } else { '+' });
+    text.push_str(&exponent.unsigned_abs().to_string());
+}
+
+/// The first `count` significant digits of `abs`, rounded with ties away
+/// from zero, and the decimal exponent of the first digit.
+fn significant_digits(abs: f64, count: usize) -> (String, i32) {
+    let tie =
+        exact_digits(abs).is_some_and(|exact| exact.len() == count + 1 && exact.ends_with('5'));
+    if !tie {
+        let rounded = format!("{:.*e}", count - 1, abs);
+        let (mantissa, exponent) = rounded.split_once('e').unwrap();
+        return (mantissa.replace('.', ""), exponent.parse().unwrap());
+    }
+
+    // Rust rounds ties to even; keep one more digit and round up instead
+    let exact = format!("{:.*e}", count, abs);
+    let (mantissa, exponent) = exact.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let mut digits = mantissa.replace('.', "");
+    digits.pop();
+    if round_up(&mut digits) {
+        // 9.99 became 10.00: one digit too many, one place higher
+        digits.pop();
+        return (digits, exponent + 1);
+    }
+    (digits, exponent)
+}
+
+/// Add one to the last digit of a decimal string in place, skipping the
+/// decimal point, and return whether the carry added a leading digit.
+fn round_up(text: &mut String) -> bool {
+    let mut bytes = std::mem::take(text).into_bytes();
+    for i in (0..bytes.len()).rev() {
+        match bytes[i] {
+            b'9' => bytes[i] = b'0',
+            b'0'..=b'8' => {
+                bytes[i] += 1;
+                *text = String::from_utf8(bytes).unwrap();
+                return false;
+            }
+            _ => {}
+        }
+    }
+    bytes.insert(0, b'1');
+    *text = String::from_utf8(bytes).unwrap();
+    true
+}
+
+/// Number of decimal places in the exact binary value of `abs`.
+fn fraction_digits(abs: f64) -> usize {
+    let bits = abs.to_bits();
+    let biased = ((bits >> 52) & 0x7FF) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if biased == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased - 1075)
+    };
+    if mantissa == 0 {
+        return 0;
+    }
+    (-(exponent + mantissa.trailing_zeros() as i32)).max(0) as usize
+}
+
+/// The significant digits of the exact value of `abs`, when there are few
+/// enough of them to matter for rounding ties. Halves of powers of two
+/// past 2^-40 have more significant digits than any rounding here keeps.
+fn exact_digits(abs: f64) -> Option<String> {
+    let places = fraction_digits(abs);
+    if abs == 0.0 || places > 40 {
+        return None;
+    }
+    let exact = format!("{:.*}", places, abs);
+    let digits = exact.replace('.', "");
+    let digits = digits.trim_start_matches('0').trim_end_matches('0');
+    Some(digits.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js_number_methods() {
+        assert_eq!(to_fixed(1.005, 2), "1.00");
+        assert_eq!(to_fixed(0.5, 0), "1");
+        assert_eq!(to_fixed(2.5, 0), "3");
+        assert_eq!(to_fixed(-2.5, 0), "-3");
+        assert_eq!(to_fixed(1.0 / 8192.0, 12), "0.000122070313");
+        assert_eq!(to_fixed(1.5e21, 2), "1.5e+21");
+        assert_eq!(to_precision(125.0, 2), "1.3e+2");
+        assert_eq!(to_precision(0.00012345, 3), "0.000123");
+        assert_eq!(to_precision(9.995, 3), "9.99");
+        assert_eq!(to_precision(99.5, 2), "1.0e+2");
+        assert_eq!(to_precision(1e-7, 2), "1.0e-7");
+        assert_eq!(to_exponential(123456.5, 5), "1.23457e+5");
+        assert_eq!(to_exponential(-0.5, 0), "-5e-1");
+    }
+
+    #[test]
+    fn test_general_format() {
+        assert_eq!(general_format(0.0), "0");
+        assert_eq!(general_format(-0.0), "0");
+        assert_eq!(general_format(2147483647.0), "2147483647");
+        assert_eq!(general_format(2147483648.0), "2147483648");
+        assert_eq!(general_format(12345678901.0), "12345678901");
+        assert_eq!(general_format(12345678901.99), "12345678901");
+        assert_eq!(general_format(100000000000.0), "1E+11");
+        assert_eq!(general_format(-123456700000.0), "-1.23457E+11");
+        assert_eq!(general_format(1e100), "1E+100");
+        assert_eq!(general_format(0.1), "0.1");
+        assert_eq!(general_format(-0.0001), "-0.0001");
+        assert_eq!(general_format(0.00001), "0.00001");
+        assert_eq!(general_format(1.23456789e-5), "1.23457E-05");
+        assert_eq!(general_format(1e-10), "1E-10");
+        assert_eq!(general_format(123.456789012345), "123.456789");
+        assert_eq!(general_format(0.000012345), "0.000012345");
+        assert_eq!(general_format(f64::NAN), "NaN");
+    }
+
+    #[test]
+    fn test_general_format_with_width() {
+        assert_eq!(general_format_with_width(0.123456, 4).unwrap(), "0.12");
+        assert_eq!(general_format_with_width(-0.123456, 4).unwrap(), "-0.1");
+        assert_eq!(general_format_with_width(9.99, 3).unwrap(), "10");
+        assert_eq!(general_format_with_width(-0.0001, 3).unwrap(), "0");
+        assert_eq!(general_format_with_width(12345.0, 4), None);
+        assert_eq!(general_format_with_width(12345.0, 5).unwrap(), "12345");
+        assert_eq!(general_format_with_width(123456.0, 5).unwrap(), "1E+05");
+        // Scientific notation wins when it keeps more digits
+        assert_eq!(general_format_with_width(0.0000123, 8).unwrap(), "1.23E-05");
+        assert_eq!(general_format_with_width(0.000123, 6).unwrap(), "0.0001");
+        assert_eq!(general_format_with_width(1.6e100, 7).unwrap(), "2E+100");
+        assert_eq!(general_format_with_width(f64::INFINITY, 3), None);
+    }
+}
@@ -1,22 +1,45 @@
 //! Format value formatting engine
 
+mod batch;
+mod column;
 mod date;
+mod display;
 mod fraction;
+mod general;
 mod number;
+mod numerals;
+mod output;
+mod rich;
 mod text;
 
 #[cfg(feature = "bigint")]
 mod bigint;
 
-pub use number::format_number;
+#[cfg(feature = "bigint")]
+use number::format_number;
+pub use batch::{ColumnIter, FormatIter};
+pub use column::{ColumnCell, ColumnFormatter};
+pub use display::Formatted;
+pub use general::{general_format, general_format_with_width};
+pub use rich::{FormattedRun, RichText};
 
 #[cfg(feature = "bigint")]
 #[allow(unused_imports)]
 pub use bigint::{format_bigint, fallback_format_bigint, is_safe_integer};
 
+use std::cell::RefCell;
+use std::fmt;
+
 use crate::ast::{FormatPart, NumberFormat, Section};
 use crate::error::FormatError;
 use crate::options::FormatOptions;
+use crate::value::Value;
+use output::Output;
+
+thread_local! {
+    /// Scratch buffer reused by [`NumberFormat::format_to`].
+    static SCRATCH: RefCell<Output> = RefCell::new(Output::new());
+}
 
 impl NumberFormat {
     /// Format a numeric value using this format code.
@@ -27,7 +50,59 @@ impl NumberFormat {
     pub fn format(&self, value: f64, opts: &FormatOptions) -> String {
         match self.try_format(value, opts) {
             Ok(result) => result,
-            Err(_) => fallback_format(value),
+            Err(_) => general_format(value),
+        }
+    }
+
+    /// Format a numeric value into `out` instead of returning a new `String`.
+    ///
+    /// Produces the same text as [`format`](Self::format). The formatted
+    /// text is built in a per-thread buffer that is reused across calls, so
+    /// formatting many values into one writer doesn't allocate a `String`
+    /// per value.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    /// let opts = FormatOptions::default();
+    /// let mut csv = String::new();
+    /// for value in [1234.5, 42.0] {
+    ///     fmt.format_to(value, &opts, &mut csv).unwrap();
+    ///     csv.push('\n');
+    /// }
+    /// assert_eq!(csv, "1,234.50\n42.00\n");
+    /// ```
+    pub fn format_to<W: fmt::Write>(
+        &self,
+        value: f64,
+        opts: &FormatOptions,
+        out: &mut W,
+    ) -> fmt::Result {
+        SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+            Ok(mut buffer) => {
+                buffer.clear();
+                self.write_to(value, opts, &mut buffer, out)
+            }
+            // Called again from inside `out.write_str`; use a fresh buffer
+            Err(_) => self.write_to(value, opts, &mut Output::new(), out),
+        })
+    }
+
+    fn write_to<W: fmt::Write>(
+        &self,
+        value: f64,
+        opts: &FormatOptions,
+        buffer: &mut Output,
+        out: &mut W,
+    ) -> fmt::Result {
+        match self
+            .write_value(value, opts, buffer)
+            .and_then(|_| buffer.finish(opts))
+        {
+            Ok(()) => out.write_str(buffer.as_str()),
+            Err(_) => out.write_str(&general_format(value)),
         }
     }
 
@@ -35,34 +110,96 @@ impl NumberFormat {
     ///
     /// Returns an error if the format cannot be applied to the value.
     pub fn try_format(&self, value: f64, opts: &FormatOptions) -> Result<String, FormatError> {
+        let mut out = Output::new();
+        self.write_value(value, opts, &mut out)?;
+        out.finish(opts)?;
+        Ok(out.into_string())
+    }
+
+    /// The value as displayed by this format, for Excel's "Set precision as
+    /// displayed" workbook option.
+    ///
+    /// Applies the rounding of the selected section along with its percent
+    /// and trailing-comma scaling, so the result is the number the
+    /// formatted text shows. General, date, time, fraction and text
+    /// sections return the value unchanged, as do NaN and infinities.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let opts = FormatOptions::default();
+    /// let round = |code, value| NumberFormat::parse(code).unwrap().round_to_display(value, &opts);
+    /// assert_eq!(round("0.00", 2.675), 2.68);
+    /// assert_eq!(round("0%", 0.125), 0.13);
+    /// assert_eq!(round("#,##0.0,,\"M\"", 1_234_567.0), 1_200_000.0);
+    /// assert_eq!(round("0.0E+00", 12345.0), 12000.0);
+    /// assert_eq!(round("General", 1.0 / 3.0), 1.0 / 3.0);
+    /// ```
+    pub fn round_to_display(&self, value: f64, opts: &FormatOptions) -> f64 {
+        if !value.is_finite() {
+            return value;
+        }
+        let section = self.select_section(value);
+        if section.is_general() || section.has_date_parts() {
+            return value;
+        }
+        number::displayed_value(value, section, opts).unwrap_or(value)
+    }
+
+    /// Format a numeric value into `out`, returning the section that was used
+    /// (`None` for NaN and infinities, which bypass the format code).
+    fn write_value(
+        &self,
+        value: f64,
+        opts: &FormatOptions,
+        out: &mut Output,
+    ) -> Result<Option<&Section>, FormatError> {
         // Handle special float values
         if value.is_nan() {
-            return Ok("NaN".to_string());
+            out.push_digits("NaN");
+            return Ok(None);
         }
         if value.is_infinite() {
-            return Ok(if value.is_sign_positive() {
+            out.push_digits(if value.is_sign_positive() {
                 "Infinity"
             } else {
                 "-Infinity"
-            }
-            .to_string());
+            });
+            return Ok(None);
         }
 
         // Select the appropriate section based on value
-        let section = self.select_section(value);
+        let (index, section) = self.section_for(value);
+        if let Some(feature) = numerals::unsupported_style(&section.metadata) {
+            return Err(FormatError::Unsupported { feature });
+        }
+        if let Some(style) = numerals::section_numerals(&section.metadata) {
+            out.set_numerals(
+                style,
+                opts.locale.decimal_separator,
+                opts.locale.thousands_separator,
+            );
+        }
 
         // Excel behavior: when a conditional section strictly matches, format using absolute value
         // Use absolute value only when the condition is strictly satisfied (not at boundary)
-        let has_conditions = self.sections().iter().any(|s| s.condition.is_some());
-        let use_abs_value = has_conditions
-            && section.condition.is_some()
-            && section.condition.unwrap().is_strict_match(value);
+        let use_abs_value = index < 2
+            && section
+                .condition
+                .is_some_and(|condition| condition.is_strict_match(value));
         let format_value = if use_abs_value { value.abs() } else { value };
 
-        // Handle "General" format (empty section with no parts)
+        // An empty section in a code with several sections hides the value:
+        // `0;;` shows nothing for negatives and zero, `;;;` nothing at all
+        if section.parts.is_empty() && self.sections().len() > 1 {
+            return Ok(Some(section));
+        }
+
+        // Handle "General" format (a section with no parts or just `General`)
         // This uses fallback formatting which matches Excel's General behavior
         // Note: sections can have conditions or colors and still be General format
-        if section.parts.is_empty() {
+        if section.is_general() {
             // Special case: if this is a strict conditional match, Excel truncates decimals
             // This handles formats like "[<-25]General" which show "50" instead of "50.1"
             let truncated_value = if use_abs_value && format_value.fract() != 0.0 {
@@ -70,12 +207,32 @@ impl NumberFormat {
             } else {
                 format_value
             };
-            return Ok(fallback_format(truncated_value));
+            // In a known column width, General narrows to fit
+            let narrowed = opts
+                .cell_width
+                .and_then(|width| general_format_with_width(truncated_value, width));
+            match narrowed {
+                Some(text) => out.push_digits(&text),
+                None => out.push_digits(&general_format(truncated_value)),
+            }
+            return Ok(Some(section));
         }
 
         // Check if this is a date format
         if section.has_date_parts() {
-            return date::format_date(format_value, section, opts);
+            let signed_duration = format_value < 0.0
+                && opts.allow_negative_durations
+                && section.metadata.has_elapsed_time;
+            if signed_duration {
+                // Show the magnitude; a negative section brings its own sign
+                date::write_date(-format_value, section, opts, out)?;
+                if numeric_section_count(self.sections()) <= 1 {
+                    out.prepend_sign();
+                }
+            } else {
+                date::write_date(format_value, section, opts, out)?;
+            }
+            return Ok(Some(section));
         }
 
         // Determine if we need to add a minus sign
@@ -83,127 +240,281 @@ impl NumberFormat {
         // For multi-section formats, the section handles it
         // For literal-only formats (no numeric parts), add minus ONLY if it's a single unescaped single-char literal
         // But NOT if we're using absolute value due to conditional matching
-        // EXCEPTION: Fraction and scientific notation formats add their own minus sign
+        // EXCEPTION: Scientific notation formats add their own minus sign
         let sections = self.sections();
-        let num_sections = sections.len();
+        let num_sections = numeric_section_count(sections);
         let has_numeric_parts = section.parts.iter().any(|p| p.is_numeric_part());
         let is_single_char_literal = section.parts.len() == 1
-            && matches!(&section.parts[0], FormatPart::Literal(s) if s.len() == 1);
-        let has_fraction = section
-            .parts
-            .iter()
-            .any(|p| matches!(p, FormatPart::Fraction { .. }));
+            && matches!(
+                &section.parts[0],
+                FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) if s.len() == 1
+            );
         let has_scientific = section
             .parts
             .iter()
             .any(|p| matches!(p, FormatPart::Scientific { .. }));
-        let need_minus_sign = num_sections == 1 && value < 0.0 && (has_numeric_parts || is_single_char_literal) && !use_abs_value && !has_fraction && !has_scientific;
+        // Negative values that round to zero show no minus sign unless
+        // `show_negative_zero` asks for it, which also signs -0.0
+        let is_negative = if opts.show_negative_zero {
+            value.is_sign_negative()
+        } else {
+            value < 0.0 && number::displayed_value(format_value, section, opts) != Some(0.0)
+        };
+        let need_minus_sign = num_sections <= 1 && is_negative && (has_numeric_parts || is_single_char_literal) && !use_abs_value && !has_scientific;
 
         // Format as a number
-        let mut result = format_number(format_value, section, opts)?;
+        number::write_number(format_value, section, opts, out)?;
 
         // Add minus sign for single-section formats with negative values
         // Note: format_number uses abs(value), so it never includes the minus sign
-        // Exception: Fraction and scientific notation formats add their own minus sign
+        // Exception: Scientific notation formats add their own minus sign
         if need_minus_sign {
-            result.insert(0, '-');
+            out.prepend_sign();
         }
 
-        Ok(result)
+        Ok(Some(section))
     }
 
-    /// Select the appropriate format section based on the value.
+    /// The section used to format `value`, with its index in
+    /// [`sections`](Self::sections).
     ///
     /// Section selection rules:
+    /// - With conditions on the first two sections: the first of those whose
+    ///   condition holds. Otherwise the third section if both have
+    ///   conditions, and the second if only one does. Conditions on later
+    ///   sections are ignored, as in Excel.
     /// - 1 section: used for all values
     /// - 2 sections: first for positive/zero, second for negative
     /// - 3 sections: positive, negative, zero
     /// - 4 sections: positive, negative, zero, text
+    ///
+    /// Text values use the text section instead; see
+    /// [`format_text`](Self::format_text).
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::ast::{Color, NamedColor};
+    /// use ssfmt::NumberFormat;
+    ///
+    /// let fmt = NumberFormat::parse("0.00;[Red]-0.00;\"zero\"").unwrap();
+    /// let (index, section) = fmt.section_for(-5.0);
+    /// assert_eq!(index, 1);
+    /// assert_eq!(section.color, Some(Color::Named(NamedColor::Red)));
+    /// assert_eq!(fmt.section_for(0.0).0, 2);
+    /// ```
+    pub fn section_for(&self, value: f64) -> (usize, &Section) {
+        let index = self.section_index(value);
+        (index, &self.sections()[index])
+    }
+
     fn select_section(&self, value: f64) -> &Section {
+        self.section_for(value).1
+    }
+
+    fn section_index(&self, value: f64) -> usize {
         let sections = self.sections();
 
-        // Check if any section has conditions
-        let has_conditions = sections.iter().any(|s| s.condition.is_some());
+        // Excel only honors conditions on the first two sections
+        if sections.iter().take(2).any(|s| s.condition.is_some()) {
+            return conditional_section_index(sections, value);
+        }
 
-        if has_conditions {
-            // With conditions: find matching conditional, or first non-conditional
-            for section in sections {
-                if let Some(ref condition) = section.condition {
-                    if condition.evaluate(value) {
-                        return section;
-                    }
-                } else {
-                    // No condition on this section - use it as fallback
-                    return section;
-                }
-            }
-            // Fallback to last section if nothing matched
-            return sections.last().unwrap();
+        // Standard section selection based on value sign (no conditions).
+        // A missing negative section repeats the first one (with a minus
+        // sign), as does a missing zero section; an empty one hides the value
+        match numeric_section_count(sections) {
+            0 | 1 => 0,
+            2 if value < 0.0 => 1,
+            2 => 0,
+            _ if value > 0.0 => 0,
+            _ if value < 0.0 => 1,
+            _ => 2,
         }
+    }
 
-        // Standard section selection based on value sign (no conditions)
-        match sections.len() {
-            0 => unreachable!("NumberFormat should always have at least one section"),
-            1 => &sections[0],
-            2 => {
-                if value < 0.0 {
-                    &sections[1]
-                } else {
-                    &sections[0]
-                }
+    /// Format a text value using this format code.
+    ///
+    /// The 4th section is used if present. Otherwise the first section
+    /// containing `@` is used, so single-section codes such as `"ID-"@` apply
+    /// to text; every `@` is replaced by the text. Without either, the text is
+    /// returned as-is.
+    ///
+    /// Digit placeholders and date codes in the section are skipped, so a
+    /// section such as `0.00 "units" @` shows text and numbers differently.
+    /// Excel refuses such sections; [`validate`](crate::validate) reports them.
+    pub fn format_text(&self, text: &str, opts: &FormatOptions) -> String {
+        let mut out = Output::new();
+        self.write_text(text, opts, &mut out);
+        out.into_string()
+    }
+
+    /// Format a boolean using this format code.
+    ///
+    /// Excel shows booleans as `TRUE` or `FALSE`, in the words of
+    /// `opts.locale`, whatever the number sections say. A text section
+    /// applies to them as it does to text.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, Locale, NumberFormat};
+    ///
+    /// let opts = FormatOptions::default();
+    /// let fmt = NumberFormat::parse("0.00").unwrap();
+    /// assert_eq!(fmt.format_bool(true, &opts), "TRUE");
+    /// let fmt = NumberFormat::parse("0;-0;0;\"[\"@\"]\"").unwrap();
+    /// assert_eq!(fmt.format_bool(false, &opts), "[FALSE]");
+    ///
+    /// let de = FormatOptions::builder().locale(Locale::from_tag("de-DE").unwrap()).build();
+    /// assert_eq!(fmt.format_bool(true, &de), "[WAHR]");
+    /// ```
+    pub fn format_bool(&self, value: bool, opts: &FormatOptions) -> String {
+        let text = if value {
+            opts.locale.true_string
+        } else {
+            opts.locale.false_string
+        };
+        self.format_text(text, opts)
+    }
+
+    /// Write a text value, returning the section used, or `None` if the
+    /// text was written as-is.
+    pub(crate) fn write_text(
+        &self,
+        text: &str,
+        opts: &FormatOptions,
+        out: &mut Output,
+    ) -> Option<&Section> {
+        let section = match self.text_section() {
+            Some(section) if !section.parts.is_empty() => section,
+            // Default: write text as-is
+            _ => {
+                out.push_digits(text);
+                return None;
             }
-            3 | 4 => {
-                if value > 0.0 {
-                    &sections[0]
-                } else if value < 0.0 {
-                    &sections[1]
-                } else {
-                    // Zero value - use section[2]
-                    // Unless it's text-only (@), then use positive section
-                    if sections[2].has_text_placeholder()
-                        && !sections[2].parts.iter().any(|p| p.is_numeric_part() || matches!(p, FormatPart::Literal(_) | FormatPart::EscapedLiteral(_))) {
-                        &sections[0]
-                    } else {
-                        &sections[2]
+        };
+
+        for part in &section.parts {
+            match part {
+                // A General text section shows the text unchanged
+                FormatPart::TextPlaceholder | FormatPart::GeneralNumber => out.push_digits(text),
+                FormatPart::Literal(s)
+                | FormatPart::QuotedLiteral(s)
+                | FormatPart::EscapedLiteral(s) => out.push_literal(s),
+                FormatPart::Locale(locale) => {
+                    if let Some(ref currency) = locale.currency {
+                        out.push_literal(currency);
                     }
                 }
+                FormatPart::Skip(c) => out.push_skip(*c, opts.layout),
+                FormatPart::Fill(c) => out.push_fill(*c),
+                _ => {}
             }
-            _ => &sections[0],
         }
+        out.expand_fill(opts);
+        Some(section)
     }
 
-    /// Format a text value using this format code.
+    /// Format any cell value, picking the formatting path for its type.
     ///
-    /// If this format has a text section (4th section), it will be used.
-    /// Otherwise, the text is returned as-is.
-    pub fn format_text(&self, text: &str, _opts: &FormatOptions) -> String {
-        let sections = self.sections();
-
-        // Text section is the 4th section if present
-        if sections.len() >= 4 {
-            let text_section = &sections[3];
-            let mut result = String::new();
-
-            for part in &text_section.parts {
-                match part {
-                    FormatPart::TextPlaceholder => result.push_str(text),
-                    FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
-                    _ => {}
-                }
+    /// Numbers and date/time values use [`format`](Self::format), with
+    /// dates converted to serials in `opts.date_system`; text uses
+    /// [`format_text`](Self::format_text) and booleans
+    /// [`format_bool`](Self::format_bool). Empty cells show as an empty
+    /// string, whatever the format.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat, Value};
+    ///
+    /// let fmt = NumberFormat::parse("0.00;-0.00;0;\"<\"@\">\"").unwrap();
+    /// let opts = FormatOptions::default();
+    /// assert_eq!(fmt.format_value(&Value::Number(1.5), &opts), "1.50");
+    /// assert_eq!(fmt.format_value(&Value::Text("n/a"), &opts), "<n/a>");
+    /// assert_eq!(fmt.format_value(&Value::Bool(true), &opts), "<TRUE>");
+    /// assert_eq!(fmt.format_value(&Value::Empty, &opts), "");
+    /// ```
+    pub fn format_value(&self, value: &Value<'_>, opts: &FormatOptions) -> String {
+        match value {
+            Value::Number(n) => self.format(*n, opts),
+            Value::Text(text) => self.format_text(text, opts),
+            Value::Bool(b) => self.format_bool(*b, opts),
+            Value::Empty => String::new(),
+            #[cfg(feature = "bigint")]
+            Value::BigInt(n) => self.format_bigint(n, opts),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => self.format_decimal(d, opts),
+            #[cfg(feature = "chrono")]
+            Value::DateTime(dt) => {
+                let serial = crate::date_serial::naive_datetime_to_serial(*dt, opts.date_system);
+                self.format(serial, opts)
             }
+            #[cfg(feature = "chrono")]
+            Value::Date(d) => {
+                let midnight = d.and_time(chrono::NaiveTime::MIN);
+                let serial =
+                    crate::date_serial::naive_datetime_to_serial(midnight, opts.date_system);
+                self.format(serial, opts)
+            }
+            #[cfg(feature = "chrono")]
+            Value::Time(t) => self.format(crate::date_serial::naive_time_to_day_fraction(*t), opts),
+            #[cfg(feature = "chrono")]
+            Value::ZonedDateTime(dt) => match zoned_serial(dt, opts) {
+                Ok(serial) => self.format(serial, opts),
+                Err(_) => general::general_format(crate::date_serial::naive_datetime_to_serial(
+                    dt.naive_utc(),
+                    opts.date_system,
+                )),
+            },
+            #[cfg(feature = "time")]
+            Value::PrimitiveDateTime(dt) => {
+                let serial = crate::date_serial::primitive_datetime_to_serial(*dt, opts.date_system);
+                self.format(serial, opts)
+            }
+            #[cfg(feature = "jiff")]
+            Value::CivilDateTime(dt) => {
+                let serial = crate::date_serial::civil_datetime_to_serial(*dt, opts.date_system);
+                self.format(serial, opts)
+            }
+        }
+    }
 
-            return result;
+    /// Format any cell value, failing where [`format_value`](Self::format_value)
+    /// would fall back.
+    ///
+    /// The only failure today is a date with a time zone under
+    /// [`TimezonePolicy::Error`](crate::TimezonePolicy::Error).
+    pub fn try_format_value(
+        &self,
+        value: &Value<'_>,
+        opts: &FormatOptions,
+    ) -> Result<String, FormatError> {
+        match value {
+            #[cfg(feature = "chrono")]
+            Value::ZonedDateTime(dt) => Ok(self.format(zoned_serial(dt, opts)?, opts)),
+            _ => Ok(self.format_value(value, opts)),
         }
+    }
 
-        // Default: return text as-is
-        text.to_string()
+    /// The section applied to text values, if any.
+    fn text_section(&self) -> Option<&Section> {
+        self.text_section_index().map(|index| &self.sections()[index])
+    }
+
+    /// Index of the section [`format_text`](Self::format_text) uses, if any.
+    pub(crate) fn text_section_index(&self) -> Option<usize> {
+        let sections = self.sections();
+        if sections.len() >= 4 {
+            return Some(3);
+        }
+        sections.iter().position(|s| s.has_text_placeholder())
     }
 
     /// Format a BigInt value using this format code (requires `bigint` feature).
     ///
     /// For values within f64's safe integer range (±2^53), converts to f64 and uses
     /// standard formatting. For larger values, uses string-based formatting to
-    /// preserve precision.
+    /// preserve precision, except in General sections, which show them in
+    /// scientific notation like any other number of 12 or more digits.
     #[cfg(feature = "bigint")]
     pub fn format_bigint(&self, value: &num_bigint::BigInt, opts: &FormatOptions) -> String {
         match self.try_format_bigint(value, opts) {
@@ -237,7 +548,7 @@ impl NumberFormat {
         let section = if is_negative {
             // Select negative section if available
             let sections = self.sections();
-            if sections.len() >= 2 {
+            if numeric_section_count(sections) >= 2 {
                 &sections[1]
             } else {
                 &sections[0]
@@ -246,9 +557,16 @@ impl NumberFormat {
             &self.sections()[0]
         };
 
-        // Handle "General" format (empty section with no parts)
-        if section.parts.is_empty() {
-            return Ok(bigint::fallback_format_bigint(value));
+        // An empty section in a code with several sections hides the value
+        if section.parts.is_empty() && self.sections().len() > 1 {
+            return Ok(String::new());
+        }
+
+        // General shows integers past 11 digits in scientific notation, so
+        // the f64 path gives the same text as for any other number
+        if section.is_general() {
+            let float_val: f64 = value.to_string().parse().unwrap_or(0.0);
+            return self.try_format(float_val, opts);
         }
 
         // Check if this is a date format - BigInt can't be used for dates
@@ -265,143 +583,144 @@ impl NumberFormat {
         // Add minus sign for negative values in single-section formats
         let sections = self.sections();
         let has_numeric_parts = section.parts.iter().any(|p| p.is_numeric_part());
-        if sections.len() == 1 && is_negative && has_numeric_parts {
+        if numeric_section_count(sections) <= 1 && is_negative && has_numeric_parts {
             result.insert(0, '-');
         }
 
         Ok(result)
     }
-}
 
-/// Fallback formatting for when the format code cannot be applied.
-///
-/// Implements Excel's "General" number format behavior:
-/// - Very small numbers (0 < |x| < 1E-4) use scientific notation
-/// - Exact integers within safe range are displayed without scientific notation
-/// - Floating point numbers with many significant digits may use scientific notation
-/// - No trailing zeros after decimal point
-pub fn fallback_format(value: f64) -> String {
-    // Handle zero
-    if value == 0.0 {
-        return "0".to_string();
-    }
-
-    // Integer fast path: check if value is a whole integer
-    // This avoids expensive log10() and format!() operations for common integer values
-    // Safe integer range for f64 is < 2^53 (9007199254740992)
-    // Excel displays exact integers without scientific notation (scientific notation
-    // is only used for display width reasons, which we don't have here)
-    const MAX_SAFE_INTEGER: u64 = 9007199254740992; // 2^53
-    let int_val = value.trunc() as i64;
-    if (value - int_val as f64).abs() < f64::EPSILON && value.abs() >= 1.0 {
-        let abs_int = int_val.unsigned_abs();
-        // For exact integers within the safe f64 range, display without scientific notation
-        // This matches Excel's behavior where General format shows integers as-is
-        if abs_int < MAX_SAFE_INTEGER {
-            return if value < 0.0 {
-                format!("-{}", abs_int)
-            } else {
-                abs_int.to_string()
-            };
+    /// Format a decimal value using this format code (requires `decimal` feature).
+    ///
+    /// Number sections keep the exact decimal digits of the value, so
+    /// `0.1 + 0.2` shows as `0.30` and digits past f64's ~15 significant
+    /// digits survive. Dates, fractions, scientific notation and General
+    /// format the value as an f64.
+    #[cfg(feature = "decimal")]
+    pub fn format_decimal(&self, value: &rust_decimal::Decimal, opts: &FormatOptions) -> String {
+        match self.try_format_decimal(value, opts) {
+            Ok(result) => result,
+            Err(_) => value.to_string(),
         }
     }
 
-    let abs_value = value.abs();
-
-    // At this point, we're dealing with non-integer values (integers handled above)
-    // For non-integer values, use scientific notation for:
-    // 1. Very small numbers (< 0.0001) that would have too many leading zeros
-    // 2. Very large non-integer values (>= 1E11) where precision is limited anyway
-    // Note: Exact integers are handled above and never use scientific notation
-
-    // Check if we should use scientific notation
-    let use_scientific = if abs_value >= 1e11 {
-        // Large non-integer values use scientific notation
-        true
-    } else if abs_value > 0.0 && abs_value < 0.0001 {
-        // For very small numbers (< 0.0001), check if decimal representation fits in 11 chars
-        // Excel uses decimal notation for values >= 0.0001, even if they need rounding
-        // But for values < 0.0001, it uses scientific if the representation is too long
-        let test_str = format!("{:.15}", abs_value);
-        // Trim trailing zeros
-        let trimmed = test_str.trim_end_matches('0').trim_end_matches('.');
-
-        // If it doesn't fit in 11 chars, use scientific notation
-        trimmed.len() > 11
-    } else {
-        false
-    };
+    /// Try to format a decimal value using this format code (requires `decimal` feature).
+    ///
+    /// See [`format_decimal`](Self::format_decimal).
+    #[cfg(feature = "decimal")]
+    pub fn try_format_decimal(
+        &self,
+        value: &rust_decimal::Decimal,
+        opts: &FormatOptions,
+    ) -> Result<String, FormatError> {
+        use rust_decimal::prelude::ToPrimitive;
 
-    if use_scientific {
-        // Format in scientific notation with up to 5 decimal places
-        // Excel shows "1.23457E+12" format
-        let formatted = format!("{:.5E}", value);
-
-        // Excel uses specific scientific notation format:
-        // Remove trailing zeros from mantissa, but keep at least one decimal place
-        if let Some(e_pos) = formatted.find('E') {
-            let (mantissa, exponent) = formatted.split_at(e_pos);
-            let trimmed_mantissa = mantissa.trim_end_matches('0');
-            let final_mantissa = trimmed_mantissa.strip_suffix('.').unwrap_or(trimmed_mantissa);
-
-            // Format exponent to match Excel: E+12, E-05, etc.
-            let exp_str = &exponent[1..]; // Skip 'E'
-            let exp_value: i32 = exp_str.parse().unwrap_or(0);
-            format!("{}E{:+03}", final_mantissa, exp_value)
-        } else {
-            formatted
+        let float_val = value.to_f64().unwrap_or(0.0);
+        let section = self.select_section(float_val);
+
+        // Only plain digit sections gain from exact digits; conditions take
+        // the absolute value in the f64 path, so leave those to it as well
+        let sections = self.sections();
+        let is_plain_number = !section.has_date_parts()
+            && sections.iter().all(|s| s.condition.is_none())
+            && section.metadata.format_type != crate::ast::FormatType::Fraction
+            && section.metadata.format_type != crate::ast::FormatType::Text
+            && section
+                .parts
+                .iter()
+                .any(|p| matches!(p, FormatPart::Digit(_) | FormatPart::DecimalPoint))
+            && !section
+                .parts
+                .iter()
+                .any(|p| matches!(p, FormatPart::Scientific { .. }));
+        if !is_plain_number {
+            return self.try_format(float_val, opts);
         }
-    } else {
-        // Use decimal notation
-        // Excel's General format shows up to 11 characters total (including decimal point)
-        // but we need to be smart about significant figures
-
-        // Try to format with enough precision to show the value accurately
-        // but within Excel's 11-digit display limit
-        let formatted = if abs_value >= 1.0 {
-            // For numbers >= 1, format with appropriate decimal places
-            let integer_digits = abs_value.log10().floor() as usize + 1;
-            let decimal_places = if integer_digits >= 10 {
-                0
-            } else {
-                (10 - integer_digits).min(10)
-            };
-            format!("{:.prec$}", value, prec = decimal_places)
-        } else {
-            // For numbers < 1, format with up to 9 decimal places (to fit in 11 chars: "0." + 9 digits)
-            // Excel's limit is 11 chars for the numeric part, not counting the sign
-            // So negative numbers can be up to 12 chars total
-            let max_decimals = 9;
-            let test_format = format!("{:.prec$}", value, prec = max_decimals);
-
-            // Check length of numeric part only (excluding sign for negative numbers)
-            let numeric_part = if value < 0.0 {
-                &test_format[1..] // Skip the '-' sign
-            } else {
-                &test_format[..]
-            };
 
-            // If numeric part exceeds 11 chars, reduce decimal places
-            if numeric_part.len() > 11 {
-                let excess = numeric_part.len() - 11;
-                let reduced_decimals = max_decimals.saturating_sub(excess);
-                format!("{:.prec$}", value, prec = reduced_decimals)
-            } else {
-                test_format
-            }
-        };
+        let mut out = Output::new();
+        if let Some(style) = numerals::section_numerals(&section.metadata) {
+            out.set_numerals(
+                style,
+                opts.locale.decimal_separator,
+                opts.locale.thousands_separator,
+            );
+        }
+        number::write_decimal(value, section, opts, &mut out)?;
 
-        // Trim trailing zeros after decimal point
-        if formatted.contains('.') {
-            let trimmed = formatted.trim_end_matches('0');
-            if trimmed.ends_with('.') {
-                trimmed.trim_end_matches('.').to_string()
-            } else {
-                trimmed.to_string()
-            }
+        // Single-section formats show the minus sign themselves, unless the
+        // value displays as zero
+        let is_negative = if opts.show_negative_zero {
+            value.is_sign_negative()
         } else {
-            formatted
+            value.is_sign_negative()
+                && !value.is_zero()
+                && !number::decimal_displays_zero(value, section, opts)
+        };
+        if numeric_section_count(sections) <= 1 && is_negative {
+            out.prepend_sign();
         }
+
+        out.finish(opts)?;
+        Ok(out.into_string())
+    }
+}
+
+/// The serial of a date with a time zone under `opts.timezone_policy`.
+#[cfg(feature = "chrono")]
+fn zoned_serial(
+    dt: &chrono::DateTime<chrono::FixedOffset>,
+    opts: &FormatOptions,
+) -> Result<f64, FormatError> {
+    let naive = match opts.timezone_policy {
+        crate::options::TimezonePolicy::Local => dt.naive_local(),
+        crate::options::TimezonePolicy::Utc => dt.naive_utc(),
+        crate::options::TimezonePolicy::Error => {
+            return Err(FormatError::TypeMismatch {
+                expected: "date without a time zone",
+                got: "date with a time zone",
+            })
+        }
+    };
+    Ok(crate::date_serial::naive_datetime_to_serial(naive, opts.date_system))
+}
+
+/// Number of sections that format numbers: a trailing `@` section in a code
+/// with fewer than four sections is the text section.
+pub(crate) fn numeric_section_count(sections: &[Section]) -> usize {
+    match sections.last() {
+        Some(last) if sections.len() < 4 && last.has_text_placeholder() => sections.len() - 1,
+        _ => sections.len(),
+    }
+}
+
+/// Section selection when the first or second section has a condition.
+///
+/// A value meeting neither condition falls through to the section with the
+/// implied condition "none of the above": the third section when both have
+/// conditions, otherwise the second. A text section never takes numbers;
+/// its place goes to the first section.
+fn conditional_section_index(sections: &[Section], value: f64) -> usize {
+    let matches = |index: usize| {
+        sections
+            .get(index)
+            .and_then(|s| s.condition)
+            .is_some_and(|condition| condition.evaluate(value))
+    };
+    if matches(0) {
+        return 0;
+    }
+    if matches(1) {
+        return 1;
+    }
+
+    let numeric_sections = numeric_section_count(sections);
+    let both_conditional =
+        sections.len() >= 2 && sections[0].condition.is_some() && sections[1].condition.is_some();
+    let fallback = if both_conditional { 2 } else { 1 };
+    if fallback < numeric_sections {
+        fallback
+    } else {
+        0
     }
 }
 
@@ -491,9 +810,15 @@ mod tests {
 
     #[test]
     fn test_fallback_format() {
-        assert_eq!(fallback_format(42.0), "42");
-        assert_eq!(fallback_format(42.5), "42.5");
-        assert_eq!(fallback_format(42.123456), "42.123456");
+        // A value the format cannot show falls back to General
+        let fmt = NumberFormat::parse("yyyy-mm-dd").unwrap();
+        let opts = FormatOptions::builder()
+            .overflow(crate::options::OverflowBehavior::Error)
+            .build();
+        assert_eq!(fmt.format(42.5, &opts), "1900-02-11");
+        assert_eq!(fmt.format(-42.5, &opts), "-42.5");
+        assert_eq!(fmt.format(1e11, &opts), "1E+11");
+        assert_eq!(fmt.format(123456789012345.0, &opts), "1.23457E+14");
     }
 
     #[test]
@@ -512,4 +837,15 @@ mod tests {
         let opts = FormatOptions::default();
         assert_eq!(fmt.format_text("hello", &opts), "<<hello>>");
     }
+
+    #[test]
+    fn test_format_text_single_section() {
+        let fmt = make_format(vec![make_section(vec![
+            FormatPart::Literal("ID-".to_string()),
+            FormatPart::TextPlaceholder,
+        ])]);
+
+        let opts = FormatOptions::default();
+        assert_eq!(fmt.format_text("42", &opts), "ID-42");
+    }
 }
@@ -1,8 +1,11 @@
 //! Number formatting (integers, decimals, percentages, scientific notation)
 
-use crate::ast::{DigitPlaceholder, FormatPart, Section};
+use crate::ast::{DigitPlaceholder, FormatAnalysis, FormatPart, Section};
 use crate::error::FormatError;
-use crate::options::FormatOptions;
+use crate::options::{FormatOptions, RoundingMode};
+
+use super::output::Output;
+use crate::parser::analysis::section_analysis;
 
 /// Format a simple integer value with digit placeholders (no separators or literals).
 /// Based on SSF's write_num helper in bits/59_numhelp.js.
@@ -47,220 +50,32 @@ pub(crate) fn format_simple_with_placeholders(
     chars.into_iter().collect()
 }
 
-/// Analysis of a format section's numeric structure.
-#[derive(Debug, Clone)]
-pub struct FormatAnalysis {
-    /// Number of integer digit placeholders
-    pub integer_placeholders: Vec<DigitPlaceholder>,
-    /// Number of decimal digit placeholders
-    pub decimal_placeholders: Vec<DigitPlaceholder>,
-    /// Whether the format has a thousands separator
-    pub has_thousands_separator: bool,
-    /// Number of percent signs (each multiplies by 100)
-    pub percent_count: usize,
-    /// Thousands scaling factor (trailing commas divide by 1000 each)
-    pub thousands_scale: usize,
-    /// Literals that appear inline with integer digits (position -> literal)
-    /// Position is counted from the right (0 = ones place, 1 = tens, etc.)
-    pub inline_literals: Vec<(usize, String)>,
-    /// Literals that appear inline with decimal digits (position -> literal)
-    /// Position is counted from the left (0 = first decimal place, 1 = second, etc.)
-    pub decimal_inline_literals: Vec<(usize, String)>,
-    /// Parts before the number (literals, etc.)
-    pub prefix_parts: Vec<FormatPart>,
-    /// Parts after the number (literals, percent, etc.)
-    pub suffix_parts: Vec<FormatPart>,
-}
-
-impl FormatAnalysis {
-    /// Get the number of required decimal places
-    pub fn decimal_places(&self) -> usize {
-        self.decimal_placeholders.len()
-    }
-
-    /// Get the minimum integer digits (count of Zero placeholders)
-    #[allow(dead_code)]
-    pub fn min_integer_digits(&self) -> usize {
-        self.integer_placeholders
-            .iter()
-            .filter(|p| p.is_required())
-            .count()
-    }
-}
-
-/// Analyze a format section to extract its numeric structure.
-pub fn analyze_format(section: &Section) -> FormatAnalysis {
-    let mut integer_placeholders = Vec::new();
-    let mut decimal_placeholders = Vec::new();
-    let mut has_thousands_separator = false;
-    let mut percent_count = 0;
-    let mut inline_literals = Vec::new();
-    let mut decimal_inline_literals = Vec::new();
-    let mut prefix_parts = Vec::new();
-    let mut suffix_parts = Vec::new();
-
-    // First, count trailing commas by scanning backwards from the end
-    // Any ThousandsSeparator after the last Digit/DecimalPoint is a trailing comma
-    let mut trailing_comma_count = 0;
-    for part in section.parts.iter().rev() {
-        match part {
-            FormatPart::ThousandsSeparator => {
-                trailing_comma_count += 1;
-            }
-            FormatPart::Digit(_) | FormatPart::DecimalPoint => {
-                // Found a digit or decimal, stop counting trailing commas
-                break;
-            }
-            _ => {
-                // Other parts (Fill, Skip, Literal) - continue scanning
-            }
-        }
-    }
-
-    // Track which commas are trailing (to exclude from has_thousands_separator)
-    let mut commas_seen = 0;
-    let total_commas = section.parts.iter().filter(|p| matches!(p, FormatPart::ThousandsSeparator)).count();
-    let non_trailing_comma_count = total_commas - trailing_comma_count;
-
-    let mut seen_digit = false;
-    let mut after_decimal = false;
-    let mut after_digits = false;
-
-    for part in &section.parts {
-        match part {
-            FormatPart::Digit(placeholder) => {
-                seen_digit = true;
-                after_digits = false;
-                if after_decimal {
-                    decimal_placeholders.push(*placeholder);
-                } else {
-                    integer_placeholders.push(*placeholder);
-                }
-            }
-            FormatPart::DecimalPoint => {
-                after_decimal = true;
-                seen_digit = true;
-                after_digits = true;  // Mark that integer digit sequence is complete
-            }
-            FormatPart::ThousandsSeparator => {
-                commas_seen += 1;
-                // Only count as thousands separator if it's not a trailing comma
-                // Trailing commas are only for scaling, not for formatting separators
-                if commas_seen <= non_trailing_comma_count {
-                    has_thousands_separator = true;
-                }
-            }
-            FormatPart::Percent => {
-                percent_count += 1;
-                if seen_digit {
-                    after_digits = true;
-                    suffix_parts.push(part.clone());
-                } else {
-                    prefix_parts.push(part.clone());
-                }
-            }
-            FormatPart::Literal(_) | FormatPart::EscapedLiteral(_) | FormatPart::Locale(crate::ast::LocaleCode { currency: Some(_), .. }) => {
-                let literal_str = if let FormatPart::Literal(s) = part {
-                    s.clone()
-                } else if let FormatPart::EscapedLiteral(s) = part {
-                    s.clone()
-                } else if let FormatPart::Locale(loc) = part {
-                    loc.currency.clone().unwrap_or_default()
-                } else {
-                    String::new()
-                };
-
-                if !seen_digit {
-                    // Before any digits - prefix
-                    prefix_parts.push(part.clone());
-                } else if after_digits {
-                    // After all digits (after decimal or after digit sequence ended) - suffix
-                    suffix_parts.push(part.clone());
-                } else if after_decimal {
-                    // Among decimal digits - inline literal in decimal part
-                    // Store position from left (index in decimal_placeholders)
-                    decimal_inline_literals.push((decimal_placeholders.len(), literal_str));
-                } else {
-                    // Among integer digits - inline literal
-                    // Store the current placeholder count - we'll convert to position later
-                    inline_literals.push((integer_placeholders.len(), literal_str));
-                }
-            }
-            FormatPart::Locale(loc) if loc.currency.is_none() => {
-                // Locale without currency - treat as before
-                if !seen_digit {
-                    prefix_parts.push(part.clone());
-                } else if after_digits {
-                    suffix_parts.push(part.clone());
-                }
-            }
-            FormatPart::Skip(c) => {
-                // Skip adds space equivalent to character width
-                if !seen_digit {
-                    prefix_parts.push(FormatPart::Literal(" ".to_string()));
-                } else {
-                    suffix_parts.push(FormatPart::Literal(" ".to_string()));
-                }
-                let _ = c; // suppress unused warning
-            }
-            _ => {
-                // Handle other parts as literals in prefix/suffix
-                if !seen_digit {
-                    prefix_parts.push(part.clone());
-                } else if after_digits {
-                    suffix_parts.push(part.clone());
-                }
-            }
-        }
-    }
-
-    // Ensure we have at least one integer placeholder for output
-    if integer_placeholders.is_empty() && !after_decimal {
-        integer_placeholders.push(DigitPlaceholder::Hash);
-    }
-
-    // Use the trailing comma count we calculated earlier
-    let thousands_scale = trailing_comma_count;
-
-    // Convert inline_literals from placeholder indices to positions from right
-    // Inline literals are stored as (placeholder_count, string) where placeholder_count
-    // is the number of placeholders added BEFORE seeing the literal.
-    // This means the literal appears before placeholder at index=placeholder_count.
-    // When formatting right-to-left, placeholder at index I is at position (total-1-I) from right.
-    let total_placeholders = integer_placeholders.len();
-    let inline_literals_converted: Vec<(usize, String)> = inline_literals
-        .into_iter()
-        .map(|(placeholder_count, literal)| {
-            // Literal appears before placeholder[placeholder_count]
-            // That placeholder is at position (total - 1 - placeholder_count) from right
-            // Insert the literal AT that position (before that placeholder's digit)
-            let pos_from_right = total_placeholders - placeholder_count;
-            (pos_from_right, literal)
-        })
-        .collect();
-
-    FormatAnalysis {
-        integer_placeholders,
-        decimal_placeholders,
-        has_thousands_separator,
-        percent_count,
-        thousands_scale,
-        inline_literals: inline_literals_converted,
-        decimal_inline_literals,
-        prefix_parts,
-        suffix_parts,
-    }
-}
-
 /// Format a number according to a section.
+#[cfg(feature = "bigint")]
 pub fn format_number(
     value: f64,
     section: &Section,
     opts: &FormatOptions,
 ) -> Result<String, FormatError> {
+    let mut out = Output::new();
+    write_number(value, section, opts, &mut out)?;
+    out.finish(opts)?;
+    Ok(out.into_string())
+}
+
+/// Format a number according to a section into `out`.
+pub(crate) fn write_number(
+    value: f64,
+    section: &Section,
+    opts: &FormatOptions,
+    out: &mut Output,
+) -> Result<(), FormatError> {
     // Check if this is scientific notation
     let scientific_part = section.parts.iter().find_map(|p| {
-        if let FormatPart::Scientific { upper, show_plus } = p {
+        if let FormatPart::Scientific {
+            upper, show_plus, ..
+        } = p
+        {
             Some((*upper, *show_plus))
         } else {
             None
@@ -268,7 +83,8 @@ pub fn format_number(
     });
 
     if let Some((upper, show_plus)) = scientific_part {
-        return format_scientific(value, section, upper, show_plus, opts);
+        out.push_digits(&format_scientific(value, section, upper, show_plus, opts)?);
+        return Ok(());
     }
 
     // Use pre-computed format type from metadata for better performance
@@ -276,12 +92,13 @@ pub fn format_number(
 
     // Check if this is a fraction format
     if section.metadata.format_type == FormatType::Fraction {
-        return crate::formatter::fraction::format_fraction(value, section, opts);
+        return crate::formatter::fraction::write_fraction(value, section, opts, out);
     }
 
     // Check if this is a text-only format
     if section.metadata.format_type == FormatType::Text {
-        return Ok(crate::formatter::fallback_format(value));
+        out.push_digits(&crate::formatter::general_format(value));
+        return Ok(());
     }
 
     // Check if section has any numeric placeholders
@@ -301,19 +118,22 @@ pub fn format_number(
         if has_general_number {
             // Section has GeneralNumber part - use General format + append literals
             // This handles cases like "General " where we want to format the number and add a suffix
-            let mut result = crate::formatter::fallback_format(value);
+            out.push_digits(&crate::formatter::general_format(value));
             for part in &section.parts {
                 match part {
-                    FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
+                    FormatPart::Literal(s)
+                    | FormatPart::QuotedLiteral(s)
+                    | FormatPart::EscapedLiteral(s) => out.push_literal(s),
                     FormatPart::Locale(locale_code) => {
                         if let Some(ref currency) = locale_code.currency {
-                            result.push_str(currency);
+                            out.push_literal(currency);
                         }
                     }
-                    FormatPart::Percent => result.push('%'),
-                    FormatPart::Skip(_) => result.push(' '),
-                    FormatPart::Fill(_) => {
-                        // Fill character - for now just skip it
+                    FormatPart::Percent => out.push_literal("%"),
+                    FormatPart::Skip(c) => out.push_skip(*c, opts.layout),
+                    FormatPart::Fill(c) => {
+                        // Fill character - expanded once the full width is known
+                        out.push_fill(*c);
                     }
                     FormatPart::GeneralNumber => {
                         // Already handled - skip
@@ -321,32 +141,33 @@ pub fn format_number(
                     _ => {}
                 }
             }
-            return Ok(result);
+            return Ok(());
         } else {
             // No GeneralNumber - just return the literals without formatting the number
-            let mut result = String::new();
             for part in &section.parts {
                 match part {
-                    FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
+                    FormatPart::Literal(s)
+                    | FormatPart::QuotedLiteral(s)
+                    | FormatPart::EscapedLiteral(s) => out.push_literal(s),
                     FormatPart::Locale(locale_code) => {
                         if let Some(ref currency) = locale_code.currency {
-                            result.push_str(currency);
+                            out.push_literal(currency);
                         }
                     }
-                    FormatPart::Percent => result.push('%'),
-                    FormatPart::Skip(_) => result.push(' '),
-                    FormatPart::Fill(_) => {
-                        // Fill character - for now just skip it in literal-only formats
-                        // TODO: implement proper fill behavior with available width
+                    FormatPart::Percent => out.push_literal("%"),
+                    FormatPart::Skip(c) => out.push_skip(*c, opts.layout),
+                    FormatPart::Fill(c) => {
+                        // Fill character - expanded once the full width is known
+                        out.push_fill(*c);
                     }
                     _ => {}
                 }
             }
-            return Ok(result);
+            return Ok(());
         }
     }
 
-    let analysis = analyze_format(section);
+    let analysis = &*section_analysis(section);
 
     // Integer fast path: use integer-only arithmetic to avoid precision loss
     // Based on SSF's separate code paths in bits/66_numint.js vs bits/63_numflt.js
@@ -360,7 +181,7 @@ pub fn format_number(
         && analysis.decimal_placeholders.is_empty()
     {
         // Value is an exact integer within safe range and no decimal formatting needed
-        return format_number_as_integer(value as i64, section, opts);
+        return format_number_as_integer(value as i64, analysis, opts, out);
     }
 
     // Apply percent multiplication
@@ -374,21 +195,85 @@ pub fn format_number(
         adjusted_value /= 1000.0;
     }
 
-    // Round to the required decimal places
-    // Use limited precision rounding to avoid overflow with large decimal_places
-    // f64 has ~15-16 significant digits, so clamping to 15 decimal places is safe
-    let decimal_places = analysis.decimal_places();
-    let effective_decimal_places = decimal_places.min(15);
-    let multiplier = 10_f64.powi(effective_decimal_places as i32);
-    let rounded = (adjusted_value * multiplier).round() / multiplier;
+    // Round to the required decimal places on the decimal digits, so the
+    // float error in values like 2.675 can't leak into the output
+    let (integer_digits, decimal_digits) =
+        round_to_digits(adjusted_value, shown_decimal_places(analysis), opts.rounding);
 
     // Format the number with placeholders
-    let formatted = format_with_placeholders(rounded, &analysis, opts);
+    let formatted = format_with_placeholders(&integer_digits, &decimal_digits, analysis, opts);
 
     // Build the final result with prefix and suffix
-    let result = build_result(&analysis, &formatted, opts);
+    build_result(analysis, &formatted, opts, out);
+
+    Ok(())
+}
+
+/// Format a plain number section with a decimal value, keeping its exact
+/// digits instead of rounding through f64.
+///
+/// Falls back to [`write_number`] if scaling for percents overflows the
+/// decimal range.
+#[cfg(feature = "decimal")]
+pub(crate) fn write_decimal(
+    value: &rust_decimal::Decimal,
+    section: &Section,
+    opts: &FormatOptions,
+    out: &mut Output,
+) -> Result<(), FormatError> {
+    use rust_decimal::prelude::ToPrimitive;
+
+    let analysis = &*section_analysis(section);
+    let Some(rounded) = round_decimal(value, analysis, opts) else {
+        return write_number(value.to_f64().unwrap_or(0.0), section, opts, out);
+    };
+
+    let digits = rounded.to_string();
+    let (integer_digits, fraction_digits) = digits.split_once('.').unwrap_or((&digits, ""));
+    let mut decimal_digits = fraction_digits.as_bytes().to_vec();
+    decimal_digits.resize(analysis.decimal_places(), b'0');
+    let formatted =
+        format_with_placeholders(integer_digits.as_bytes(), &decimal_digits, analysis, opts);
+
+    build_result(analysis, &formatted, opts, out);
+    Ok(())
+}
 
-    Ok(result)
+/// Whether a plain number section shows a decimal value as zero.
+#[cfg(feature = "decimal")]
+pub(crate) fn decimal_displays_zero(
+    value: &rust_decimal::Decimal,
+    section: &Section,
+    opts: &FormatOptions,
+) -> bool {
+    round_decimal(value, &section_analysis(section), opts).is_some_and(|d| d.is_zero())
+}
+
+/// The magnitude of a decimal value scaled for percents and trailing commas
+/// and rounded to the displayed places, or `None` on overflow.
+#[cfg(feature = "decimal")]
+fn round_decimal(
+    value: &rust_decimal::Decimal,
+    analysis: &FormatAnalysis,
+    opts: &FormatOptions,
+) -> Option<rust_decimal::Decimal> {
+    use rust_decimal::{Decimal, RoundingStrategy};
+
+    // Apply percent multiplication and thousands scaling exactly
+    let mut adjusted = value.abs();
+    for _ in 0..analysis.percent_count {
+        adjusted = adjusted.checked_mul(Decimal::ONE_HUNDRED)?;
+    }
+    for _ in 0..analysis.thousands_scale {
+        adjusted = adjusted.checked_div(Decimal::ONE_THOUSAND)?;
+    }
+
+    let strategy = match opts.rounding {
+        RoundingMode::HalfAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+        RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+    };
+    let places = u32::try_from(analysis.decimal_places()).unwrap_or(u32::MAX);
+    Some(adjusted.round_dp_with_strategy(places, strategy))
 }
 
 /// Format an integer value using integer-only arithmetic (no precision loss).
@@ -396,11 +281,10 @@ pub fn format_number(
 /// This path is used for values that are exact integers within safe range (< 2^53).
 fn format_number_as_integer(
     value: i64,
-    section: &Section,
+    analysis: &FormatAnalysis,
     opts: &FormatOptions,
-) -> Result<String, FormatError> {
-    let analysis = analyze_format(section);
-
+    out: &mut Output,
+) -> Result<(), FormatError> {
     // Work with absolute value, track sign separately
     let mut adjusted_value = value.abs();
 
@@ -409,9 +293,9 @@ fn format_number_as_integer(
         adjusted_value = adjusted_value.saturating_mul(100);
     }
 
-    // Apply thousands scaling (integer division)
-    for _ in 0..analysis.thousands_scale {
-        adjusted_value /= 1000;
+    // Apply thousands scaling (integer division, rounding the dropped digits)
+    if analysis.thousands_scale > 0 {
+        adjusted_value = scale_thousands(adjusted_value, analysis.thousands_scale, opts.rounding);
     }
 
     // For integers, decimal places should be zero unless explicitly formatted
@@ -420,250 +304,261 @@ fn format_number_as_integer(
     if decimal_places > 0 {
         // Integer displayed with decimal places (e.g., "0.00" formatting integer 42 -> "42.00")
         // Convert to string and pad with zeros
-        let integer_str = format_integer(
-            adjusted_value as u64,
-            &analysis.integer_placeholders,
-            analysis.has_thousands_separator,
-            &analysis.inline_literals,
-            opts,
-        );
+        let integer_str = format_integer(adjusted_value as u64, analysis, opts);
 
         // Add decimal point and zeros
-        let decimal_str = "0".repeat(decimal_places);
-        let formatted = format!(
-            "{}{}{}",
-            integer_str, opts.locale.decimal_separator, decimal_str
-        );
+        let mut formatted = integer_str;
+        formatted.push(opts.locale.decimal_separator);
+        formatted.extend(std::iter::repeat_n('0', decimal_places));
 
         // Build the final result with prefix and suffix
-        let result = build_result(&analysis, &formatted, opts);
-        Ok(result)
+        build_result(analysis, &formatted, opts, out);
+        Ok(())
     } else {
         // Pure integer formatting (no decimal places)
-        let formatted = format_integer(
-            adjusted_value as u64,
-            &analysis.integer_placeholders,
-            analysis.has_thousands_separator,
-            &analysis.inline_literals,
-            opts,
-        );
+        let formatted = format_integer(adjusted_value as u64, analysis, opts);
 
         // Build the final result with prefix and suffix
-        let result = build_result(&analysis, &formatted, opts);
-        Ok(result)
+        build_result(analysis, &formatted, opts, out);
+        Ok(())
     }
 }
 
-/// Format a number according to the analysis.
-fn format_with_placeholders(value: f64, analysis: &FormatAnalysis, opts: &FormatOptions) -> String {
-    let decimal_places = analysis.decimal_places();
+/// Divide a non-negative `value` by 1000 per scaling comma, rounding to
+/// the nearest integer.
+fn scale_thousands(value: i64, scale: usize, rounding: RoundingMode) -> i64 {
+    let divisor = u32::try_from(scale * 3)
+        .ok()
+        .and_then(|exponent| 10_i64.checked_pow(exponent));
+    let Some(divisor) = divisor else {
+        // Larger than any i64, so even the first dropped digit is zero
+        return 0;
+    };
+    let (quotient, remainder) = (value / divisor, value % divisor);
+    let unit = divisor / 10;
+    let first_dropped = (remainder / unit) as u8;
+    if rounding.rounds_up(first_dropped, remainder % unit != 0, (quotient % 10) as u8) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
 
-    // Split into integer and decimal parts
-    let integer_part = value.trunc() as u64;
-    let decimal_part = value.fract();
+/// Decimal digits of a non-negative `value` rounded to `places` decimal
+/// places.
+///
+/// Like Excel, rounds from the value's 15 significant digits rather than
+/// from its binary expansion: 2.675 is stored as 2.67499999999999982...
+/// but shows as 2.68 with `0.00`. Returns the integer digits (at least
+/// one) and exactly `places` decimal digits.
+fn round_to_digits(value: f64, places: usize, rounding: RoundingMode) -> (Vec<u8>, Vec<u8>) {
+    let scientific = format!("{:.14e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: isize = exponent.parse().unwrap_or(0);
+    let significant: Vec<u8> = mantissa.bytes().filter(u8::is_ascii_digit).collect();
+
+    // The digit worth 10^power
+    let digit_at = |power: isize| {
+        usize::try_from(exponent - power)
+            .ok()
+            .and_then(|i| significant.get(i).copied())
+            .unwrap_or(b'0')
+    };
 
+    let places = places as isize;
+    let mut digits: Vec<u8> = (-places..=exponent.max(0)).rev().map(digit_at).collect();
+    let lowest_power = exponent - significant.len() as isize + 1;
+    let rest_nonzero = (lowest_power..-places - 1).any(|power| digit_at(power) != b'0');
+    let last_kept = digits.last().map_or(0, |d| d - b'0');
+    if rounding.rounds_up(digit_at(-places - 1) - b'0', rest_nonzero, last_kept) {
+        // Carry the rounding up through the digits
+        let mut carried = true;
+        for digit in digits.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                carried = false;
+                break;
+            }
+        }
+        if carried {
+            digits.insert(0, b'1');
+        }
+    }
+
+    let decimal_digits = digits.split_off(digits.len() - places as usize);
+    let leading_zeros = digits.iter().take_while(|&&d| d == b'0').count();
+    digits.drain(..leading_zeros.min(digits.len() - 1));
+    (digits, decimal_digits)
+}
+
+/// Most decimal places that take digits of the value.
+///
+/// Matches SSF, which clamps to 10 (bits/66_numint.js); placeholders past
+/// the tenth are padded like digits past the end of the value, so float
+/// noise never shows in long decimal formats.
+const MAX_DECIMAL_PLACES: usize = 10;
+
+/// The decimal places a section rounds to, at most [`MAX_DECIMAL_PLACES`].
+fn shown_decimal_places(analysis: &FormatAnalysis) -> usize {
+    analysis.decimal_places().min(MAX_DECIMAL_PLACES)
+}
+
+/// Format rounded integer and decimal digits according to the analysis.
+fn format_with_placeholders(
+    integer_digits: &[u8],
+    decimal_digits: &[u8],
+    analysis: &FormatAnalysis,
+    opts: &FormatOptions,
+) -> String {
     // Format integer part
-    let integer_str = format_integer(
-        integer_part,
-        &analysis.integer_placeholders,
-        analysis.has_thousands_separator,
-        &analysis.inline_literals,
-        opts,
-    );
+    let mut formatted = format_integer_digits(integer_digits, analysis, opts);
 
     // Format decimal part
-    if decimal_places > 0 {
-        let decimal_str = format_decimal(
-            decimal_part,
+    if !analysis.decimal_placeholders.is_empty() {
+        formatted.push(opts.locale.decimal_separator);
+        formatted.push_str(&format_decimal_digits(
+            decimal_digits,
             &analysis.decimal_placeholders,
             &analysis.decimal_inline_literals,
-            opts,
-        );
-        format!(
-            "{}{}{}",
-            integer_str, opts.locale.decimal_separator, decimal_str
-        )
-    } else {
-        integer_str
+        ));
     }
+    formatted
 }
 
 /// Format the integer part with placeholders and thousands separator.
-fn format_integer(
-    value: u64,
-    placeholders: &[DigitPlaceholder],
-    use_thousands: bool,
-    inline_literals: &[(usize, String)],
+fn format_integer(value: u64, analysis: &FormatAnalysis, opts: &FormatOptions) -> String {
+    let mut digit_buf = [0u8; 20];
+    let value_digits = u64_digits(value, &mut digit_buf);
+    format_integer_digits(value_digits, analysis, opts)
+}
+
+/// Format the ASCII decimal digits of an integer part with placeholders and
+/// thousands separator.
+///
+/// Walks the integer digit run right to left, giving each placeholder the
+/// value's digit at its position or its padding (see
+/// [`DigitPlaceholder::empty_char`]). Digits beyond the leftmost placeholder
+/// all show there (12345 with `00` is "12345"). Literals keep their place
+/// between placeholders; thousands separators follow every third digit
+/// wherever the commas were written, as spaces within `?` padding.
+pub(crate) fn format_integer_digits(
+    value_digits: &[u8],
+    analysis: &FormatAnalysis,
     opts: &FormatOptions,
 ) -> String {
-    let value_str = value.to_string();
-    let value_digits: Vec<char> = value_str.chars().collect();
-
-    let min_digits = placeholders.iter().filter(|p| p.is_required()).count();
-
-    // Special case: if value is 0 and all placeholders are optional, return empty
-    // BUT still include any inline literals
-    if value == 0 && min_digits == 0 {
-        let mut result = String::new();
-        // Add any inline literals that would be in the optional placeholder region
-        // Sort by position (descending) to add them left-to-right
-        let mut sorted_literals: Vec<_> = inline_literals.iter().collect();
-        sorted_literals.sort_by(|a, b| b.0.cmp(&a.0)); // Descending order
-
-        for (_literal_pos, literal_str) in sorted_literals {
-            // Add literals in order (left to right)
-            result.push_str(literal_str);
-        }
-        return result;
-    }
-
-    // SSF has different logic based on whether the format includes thousands separators
-    // For formats WITHOUT thousands separators (e.g., "0#######0"):
-    //   - Output length = max(value_digits, total_placeholders)
-    //   - Pad using "hashq" logic: 0->'0', #->skip, ?->' '
-    //   - This matches SSF's bits/66_numint.js line 69-73 for ^([#0]+)\.([#0]+)$ patterns
-    // For formats WITH thousands separators (e.g., "#,###"):
-    //   - Use SSF's commaify approach which formats the number then adds separators
-    //   - Only show digits that fit the placeholder pattern
-    let output_len = if use_thousands {
-        // With thousands separators: use the narrower width to avoid spurious separators
-        // This matches SSF's behavior for patterns like #{1,3},##0
-        value_digits.len().max(min_digits)
+    // A zero shown only through optional placeholders is all padding
+    let value_digits = if value_digits == b"0" && analysis.min_integer_digits() == 0 {
+        &[]
     } else {
-        // Without thousands separators: use the full pattern width
-        value_digits.len().max(placeholders.len())
+        value_digits
+    };
+    let separator = analysis
+        .has_thousands_separator
+        .then_some(opts.locale.thousands_separator);
+
+    // The value's digit at a position, or else the placeholder's padding
+    let slot = |pos: usize, placeholder: Option<DigitPlaceholder>| {
+        match value_digits.len().checked_sub(pos + 1) {
+            Some(digit_index) => Some(value_digits[digit_index] as char),
+            None => placeholder.and_then(|p| p.empty_char()),
+        }
     };
 
     // Build right-to-left into Vec, then reverse once (O(n) instead of O(n²) with insert(0))
-    // Estimate capacity: output_len + separators + inline literals
-    let separator_count = if use_thousands { output_len / 3 } else { 0 };
-    let literal_chars: usize = inline_literals.iter().map(|(_, s)| s.len()).sum();
-    let estimated_capacity = output_len + separator_count + literal_chars;
-    let mut chars = Vec::with_capacity(estimated_capacity);
-
-    // Process from right to left (least significant first)
-    for (digit_count, pos_from_right) in (0..output_len).enumerate() {
-        let digit_index = value_digits.len() as isize - 1 - pos_from_right as isize;
-
-        // Add thousands separator if needed (but not at position 0)
-        if use_thousands && digit_count > 0 && digit_count % 3 == 0 {
-            chars.push(opts.locale.thousands_separator);
-        }
+    let width = value_digits.len().max(analysis.integer_placeholders.len());
+    let mut chars = Vec::with_capacity(width + width / 3);
 
-        // Check if there's an inline literal at this position
-        // Position is from the right (0 = ones place, 1 = tens, etc.)
-        // Collect all literals at this position and push them
-        let literals_at_pos: Vec<&str> = inline_literals
-            .iter()
-            .filter(|(pos, _)| *pos == pos_from_right)
-            .map(|(_, s)| s.as_str())
-            .collect();
-
-        // Push in reverse order (will be reversed back at the end)
-        for literal_str in literals_at_pos.iter().rev() {
-            for ch in literal_str.chars().rev() {
-                chars.push(ch);
+    // Position 0 is the ones place
+    let mut pos = 0;
+    for part in analysis.integer_parts.iter().rev() {
+        match part {
+            FormatPart::Digit(placeholder) => {
+                push_grouped(&mut chars, pos, slot(pos, Some(*placeholder)), separator);
+                pos += 1;
             }
-        }
-
-        if digit_index >= 0 {
-            // We have a digit from the value
-            chars.push(value_digits[digit_index as usize]);
-        } else {
-            // No digit from value - apply SSF "hashq" padding logic
-            // Use the placeholder at this position to determine padding character:
-            //   0 (Zero) -> '0'
-            //   # (Hash) -> nothing (skip)
-            //   ? (Question) -> ' '
-            let placeholder_index = placeholders.len() as isize - 1 - pos_from_right as isize;
-            if placeholder_index >= 0 {
-                let placeholder = placeholders[placeholder_index as usize];
-                // empty_char returns Some('0') for Zero, None for Hash, Some(' ') for Question
-                if let Some(c) = placeholder.empty_char() {
-                    chars.push(c);
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => {
+                chars.extend(s.chars().rev());
+            }
+            FormatPart::Locale(locale_code) => {
+                if let Some(currency) = &locale_code.currency {
+                    chars.extend(currency.chars().rev());
                 }
-                // If None (Hash), we don't push anything - this truncates the output
             }
+            FormatPart::Percent => chars.push('%'),
+            // Grouping applies to the whole run, not where the comma is
+            _ => {}
         }
     }
 
-    // Handle the case where we have no digits but need at least one
-    if chars.is_empty() {
-        // Check if we have any required placeholders
-        if placeholders.iter().any(|p| p.is_required()) {
-            chars.push('0');
-        }
+    // The run starts with a placeholder, so any remaining digits land on it
+    for pos in pos..value_digits.len() {
+        push_grouped(&mut chars, pos, slot(pos, None), separator);
     }
 
-    // Push any inline literals that are at positions beyond what we formatted
-    // (literals in the leftmost optional placeholder region)
-    for (literal_pos, literal_str) in inline_literals {
-        if *literal_pos >= output_len {
-            // This literal is to the left of all displayed digits
-            for ch in literal_str.chars().rev() {
-                chars.push(ch);
-            }
+    chars.reverse();
+    chars.into_iter().collect()
+}
+
+/// Push the character at `pos` of a reversed integer part, preceded by
+/// the thousands separator that follows it when grouping.
+fn push_grouped(chars: &mut Vec<char>, pos: usize, slot: Option<char>, separator: Option<char>) {
+    if let Some(separator) = separator.filter(|_| pos > 0 && pos.is_multiple_of(3)) {
+        // Within `?` padding the separator becomes a space so columns align
+        match slot {
+            Some(' ') => chars.push(' '),
+            Some(_) => chars.push(separator),
+            None => {}
         }
     }
+    chars.extend(slot);
+}
 
-    // Reverse once and collect into String
-    chars.reverse();
-    let result: String = chars.into_iter().collect();
-
-    result
+/// Write the decimal digits of `value` into the end of `buf`, returning them.
+fn u64_digits(mut value: u64, buf: &mut [u8; 20]) -> &[u8] {
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            return &buf[start..];
+        }
+    }
 }
 
-/// Format the decimal part with placeholders.
-fn format_decimal(
-    value: f64,
+/// Format the decimal digits of a number with placeholders.
+///
+/// Expects one ASCII digit per placeholder, up to [`MAX_DECIMAL_PLACES`].
+/// Placeholders past the digits are padded as SSF does: `0` shows a zero,
+/// `?` a space and `#` nothing.
+fn format_decimal_digits(
+    decimal_digits: &[u8],
     placeholders: &[DigitPlaceholder],
     decimal_inline_literals: &[(usize, String)],
-    _opts: &FormatOptions,
 ) -> String {
-    if placeholders.is_empty() {
-        return String::new();
-    }
-
-    // Match SSF behavior: clamp decimal places to 10 (bits/66_numint.js line 70)
-    // This avoids floating-point precision issues when multiplying by large powers of 10
-    // SSF uses Math.min(r[2].length, 10) where r[2] is the decimal placeholder count
-    let effective_places = placeholders.len().min(10);
-
-    // Get the decimal digits by multiplying and truncating
-    let multiplier = 10_f64.powi(effective_places as i32);
-    let decimal_int = (value * multiplier).round() as u64;
-    let decimal_str = format!("{:0>width$}", decimal_int, width = effective_places);
-    let decimal_chars: Vec<char> = decimal_str.chars().collect();
-
     let mut result = String::new();
 
-    // Check if the entire decimal part is zeros (matches SSF behavior)
-    // SSF strips all trailing zeros with regex /([^0])0+$/ before applying format
-    let all_zeros = decimal_chars.iter().all(|&c| c == '0');
-
-    // Find where trailing zeros start (for # placeholders)
-    // If all zeros, start from position 0 (all Hash placeholders are skipped)
-    // Otherwise, scan backwards to find trailing zeros
-    let mut trailing_zeros_start = if all_zeros {
+    // Zeros are dropped for optional placeholders after the last nonzero
+    // digit or required placeholder, and everywhere if all digits are zero
+    // (SSF strips them with /([^0])0+$/ before applying the format)
+    let trailing_zeros_start = if decimal_digits.iter().all(|&d| d == b'0') {
         0
     } else {
-        placeholders.len()
+        placeholders
+            .iter()
+            .zip(decimal_digits)
+            .rposition(|(placeholder, &digit)| digit != b'0' || placeholder.is_required())
+            .map_or(0, |i| i + 1)
+    };
+    let padded = |placeholder: &DigitPlaceholder| match placeholder {
+        DigitPlaceholder::Zero => Some('0'),
+        DigitPlaceholder::Question => Some(' '),
+        DigitPlaceholder::Hash => None,
     };
-
-    // Only scan within effective_places to avoid index out of bounds
-    if !all_zeros {
-        for i in (0..placeholders.len().min(effective_places)).rev() {
-            if decimal_chars.get(i) == Some(&'0') {
-                if !placeholders[i].is_required() {
-                    trailing_zeros_start = i;
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
-    }
 
     // Build result, respecting placeholder rules
     for (i, placeholder) in placeholders.iter().enumerate() {
@@ -674,32 +569,17 @@ fn format_decimal(
             }
         }
 
-        // Get the character for this position
-        let ch = if i < effective_places {
-            decimal_chars.get(i).copied().unwrap_or('0')
-        } else {
-            // Beyond effective precision: apply SSF "hashq" logic
-            // Hash (#) -> skip (no output)
-            // Zero (0) -> '0'
-            // Question (?) -> ' '
-            match placeholder {
-                DigitPlaceholder::Hash => {
-                    // Skip - don't add anything
-                    continue;
-                }
-                DigitPlaceholder::Zero => '0',
-                DigitPlaceholder::Question => ' ',
-            }
+        let Some(&digit) = decimal_digits.get(i) else {
+            result.extend(padded(placeholder));
+            continue;
         };
-
-        if i >= trailing_zeros_start && ch == '0' && !placeholder.is_required() {
-            // Skip trailing zeros for # placeholders (only within effective_places)
+        if i >= trailing_zeros_start && digit == b'0' && !placeholder.is_required() {
+            // Trailing zero: # shows nothing, ? shows a space
             if matches!(placeholder, DigitPlaceholder::Question) {
                 result.push(' ');
             }
-            // For Hash, we don't add anything
         } else {
-            result.push(ch);
+            result.push(digit as char);
         }
     }
 
@@ -717,60 +597,59 @@ fn format_decimal(
 fn count_part_chars(parts: &[FormatPart]) -> usize {
     parts.iter().map(|part| {
         match part {
-            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => s.len(),
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => s.len(),
             FormatPart::Locale(locale_code) => {
                 locale_code.currency.as_ref().map_or(0, |s| s.len())
             }
-            FormatPart::Percent => 1,
+            FormatPart::Percent | FormatPart::Skip(_) => 1,
             _ => 0,
         }
     }).sum()
 }
 
-/// Build the final result string with prefix and suffix parts.
+/// Write the final result with prefix and suffix parts.
 fn build_result(
     analysis: &FormatAnalysis,
     formatted_number: &str,
-    _opts: &FormatOptions,
-) -> String {
+    opts: &FormatOptions,
+    out: &mut Output,
+) {
     // Pre-allocate exact capacity (no reallocation, no waste)
     let capacity = count_part_chars(&analysis.prefix_parts)
         + formatted_number.len()
         + count_part_chars(&analysis.suffix_parts);
-    let mut result = String::with_capacity(capacity);
+    out.reserve(capacity);
 
     // Add prefix parts
-    for part in &analysis.prefix_parts {
-        match part {
-            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
-            FormatPart::Locale(locale_code) => {
-                if let Some(ref currency) = locale_code.currency {
-                    result.push_str(currency);
-                }
-            }
-            FormatPart::Percent => result.push('%'),
-            _ => {}
-        }
-    }
+    write_affix_parts(&analysis.prefix_parts, opts, out);
 
     // Add the formatted number
-    result.push_str(formatted_number);
+    out.push_digits(formatted_number);
 
     // Add suffix parts
-    for part in &analysis.suffix_parts {
+    write_affix_parts(&analysis.suffix_parts, opts, out);
+}
+
+/// Write the literal parts that surround the number.
+fn write_affix_parts(parts: &[FormatPart], opts: &FormatOptions, out: &mut Output) {
+    for part in parts {
         match part {
-            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) => result.push_str(s),
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => out.push_literal(s),
             FormatPart::Locale(locale_code) => {
                 if let Some(ref currency) = locale_code.currency {
-                    result.push_str(currency);
+                    out.push_literal(currency);
                 }
             }
-            FormatPart::Percent => result.push('%'),
+            FormatPart::Percent => out.push_literal("%"),
+            FormatPart::Fill(c) => out.push_fill(*c),
+            FormatPart::Skip(c) => out.push_skip(*c, opts.layout),
             _ => {}
         }
     }
-
-    result
 }
 
 /// Format a number in scientific notation according to a format section.
@@ -779,168 +658,191 @@ fn format_scientific(
     section: &Section,
     upper: bool,
     show_plus: bool,
-    _opts: &FormatOptions,
+    opts: &FormatOptions,
 ) -> Result<String, FormatError> {
-    // Count digits before and after decimal in mantissa, and exponent digits
-    let mut mantissa_integer_places = 0;
-    let mut mantissa_decimal_places = 0;
-    let mut exponent_digits = 0;
+    let layout = scientific_layout(section);
+    let abs_value = value.abs();
+
+    // Zero shows as a zero mantissa with a zero exponent
+    let (integer_digits, decimal_digits, exponent) = if abs_value == 0.0 {
+        (vec![b'0'], vec![b'0'; layout.decimal_placeholders.len()], 0)
+    } else {
+        scientific_digits(abs_value, &layout, opts.rounding)
+    };
+
+    let mut formatted = String::new();
+    if value < 0.0 || (abs_value == 0.0 && opts.show_negative_zero && value.is_sign_negative()) {
+        formatted.push('-');
+    }
+    formatted.extend(integer_digits.iter().map(|&d| d as char));
+    if !layout.decimal_placeholders.is_empty() {
+        formatted.push(opts.locale.decimal_separator);
+        formatted.push_str(&format_decimal_digits(
+            &decimal_digits,
+            &layout.decimal_placeholders,
+            &[],
+        ));
+    }
+
+    // E+ shows the sign of every exponent, E- only a minus
+    formatted.push(if upper { 'E' } else { 'e' });
+    if exponent < 0 {
+        formatted.push('-');
+    } else if show_plus {
+        formatted.push('+');
+    }
+
+    // Exponent placeholders pad like integer ones: 0 with zeros, ? with
+    // spaces, # not at all
+    let exponent_str = exponent.unsigned_abs().to_string();
+    let padding = layout
+        .exponent_placeholders
+        .len()
+        .saturating_sub(exponent_str.len());
+    formatted.extend(
+        layout.exponent_placeholders[..padding]
+            .iter()
+            .filter_map(|p| p.empty_char()),
+    );
+    formatted.push_str(&exponent_str);
+
+    Ok(formatted)
+}
+
+/// The digit placeholders of a scientific section.
+struct ScientificLayout {
+    /// Placeholders before the decimal point of the mantissa; with more
+    /// than one, the exponent is a multiple of their count
+    integer_places: usize,
+    decimal_placeholders: Vec<DigitPlaceholder>,
+    exponent_placeholders: Vec<DigitPlaceholder>,
+}
+
+/// Collect the mantissa and exponent placeholders of a scientific section.
+fn scientific_layout(section: &Section) -> ScientificLayout {
+    let mut layout = ScientificLayout {
+        integer_places: 0,
+        decimal_placeholders: Vec::new(),
+        exponent_placeholders: Vec::new(),
+    };
     let mut seen_decimal = false;
     let mut after_exponent = false;
 
     for part in &section.parts {
         match part {
-            FormatPart::Digit(_) if !seen_decimal && !after_exponent => {
-                mantissa_integer_places += 1;
-            }
-            FormatPart::DecimalPoint if !after_exponent => {
-                seen_decimal = true;
-            }
-            FormatPart::Digit(_) if seen_decimal && !after_exponent => {
-                mantissa_decimal_places += 1;
-            }
-            FormatPart::Scientific { .. } => {
+            _ if after_exponent => {}
+            FormatPart::Digit(p) if seen_decimal => layout.decimal_placeholders.push(*p),
+            FormatPart::Digit(_) => layout.integer_places += 1,
+            FormatPart::DecimalPoint => seen_decimal = true,
+            FormatPart::Scientific {
+                exponent_digits, ..
+            } => {
+                layout.exponent_placeholders = exponent_digits.clone();
                 after_exponent = true;
             }
-            FormatPart::Digit(_) if after_exponent => {
-                exponent_digits += 1;
-            }
             _ => {}
         }
     }
+    layout
+}
 
-    // Convert value to scientific notation
-    let abs_value = value.abs();
-
-    // Handle zero specially
-    if abs_value == 0.0 {
-        let zeros = "0".repeat(mantissa_decimal_places);
-        let decimal_part = if mantissa_decimal_places > 0 {
-            format!(".{}", zeros)
-        } else {
-            String::new()
-        };
-        let exp_char = if upper { 'E' } else { 'e' };
-        let sign = if show_plus { "+" } else { "" };
-        return Ok(format!("0{}{}{sign}00", decimal_part, exp_char));
-    }
+/// Rounded mantissa digits and exponent of a nonzero `abs_value`.
+///
+/// With several integer placeholders (`##0.0E+0`) the exponent is a
+/// multiple of their count, so the mantissa runs from 1 up to 10^count
+/// (1 to 999 for engineering notation); with one or none it runs from 1
+/// to 10. A mantissa that rounds up out of its range moves to the next
+/// exponent, so 999.96 shows as `1.0E+3`, not `1000.0E+0`.
+fn scientific_digits(
+    abs_value: f64,
+    layout: &ScientificLayout,
+    rounding: RoundingMode,
+) -> (Vec<u8>, Vec<u8>, i32) {
+    let group_size = layout.integer_places.max(1) as i32;
+    let decimal_places = layout.decimal_placeholders.len();
+    let mut exponent = scientific_exponent(abs_value, layout.integer_places);
+
+    let (mut integer_digits, mut decimal_digits) =
+        round_to_digits(abs_value / 10_f64.powi(exponent), decimal_places, rounding);
+    if integer_digits.len() > group_size as usize {
+        exponent += group_size;
+        (integer_digits, decimal_digits) =
+            round_to_digits(abs_value / 10_f64.powi(exponent), decimal_places, rounding);
+    }
+    (integer_digits, decimal_digits, exponent)
+}
 
-    // Calculate exponent based on integer placeholder count
+/// The exponent a nonzero `abs_value` is shown with, before rounding.
+fn scientific_exponent(abs_value: f64, mantissa_integer_places: usize) -> i32 {
     // Standard format (0) or minimal format (no placeholder): mantissa 1-10, exponent = log10(value)
     // Format with multiple placeholders (##0): adjust exponent to use more mantissa digits
     let base_exponent = abs_value.log10().floor() as i32;
 
-    let exponent = if mantissa_integer_places > 1 {
-        // For ##0 (3 places), we want mantissa to be in range [1, 1000)
-        // Adjust exponent to be a multiple of group_size to group digits
+    if mantissa_integer_places > 1 {
         // For ##0: exponent should be multiple of 3, giving mantissa like 123.5E+6, not 1.235E+8
-        let group_size = mantissa_integer_places.max(1);
-        // Use floor division to handle negative exponents correctly
-        // For base_exponent = -1, group_size = 3: floor(-1/3) * 3 = -1 * 3 = -3
-        ((base_exponent as f64) / (group_size as f64)).floor() as i32 * group_size
+        // Floor division handles negative exponents: base -1 with ##0 gives -3
+        let group_size = mantissa_integer_places as i32;
+        base_exponent.div_euclid(group_size) * group_size
     } else {
         base_exponent
-    };
-
-    let mantissa = abs_value / 10_f64.powi(exponent);
-
-    // Format mantissa with appropriate decimal places
-    let mantissa_str = if mantissa_decimal_places > 0 {
-        format!("{:.prec$}", mantissa, prec = mantissa_decimal_places)
-    } else {
-        format!("{:.0}", mantissa)
-    };
-
-    // Format exponent
-    let exp_char = if upper { 'E' } else { 'e' };
-    let exp_sign = if exponent >= 0 {
-        if show_plus { "+" } else { "" }
-    } else {
-        "-"
-    };
-    let exp_abs = exponent.abs();
-
-    // Format exponent with appropriate zero padding
-    let exp_str = if exponent_digits >= 2 {
-        // 0.00E+00 format uses 2-digit exponents
-        format!("{:02}", exp_abs)
-    } else {
-        // ##0.0E+0 format uses minimal digits
-        format!("{}", exp_abs)
-    };
-    let formatted = format!("{}{}{}{}", mantissa_str, exp_char, exp_sign, exp_str);
-
-    // Apply sign for negative values
-    if value < 0.0 {
-        Ok(format!("-{}", formatted))
-    } else {
-        Ok(formatted)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::ast::Section;
-
-    fn make_section(parts: Vec<FormatPart>) -> Section {
-        Section {
-            condition: None,
-            color: None,
-            parts,
-            metadata: crate::ast::SectionMetadata::default(),
-        }
-    }
-
-    #[test]
-    fn test_analyze_simple_integer() {
-        let section = make_section(vec![FormatPart::Digit(DigitPlaceholder::Zero)]);
-        let analysis = analyze_format(&section);
-
-        assert_eq!(analysis.integer_placeholders.len(), 1);
-        assert_eq!(analysis.decimal_placeholders.len(), 0);
-        assert!(!analysis.has_thousands_separator);
-        assert_eq!(analysis.percent_count, 0);
-    }
-
-    #[test]
-    fn test_analyze_decimal_format() {
-        let section = make_section(vec![
-            FormatPart::Digit(DigitPlaceholder::Zero),
-            FormatPart::DecimalPoint,
-            FormatPart::Digit(DigitPlaceholder::Zero),
-            FormatPart::Digit(DigitPlaceholder::Zero),
-        ]);
-        let analysis = analyze_format(&section);
-
-        assert_eq!(analysis.integer_placeholders.len(), 1);
-        assert_eq!(analysis.decimal_placeholders.len(), 2);
-    }
-
-    #[test]
-    fn test_analyze_thousands() {
-        let section = make_section(vec![
-            FormatPart::Digit(DigitPlaceholder::Hash),
-            FormatPart::ThousandsSeparator,
-            FormatPart::Digit(DigitPlaceholder::Hash),
-            FormatPart::Digit(DigitPlaceholder::Hash),
-            FormatPart::Digit(DigitPlaceholder::Zero),
-        ]);
-        let analysis = analyze_format(&section);
+/// The value a number section displays for `value`, after rounding,
+/// percent and thousands scaling, or `None` for sections that show no
+/// digits or show a fraction.
+pub(crate) fn displayed_value(value: f64, section: &Section, opts: &FormatOptions) -> Option<f64> {
+    use crate::ast::FormatType;
 
-        assert!(analysis.has_thousands_separator);
-        assert_eq!(analysis.integer_placeholders.len(), 4);
-    }
+    let abs_value = value.abs();
+    let is_scientific = section
+        .parts
+        .iter()
+        .any(|p| matches!(p, FormatPart::Scientific { .. }));
+
+    // Rounded digits and the power of ten that turns them back into the value
+    let (integer_digits, decimal_digits, shift) = if is_scientific {
+        if abs_value == 0.0 {
+            return Some(value);
+        }
+        let layout = scientific_layout(section);
+        let (integer_digits, decimal_digits, exponent) =
+            scientific_digits(abs_value, &layout, opts.rounding);
+        (integer_digits, decimal_digits, exponent as isize)
+    } else {
+        let has_digits = section
+            .parts
+            .iter()
+            .any(|p| matches!(p, FormatPart::Digit(_) | FormatPart::DecimalPoint));
+        let format_type = section.metadata.format_type;
+        if !has_digits || matches!(format_type, FormatType::Fraction | FormatType::Text) {
+            return None;
+        }
 
-    #[test]
-    fn test_analyze_percent() {
-        let section = make_section(vec![
-            FormatPart::Digit(DigitPlaceholder::Zero),
-            FormatPart::Percent,
-        ]);
-        let analysis = analyze_format(&section);
+        let analysis = &*section_analysis(section);
+        let mut adjusted_value = abs_value;
+        for _ in 0..analysis.percent_count {
+            adjusted_value *= 100.0;
+        }
+        for _ in 0..analysis.thousands_scale {
+            adjusted_value /= 1000.0;
+        }
+        let (integer_digits, decimal_digits) =
+            round_to_digits(adjusted_value, shown_decimal_places(analysis), opts.rounding);
+        let shift = 3 * analysis.thousands_scale as isize - 2 * analysis.percent_count as isize;
+        (integer_digits, decimal_digits, shift)
+    };
 
-        assert_eq!(analysis.percent_count, 1);
-        assert_eq!(analysis.suffix_parts.len(), 1);
+    let mut text: String = integer_digits.iter().map(|&d| d as char).collect();
+    if !decimal_digits.is_empty() {
+        text.push('.');
+        text.extend(decimal_digits.iter().map(|&d| d as char));
     }
+    text.push_str(&format!("e{}", shift));
+    let magnitude: f64 = text.parse().ok()?;
+    Some(if value < 0.0 && magnitude != 0.0 {
+        -magnitude
+    } else {
+        magnitude
+    })
 }
@@ -0,0 +1,354 @@
+//! Numeral transliteration for `[DBNumN]`, `[NatNumN]` and LCID digit
+//! substitution.
+//!
+//! The number is formatted with ASCII digits first and then rewritten here.
+//! Under `[DBNum1]`-`[DBNum3]`, whole numbers are spelled with place-value
+//! characters (1234 → 一千二百三十四); decimals, grouped digits and runs with
+//! leading zeros are converted digit by digit, as Excel does. The numeral set
+//! follows the section's LCID: Japanese, Korean, Traditional Chinese, or
+//! Simplified Chinese otherwise.
+//!
+//! `[NatNum1]` and the numeral-system byte of an LCID (`[$-2010401]`) swap
+//! each ASCII digit for the native digit of the same value.
+
+use crate::ast::SectionMetadata;
+use crate::locale::digits;
+
+/// How the ASCII digits of a section's output are rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumeralStyle {
+    /// East Asian numerals with place values (`[DBNumN]`)
+    PlaceValue(&'static Numerals),
+    /// East Asian numerals, digit by digit (`[NatNumN]` in CJK locales)
+    DigitByDigit(&'static Numerals),
+    /// A native digit set, given by its zero
+    Native(char),
+}
+
+/// The numeral style a section asks for, if any.
+///
+/// `[DBNumN]` wins over `[NatNumN]`, which wins over the LCID's
+/// numeral-system byte. `[NatNum1]`-`[NatNum3]` map to the East Asian digit
+/// sets in Chinese, Japanese and Korean sections; elsewhere only `[NatNum1]`
+/// has an effect, selecting the language's native digits.
+pub(crate) fn section_numerals(metadata: &SectionMetadata) -> Option<NumeralStyle> {
+    if let Some(style) = metadata.dbnum {
+        return Some(NumeralStyle::PlaceValue(numerals_for(style, metadata.lcid)));
+    }
+    if let Some(style) = metadata.natnum {
+        let lcid = metadata.lcid.unwrap_or(0);
+        let is_cjk = matches!(lcid & 0x3FF, 0x04 | 0x11 | 0x12);
+        return match style {
+            1..=3 if is_cjk => Some(NumeralStyle::DigitByDigit(numerals_for(
+                style,
+                metadata.lcid,
+            ))),
+            1 => digits::native_zero(lcid).map(NumeralStyle::Native),
+            _ => None,
+        };
+    }
+    metadata
+        .lcid
+        .and_then(digits::numeral_system_zero)
+        .map(NumeralStyle::Native)
+}
+
+/// The numeral style a section asks for that we cannot write, which the
+/// parser rejects but a hand-built section may still carry.
+pub(crate) fn unsupported_style(metadata: &SectionMetadata) -> Option<&'static str> {
+    match (metadata.dbnum, metadata.natnum) {
+        (Some(style), _) if !(1..=3).contains(&style) => Some("[DBNum4]-[DBNum9]"),
+        (None, Some(style)) if style > 11 => Some("[NatNum12]"),
+        _ => None,
+    }
+}
+
+impl NumeralStyle {
+    /// Rewrite the ASCII digits in `s`, which was formatted with the given
+    /// decimal and thousands separators.
+    pub(crate) fn convert(&self, s: &str, decimal: char, thousands: char) -> String {
+        match self {
+            NumeralStyle::PlaceValue(numerals) => numerals.convert(s, decimal, thousands),
+            _ => self.convert_sequence(s),
+        }
+    }
+
+    /// Rewrite ASCII digits one by one, without place values.
+    pub(crate) fn convert_sequence(&self, s: &str) -> String {
+        match self {
+            NumeralStyle::PlaceValue(numerals) | NumeralStyle::DigitByDigit(numerals) => {
+                numerals.convert_sequence(s)
+            }
+            NumeralStyle::Native(zero) => s
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(d) => char::from_u32(*zero as u32 + d).unwrap_or(c),
+                    None => c,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// When the digit one is left out before 十, 百 and 千.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OmitOne {
+    /// Always written (financial styles: 壱拾)
+    Never,
+    /// Left out before every unit (Japanese and Korean: 千百十一)
+    Always,
+    /// Left out only for a leading ten (Chinese: 十二, but 一百一十)
+    LeadingTen,
+}
+
+/// A numeral system used by a `[DBNumN]` style.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Numerals {
+    digits: [char; 10],
+    /// 十, 百, 千 (empty for digit-only styles)
+    units: &'static [char],
+    /// 万, 億, 兆
+    myriads: [char; 3],
+    omit_one: OmitOne,
+    /// Whether a gap of zeros is spoken (一千〇一) or silent (千一)
+    mark_zero_gaps: bool,
+}
+
+const JA_DBNUM1: Numerals = Numerals {
+    digits: ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'],
+    units: &['十', '百', '千'],
+    myriads: ['万', '億', '兆'],
+    omit_one: OmitOne::Always,
+    mark_zero_gaps: false,
+};
+
+const JA_DBNUM2: Numerals = Numerals {
+    digits: ['〇', '壱', '弐', '参', '四', '伍', '六', '七', '八', '九'],
+    units: &['拾', '百', '阡'],
+    myriads: ['萬', '億', '兆'],
+    omit_one: OmitOne::Never,
+    mark_zero_gaps: false,
+};
+
+const ZH_CN_DBNUM1: Numerals = Numerals {
+    digits: ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'],
+    units: &['十', '百', '千'],
+    myriads: ['万', '亿', '兆'],
+    omit_one: OmitOne::LeadingTen,
+    mark_zero_gaps: true,
+};
+
+const ZH_CN_DBNUM2: Numerals = Numerals {
+    digits: ['零', '壹', '贰', '叁', '肆', '伍', '陆', '柒', '捌', '玖'],
+    units: &['拾', '佰', '仟'],
+    myriads: ['万', '亿', '兆'],
+    omit_one: OmitOne::Never,
+    mark_zero_gaps: true,
+};
+
+const ZH_TW_DBNUM1: Numerals = Numerals {
+    digits: ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'],
+    units: &['十', '百', '千'],
+    myriads: ['萬', '億', '兆'],
+    omit_one: OmitOne::LeadingTen,
+    mark_zero_gaps: true,
+};
+
+const ZH_TW_DBNUM2: Numerals = Numerals {
+    digits: ['零', '壹', '貳', '參', '肆', '伍', '陸', '柒', '捌', '玖'],
+    units: &['拾', '佰', '仟'],
+    myriads: ['萬', '億', '兆'],
+    omit_one: OmitOne::Never,
+    mark_zero_gaps: true,
+};
+
+const KO_DBNUM1: Numerals = Numerals {
+    digits: ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'],
+    units: &['十', '百', '千'],
+    myriads: ['萬', '億', '兆'],
+    omit_one: OmitOne::Always,
+    mark_zero_gaps: false,
+};
+
+const KO_DBNUM2: Numerals = Numerals {
+    digits: ['零', '壹', '貳', '參', '四', '伍', '六', '七', '八', '九'],
+    units: &['拾', '百', '千'],
+    myriads: ['萬', '億', '兆'],
+    omit_one: OmitOne::Never,
+    mark_zero_gaps: false,
+};
+
+/// `[DBNum3]`: fullwidth digits, converted one by one in every language.
+const FULLWIDTH: Numerals = Numerals {
+    digits: ['０', '１', '２', '３', '４', '５', '６', '７', '８', '９'],
+    units: &[],
+    myriads: ['万', '億', '兆'],
+    omit_one: OmitOne::Never,
+    mark_zero_gaps: false,
+};
+
+/// Look up the numerals for a `[DBNumN]` style and the section's LCID.
+///
+/// Sections without a Chinese, Japanese or Korean LCID use Simplified
+/// Chinese numerals.
+pub(crate) fn numerals_for(style: u8, lcid: Option<u32>) -> &'static Numerals {
+    let language_id = lcid.map_or(0x0804, |lcid| lcid & 0xFFFF);
+    match (style, language_id & 0x3FF, language_id) {
+        (3, _, _) => &FULLWIDTH,
+        (1, 0x11, _) => &JA_DBNUM1,
+        (_, 0x11, _) => &JA_DBNUM2,
+        (1, 0x12, _) => &KO_DBNUM1,
+        (_, 0x12, _) => &KO_DBNUM2,
+        (1, 0x04, 0x0404 | 0x0C04 | 0x1404) => &ZH_TW_DBNUM1,
+        (_, 0x04, 0x0404 | 0x0C04 | 0x1404) => &ZH_TW_DBNUM2,
+        (1, _, _) => &ZH_CN_DBNUM1,
+        _ => &ZH_CN_DBNUM2,
+    }
+}
+
+impl Numerals {
+    fn convert(&self, s: &str, decimal: char, thousands: char) -> String {
+        let mut result = String::with_capacity(s.len() * 3);
+        let mut prev = None;
+        let mut chars = s.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if !c.is_ascii_digit() {
+                result.push(c);
+                prev = Some(c);
+                continue;
+            }
+            let mut end = start + 1;
+            while let Some(&(i, d)) = chars.peek() {
+                if !d.is_ascii_digit() {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            let run = &s[start..end];
+            let next = chars.peek().map(|&(_, c)| c);
+            let whole = prev != Some(decimal) && prev != Some(thousands) && next != Some(thousands);
+            if whole {
+                self.push_place_value(run, &mut result);
+            } else {
+                self.push_sequence(run, &mut result);
+            }
+            prev = run.chars().last();
+        }
+        result
+    }
+
+    fn convert_sequence(&self, s: &str) -> String {
+        let mut result = String::with_capacity(s.len() * 3);
+        self.push_sequence(s, &mut result);
+        result
+    }
+
+    fn push_sequence(&self, s: &str, result: &mut String) {
+        result.extend(s.chars().map(|c| match c.to_digit(10) {
+            Some(d) => self.digits[d as usize],
+            None => c,
+        }));
+    }
+
+    /// Spell a run of digits with place values, falling back to digit by
+    /// digit for leading zeros and numbers too large for 兆.
+    fn push_place_value(&self, run: &str, result: &mut String) {
+        if self.units.is_empty() || run.len() > 16 || (run.len() > 1 && run.starts_with('0')) {
+            self.push_sequence(run, result);
+            return;
+        }
+        let Ok(mut n) = run.parse::<u64>() else {
+            self.push_sequence(run, result);
+            return;
+        };
+        if n == 0 {
+            result.push(self.digits[0]);
+            return;
+        }
+
+        // Four-digit groups, least significant first
+        let mut groups = Vec::with_capacity(4);
+        while n > 0 {
+            groups.push(n % 10_000);
+            n /= 10_000;
+        }
+
+        let mut written = false;
+        let mut pending_zero = false;
+        for (index, &group) in groups.iter().enumerate().rev() {
+            if group == 0 {
+                pending_zero |= written;
+                continue;
+            }
+            for place in (0..4).rev() {
+                let digit = (group / 10u64.pow(place)) % 10;
+                if digit == 0 {
+                    pending_zero |= written;
+                    continue;
+                }
+                if pending_zero && self.mark_zero_gaps {
+                    result.push(self.digits[0]);
+                }
+                pending_zero = false;
+                let omit = digit == 1
+                    && place > 0
+                    && match self.omit_one {
+                        OmitOne::Never => false,
+                        OmitOne::Always => true,
+                        OmitOne::LeadingTen => place == 1 && !written,
+                    };
+                if !omit {
+                    result.push(self.digits[digit as usize]);
+                }
+                if place > 0 {
+                    result.push(self.units[place as usize - 1]);
+                }
+                written = true;
+            }
+            if index > 0 {
+                result.push(self.myriads[index - 1]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spell(style: u8, lcid: u32, s: &str) -> String {
+        numerals_for(style, Some(lcid)).convert(s, '.', ',')
+    }
+
+    #[test]
+    fn test_place_values() {
+        assert_eq!(spell(1, 0x804, "1234"), "一千二百三十四");
+        assert_eq!(spell(1, 0x804, "12"), "十二");
+        assert_eq!(spell(1, 0x804, "110"), "一百一十");
+        assert_eq!(spell(1, 0x804, "1001"), "一千〇一");
+        assert_eq!(spell(1, 0x804, "100000"), "十万");
+        assert_eq!(spell(1, 0x411, "1111"), "千百十一");
+        assert_eq!(spell(1, 0x411, "10001"), "一万一");
+        assert_eq!(spell(2, 0x804, "1234"), "壹仟贰佰叁拾肆");
+        assert_eq!(spell(2, 0x411, "12"), "壱拾弐");
+        assert_eq!(spell(1, 0x404, "20000"), "二萬");
+    }
+
+    #[test]
+    fn test_digit_sequences() {
+        assert_eq!(spell(1, 0x804, "0"), "〇");
+        assert_eq!(spell(1, 0x804, "12.05"), "十二.〇五");
+        assert_eq!(spell(1, 0x411, "1,234"), "一,二三四");
+        assert_eq!(spell(1, 0x804, "007"), "〇〇七");
+        assert_eq!(spell(3, 0x411, "1234"), "１２３４");
+        assert_eq!(numerals_for(1, None).convert_sequence("2024"), "二〇二四");
+    }
+
+    #[test]
+    fn test_lcid_calendar_bits_ignored() {
+        assert_eq!(numerals_for(1, Some(0x0003_0411)), &JA_DBNUM1);
+        assert_eq!(numerals_for(1, Some(0x0C04)), &ZH_TW_DBNUM1);
+        assert_eq!(numerals_for(2, Some(0x409)), &ZH_CN_DBNUM2);
+    }
+}
@@ -0,0 +1,355 @@
+//! Output buffer shared by the formatting paths.
+//!
+//! Formatters write through [`Output`] instead of pushing onto a `String`
+//! directly, so the same code path can also record which pieces of the
+//! result came from literals, from the value, or from fill characters.
+//! Recording is off for plain `format()` calls and costs nothing there.
+
+use super::numerals::NumeralStyle;
+use super::rich::FormattedRun;
+use crate::error::FormatError;
+use crate::options::{FillFallback, FormatOptions, LayoutMode, OverflowBehavior};
+
+/// Formatted text plus, optionally, the runs it was built from.
+#[derive(Debug, Default)]
+pub(crate) struct Output {
+    text: String,
+    runs: Option<Vec<FormattedRun>>,
+    /// Byte offset and character of the first `*` fill
+    fill: Option<(usize, char)>,
+    /// Numeral style with the decimal and thousands separators in use
+    numerals: Option<(NumeralStyle, char, char)>,
+}
+
+impl Output {
+    /// An output that only collects text.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// An output that also records structural runs.
+    pub(crate) fn with_runs() -> Self {
+        Self {
+            text: String::new(),
+            runs: Some(Vec::new()),
+            fill: None,
+            numerals: None,
+        }
+    }
+
+    /// Rewrite value digits in the given numeral style from here on.
+    pub(crate) fn set_numerals(&mut self, numerals: NumeralStyle, decimal: char, thousands: char) {
+        self.numerals = Some((numerals, decimal, thousands));
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.text.reserve(additional);
+    }
+
+    /// Append text that comes from the format code itself.
+    pub(crate) fn push_literal(&mut self, s: &str) {
+        self.text.push_str(s);
+        self.record(s, RunKind::Literal);
+    }
+
+    pub(crate) fn push_literal_char(&mut self, c: char) {
+        self.push_literal(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Append text rendered from the value (digits, date fields, signs).
+    pub(crate) fn push_digits(&mut self, s: &str) {
+        match self.numerals {
+            Some((numerals, decimal, thousands)) => {
+                let converted = numerals.convert(s, decimal, thousands);
+                self.push_converted(&converted);
+            }
+            None => self.push_converted(s),
+        }
+    }
+
+    /// Append value digits that are read one by one, such as a year.
+    pub(crate) fn push_digit_sequence(&mut self, s: &str) {
+        match self.numerals {
+            Some((numerals, ..)) => {
+                let converted = numerals.convert_sequence(s);
+                self.push_converted(&converted);
+            }
+            None => self.push_converted(s),
+        }
+    }
+
+    fn push_converted(&mut self, s: &str) {
+        self.text.push_str(s);
+        self.record(s, RunKind::Digits);
+    }
+
+    /// Append the blank placeholder for a `_x` skip.
+    pub(crate) fn push_skip(&mut self, c: char, layout: LayoutMode) {
+        let width = match layout {
+            LayoutMode::Plain => 1,
+            LayoutMode::Monospace => char_width(c),
+        };
+        let blank = " ".repeat(width);
+        self.text.push_str(&blank);
+        if let Some(runs) = &mut self.runs {
+            runs.push(FormattedRun::Skip {
+                skip: c,
+                text: blank,
+            });
+        }
+    }
+
+    /// Mark the position of a `*` fill. The fill is expanded by
+    /// [`Output::finish`]; only the first fill in a section takes effect.
+    pub(crate) fn push_fill(&mut self, c: char) {
+        if self.fill.is_none() {
+            self.fill = Some((self.text.len(), c));
+        }
+        if let Some(runs) = &mut self.runs {
+            runs.push(FormattedRun::Fill {
+                fill: c,
+                text: String::new(),
+            });
+        }
+    }
+
+    /// Put a minus sign in front of everything written so far.
+    pub(crate) fn prepend_sign(&mut self) {
+        self.text.insert(0, '-');
+        if let Some((pos, _)) = &mut self.fill {
+            *pos += 1;
+        }
+        if let Some(runs) = &mut self.runs {
+            match runs.first_mut() {
+                Some(FormattedRun::Digits(s)) => s.insert(0, '-'),
+                _ => runs.insert(0, FormattedRun::Digits("-".to_string())),
+            }
+        }
+    }
+
+    /// Finish a formatted value: expand the fill, then apply
+    /// `opts.overflow` if the text is wider than `opts.cell_width`.
+    pub(crate) fn finish(&mut self, opts: &FormatOptions) -> Result<(), FormatError> {
+        self.expand_fill(opts);
+        let Some(width) = opts.cell_width else {
+            return Ok(());
+        };
+        let needed = self.width(opts.layout);
+        if needed <= width {
+            return Ok(());
+        }
+        match opts.overflow {
+            OverflowBehavior::Empty => Ok(()),
+            OverflowBehavior::Hashes(_) => {
+                let hashes = "#".repeat(width);
+                if let Some(runs) = &mut self.runs {
+                    *runs = vec![FormattedRun::Literal(hashes.clone())];
+                }
+                self.text = hashes;
+                Ok(())
+            }
+            OverflowBehavior::Error => Err(FormatError::CellOverflow { needed, width }),
+        }
+    }
+
+    /// Expand the fill, if any, to pad the text to `opts.cell_width`.
+    ///
+    /// Text values spill into neighbouring cells in Excel, so they only
+    /// get this half of [`Output::finish`].
+    pub(crate) fn expand_fill(&mut self, opts: &FormatOptions) {
+        let Some((pos, c)) = self.fill else {
+            return;
+        };
+        let count = match opts.cell_width {
+            Some(width) => {
+                width.saturating_sub(self.width(opts.layout)) / fill_width(c, opts.layout)
+            }
+            None => match opts.fill_fallback {
+                FillFallback::Omit => 0,
+                FillFallback::Single => 1,
+            },
+        };
+        if count == 0 {
+            return;
+        }
+        let padding: String = std::iter::repeat_n(c, count).collect();
+        self.text.insert_str(pos, &padding);
+        if let Some(runs) = &mut self.runs {
+            if let Some(FormattedRun::Fill { text, .. }) = runs
+                .iter_mut()
+                .find(|run| matches!(run, FormattedRun::Fill { .. }))
+            {
+                *text = padding;
+            }
+        }
+    }
+
+    /// Width of the text so far, as measured by `layout`.
+    fn width(&self, layout: LayoutMode) -> usize {
+        text_width(&self.text, layout)
+    }
+
+    /// Empty the buffer for reuse, keeping its capacity.
+    pub(crate) fn clear(&mut self) {
+        self.text.clear();
+        self.runs = None;
+        self.fill = None;
+        self.numerals = None;
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn into_string(self) -> String {
+        self.text
+    }
+
+    pub(crate) fn into_parts(self) -> (String, Vec<FormattedRun>) {
+        (self.text, self.runs.unwrap_or_default())
+    }
+
+    /// Record a run, merging it into the previous one when the kinds match.
+    fn record(&mut self, s: &str, kind: RunKind) {
+        let Some(runs) = &mut self.runs else {
+            return;
+        };
+        if s.is_empty() {
+            return;
+        }
+        if let (Some(FormattedRun::Literal(prev)), RunKind::Literal)
+        | (Some(FormattedRun::Digits(prev)), RunKind::Digits) = (runs.last_mut(), kind)
+        {
+            prev.push_str(s);
+            return;
+        }
+        runs.push(match kind {
+            RunKind::Literal => FormattedRun::Literal(s.to_string()),
+            RunKind::Digits => FormattedRun::Digits(s.to_string()),
+        });
+    }
+}
+
+/// Width of `s`: characters for [`LayoutMode::Plain`], terminal columns for
+/// [`LayoutMode::Monospace`].
+pub(crate) fn text_width(s: &str, layout: LayoutMode) -> usize {
+    match layout {
+        LayoutMode::Plain => s.chars().count(),
+        LayoutMode::Monospace => s.chars().map(char_width).sum(),
+    }
+}
+
+/// Terminal column width of a character: 2 for wide East Asian characters,
+/// 0 for combining marks and zero-width spaces, 1 otherwise.
+pub(crate) fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Width of one repetition of a fill character (never zero).
+fn fill_width(c: char, layout: LayoutMode) -> usize {
+    match layout {
+        LayoutMode::Plain => 1,
+        LayoutMode::Monospace => char_width(c).max(1),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RunKind {
+    Literal,
+    Digits,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_merge_and_sign() {
+        let mut out = Output::with_runs();
+        out.push_literal("$");
+        out.push_digits("1");
+        out.push_digits(",234");
+        out.push_fill(' ');
+        out.push_literal(" ");
+        out.prepend_sign();
+        let (text, runs) = out.into_parts();
+        assert_eq!(text, "-$1,234 ");
+        assert_eq!(
+            runs,
+            vec![
+                FormattedRun::Digits("-".to_string()),
+                FormattedRun::Literal("$".to_string()),
+                FormattedRun::Digits("1,234".to_string()),
+                FormattedRun::Fill {
+                    fill: ' ',
+                    text: String::new(),
+                },
+                FormattedRun::Literal(" ".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_output_has_no_runs() {
+        let mut out = Output::new();
+        out.push_literal("x");
+        out.push_fill('-');
+        let (text, runs) = out.into_parts();
+        assert_eq!(text, "x");
+        assert!(runs.is_empty());
+    }
+
+    #[test]
+    fn test_skip_width() {
+        let mut out = Output::with_runs();
+        out.push_digits("1");
+        out.push_skip(')', LayoutMode::Monospace);
+        out.push_skip('円', LayoutMode::Monospace);
+        out.push_skip('円', LayoutMode::Plain);
+        let (text, runs) = out.into_parts();
+        assert_eq!(text, "1    ");
+        assert_eq!(
+            runs[2],
+            FormattedRun::Skip {
+                skip: '円',
+                text: "  ".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_finish_expands_first_fill() {
+        let opts = FormatOptions::builder().cell_width(8).build();
+        let mut out = Output::with_runs();
+        out.push_literal("$");
+        out.push_fill('*');
+        out.push_digits("12");
+        out.push_fill('-');
+        out.prepend_sign();
+        out.finish(&opts).unwrap();
+        let (text, runs) = out.into_parts();
+        assert_eq!(text, "-$****12");
+        assert_eq!(
+            runs[2],
+            FormattedRun::Fill {
+                fill: '*',
+                text: "****".to_string(),
+            }
+        );
+    }
+}
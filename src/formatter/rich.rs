@@ -0,0 +1,124 @@
+//! Rich formatted output: text plus section color and structural runs.
+
+use crate::ast::{Color, NumberFormat};
+use crate::options::FormatOptions;
+
+use super::output::Output;
+
+/// A formatted value together with the color of the section that produced it.
+///
+/// `runs` splits `text` into the pieces it was built from, so renderers can
+/// treat literals, value digits and fill characters differently. Concatenating
+/// the text of all runs yields `text`; a [`FormattedRun::Fill`] run holds the
+/// characters a `*` fill expanded to, which is empty unless a cell width is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichText {
+    /// The formatted string, identical to [`NumberFormat::format`]'s output
+    /// (or [`NumberFormat::format_text`]'s, for text)
+    pub text: String,
+    /// Color of the selected section (e.g. `[Red]`, `[Color10]`), if any
+    pub color: Option<Color>,
+    /// Structural segments of `text`
+    pub runs: Vec<FormattedRun>,
+}
+
+/// A structural segment of formatted output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormattedRun {
+    /// Text taken from the format code (quoted strings, currency, `%`)
+    Literal(String),
+    /// Text rendered from the value: digits, separators, signs, date fields
+    Digits(String),
+    /// A `*` fill: the character to repeat and what it expanded to
+    Fill { fill: char, text: String },
+    /// A `_x` skip: the character whose width is reserved and the blank
+    /// placeholder emitted for it
+    Skip { skip: char, text: String },
+}
+
+impl FormattedRun {
+    /// The characters this run contributes to [`RichText::text`].
+    pub fn as_str(&self) -> &str {
+        match self {
+            FormattedRun::Literal(s) | FormattedRun::Digits(s) => s,
+            FormattedRun::Fill { text, .. } | FormattedRun::Skip { text, .. } => text,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Format a numeric value and return the section color and output runs.
+    ///
+    /// The text is the same as [`NumberFormat::format`] returns; the color is
+    /// the one attached to the section chosen for `value`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, FormattedRun, NumberFormat};
+    /// use ssfmt::ast::{Color, NamedColor};
+    ///
+    /// let fmt = NumberFormat::parse("#,##0;[Red]\"(\"#,##0\")\"").unwrap();
+    /// let rich = fmt.format_rich(-1234.0, &FormatOptions::default());
+    /// assert_eq!(rich.text, "(1,234)");
+    /// assert_eq!(rich.color, Some(Color::Named(NamedColor::Red)));
+    /// assert_eq!(rich.runs[1], FormattedRun::Digits("1,234".to_string()));
+    /// ```
+    pub fn format_rich(&self, value: f64, opts: &FormatOptions) -> RichText {
+        let mut out = Output::with_runs();
+        let result = self
+            .write_value(value, opts, &mut out)
+            .and_then(|section| out.finish(opts).map(|()| section));
+        match result {
+            Ok(section) => {
+                let (text, runs) = out.into_parts();
+                RichText {
+                    text,
+                    color: section.and_then(|s| s.color),
+                    runs,
+                }
+            }
+            Err(_) => {
+                let text = super::general_format(value);
+                RichText {
+                    runs: vec![FormattedRun::Digits(text.clone())],
+                    text,
+                    color: None,
+                }
+            }
+        }
+    }
+    /// Format a text value and return the text section's color and output
+    /// runs.
+    ///
+    /// The text is the same as [`NumberFormat::format_text`] returns. Text
+    /// shown as-is, for codes without a text section, has no color.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, FormattedRun, NumberFormat};
+    /// use ssfmt::ast::{Color, NamedColor};
+    ///
+    /// let fmt = NumberFormat::parse("0;-0;0;[Blue]@*.").unwrap();
+    /// let opts = FormatOptions::builder().cell_width(6).build();
+    /// let rich = fmt.format_text_rich("abc", &opts);
+    /// assert_eq!(rich.text, "abc...");
+    /// assert_eq!(rich.color, Some(Color::Named(NamedColor::Blue)));
+    /// assert_eq!(
+    ///     rich.runs,
+    ///     [
+    ///         FormattedRun::Digits("abc".to_string()),
+    ///         FormattedRun::Fill { fill: '.', text: "...".to_string() },
+    ///     ]
+    /// );
+    /// ```
+    pub fn format_text_rich(&self, text: &str, opts: &FormatOptions) -> RichText {
+        let mut out = Output::with_runs();
+        let section = self.write_text(text, opts, &mut out);
+        let (text, runs) = out.into_parts();
+        RichText {
+            text,
+            color: section.and_then(|s| s.color),
+            runs,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Hebrew calendar
+//!
+//! Excel switches a date section to the Hebrew calendar when its locale
+//! tag carries calendar type 08, as in `[$-8040D]`. Years count from the
+//! creation epoch and start at Tishrei; leap years insert Adar I before
+//! Adar II, giving 13 months. Months are numbered from Tishrei as Windows
+//! does, so Nisan is month 7 in a common year and month 8 in a leap year.
+//!
+//! The conversion follows the arithmetic rules of the fixed calendar
+//! (molad and postponements) as given by Dershowitz and Reingold,
+//! *Calendrical Calculations*.
+
+/// Julian Day Number of 1 Tishrei AM 1
+const EPOCH: i64 = 347_998;
+
+/// Month names from Tishrei, in Hebrew and transliterated, for common years
+const COMMON_MONTHS: [(&str, &str); 12] = [
+    ("תשרי", "Tishrei"),
+    ("חשון", "Cheshvan"),
+    ("כסלו", "Kislev"),
+    ("טבת", "Tevet"),
+    ("שבט", "Shevat"),
+    ("אדר", "Adar"),
+    ("ניסן", "Nisan"),
+    ("אייר", "Iyar"),
+    ("סיון", "Sivan"),
+    ("תמוז", "Tammuz"),
+    ("אב", "Av"),
+    ("אלול", "Elul"),
+];
+
+/// Month names from Tishrei for leap years
+const LEAP_MONTHS: [(&str, &str); 13] = [
+    ("תשרי", "Tishrei"),
+    ("חשון", "Cheshvan"),
+    ("כסלו", "Kislev"),
+    ("טבת", "Tevet"),
+    ("שבט", "Shevat"),
+    ("אדר א", "Adar I"),
+    ("אדר ב", "Adar II"),
+    ("ניסן", "Nisan"),
+    ("אייר", "Iyar"),
+    ("סיון", "Sivan"),
+    ("תמוז", "Tammuz"),
+    ("אב", "Av"),
+    ("אלול", "Elul"),
+];
+
+/// A date in the Hebrew calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HebrewDate {
+    pub year: i32,
+    /// Month counted from Tishrei (1–12, or 1–13 in leap years)
+    pub month: u32,
+    pub day: u32,
+}
+
+impl HebrewDate {
+    /// The month's name, in Hebrew script or transliterated.
+    pub fn month_name(&self, hebrew_script: bool) -> &'static str {
+        let names: &[(&'static str, &'static str)] = if is_leap_year(self.year) {
+            &LEAP_MONTHS
+        } else {
+            &COMMON_MONTHS
+        };
+        let (hebrew, latin) = names[(self.month - 1) as usize];
+        if hebrew_script {
+            hebrew
+        } else {
+            latin
+        }
+    }
+}
+
+/// Convert a Gregorian date to the Hebrew calendar.
+pub(crate) fn gregorian_to_hebrew(year: i32, month: u32, day: u32) -> HebrewDate {
+    let jdn = crate::hijri::gregorian_to_jdn(year, month, day) as i64;
+
+    // Hebrew years run about 365.2468 days; correct the estimate either way
+    let mut hebrew_year = ((jdn - EPOCH) as f64 / 365.2468).floor() as i32 + 1;
+    while new_year(hebrew_year + 1) <= jdn {
+        hebrew_year += 1;
+    }
+    while new_year(hebrew_year) > jdn {
+        hebrew_year -= 1;
+    }
+
+    let mut remaining = jdn - new_year(hebrew_year);
+    let mut hebrew_month = 1;
+    loop {
+        let length = month_length(hebrew_year, hebrew_month);
+        if remaining < length {
+            break;
+        }
+        remaining -= length;
+        hebrew_month += 1;
+    }
+
+    HebrewDate {
+        year: hebrew_year,
+        month: hebrew_month,
+        day: remaining as u32 + 1,
+    }
+}
+
+/// Leap years have a second Adar: 7 of every 19 years.
+fn is_leap_year(year: i32) -> bool {
+    (7 * year as i64 + 1).rem_euclid(19) < 7
+}
+
+/// Days from the epoch's molad reckoning to 1 Tishrei of `year`, before
+/// the postponements that depend on the neighbouring years.
+fn elapsed_days(year: i32) -> i64 {
+    let months_elapsed = (235 * year as i64 - 234).div_euclid(19);
+    let parts_elapsed = 12_084 + 13_753 * months_elapsed;
+    let days = 29 * months_elapsed + parts_elapsed.div_euclid(25_920);
+    // Rosh Hashanah never falls on Sunday, Wednesday or Friday
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+/// Julian Day Number of 1 Tishrei of `year`.
+fn new_year(year: i32) -> i64 {
+    let previous = elapsed_days(year - 1);
+    let current = elapsed_days(year);
+    let next = elapsed_days(year + 1);
+    // Keep every year between 353 and 385 days long
+    let delay = if next - current == 356 {
+        2
+    } else if current - previous == 382 {
+        1
+    } else {
+        0
+    };
+    EPOCH + current + delay
+}
+
+/// Length of a month counted from Tishrei.
+fn month_length(year: i32, month: u32) -> i64 {
+    let leap = is_leap_year(year);
+    if leap && month == 6 {
+        // Adar I
+        return 30;
+    }
+    // Months after Adar shift by one in leap years
+    let month = if leap && month > 6 { month - 1 } else { month };
+    let year_length = new_year(year + 1) - new_year(year);
+    match month {
+        // Cheshvan gains a day in complete years, Kislev loses one in
+        // deficient years
+        2 if year_length % 10 == 5 => 30,
+        3 if year_length % 10 == 3 => 29,
+        1 | 3 | 5 | 7 | 9 | 11 => 30,
+        _ => 29,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> HebrewDate {
+        HebrewDate { year, month, day }
+    }
+
+    #[test]
+    fn test_gregorian_to_hebrew() {
+        // Rosh Hashanah
+        assert_eq!(gregorian_to_hebrew(2024, 10, 3), date(5785, 1, 1));
+        assert_eq!(gregorian_to_hebrew(2025, 9, 23), date(5786, 1, 1));
+        assert_eq!(gregorian_to_hebrew(2025, 9, 22), date(5785, 12, 29));
+        // Pesach in a common year
+        assert_eq!(gregorian_to_hebrew(2025, 4, 13), date(5785, 7, 15));
+        // Purim in the leap year 5784 falls in Adar II
+        assert_eq!(gregorian_to_hebrew(2024, 3, 24), date(5784, 7, 14));
+        assert_eq!(gregorian_to_hebrew(2024, 2, 23), date(5784, 6, 14));
+    }
+
+    #[test]
+    fn test_month_names() {
+        assert_eq!(date(5785, 7, 15).month_name(false), "Nisan");
+        assert_eq!(date(5784, 7, 14).month_name(false), "Adar II");
+        assert_eq!(date(5784, 6, 14).month_name(true), "אדר א");
+        assert_eq!(date(5785, 1, 1).month_name(true), "תשרי");
+    }
+}
@@ -0,0 +1,136 @@
+//! Classifying the text of a format code for syntax highlighting.
+
+use std::ops::Range;
+
+use crate::ast::{DatePart, FormatPart};
+
+/// What a stretch of format code text means, as found by [`highlight`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenClass {
+    /// Digit placeholders `0`, `#` and `?`
+    Digit,
+    /// Decimal point, thousands separator, fraction slash or `;` between
+    /// sections
+    Separator,
+    /// `%`
+    Percent,
+    /// Scientific notation such as `E+00`
+    Exponent,
+    /// The text placeholder `@`
+    Text,
+    /// Year, month, day and era codes
+    Date,
+    /// Hour, minute and second codes, AM/PM markers and elapsed time
+    Time,
+    /// Text shown as is
+    Literal,
+    /// A color such as `[Red]` or `[Color10]`
+    Color,
+    /// A condition such as `[>=100]`
+    Condition,
+    /// A locale code such as `[$-409]`, or a numeral style such as
+    /// `[DBNum1]`
+    Locale,
+    /// A fill (`*-`) or space (`_)`) with its character
+    Layout,
+    /// `General`
+    Keyword,
+    /// Bracket content the parser ignores
+    Unknown,
+}
+
+impl TokenClass {
+    /// Stable kebab-case name, e.g. `"locale"`, usable as a CSS class.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenClass::Digit => "digit",
+            TokenClass::Separator => "separator",
+            TokenClass::Percent => "percent",
+            TokenClass::Exponent => "exponent",
+            TokenClass::Text => "text",
+            TokenClass::Date => "date",
+            TokenClass::Time => "time",
+            TokenClass::Literal => "literal",
+            TokenClass::Color => "color",
+            TokenClass::Condition => "condition",
+            TokenClass::Locale => "locale",
+            TokenClass::Layout => "layout",
+            TokenClass::Keyword => "keyword",
+            TokenClass::Unknown => "unknown",
+        }
+    }
+
+    /// The class of the text that produced `part`.
+    pub(crate) fn of_part(part: &FormatPart) -> TokenClass {
+        match part {
+            FormatPart::Digit(_) => TokenClass::Digit,
+            FormatPart::DecimalPoint | FormatPart::ThousandsSeparator => TokenClass::Separator,
+            FormatPart::Percent => TokenClass::Percent,
+            FormatPart::Scientific { .. } => TokenClass::Exponent,
+            FormatPart::Fraction { .. } => TokenClass::Digit,
+            FormatPart::DatePart(
+                DatePart::Hour
+                | DatePart::Hour2
+                | DatePart::Minute
+                | DatePart::Minute2
+                | DatePart::Second
+                | DatePart::Second2
+                | DatePart::SubSecond(_),
+            )
+            | FormatPart::AmPm(_)
+            | FormatPart::Elapsed(_) => TokenClass::Time,
+            FormatPart::DatePart(_) => TokenClass::Date,
+            FormatPart::TextPlaceholder => TokenClass::Text,
+            FormatPart::Fill(_) | FormatPart::Skip(_) => TokenClass::Layout,
+            FormatPart::Locale(_) | FormatPart::DbNum(_) | FormatPart::NatNum(_) => {
+                TokenClass::Locale
+            }
+            FormatPart::GeneralNumber => TokenClass::Keyword,
+            FormatPart::Literal(_)
+            | FormatPart::QuotedLiteral(_)
+            | FormatPart::EscapedLiteral(_) => TokenClass::Literal,
+        }
+    }
+}
+
+/// Classify every part of a format code, for highlighting it in an editor.
+///
+/// The ranges are byte ranges in order, covering the whole code; adjacent
+/// ranges of the same class are merged. Classes come from the parser, so
+/// `m` is a [`Date`](TokenClass::Date) as a month and a
+/// [`Time`](TokenClass::Time) as a minute. Malformed codes are classified
+/// the way [`NumberFormat::parse_lenient`](crate::NumberFormat::parse_lenient)
+/// repairs them, so highlighting never fails while the code is typed.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{highlight, TokenClass};
+///
+/// assert_eq!(
+///     highlight("[Red]h:mm;0.0"),
+///     [
+///         (0..5, TokenClass::Color),
+///         (5..6, TokenClass::Time),
+///         (6..7, TokenClass::Literal),
+///         (7..9, TokenClass::Time),
+///         (9..10, TokenClass::Separator),
+///         (10..11, TokenClass::Digit),
+///         (11..12, TokenClass::Separator),
+///         (12..13, TokenClass::Digit),
+///     ]
+/// );
+/// assert_eq!(highlight("mm/yy")[0], (0..2, TokenClass::Date));
+/// ```
+pub fn highlight(format_code: &str) -> Vec<(Range<usize>, TokenClass)> {
+    let mut merged: Vec<(Range<usize>, TokenClass)> = Vec::new();
+    for (span, class) in crate::parser::classify(format_code) {
+        match merged.last_mut() {
+            Some((last, last_class)) if *last_class == class && last.end == span.start => {
+                last.end = span.end;
+            }
+            _ => merged.push((span, class)),
+        }
+    }
+    merged
+}
@@ -14,6 +14,29 @@
 //!
 //! The implementation here aims to match Excel's B2 calendar format behavior
 //! for most dates.
+//!
+//! [`HijriAlgorithm::UmmAlQura`] switches to Saudi Arabia's official
+//! calendar instead, see the `umm_al_qura` module.
+
+use crate::options::HijriAlgorithm;
+
+mod umm_al_qura;
+
+/// Convert a Gregorian date to Hijri with the chosen algorithm.
+///
+/// Umm al-Qura dates outside Gregorian 1900–2100 use the tabular calendar.
+pub(crate) fn to_hijri(
+    algorithm: HijriAlgorithm,
+    year: i32,
+    month: u32,
+    day: u32,
+) -> (i32, u32, u32) {
+    match algorithm {
+        HijriAlgorithm::Tabular => gregorian_to_hijri(year, month, day),
+        HijriAlgorithm::UmmAlQura => umm_al_qura::gregorian_to_hijri(year, month, day)
+            .unwrap_or_else(|| gregorian_to_hijri(year, month, day)),
+    }
+}
 
 /// Convert a Gregorian date to Hijri (Islamic) date using the Kuwaiti algorithm
 ///
@@ -36,7 +59,7 @@ pub fn gregorian_to_hijri(year: i32, month: u32, day: u32) -> (i32, u32, u32) {
 }
 
 /// Convert a Gregorian date to Julian Day Number
-fn gregorian_to_jdn(year: i32, month: u32, day: u32) -> i32 {
+pub(crate) fn gregorian_to_jdn(year: i32, month: u32, day: u32) -> i32 {
     let mut y = year;
     let mut m = month as i32;
 
@@ -67,13 +90,11 @@ fn gregorian_to_jdn(year: i32, month: u32, day: u32) -> i32 {
     }
 
     // Calculate Julian Day Number
-    let jd = ((365.25 * (y + 4716) as f64).floor() as i32)
+    ((365.25 * (y + 4716) as f64).floor() as i32)
         + ((30.6001 * (m + 1) as f64).floor() as i32)
         + day as i32
         + b
-        - 1524;
-
-    jd
+        - 1524
 }
 
 /// Convert Julian Day Number to Hijri date
@@ -117,7 +138,7 @@ fn jdn_to_hijri(jd: i32) -> (i32, u32, u32) {
     }
 
     // Clamp day to valid range (1-30)
-    let id = id.max(1).min(30);
+    let id = id.clamp(1, 30);
 
     (iy, im, id)
 }
@@ -0,0 +1,379 @@
+//! Umm al-Qura calendar
+//!
+//! Saudi Arabia's official calendar ends a month after its 29th day when,
+//! seen from Mecca that evening, the new moon came before sunset and the
+//! moon sets after the sun; otherwise the month runs to 30 days. Month
+//! starts from 1317 to 1525 AH (covering Gregorian 1900–2100) are worked
+//! out once from that rule and kept as a table.
+//!
+//! The rule has been in force since 1423 AH (2002). Calendars published
+//! before then used other criteria and can differ by a day.
+//!
+//! Sun and moon positions use the truncated series from Meeus,
+//! *Astronomical Algorithms* (chapters 25, 47 and 49), ignoring ΔT. That is
+//! good to a minute or two, which only matters when the moon sets within
+//! moments of the sun.
+
+use std::sync::OnceLock;
+
+/// First Hijri year in the table
+const FIRST_YEAR: i32 = 1317;
+/// Last Hijri year in the table
+const LAST_YEAR: i32 = 1525;
+
+/// Month count (year × 12 + month − 1) of Shawwal 1420, the month that
+/// starts with Meeus's lunation 0 (the new moon of 6 January 2000)
+const LUNATION_ZERO_MONTH: i32 = 1420 * 12 + 9;
+
+const MECCA_LATITUDE: f64 = 21.4225;
+const MECCA_LONGITUDE: f64 = 39.8262;
+
+/// Altitude of the sun's center at sunset, allowing for refraction and
+/// its semidiameter
+const SUN_SET_ALTITUDE: f64 = -0.8333;
+
+/// Convert a Gregorian date to an Umm al-Qura Hijri date.
+///
+/// Returns `None` outside Gregorian 1900–2100.
+pub(super) fn gregorian_to_hijri(year: i32, month: u32, day: u32) -> Option<(i32, u32, u32)> {
+    if !(1900..=2100).contains(&year) {
+        return None;
+    }
+    let jdn = super::gregorian_to_jdn(year, month, day);
+    let starts = month_starts();
+    let next = starts.partition_point(|&start| start <= jdn);
+    if next == 0 || next == starts.len() {
+        return None;
+    }
+    let index = next - 1;
+    let month_count = FIRST_YEAR * 12 + index as i32;
+    Some((
+        month_count / 12,
+        (month_count % 12 + 1) as u32,
+        (jdn - starts[index] + 1) as u32,
+    ))
+}
+
+/// Julian Day Numbers of the first day of every month in the table, plus
+/// the day after the last one.
+fn month_starts() -> &'static [i32] {
+    static STARTS: OnceLock<Vec<i32>> = OnceLock::new();
+    STARTS.get_or_init(|| {
+        // Find the start of the first month from its new moon, then step
+        // month by month checking the evening of each 29th day
+        let lunation = FIRST_YEAR * 12 - LUNATION_ZERO_MONTH;
+        let conjunction = new_moon(lunation as f64);
+        let mut evening = (conjunction + 0.5).floor() as i32;
+        while !month_ends_after(evening) {
+            evening += 1;
+        }
+
+        let months = (LAST_YEAR - FIRST_YEAR + 1) * 12;
+        let mut starts = Vec::with_capacity(months as usize + 1);
+        starts.push(evening + 1);
+        for _ in 0..months {
+            let start = starts[starts.len() - 1];
+            let length = if month_ends_after(start + 28) { 29 } else { 30 };
+            starts.push(start + length);
+        }
+        starts
+    })
+}
+
+/// Whether a new month starts the day after `day` (a Julian Day Number):
+/// that evening in Mecca the new moon has passed and the moon sets after
+/// the sun.
+fn month_ends_after(day: i32) -> bool {
+    let midnight = day as f64 - 0.5;
+    let sunset = setting_time(midnight, sun_position);
+    let lunation = ((sunset - 2451550.09766) / 29.530588861).round();
+    new_moon(lunation) < sunset && setting_time(midnight, moon_position) > sunset
+}
+
+/// A body's place in the sky and the altitude at which it sets.
+struct Position {
+    right_ascension: f64,
+    declination: f64,
+    set_altitude: f64,
+}
+
+/// Julian Date (UT) at which a body sets in Mecca on the day starting at
+/// `midnight`.
+fn setting_time(midnight: f64, position: fn(f64) -> Position) -> f64 {
+    // Start near sunset in Mecca, about 15:30 UT
+    let mut time = midnight + 0.65;
+    for _ in 0..10 {
+        let body = position(time);
+        let cos_set_hour_angle = (sin(body.set_altitude)
+            - sin(MECCA_LATITUDE) * sin(body.declination))
+            / (cos(MECCA_LATITUDE) * cos(body.declination));
+        let set_hour_angle = cos_set_hour_angle.clamp(-1.0, 1.0).acos().to_degrees();
+        let hour_angle = sidereal_time(time) + MECCA_LONGITUDE - body.right_ascension;
+        let step = normalize_signed(set_hour_angle - hour_angle) / 360.985647;
+        time += step;
+        if step.abs() < 1e-5 {
+            break;
+        }
+    }
+    time
+}
+
+/// Greenwich mean sidereal time in degrees.
+fn sidereal_time(jd: f64) -> f64 {
+    let t = (jd - 2451545.0) / 36525.0;
+    280.46061837 + 360.98564736629 * (jd - 2451545.0) + 0.000387933 * t * t
+}
+
+/// Julian Ephemeris Day of mean-corrected new moon number `k`, counted
+/// from the new moon of 6 January 2000 (Meeus chapter 49).
+fn new_moon(k: f64) -> f64 {
+    let t = k / 1236.85;
+    let t2 = t * t;
+    let jde = 2451550.09766 + 29.530588861 * k + 0.00015437 * t2 - 0.00000015 * t2 * t;
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let m = 2.5534 + 29.1053567 * k - 0.0000014 * t2;
+    let mp = 201.5643 + 385.81693528 * k + 0.0107582 * t2;
+    let f = 160.7108 + 390.67050284 * k - 0.0016118 * t2;
+    let omega = 124.7746 - 1.56375588 * k + 0.0020672 * t2;
+
+    jde - 0.4072 * sin(mp)
+        + 0.17241 * e * sin(m)
+        + 0.01608 * sin(2.0 * mp)
+        + 0.01039 * sin(2.0 * f)
+        + 0.00739 * e * sin(mp - m)
+        - 0.00514 * e * sin(mp + m)
+        + 0.00208 * e * e * sin(2.0 * m)
+        - 0.00111 * sin(mp - 2.0 * f)
+        - 0.00057 * sin(mp + 2.0 * f)
+        + 0.00056 * e * sin(2.0 * mp + m)
+        - 0.00042 * sin(3.0 * mp)
+        + 0.00042 * e * sin(m + 2.0 * f)
+        + 0.00038 * e * sin(m - 2.0 * f)
+        - 0.00024 * e * sin(2.0 * mp - m)
+        - 0.00017 * sin(omega)
+        - 0.00007 * sin(mp + 2.0 * m)
+        + 0.00004 * sin(2.0 * mp - 2.0 * f)
+        + 0.00004 * sin(3.0 * m)
+        + 0.00003 * sin(mp + m - 2.0 * f)
+        + 0.00003 * sin(2.0 * mp + 2.0 * f)
+        - 0.00003 * sin(mp + m + 2.0 * f)
+        + 0.00003 * sin(mp - m + 2.0 * f)
+        - 0.00002 * sin(mp - m - 2.0 * f)
+        - 0.00002 * sin(3.0 * mp + m)
+        + 0.00002 * sin(4.0 * mp)
+}
+
+/// Apparent position of the sun (Meeus chapter 25, low accuracy).
+fn sun_position(jd: f64) -> Position {
+    let t = (jd - 2451545.0) / 36525.0;
+    let mean_longitude = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let anomaly = 357.52911 + 35999.05029 * t - 0.0001537 * t * t;
+    let center = (1.914602 - 0.004817 * t) * sin(anomaly)
+        + (0.019993 - 0.000101 * t) * sin(2.0 * anomaly)
+        + 0.000289 * sin(3.0 * anomaly);
+    let omega = 125.04 - 1934.136 * t;
+    let longitude = mean_longitude + center - 0.00569 - 0.00478 * sin(omega);
+    let (right_ascension, declination) = to_equatorial(longitude, 0.0, t);
+    Position {
+        right_ascension,
+        declination,
+        set_altitude: SUN_SET_ALTITUDE,
+    }
+}
+
+/// Periodic terms for the moon's longitude and distance: multiples of
+/// D, M, M′ and F, then the longitude (10⁻⁶ degrees) and distance
+/// (10⁻³ km) coefficients.
+const MOON_LONGITUDE_TERMS: [(f64, f64, f64, f64, f64, f64); 30] = [
+    (0.0, 0.0, 1.0, 0.0, 6288774.0, -20905355.0),
+    (2.0, 0.0, -1.0, 0.0, 1274027.0, -3699111.0),
+    (2.0, 0.0, 0.0, 0.0, 658314.0, -2955968.0),
+    (0.0, 0.0, 2.0, 0.0, 213618.0, -569925.0),
+    (0.0, 1.0, 0.0, 0.0, -185116.0, 48888.0),
+    (0.0, 0.0, 0.0, 2.0, -114332.0, -3149.0),
+    (2.0, 0.0, -2.0, 0.0, 58793.0, 246158.0),
+    (2.0, -1.0, -1.0, 0.0, 57066.0, -152138.0),
+    (2.0, 0.0, 1.0, 0.0, 53322.0, -170733.0),
+    (2.0, -1.0, 0.0, 0.0, 45758.0, -204586.0),
+    (0.0, 1.0, -1.0, 0.0, -40923.0, -129620.0),
+    (1.0, 0.0, 0.0, 0.0, -34720.0, 108743.0),
+    (0.0, 1.0, 1.0, 0.0, -30383.0, 104755.0),
+    (2.0, 0.0, 0.0, -2.0, 15327.0, 10321.0),
+    (0.0, 0.0, 1.0, 2.0, -12528.0, 0.0),
+    (0.0, 0.0, 1.0, -2.0, 10980.0, 79661.0),
+    (4.0, 0.0, -1.0, 0.0, 10675.0, -34782.0),
+    (0.0, 0.0, 3.0, 0.0, 10034.0, -23210.0),
+    (4.0, 0.0, -2.0, 0.0, 8548.0, -21636.0),
+    (2.0, 1.0, -1.0, 0.0, -7888.0, 24208.0),
+    (2.0, 1.0, 0.0, 0.0, -6766.0, 30824.0),
+    (1.0, 0.0, -1.0, 0.0, -5163.0, -8379.0),
+    (1.0, 1.0, 0.0, 0.0, 4987.0, -16675.0),
+    (2.0, -1.0, 1.0, 0.0, 4036.0, -12831.0),
+    (2.0, 0.0, 2.0, 0.0, 3994.0, -10445.0),
+    (4.0, 0.0, 0.0, 0.0, 3861.0, -11650.0),
+    (2.0, 0.0, -3.0, 0.0, 3665.0, 14403.0),
+    (0.0, 1.0, -2.0, 0.0, -2689.0, -7003.0),
+    (2.0, 0.0, -1.0, 2.0, -2602.0, 0.0),
+    (2.0, -1.0, -2.0, 0.0, 2390.0, 10056.0),
+];
+
+/// Periodic terms for the moon's latitude: multiples of D, M, M′ and F,
+/// then the coefficient in 10⁻⁶ degrees.
+const MOON_LATITUDE_TERMS: [(f64, f64, f64, f64, f64); 20] = [
+    (0.0, 0.0, 0.0, 1.0, 5128122.0),
+    (0.0, 0.0, 1.0, 1.0, 280602.0),
+    (0.0, 0.0, 1.0, -1.0, 277693.0),
+    (2.0, 0.0, 0.0, -1.0, 173237.0),
+    (2.0, 0.0, -1.0, 1.0, 55413.0),
+    (2.0, 0.0, -1.0, -1.0, 46271.0),
+    (2.0, 0.0, 0.0, 1.0, 32573.0),
+    (0.0, 0.0, 2.0, 1.0, 17198.0),
+    (2.0, 0.0, 1.0, -1.0, 9266.0),
+    (0.0, 0.0, 2.0, -1.0, 8822.0),
+    (2.0, -1.0, 0.0, -1.0, 8216.0),
+    (2.0, 0.0, -2.0, -1.0, 4324.0),
+    (2.0, 0.0, 1.0, 1.0, 4200.0),
+    (2.0, 1.0, 0.0, -1.0, -3359.0),
+    (2.0, -1.0, -1.0, 1.0, 2463.0),
+    (2.0, -1.0, 0.0, 1.0, 2211.0),
+    (2.0, -1.0, -1.0, -1.0, 2065.0),
+    (0.0, 1.0, -1.0, -1.0, -1870.0),
+    (4.0, 0.0, -1.0, -1.0, 1828.0),
+    (0.0, 1.0, 0.0, 1.0, -1794.0),
+];
+
+/// Apparent geocentric position of the moon (Meeus chapter 47, truncated).
+fn moon_position(jd: f64) -> Position {
+    let t = (jd - 2451545.0) / 36525.0;
+    let t2 = t * t;
+    let mean_longitude = 218.3164477 + 481267.88123421 * t - 0.0015786 * t2;
+    let d = 297.8501921 + 445267.1114034 * t - 0.0018819 * t2;
+    let m = 357.5291092 + 35999.0502909 * t - 0.0001536 * t2;
+    let mp = 134.9633964 + 477198.8675055 * t + 0.0087414 * t2;
+    let f = 93.272095 + 483202.0175233 * t - 0.0036539 * t2;
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let a1 = 119.75 + 131.849 * t;
+    let a2 = 53.09 + 479264.29 * t;
+    let a3 = 313.45 + 481266.484 * t;
+
+    // Terms involving the sun's anomaly shrink with the Earth's eccentricity
+    let eccentricity = |sun: f64| e.powi(sun.abs() as i32);
+
+    let mut longitude_sum = 3958.0 * sin(a1) + 1962.0 * sin(mean_longitude - f) + 318.0 * sin(a2);
+    let mut distance_sum = 0.0;
+    for &(dd, dm, dmp, df, longitude, distance) in &MOON_LONGITUDE_TERMS {
+        let argument = dd * d + dm * m + dmp * mp + df * f;
+        longitude_sum += longitude * eccentricity(dm) * sin(argument);
+        distance_sum += distance * eccentricity(dm) * cos(argument);
+    }
+
+    let mut latitude_sum = -2235.0 * sin(mean_longitude)
+        + 382.0 * sin(a3)
+        + 175.0 * sin(a1 - f)
+        + 175.0 * sin(a1 + f)
+        + 127.0 * sin(mean_longitude - mp)
+        - 115.0 * sin(mean_longitude + mp);
+    for &(dd, dm, dmp, df, latitude) in &MOON_LATITUDE_TERMS {
+        let argument = dd * d + dm * m + dmp * mp + df * f;
+        latitude_sum += latitude * eccentricity(dm) * sin(argument);
+    }
+
+    let omega = 125.04452 - 1934.136261 * t;
+    let longitude = mean_longitude + longitude_sum / 1e6 - 0.00478 * sin(omega);
+    let latitude = latitude_sum / 1e6;
+    let distance = 385000.56 + distance_sum / 1000.0;
+    let parallax = (6378.14 / distance).asin().to_degrees();
+
+    let (right_ascension, declination) = to_equatorial(longitude, latitude, t);
+    Position {
+        right_ascension,
+        declination,
+        set_altitude: 0.7275 * parallax - 0.5667,
+    }
+}
+
+/// Convert ecliptic longitude and latitude to right ascension and
+/// declination, all in degrees.
+fn to_equatorial(longitude: f64, latitude: f64, t: f64) -> (f64, f64) {
+    let obliquity = 23.439291 - 0.0130042 * t;
+    let right_ascension = (sin(longitude) * cos(obliquity)
+        - latitude.to_radians().tan() * sin(obliquity))
+    .atan2(cos(longitude))
+    .to_degrees();
+    let declination = (sin(latitude) * cos(obliquity)
+        + cos(latitude) * sin(obliquity) * sin(longitude))
+    .asin()
+    .to_degrees();
+    (right_ascension, declination)
+}
+
+/// Reduce an angle to the range −180° to 180°.
+fn normalize_signed(degrees: f64) -> f64 {
+    let reduced = degrees.rem_euclid(360.0);
+    if reduced > 180.0 {
+        reduced - 360.0
+    } else {
+        reduced
+    }
+}
+
+fn sin(degrees: f64) -> f64 {
+    degrees.to_radians().sin()
+}
+
+fn cos(degrees: f64) -> f64 {
+    degrees.to_radians().cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_starts_match_published_calendar() {
+        // First days of months in the official Umm al-Qura calendar
+        let cases = [
+            ((2022, 4, 2), (1443, 9)),
+            ((2022, 5, 2), (1443, 10)),
+            ((2023, 3, 23), (1444, 9)),
+            ((2023, 4, 21), (1444, 10)),
+            ((2023, 7, 19), (1445, 1)),
+            ((2024, 3, 11), (1445, 9)),
+            ((2024, 4, 10), (1445, 10)),
+            ((2024, 7, 7), (1446, 1)),
+            ((2025, 3, 1), (1446, 9)),
+            ((2025, 3, 30), (1446, 10)),
+            ((2025, 6, 26), (1447, 1)),
+        ];
+        for ((year, month, day), (hijri_year, hijri_month)) in cases {
+            assert_eq!(
+                gregorian_to_hijri(year, month, day),
+                Some((hijri_year, hijri_month, 1)),
+                "{year}-{month}-{day}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_month_lengths() {
+        let starts = month_starts();
+        assert_eq!(
+            starts.len(),
+            ((LAST_YEAR - FIRST_YEAR + 1) * 12 + 1) as usize
+        );
+        assert!(starts
+            .windows(2)
+            .all(|pair| matches!(pair[1] - pair[0], 29 | 30)));
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(
+            gregorian_to_hijri(1900, 1, 1).map(|(year, _, _)| year),
+            Some(1317)
+        );
+        assert!(gregorian_to_hijri(2100, 12, 31).is_some());
+        assert_eq!(gregorian_to_hijri(1899, 12, 31), None);
+        assert_eq!(gregorian_to_hijri(2101, 1, 1), None);
+    }
+}
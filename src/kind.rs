@@ -0,0 +1,321 @@
+//! Classifying formats into the categories of a "Format Cells" dialog.
+
+use crate::ast::{Color, DatePart, FormatPart, NamedColor, NumberFormat, Section};
+use crate::formatter::numeric_section_count;
+use crate::parser::analysis::section_analysis;
+
+/// Currency symbols recognized in literal text such as `"$"#,##0`.
+const CURRENCY_SYMBOLS: &[char] = &[
+    '$', '€', '£', '¥', '¢', '₩', '₹', '₽', '₺', '₪', '₫', '฿', '₴', '₦', '₱', '₡', '₲', '₵', '₸',
+];
+
+/// The category of a format, as shown in Excel's "Format Cells" dialog.
+///
+/// A format is classified by its first section, which is the one Excel
+/// uses to pick the category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatKind {
+    /// `General`
+    General,
+    /// Plain digits, decimals and grouping, e.g. `#,##0.00`
+    Number,
+    /// A number with a currency symbol, e.g. `$#,##0.00`
+    Currency,
+    /// A number padded with a fill character, e.g. `_($* #,##0.00_)`
+    Accounting,
+    /// A number with a percent sign, e.g. `0.00%`
+    Percentage,
+    /// Scientific notation, e.g. `0.00E+00`
+    Scientific,
+    /// A fraction, e.g. `# ?/?`
+    Fraction,
+    /// A date without a time of day, e.g. `yyyy-mm-dd`
+    Date,
+    /// A time of day without a date, e.g. `h:mm AM/PM`
+    Time,
+    /// A date with a time of day, e.g. `m/d/yy h:mm`
+    DateTime,
+    /// An elapsed time, e.g. `[h]:mm:ss`
+    Duration,
+    /// Text only, e.g. `@`
+    Text,
+    /// Anything else: conditions, literal text between digits, and so on
+    Custom,
+}
+
+/// How negative numbers are shown, as picked in the "Negative numbers"
+/// list of Excel's "Format Cells" dialog.
+///
+/// Excel's choices are a minus sign or parentheses, each optionally red.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NegativeStyle {
+    /// A minus sign, added for a single numeric section or written in the
+    /// negative section
+    pub minus_sign: bool,
+    /// Parentheses around the number, as in accounting formats
+    pub parentheses: bool,
+    /// The color of the section negative numbers use
+    pub color: Option<Color>,
+}
+
+impl NegativeStyle {
+    /// Whether negative numbers show in red.
+    pub fn is_red(&self) -> bool {
+        self.color == Some(Color::Named(NamedColor::Red))
+    }
+}
+
+impl Section {
+    /// The category of this section.
+    pub fn kind(&self) -> FormatKind {
+        if self.condition.is_some() {
+            return FormatKind::Custom;
+        }
+
+        if self.has_date_parts() {
+            let mut has_date = false;
+            let mut has_time = false;
+            for part in &self.parts {
+                match part {
+                    FormatPart::Elapsed(_) => return FormatKind::Duration,
+                    FormatPart::AmPm(_) => has_time = true,
+                    FormatPart::DatePart(part) => {
+                        if is_time_part(part) {
+                            has_time = true;
+                        } else {
+                            has_date = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            return match (has_date, has_time) {
+                (true, true) => FormatKind::DateTime,
+                (false, _) => FormatKind::Time,
+                (true, false) => FormatKind::Date,
+            };
+        }
+
+        let mut has_digits = false;
+        let mut has_fill = false;
+        let mut has_other = false;
+        for part in &self.parts {
+            match part {
+                FormatPart::Scientific { .. } => return FormatKind::Scientific,
+                FormatPart::Fraction { .. } => return FormatKind::Fraction,
+                FormatPart::Percent => return FormatKind::Percentage,
+                FormatPart::Digit(_) => has_digits = true,
+                FormatPart::Fill(_) => has_fill = true,
+                FormatPart::DecimalPoint
+                | FormatPart::ThousandsSeparator
+                | FormatPart::Skip(_)
+                | FormatPart::DbNum(_)
+                | FormatPart::NatNum(_) => {}
+                FormatPart::Locale(locale) if locale.currency.is_none() => {}
+                FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) if s.trim().is_empty() => {}
+                _ => has_other = true,
+            }
+        }
+
+        if !has_digits {
+            return if self.parts.is_empty()
+                || self
+                    .parts
+                    .iter()
+                    .all(|p| matches!(p, FormatPart::GeneralNumber))
+            {
+                FormatKind::General
+            } else if self.has_text_placeholder() {
+                FormatKind::Text
+            } else {
+                FormatKind::Custom
+            };
+        }
+
+        if has_fill {
+            FormatKind::Accounting
+        } else if section_currency_symbol(self).is_some() {
+            FormatKind::Currency
+        } else if has_other {
+            FormatKind::Custom
+        } else {
+            FormatKind::Number
+        }
+    }
+}
+
+impl NumberFormat {
+    /// The category of this format, from its first section.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatKind, NumberFormat};
+    ///
+    /// let kind = |code| NumberFormat::parse(code).unwrap().kind();
+    /// assert_eq!(kind("General"), FormatKind::General);
+    /// assert_eq!(kind("#,##0.00"), FormatKind::Number);
+    /// assert_eq!(kind("$#,##0.00;($#,##0.00)"), FormatKind::Currency);
+    /// assert_eq!(kind("_($* #,##0.00_)"), FormatKind::Accounting);
+    /// assert_eq!(kind("0.0%"), FormatKind::Percentage);
+    /// assert_eq!(kind("yyyy-mm-dd"), FormatKind::Date);
+    /// assert_eq!(kind("h:mm AM/PM"), FormatKind::Time);
+    /// assert_eq!(kind("[h]:mm:ss"), FormatKind::Duration);
+    /// assert_eq!(kind("000-00-0000"), FormatKind::Custom);
+    /// ```
+    pub fn kind(&self) -> FormatKind {
+        self.sections()
+            .first()
+            .map_or(FormatKind::General, Section::kind)
+    }
+
+    /// Number of decimal places shown by the first section.
+    ///
+    /// Counts the digit placeholders after the decimal point (before the
+    /// exponent in scientific formats), or the fractional-second digits in
+    /// time formats. Returns 0 when no decimals are shown.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// assert_eq!(NumberFormat::parse("#,##0.00").unwrap().decimal_places(), 2);
+    /// assert_eq!(NumberFormat::parse("0.000E+00").unwrap().decimal_places(), 3);
+    /// assert_eq!(NumberFormat::parse("mm:ss.0").unwrap().decimal_places(), 1);
+    /// assert_eq!(NumberFormat::parse("0%").unwrap().decimal_places(), 0);
+    /// ```
+    pub fn decimal_places(&self) -> usize {
+        let Some(section) = self.sections().first() else {
+            return 0;
+        };
+        let mut places = 0;
+        let mut after_decimal = false;
+        for part in &section.parts {
+            match part {
+                FormatPart::DatePart(DatePart::SubSecond(n)) => places = places.max(*n as usize),
+                FormatPart::DecimalPoint => after_decimal = true,
+                FormatPart::Digit(_) if after_decimal => places += 1,
+                FormatPart::Scientific { .. } | FormatPart::Fraction { .. } => break,
+                _ => {}
+            }
+        }
+        places
+    }
+
+    /// The currency symbol of the first section, if any.
+    ///
+    /// Comes from a `[$€-407]` tag or from literal text containing a
+    /// currency sign.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// assert_eq!(NumberFormat::parse("[$€-407]#,##0").unwrap().currency_symbol(), Some("€"));
+    /// assert_eq!(NumberFormat::parse("\"US$\"0.00").unwrap().currency_symbol(), Some("US$"));
+    /// assert_eq!(NumberFormat::parse("0.00").unwrap().currency_symbol(), None);
+    /// ```
+    pub fn currency_symbol(&self) -> Option<&str> {
+        self.sections().first().and_then(section_currency_symbol)
+    }
+
+    /// Whether the first section groups digits with a thousands separator.
+    ///
+    /// Trailing commas that scale by 1000 (`#,##0,`) don't count unless the
+    /// digits are grouped as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// assert!(NumberFormat::parse("#,##0").unwrap().uses_thousands_separator());
+    /// assert!(!NumberFormat::parse("0,").unwrap().uses_thousands_separator());
+    /// ```
+    pub fn uses_thousands_separator(&self) -> bool {
+        self.sections().first().is_some_and(|section| {
+            !section.has_date_parts() && section_analysis(section).has_thousands_separator
+        })
+    }
+
+    /// How negative numbers are shown, from the section -1 is formatted
+    /// with.
+    ///
+    /// A negative section that hides the value (`0;;`), and date and time
+    /// sections, which show nothing for negative serials, have neither a
+    /// minus sign nor parentheses.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// let style = |code| NumberFormat::parse(code).unwrap().negative_style();
+    /// assert!(style("#,##0.00").minus_sign);
+    /// assert!(!style("#,##0.00;[Red]#,##0.00").minus_sign);
+    /// assert!(style("#,##0.00;[Red]#,##0.00").is_red());
+    ///
+    /// let accounting = style("_(* #,##0_);_(* \\(#,##0\\);_(* \"-\"_);_(@_)");
+    /// assert!(accounting.parentheses && !accounting.minus_sign);
+    /// ```
+    pub fn negative_style(&self) -> NegativeStyle {
+        let sections = self.sections();
+        let (index, section) = self.section_for(-1.0);
+        let mut style = NegativeStyle {
+            minus_sign: false,
+            parentheses: false,
+            color: section.color,
+        };
+        if (section.parts.is_empty() && sections.len() > 1) || section.has_date_parts() {
+            return style;
+        }
+
+        // A condition met by -1 shows the magnitude, like a negative section
+        let magnitude_only =
+            index < 2 && section.condition.is_some_and(|c| c.is_strict_match(-1.0));
+        let signed_by_format = section.is_general()
+            || section
+                .parts
+                .iter()
+                .any(|p| matches!(p, FormatPart::Scientific { .. }))
+            || (numeric_section_count(sections) <= 1
+                && section.parts.iter().any(FormatPart::is_numeric_part));
+        let literal = |c: char| {
+            section.parts.iter().any(|part| {
+                matches!(
+                    part,
+                    FormatPart::Literal(s)
+                    | FormatPart::QuotedLiteral(s)
+                    | FormatPart::EscapedLiteral(s)
+                        if s.contains(c)
+                )
+            })
+        };
+        style.minus_sign = (signed_by_format && !magnitude_only) || literal('-');
+        style.parentheses = literal('(') && literal(')');
+        style
+    }
+}
+
+/// True for the time-of-day date parts.
+fn is_time_part(part: &DatePart) -> bool {
+    matches!(
+        part,
+        DatePart::Hour
+            | DatePart::Hour2
+            | DatePart::Minute
+            | DatePart::Minute2
+            | DatePart::Second
+            | DatePart::Second2
+            | DatePart::SubSecond(_)
+    )
+}
+
+fn section_currency_symbol(section: &Section) -> Option<&str> {
+    section.parts.iter().find_map(|part| match part {
+        FormatPart::Locale(locale) => locale.currency.as_deref().filter(|s| !s.is_empty()),
+        FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) | FormatPart::EscapedLiteral(s)
+            if s.contains(CURRENCY_SYMBOLS) =>
+        {
+            Some(s.trim())
+        }
+        _ => None,
+    })
+}
@@ -45,35 +45,91 @@
 //! ## Feature Flags
 //!
 //! - `chrono` (default) - Enable chrono type support
+//! - `time` - Enable `time` crate support: `Value` conversions and serial helpers in [`date_serial`]
+//! - `jiff` - Enable `jiff` civil date support: `Value` conversions and serial helpers in [`date_serial`]
+//! - `arrow` - Format Arrow numeric arrays into string arrays ([`NumberFormat::format_arrow`])
+//! - `rayon` - Parallel batch formatting ([`NumberFormat::par_format_batch`])
+//! - `bench_hooks` - Allocation counters for performance regression tests (see the `bench_hooks` module)
 //! - `bigint` - Enable BigInt support for arbitrary precision integers
+//! - `decimal` - Enable exact `rust_decimal` formatting ([`format_decimal`], `Value::Decimal`)
+//! - `serde` - Derive `Serialize`/`Deserialize` for [`NumberFormat`] and the AST
+//! - `ffi` - C ABI with a header in `include/ssfmt.h` (see the `ffi` module)
+//! - `python` - Python extension module via PyO3 (built with `maturin`)
+//! - `wasm` - JavaScript bindings via `wasm-bindgen` (see the `wasm` module)
 
+#[cfg(feature = "arrow")]
+mod arrow;
 pub mod ast;
+#[cfg(feature = "bench_hooks")]
+pub mod bench_hooks;
 pub mod builtin_formats;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod formats;
 pub mod options;
 pub mod value;
+pub mod visit;
 
 pub mod date_serial;
+mod calendar;
+mod era;
+mod hebrew;
 mod hijri;
+mod kind;
 
 mod cache;
+mod compiled;
 mod formatter;
+mod highlight;
 mod locale;
+mod palette;
 pub mod parser;
+mod preview;
+#[cfg(feature = "python")]
+mod python;
+mod registry;
+mod unformat;
+mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod writer;
 
 // Re-exports will be added once types are defined:
 pub use ast::{NumberFormat, Section};
-pub use builtin_formats::{format_code_from_id, is_builtin_format_id};
-pub use error::{FormatError, ParseError};
+pub use builtin_formats::{
+    builtin_format, format_code_from_id, format_code_from_id_with_locale, is_builtin_format_id,
+    localized_format_code,
+};
+pub use cache::{global_cache, CacheStats, FormatCache, DEFAULT_CACHE_CAPACITY};
+pub use compiled::CompiledFormat;
+pub use error::{DiagnosticCode, FormatError, ParseError, ParseWarning, ValidationError};
+pub use formatter::{
+    general_format, general_format_with_width, ColumnCell, ColumnFormatter, ColumnIter, FormatIter,
+    Formatted, FormattedRun, RichText,
+};
+pub use highlight::{highlight, TokenClass};
+pub use kind::{FormatKind, NegativeStyle};
 pub use locale::Locale;
-pub use options::{DateSystem, FormatOptions};
+pub use options::{
+    DateSystem, ExtraSections, FillFallback, FormatOptions, FormatOptionsBuilder, HijriAlgorithm,
+    LayoutMode, OverflowBehavior, ParseLimits, ParseOptions, RoundingMode, TimezonePolicy,
+};
+pub use parser::date_code::is_date_format_code;
+pub use parser::detect::{detect_format, FormatGuess};
+pub use parser::lexer::tokenize;
+pub use palette::Palette;
+pub use preview::{preview, FormatPreview, PreviewSample};
+pub use parser::tokens::{SpannedToken, Token, TokenCategory};
+pub use registry::FormatRegistry;
+pub use validate::validate;
 pub use value::Value;
 
 // Convenience functions
 
 /// Parse and format a value in one call.
 ///
-/// This function caches recently used format codes for efficiency.
+/// This function caches recently used format codes in the [`global_cache`].
 pub fn format(value: f64, format_code: &str, opts: &FormatOptions) -> Result<String, ParseError> {
     let fmt = cache::get_or_parse(format_code)?;
     Ok(fmt.format(value, opts))
@@ -81,17 +137,89 @@ pub fn format(value: f64, format_code: &str, opts: &FormatOptions) -> Result<Str
 
 /// Format a value with default options (1900 date system, en-US locale).
 ///
-/// This function caches recently used format codes for efficiency.
+/// This function caches recently used format codes in the [`global_cache`].
 pub fn format_default(value: f64, format_code: &str) -> Result<String, ParseError> {
     let opts = FormatOptions::default();
     format(value, format_code, &opts)
 }
 
+/// Rewrite a format code in a canonical form.
+///
+/// Codes that differ only in spelling, such as token case, quoting or a
+/// redundant trailing section, normalize to the same string; see
+/// [`NumberFormat::to_canonical_code`]. Codes with errors are normalized from
+/// their [lenient parse](NumberFormat::parse_lenient).
+///
+/// # Examples
+/// ```
+/// assert_eq!(ssfmt::normalize("YYYY-MM-DD"), "yyyy-mm-dd");
+/// assert_eq!(ssfmt::normalize("\"$\"#,##0"), "$#,##0");
+/// assert_eq!(ssfmt::normalize("0;(0);0;@"), "0;(0)");
+/// assert_eq!(ssfmt::normalize("0;-0"), "0");
+/// ```
+pub fn normalize(format_code: &str) -> String {
+    NumberFormat::parse_lenient(format_code).0.to_canonical_code()
+}
+
+/// Parse a format code and format a text value in one call.
+///
+/// See [`NumberFormat::format_text`]. This function caches recently used
+/// format codes in the [`global_cache`].
+///
+/// # Examples
+/// ```
+/// use ssfmt::{format_text, FormatOptions};
+///
+/// let opts = FormatOptions::default();
+/// assert_eq!(format_text("abc", "0;0;0;\"ID-\"@", &opts).unwrap(), "ID-abc");
+/// ```
+pub fn format_text(
+    text: &str,
+    format_code: &str,
+    opts: &FormatOptions,
+) -> Result<String, ParseError> {
+    let fmt = cache::get_or_parse(format_code)?;
+    Ok(fmt.format_text(text, opts))
+}
+
+/// Parse a format code and format any cell value in one call.
+///
+/// See [`NumberFormat::format_value`]. This function caches recently used
+/// format codes in the [`global_cache`].
+///
+/// # Examples
+/// ```
+/// use ssfmt::{format_value, FormatOptions, Value};
+///
+/// let opts = FormatOptions::default();
+/// for (value, expected) in [(Value::Number(0.25), "25%"), (Value::Text("n/a"), "n/a")] {
+///     assert_eq!(format_value(&value, "0%", &opts).unwrap(), expected);
+/// }
+/// ```
+pub fn format_value(
+    value: &Value<'_>,
+    format_code: &str,
+    opts: &FormatOptions,
+) -> Result<String, ParseError> {
+    let fmt = cache::get_or_parse(format_code)?;
+    Ok(fmt.format_value(value, opts))
+}
+
 /// Format a value using a built-in format ID.
 ///
 /// Excel stores built-in format IDs (0-49) in .xlsx files. This function
 /// looks up the format code for the given ID and formats the value.
 ///
+/// IDs whose codes depend on the locale, such as the short date of ID 14,
+/// resolve by `opts.locale` as [`localized_format_code`] does; locales
+/// without an LCID get the en-US codes. The en-US codes come pre-parsed
+/// from [`builtin_format`]; only localized codes go through the
+/// [`global_cache`].
+///
+/// The date and time IDs (14-22, 45-47) read the serial in
+/// `opts.date_system`, so pass [`DateSystem::Date1904`] for workbooks that
+/// use the 1904 date system (`<workbookPr date1904="1"/>`).
+///
 /// # Arguments
 /// * `value` - The numeric value to format
 /// * `format_id` - The built-in format ID (e.g., 0 for "General", 14 for "m/d/yy")
@@ -103,19 +231,30 @@ pub fn format_default(value: f64, format_code: &str) -> Result<String, ParseErro
 ///
 /// # Examples
 /// ```
-/// use ssfmt::{format_with_id, FormatOptions};
+/// use ssfmt::{format_with_id, DateSystem, FormatOptions};
 ///
 /// let opts = FormatOptions::default();
 /// assert_eq!(format_with_id(1234.56, 0, &opts).unwrap(), "1234.56"); // General
 /// assert_eq!(format_with_id(1234.56, 2, &opts).unwrap(), "1234.56"); // 0.00
+///
+/// let opts_1904 = FormatOptions::builder().date_system(DateSystem::Date1904).build();
+/// assert_eq!(format_with_id(43830.0, 14, &opts_1904).unwrap(), "1/1/24");
 /// ```
 pub fn format_with_id(
     value: f64,
     format_id: u32,
     opts: &FormatOptions,
 ) -> Result<String, ParseError> {
-    let format_code = format_code_from_id(format_id)
-        .ok_or(ParseError::InvalidFormatId(format_id))?;
+    let format_code = match opts.locale.lcid() {
+        Some(lcid) => localized_format_code(format_id, lcid),
+        None => format_code_from_id(format_id),
+    }
+    .ok_or(ParseError::InvalidFormatId(format_id))?;
+    if format_code_from_id(format_id) == Some(format_code) {
+        if let Some(fmt) = builtin_format(format_id) {
+            return Ok(fmt.format(value, opts));
+        }
+    }
     format(value, format_code, opts)
 }
 
@@ -136,6 +275,80 @@ pub fn format_with_id_default(value: f64, format_id: u32) -> Result<String, Pars
     format_with_id(value, format_id, &opts)
 }
 
+/// Parse a format code once and lazily format a column of values with it.
+///
+/// The returned [`ColumnIter`] owns the parsed format and a copy of `opts`,
+/// and reuses one buffer for every value; use [`ColumnIter::next_to`] to
+/// write each cell into a CSV writer without a `String` per value. The
+/// format code is looked up in the [`global_cache`].
+///
+/// # Examples
+/// ```
+/// use ssfmt::{format_column, FormatOptions};
+///
+/// let opts = FormatOptions::default();
+/// let cells: Vec<String> = format_column(vec![0.5, 1234.0], "#,##0.0", &opts)
+///     .unwrap()
+///     .collect();
+/// assert_eq!(cells, ["0.5", "1,234.0"]);
+/// ```
+pub fn format_column<I>(
+    values: I,
+    format_code: &str,
+    opts: &FormatOptions,
+) -> Result<ColumnIter<I::IntoIter>, ParseError>
+where
+    I: IntoIterator<Item = f64>,
+{
+    let fmt = cache::get_or_parse(format_code)?;
+    Ok(ColumnIter::new(fmt, opts.clone(), values.into_iter()))
+}
+
+/// Format a [`Duration`](std::time::Duration) with default options.
+///
+/// The duration becomes a day-fraction serial (see
+/// [`date_serial::duration_to_serial`]), so elapsed formats such as
+/// `[h]:mm:ss` show it past 24 hours and `ss.000` shows fractions of a
+/// second. This function caches recently used format codes in the
+/// [`global_cache`].
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// let d = Duration::from_millis(30 * 3_600_000 + 5 * 60_000 + 9_250);
+/// assert_eq!(ssfmt::format_duration(d, "[h]:mm:ss").unwrap(), "30:05:09");
+/// assert_eq!(ssfmt::format_duration(d, "[m]:ss.0").unwrap(), "1805:09.3");
+/// ```
+pub fn format_duration(
+    duration: std::time::Duration,
+    format_code: &str,
+) -> Result<String, ParseError> {
+    format_default(date_serial::duration_to_serial(duration), format_code)
+}
+
+/// Convert formatted text back into a value.
+///
+/// See [`NumberFormat::parse_value`] for the accepted input. This function
+/// caches recently used format codes in the [`global_cache`].
+///
+/// # Examples
+/// ```
+/// use ssfmt::{parse_value, FormatOptions, Value};
+///
+/// let opts = FormatOptions::default();
+/// assert_eq!(parse_value("50%", "0%", &opts).unwrap(), Value::Number(0.5));
+/// assert_eq!(parse_value("n/a", "0.00", &opts).unwrap(), Value::Text("n/a"));
+/// ```
+pub fn parse_value<'a>(
+    text: &'a str,
+    format_code: &str,
+    opts: &FormatOptions,
+) -> Result<Value<'a>, ParseError> {
+    let fmt = cache::get_or_parse(format_code)?;
+    Ok(fmt.parse_value(text, opts))
+}
+
 // BigInt convenience functions (requires `bigint` feature)
 
 /// Re-export BigInt type for convenience (requires `bigint` feature).
@@ -148,7 +361,7 @@ pub use num_bigint::BigInt;
 /// standard formatting. For larger values, uses string-based formatting to
 /// preserve precision.
 ///
-/// This function caches recently used format codes for efficiency.
+/// This function caches recently used format codes in the [`global_cache`].
 ///
 /// # Example
 /// ```ignore
@@ -181,3 +394,49 @@ pub fn format_bigint_default(
     let opts = FormatOptions::default();
     format_bigint(value, format_code, &opts)
 }
+
+// Decimal convenience functions (requires `decimal` feature)
+
+/// Re-export Decimal type for convenience (requires `decimal` feature).
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
+/// Format a decimal value with a format code.
+///
+/// Number formats keep the exact decimal digits instead of routing the
+/// value through f64. See [`NumberFormat::format_decimal`].
+///
+/// This function caches recently used format codes in the [`global_cache`].
+///
+/// # Example
+/// ```
+/// use ssfmt::{format_decimal, Decimal, FormatOptions};
+/// use std::str::FromStr;
+///
+/// let big = Decimal::from_str("12345678901234567.89").unwrap();
+/// let opts = FormatOptions::default();
+/// let result = format_decimal(&big, "#,##0.00", &opts).unwrap();
+/// assert_eq!(result, "12,345,678,901,234,567.89");
+/// ```
+#[cfg(feature = "decimal")]
+pub fn format_decimal(
+    value: &rust_decimal::Decimal,
+    format_code: &str,
+    opts: &FormatOptions,
+) -> Result<String, ParseError> {
+    let fmt = cache::get_or_parse(format_code)?;
+    Ok(fmt.format_decimal(value, opts))
+}
+
+/// Format a decimal value with a format code using default options.
+///
+/// Convenience wrapper around `format_decimal` using default options
+/// (1900 date system, en-US locale).
+#[cfg(feature = "decimal")]
+pub fn format_decimal_default(
+    value: &rust_decimal::Decimal,
+    format_code: &str,
+) -> Result<String, ParseError> {
+    let opts = FormatOptions::default();
+    format_decimal(value, format_code, &opts)
+}
@@ -5,9 +5,19 @@
 pub struct Locale {
     pub decimal_separator: char,
     pub thousands_separator: char,
+    /// Replaces `/` in date formats when
+    /// [`FormatOptions::localize_separators`](crate::FormatOptions::localize_separators) is set
+    pub date_separator: char,
+    /// Replaces `:` in time formats when
+    /// [`FormatOptions::localize_separators`](crate::FormatOptions::localize_separators) is set
+    pub time_separator: char,
     pub currency_symbol: &'static str,
     pub am_string: &'static str,
     pub pm_string: &'static str,
+    /// How `TRUE` shows, such as `WAHR` in German
+    pub true_string: &'static str,
+    /// How `FALSE` shows, such as `FALSCH` in German
+    pub false_string: &'static str,
     pub month_names_short: [&'static str; 12],
     pub month_names_full: [&'static str; 12],
     pub day_names_short: [&'static str; 7],
@@ -23,39 +33,77 @@ impl Default for Locale {
 impl Locale {
     /// US English locale.
     pub fn en_us() -> Self {
-        Locale {
-            decimal_separator: '.',
-            thousands_separator: ',',
-            currency_symbol: "$",
-            am_string: "AM",
-            pm_string: "PM",
-            month_names_short: [
-                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-            ],
-            month_names_full: [
-                "January",
-                "February",
-                "March",
-                "April",
-                "May",
-                "June",
-                "July",
-                "August",
-                "September",
-                "October",
-                "November",
-                "December",
-            ],
-            day_names_short: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
-            day_names_full: [
-                "Sunday",
-                "Monday",
-                "Tuesday",
-                "Wednesday",
-                "Thursday",
-                "Friday",
-                "Saturday",
-            ],
-        }
+        super::data::EN_US
+    }
+
+    /// Look up a built-in locale by BCP 47 tag, e.g. `"de-DE"`.
+    ///
+    /// Matching is case-insensitive and `"de_DE"` is accepted too. A bare
+    /// language such as `"de"` resolves to that language's primary region.
+    /// Returns `None` for tags without built-in locale data.
+    ///
+    /// With the `cldr` feature, tags without built-in data are looked up in
+    /// the CLDR data embedded at build time, which covers every CLDR
+    /// locale. Built-in data wins where both exist, since it follows the
+    /// Windows regional settings Excel uses.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::Locale;
+    ///
+    /// let fr = Locale::from_tag("fr-FR").unwrap();
+    /// assert_eq!(fr.decimal_separator, ',');
+    /// assert_eq!(Locale::from_tag("fr"), Some(fr));
+    /// ```
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let locale = super::registry::lookup_tag(tag).cloned();
+        #[cfg(feature = "cldr")]
+        let locale =
+            locale.or_else(|| super::cldr::lookup_tag(tag).map(|entry| entry.locale.clone()));
+        locale
+    }
+
+    /// The zero digit of the native digits CLDR gives a locale by default,
+    /// such as `'٠'` for `"ar-EG"`, or `None` if it writes ASCII digits or
+    /// is not in CLDR. Requires the `cldr` feature.
+    ///
+    /// Digits are consecutive from the zero, as in `[NatNum1]` formats.
+    #[cfg(feature = "cldr")]
+    pub fn native_zero_for_tag(tag: &str) -> Option<char> {
+        super::cldr::lookup_tag(tag).and_then(|entry| entry.native_zero)
+    }
+
+    /// Look up the locale for a Windows locale identifier (LCID), as used in
+    /// `[$-409]` style format codes.
+    ///
+    /// Only the language/region part (the low 16 bits) of the LCID is used;
+    /// the calendar and numeral system bits are ignored. Regions without
+    /// their own data use their language's (`0x1407`, German in
+    /// Liechtenstein, gives German). Returns `None` for other LCIDs without
+    /// built-in locale data.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::Locale;
+    ///
+    /// let de = Locale::from_lcid(0x407).unwrap();
+    /// assert_eq!(de.month_names_full[2], "März");
+    /// assert!(Locale::from_lcid(0x7F).is_none());
+    /// ```
+    pub fn from_lcid(lcid: u32) -> Option<Self> {
+        super::registry::lookup_lcid(lcid).cloned()
+    }
+
+    /// The LCID of the built-in locale with this data, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::Locale;
+    ///
+    /// assert_eq!(Locale::from_tag("ko-KR").unwrap().lcid(), Some(0x412));
+    /// assert_eq!(Locale::en_us().lcid(), Some(0x409));
+    /// ```
+    pub fn lcid(&self) -> Option<u32> {
+        super::registry::lcid_of(self)
     }
 }
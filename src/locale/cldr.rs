@@ -0,0 +1,31 @@
+//! Locale data generated from CLDR at build time (`cldr` feature).
+//!
+//! `build.rs` reads the CLDR JSON packages named by `SSFMT_CLDR_DIR` and
+//! writes one entry per CLDR locale, sorted by lowercase tag.
+
+use super::builtin::Locale;
+
+/// A CLDR locale with its lowercase BCP 47 tag.
+pub(crate) struct CldrLocale {
+    pub(crate) tag: &'static str,
+    pub(crate) locale: Locale,
+    /// Zero of the locale's default digits, when they are not ASCII
+    pub(crate) native_zero: Option<char>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/cldr_locales.rs"));
+
+/// Look up the CLDR locale for a BCP 47 tag.
+///
+/// Matching is case-insensitive and accepts `_` as the separator. Tags
+/// without their own data fall back to a shorter tag, so `"is-IS"` finds
+/// `"is"` and `"sr-Latn-BA"` tries `"sr-Latn"` next.
+pub(crate) fn lookup_tag(tag: &str) -> Option<&'static CldrLocale> {
+    let mut tag = tag.replace('_', "-").to_ascii_lowercase();
+    loop {
+        if let Ok(index) = CLDR_LOCALES.binary_search_by(|entry| entry.tag.cmp(tag.as_str())) {
+            return Some(&CLDR_LOCALES[index]);
+        }
+        tag.truncate(tag.rfind('-')?);
+    }
+}
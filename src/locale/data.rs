@@ -0,0 +1,1143 @@
+//! Static locale data.
+//!
+//! Names follow the Windows regional settings Excel uses for each locale.
+//! Locales that share a language reuse the same name tables.
+
+use super::builtin::Locale;
+
+// English
+
+const EN_MONTHS_SHORT: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const EN_MONTHS_FULL: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const EN_DAYS_SHORT: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const EN_DAYS_FULL: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+pub(crate) const EN_US: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "$",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: EN_MONTHS_SHORT,
+    month_names_full: EN_MONTHS_FULL,
+    day_names_short: EN_DAYS_SHORT,
+    day_names_full: EN_DAYS_FULL,
+};
+
+pub(crate) const EN_GB: Locale = Locale {
+    currency_symbol: "£",
+    ..EN_US
+};
+
+pub(crate) const EN_AU: Locale = EN_US;
+
+pub(crate) const EN_CA: Locale = Locale {
+    date_separator: '-',
+    ..EN_US
+};
+
+// German
+
+const DE_MONTHS_SHORT: [&str; 12] = [
+    "Jan", "Feb", "Mrz", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+];
+const DE_MONTHS_FULL: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+const DE_DAYS_SHORT: [&str; 7] = ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"];
+const DE_DAYS_FULL: [&str; 7] = [
+    "Sonntag",
+    "Montag",
+    "Dienstag",
+    "Mittwoch",
+    "Donnerstag",
+    "Freitag",
+    "Samstag",
+];
+
+pub(crate) const DE_DE: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "€",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "WAHR",
+    false_string: "FALSCH",
+    month_names_short: DE_MONTHS_SHORT,
+    month_names_full: DE_MONTHS_FULL,
+    day_names_short: DE_DAYS_SHORT,
+    day_names_full: DE_DAYS_FULL,
+};
+
+pub(crate) const DE_AT: Locale = Locale {
+    thousands_separator: '\u{a0}',
+    month_names_short: [
+        "Jän", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+    ],
+    month_names_full: [
+        "Jänner",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    ..DE_DE
+};
+
+pub(crate) const DE_CH: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: '\'',
+    currency_symbol: "CHF",
+    ..DE_DE
+};
+
+// French
+
+const FR_MONTHS_SHORT: [&str; 12] = [
+    "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+    "déc.",
+];
+const FR_MONTHS_FULL: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+const FR_DAYS_SHORT: [&str; 7] = ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."];
+const FR_DAYS_FULL: [&str; 7] = [
+    "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+];
+
+pub(crate) const FR_FR: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "€",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "VRAI",
+    false_string: "FAUX",
+    month_names_short: FR_MONTHS_SHORT,
+    month_names_full: FR_MONTHS_FULL,
+    day_names_short: FR_DAYS_SHORT,
+    day_names_full: FR_DAYS_FULL,
+};
+
+pub(crate) const FR_CA: Locale = Locale {
+    date_separator: '-',
+    currency_symbol: "$",
+    ..FR_FR
+};
+
+pub(crate) const FR_CH: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: '\'',
+    date_separator: '.',
+    currency_symbol: "CHF",
+    ..FR_FR
+};
+
+// Spanish
+
+const ES_MONTHS_SHORT: [&str; 12] = [
+    "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+];
+const ES_MONTHS_FULL: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+const ES_DAYS_SHORT: [&str; 7] = ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"];
+const ES_DAYS_FULL: [&str; 7] = [
+    "domingo",
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+];
+
+pub(crate) const ES_ES: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "€",
+    am_string: "a. m.",
+    pm_string: "p. m.",
+    true_string: "VERDADERO",
+    false_string: "FALSO",
+    month_names_short: ES_MONTHS_SHORT,
+    month_names_full: ES_MONTHS_FULL,
+    day_names_short: ES_DAYS_SHORT,
+    day_names_full: ES_DAYS_FULL,
+};
+
+pub(crate) const ES_MX: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    currency_symbol: "$",
+    ..ES_ES
+};
+
+// Italian
+
+pub(crate) const IT_IT: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "€",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "VERO",
+    false_string: "FALSO",
+    month_names_short: [
+        "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+    ],
+    month_names_full: [
+        "gennaio",
+        "febbraio",
+        "marzo",
+        "aprile",
+        "maggio",
+        "giugno",
+        "luglio",
+        "agosto",
+        "settembre",
+        "ottobre",
+        "novembre",
+        "dicembre",
+    ],
+    day_names_short: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+    day_names_full: [
+        "domenica",
+        "lunedì",
+        "martedì",
+        "mercoledì",
+        "giovedì",
+        "venerdì",
+        "sabato",
+    ],
+};
+
+// Portuguese
+
+pub(crate) const PT_BR: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "R$",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "VERDADEIRO",
+    false_string: "FALSO",
+    month_names_short: [
+        "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+    ],
+    month_names_full: [
+        "janeiro",
+        "fevereiro",
+        "março",
+        "abril",
+        "maio",
+        "junho",
+        "julho",
+        "agosto",
+        "setembro",
+        "outubro",
+        "novembro",
+        "dezembro",
+    ],
+    day_names_short: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+    day_names_full: [
+        "domingo",
+        "segunda-feira",
+        "terça-feira",
+        "quarta-feira",
+        "quinta-feira",
+        "sexta-feira",
+        "sábado",
+    ],
+};
+
+pub(crate) const PT_PT: Locale = Locale {
+    thousands_separator: '\u{a0}',
+    currency_symbol: "€",
+    ..PT_BR
+};
+
+// Dutch
+
+pub(crate) const NL_NL: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '-',
+    time_separator: ':',
+    currency_symbol: "€",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "WAAR",
+    false_string: "ONWAAR",
+    month_names_short: [
+        "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+    ],
+    month_names_full: [
+        "januari",
+        "februari",
+        "maart",
+        "april",
+        "mei",
+        "juni",
+        "juli",
+        "augustus",
+        "september",
+        "oktober",
+        "november",
+        "december",
+    ],
+    day_names_short: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+    day_names_full: [
+        "zondag",
+        "maandag",
+        "dinsdag",
+        "woensdag",
+        "donderdag",
+        "vrijdag",
+        "zaterdag",
+    ],
+};
+
+// Polish
+
+pub(crate) const PL_PL: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "zł",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "PRAWDA",
+    false_string: "FAŁSZ",
+    month_names_short: [
+        "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+    ],
+    month_names_full: [
+        "styczeń",
+        "luty",
+        "marzec",
+        "kwiecień",
+        "maj",
+        "czerwiec",
+        "lipiec",
+        "sierpień",
+        "wrzesień",
+        "październik",
+        "listopad",
+        "grudzień",
+    ],
+    day_names_short: ["niedz.", "pon.", "wt.", "śr.", "czw.", "pt.", "sob."],
+    day_names_full: [
+        "niedziela",
+        "poniedziałek",
+        "wtorek",
+        "środa",
+        "czwartek",
+        "piątek",
+        "sobota",
+    ],
+};
+
+// Russian
+
+pub(crate) const RU_RU: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "₽",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "ИСТИНА",
+    false_string: "ЛОЖЬ",
+    month_names_short: [
+        "янв", "фев", "мар", "апр", "май", "июн", "июл", "авг", "сен", "окт", "ноя", "дек",
+    ],
+    month_names_full: [
+        "январь",
+        "февраль",
+        "март",
+        "апрель",
+        "май",
+        "июнь",
+        "июль",
+        "август",
+        "сентябрь",
+        "октябрь",
+        "ноябрь",
+        "декабрь",
+    ],
+    day_names_short: ["Вс", "Пн", "Вт", "Ср", "Чт", "Пт", "Сб"],
+    day_names_full: [
+        "воскресенье",
+        "понедельник",
+        "вторник",
+        "среда",
+        "четверг",
+        "пятница",
+        "суббота",
+    ],
+};
+
+// Ukrainian
+
+pub(crate) const UK_UA: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "₴",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "ІСТИНА",
+    false_string: "ХИБНІСТЬ",
+    month_names_short: [
+        "січ", "лют", "бер", "кві", "тра", "чер", "лип", "сер", "вер", "жов", "лис", "гру",
+    ],
+    month_names_full: [
+        "січень",
+        "лютий",
+        "березень",
+        "квітень",
+        "травень",
+        "червень",
+        "липень",
+        "серпень",
+        "вересень",
+        "жовтень",
+        "листопад",
+        "грудень",
+    ],
+    day_names_short: ["нд", "пн", "вт", "ср", "чт", "пт", "сб"],
+    day_names_full: [
+        "неділя",
+        "понеділок",
+        "вівторок",
+        "середа",
+        "четвер",
+        "пʼятниця",
+        "субота",
+    ],
+};
+
+// Czech
+
+pub(crate) const CS_CZ: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "Kč",
+    am_string: "dop.",
+    pm_string: "odp.",
+    true_string: "PRAVDA",
+    false_string: "NEPRAVDA",
+    month_names_short: [
+        "led", "úno", "bře", "dub", "kvě", "čvn", "čvc", "srp", "zář", "říj", "lis", "pro",
+    ],
+    month_names_full: [
+        "leden",
+        "únor",
+        "březen",
+        "duben",
+        "květen",
+        "červen",
+        "červenec",
+        "srpen",
+        "září",
+        "říjen",
+        "listopad",
+        "prosinec",
+    ],
+    day_names_short: ["ne", "po", "út", "st", "čt", "pá", "so"],
+    day_names_full: [
+        "neděle",
+        "pondělí",
+        "úterý",
+        "středa",
+        "čtvrtek",
+        "pátek",
+        "sobota",
+    ],
+};
+
+// Scandinavian
+
+pub(crate) const SV_SE: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '-',
+    time_separator: ':',
+    currency_symbol: "kr",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "SANT",
+    false_string: "FALSKT",
+    month_names_short: [
+        "jan", "feb", "mar", "apr", "maj", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+    ],
+    month_names_full: [
+        "januari",
+        "februari",
+        "mars",
+        "april",
+        "maj",
+        "juni",
+        "juli",
+        "augusti",
+        "september",
+        "oktober",
+        "november",
+        "december",
+    ],
+    day_names_short: ["sön", "mån", "tis", "ons", "tors", "fre", "lör"],
+    day_names_full: [
+        "söndag", "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag",
+    ],
+};
+
+pub(crate) const DA_DK: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '-',
+    time_separator: ':',
+    currency_symbol: "kr.",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "SAND",
+    false_string: "FALSK",
+    month_names_short: [
+        "jan", "feb", "mar", "apr", "maj", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+    ],
+    month_names_full: [
+        "januar",
+        "februar",
+        "marts",
+        "april",
+        "maj",
+        "juni",
+        "juli",
+        "august",
+        "september",
+        "oktober",
+        "november",
+        "december",
+    ],
+    day_names_short: ["søn", "man", "tir", "ons", "tor", "fre", "lør"],
+    day_names_full: [
+        "søndag", "mandag", "tirsdag", "onsdag", "torsdag", "fredag", "lørdag",
+    ],
+};
+
+pub(crate) const NB_NO: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "kr",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "SANN",
+    false_string: "USANN",
+    month_names_short: [
+        "jan", "feb", "mar", "apr", "mai", "jun", "jul", "aug", "sep", "okt", "nov", "des",
+    ],
+    month_names_full: [
+        "januar",
+        "februar",
+        "mars",
+        "april",
+        "mai",
+        "juni",
+        "juli",
+        "august",
+        "september",
+        "oktober",
+        "november",
+        "desember",
+    ],
+    day_names_short: ["søn", "man", "tir", "ons", "tor", "fre", "lør"],
+    day_names_full: [
+        "søndag", "mandag", "tirsdag", "onsdag", "torsdag", "fredag", "lørdag",
+    ],
+};
+
+pub(crate) const FI_FI: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: '.',
+    currency_symbol: "€",
+    am_string: "ap.",
+    pm_string: "ip.",
+    true_string: "TOSI",
+    false_string: "EPÄTOSI",
+    month_names_short: [
+        "tammi", "helmi", "maalis", "huhti", "touko", "kesä", "heinä", "elo", "syys", "loka",
+        "marras", "joulu",
+    ],
+    month_names_full: [
+        "tammikuu",
+        "helmikuu",
+        "maaliskuu",
+        "huhtikuu",
+        "toukokuu",
+        "kesäkuu",
+        "heinäkuu",
+        "elokuu",
+        "syyskuu",
+        "lokakuu",
+        "marraskuu",
+        "joulukuu",
+    ],
+    day_names_short: ["su", "ma", "ti", "ke", "to", "pe", "la"],
+    day_names_full: [
+        "sunnuntai",
+        "maanantai",
+        "tiistai",
+        "keskiviikko",
+        "torstai",
+        "perjantai",
+        "lauantai",
+    ],
+};
+
+// Turkish
+
+pub(crate) const TR_TR: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "₺",
+    am_string: "ÖÖ",
+    pm_string: "ÖS",
+    true_string: "DOĞRU",
+    false_string: "YANLIŞ",
+    month_names_short: [
+        "Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara",
+    ],
+    month_names_full: [
+        "Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül", "Ekim",
+        "Kasım", "Aralık",
+    ],
+    day_names_short: ["Paz", "Pzt", "Sal", "Çar", "Per", "Cum", "Cmt"],
+    day_names_full: [
+        "Pazar",
+        "Pazartesi",
+        "Salı",
+        "Çarşamba",
+        "Perşembe",
+        "Cuma",
+        "Cumartesi",
+    ],
+};
+
+// Greek
+
+pub(crate) const EL_GR: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "€",
+    am_string: "π.μ.",
+    pm_string: "μ.μ.",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: [
+        "Ιαν", "Φεβ", "Μαρ", "Απρ", "Μαΐ", "Ιουν", "Ιουλ", "Αυγ", "Σεπ", "Οκτ", "Νοε", "Δεκ",
+    ],
+    month_names_full: [
+        "Ιανουάριος",
+        "Φεβρουάριος",
+        "Μάρτιος",
+        "Απρίλιος",
+        "Μάιος",
+        "Ιούνιος",
+        "Ιούλιος",
+        "Αύγουστος",
+        "Σεπτέμβριος",
+        "Οκτώβριος",
+        "Νοέμβριος",
+        "Δεκέμβριος",
+    ],
+    day_names_short: ["Κυρ", "Δευ", "Τρί", "Τετ", "Πέμ", "Παρ", "Σάβ"],
+    day_names_full: [
+        "Κυριακή",
+        "Δευτέρα",
+        "Τρίτη",
+        "Τετάρτη",
+        "Πέμπτη",
+        "Παρασκευή",
+        "Σάββατο",
+    ],
+};
+
+// Hungarian
+
+pub(crate) const HU_HU: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '\u{a0}',
+    date_separator: '.',
+    time_separator: ':',
+    currency_symbol: "Ft",
+    am_string: "de.",
+    pm_string: "du.",
+    true_string: "IGAZ",
+    false_string: "HAMIS",
+    month_names_short: [
+        "jan.", "febr.", "márc.", "ápr.", "máj.", "jún.", "júl.", "aug.", "szept.", "okt.", "nov.",
+        "dec.",
+    ],
+    month_names_full: [
+        "január",
+        "február",
+        "március",
+        "április",
+        "május",
+        "június",
+        "július",
+        "augusztus",
+        "szeptember",
+        "október",
+        "november",
+        "december",
+    ],
+    day_names_short: ["V", "H", "K", "Sze", "Cs", "P", "Szo"],
+    day_names_full: [
+        "vasárnap",
+        "hétfő",
+        "kedd",
+        "szerda",
+        "csütörtök",
+        "péntek",
+        "szombat",
+    ],
+};
+
+// East Asian
+
+const CJK_MONTHS_NUMERIC: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+const ZH_MONTHS_FULL: [&str; 12] = [
+    "一月",
+    "二月",
+    "三月",
+    "四月",
+    "五月",
+    "六月",
+    "七月",
+    "八月",
+    "九月",
+    "十月",
+    "十一月",
+    "十二月",
+];
+const ZH_DAYS_FULL: [&str; 7] = [
+    "星期日",
+    "星期一",
+    "星期二",
+    "星期三",
+    "星期四",
+    "星期五",
+    "星期六",
+];
+
+pub(crate) const JA_JP: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "¥",
+    am_string: "午前",
+    pm_string: "午後",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: CJK_MONTHS_NUMERIC,
+    month_names_full: CJK_MONTHS_NUMERIC,
+    day_names_short: ["日", "月", "火", "水", "木", "金", "土"],
+    day_names_full: [
+        "日曜日",
+        "月曜日",
+        "火曜日",
+        "水曜日",
+        "木曜日",
+        "金曜日",
+        "土曜日",
+    ],
+};
+
+pub(crate) const ZH_CN: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "¥",
+    am_string: "上午",
+    pm_string: "下午",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: CJK_MONTHS_NUMERIC,
+    month_names_full: ZH_MONTHS_FULL,
+    day_names_short: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+    day_names_full: ZH_DAYS_FULL,
+};
+
+pub(crate) const ZH_TW: Locale = Locale {
+    currency_symbol: "NT$",
+    day_names_short: ["週日", "週一", "週二", "週三", "週四", "週五", "週六"],
+    ..ZH_CN
+};
+
+pub(crate) const KO_KR: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '-',
+    time_separator: ':',
+    currency_symbol: "₩",
+    am_string: "오전",
+    pm_string: "오후",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: [
+        "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+    ],
+    month_names_full: [
+        "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+    ],
+    day_names_short: ["일", "월", "화", "수", "목", "금", "토"],
+    day_names_full: [
+        "일요일",
+        "월요일",
+        "화요일",
+        "수요일",
+        "목요일",
+        "금요일",
+        "토요일",
+    ],
+};
+
+// Middle Eastern
+
+const AR_MONTHS: [&str; 12] = [
+    "يناير",
+    "فبراير",
+    "مارس",
+    "أبريل",
+    "مايو",
+    "يونيو",
+    "يوليو",
+    "أغسطس",
+    "سبتمبر",
+    "أكتوبر",
+    "نوفمبر",
+    "ديسمبر",
+];
+const AR_DAYS: [&str; 7] = [
+    "الأحد",
+    "الاثنين",
+    "الثلاثاء",
+    "الأربعاء",
+    "الخميس",
+    "الجمعة",
+    "السبت",
+];
+
+pub(crate) const AR_SA: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "ر.س.",
+    am_string: "ص",
+    pm_string: "م",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: AR_MONTHS,
+    month_names_full: AR_MONTHS,
+    day_names_short: AR_DAYS,
+    day_names_full: AR_DAYS,
+};
+
+pub(crate) const HE_IL: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "₪",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: [
+        "ינו", "פבר", "מרץ", "אפר", "מאי", "יונ", "יול", "אוג", "ספט", "אוק", "נוב", "דצמ",
+    ],
+    month_names_full: [
+        "ינואר",
+        "פברואר",
+        "מרץ",
+        "אפריל",
+        "מאי",
+        "יוני",
+        "יולי",
+        "אוגוסט",
+        "ספטמבר",
+        "אוקטובר",
+        "נובמבר",
+        "דצמבר",
+    ],
+    day_names_short: [
+        "יום א׳",
+        "יום ב׳",
+        "יום ג׳",
+        "יום ד׳",
+        "יום ה׳",
+        "יום ו׳",
+        "שבת",
+    ],
+    day_names_full: [
+        "יום ראשון",
+        "יום שני",
+        "יום שלישי",
+        "יום רביעי",
+        "יום חמישי",
+        "יום שישי",
+        "שבת",
+    ],
+};
+
+// South and Southeast Asian
+
+pub(crate) const TH_TH: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "฿",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: [
+        "ม.ค.",
+        "ก.พ.",
+        "มี.ค.",
+        "เม.ย.",
+        "พ.ค.",
+        "มิ.ย.",
+        "ก.ค.",
+        "ส.ค.",
+        "ก.ย.",
+        "ต.ค.",
+        "พ.ย.",
+        "ธ.ค.",
+    ],
+    month_names_full: [
+        "มกราคม",
+        "กุมภาพันธ์",
+        "มีนาคม",
+        "เมษายน",
+        "พฤษภาคม",
+        "มิถุนายน",
+        "กรกฎาคม",
+        "สิงหาคม",
+        "กันยายน",
+        "ตุลาคม",
+        "พฤศจิกายน",
+        "ธันวาคม",
+    ],
+    day_names_short: ["อา.", "จ.", "อ.", "พ.", "พฤ.", "ศ.", "ส."],
+    day_names_full: ["อาทิตย์", "จันทร์", "อังคาร", "พุธ", "พฤหัสบดี", "ศุกร์", "เสาร์"],
+};
+
+const HI_MONTHS: [&str; 12] = [
+    "जनवरी",
+    "फ़रवरी",
+    "मार्च",
+    "अप्रैल",
+    "मई",
+    "जून",
+    "जुलाई",
+    "अगस्त",
+    "सितंबर",
+    "अक्तूबर",
+    "नवंबर",
+    "दिसंबर",
+];
+
+pub(crate) const HI_IN: Locale = Locale {
+    decimal_separator: '.',
+    thousands_separator: ',',
+    date_separator: '-',
+    time_separator: ':',
+    currency_symbol: "₹",
+    am_string: "पूर्वाह्न",
+    pm_string: "अपराह्न",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: HI_MONTHS,
+    month_names_full: HI_MONTHS,
+    day_names_short: ["रवि", "सोम", "मंगल", "बुध", "गुरु", "शुक्र", "शनि"],
+    day_names_full: [
+        "रविवार",
+        "सोमवार",
+        "मंगलवार",
+        "बुधवार",
+        "गुरुवार",
+        "शुक्रवार",
+        "शनिवार",
+    ],
+};
+
+pub(crate) const ID_ID: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '/',
+    time_separator: '.',
+    currency_symbol: "Rp",
+    am_string: "AM",
+    pm_string: "PM",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: [
+        "Jan", "Feb", "Mar", "Apr", "Mei", "Jun", "Jul", "Agu", "Sep", "Okt", "Nov", "Des",
+    ],
+    month_names_full: [
+        "Januari",
+        "Februari",
+        "Maret",
+        "April",
+        "Mei",
+        "Juni",
+        "Juli",
+        "Agustus",
+        "September",
+        "Oktober",
+        "November",
+        "Desember",
+    ],
+    day_names_short: ["Min", "Sen", "Sel", "Rab", "Kam", "Jum", "Sab"],
+    day_names_full: [
+        "Minggu", "Senin", "Selasa", "Rabu", "Kamis", "Jumat", "Sabtu",
+    ],
+};
+
+pub(crate) const VI_VN: Locale = Locale {
+    decimal_separator: ',',
+    thousands_separator: '.',
+    date_separator: '/',
+    time_separator: ':',
+    currency_symbol: "₫",
+    am_string: "SA",
+    pm_string: "CH",
+    true_string: "TRUE",
+    false_string: "FALSE",
+    month_names_short: [
+        "Thg1", "Thg2", "Thg3", "Thg4", "Thg5", "Thg6", "Thg7", "Thg8", "Thg9", "Thg10", "Thg11",
+        "Thg12",
+    ],
+    month_names_full: [
+        "Tháng 1",
+        "Tháng 2",
+        "Tháng 3",
+        "Tháng 4",
+        "Tháng 5",
+        "Tháng 6",
+        "Tháng 7",
+        "Tháng 8",
+        "Tháng 9",
+        "Tháng 10",
+        "Tháng 11",
+        "Tháng 12",
+    ],
+    day_names_short: ["CN", "T2", "T3", "T4", "T5", "T6", "T7"],
+    day_names_full: [
+        "Chủ Nhật",
+        "Thứ Hai",
+        "Thứ Ba",
+        "Thứ Tư",
+        "Thứ Năm",
+        "Thứ Sáu",
+        "Thứ Bảy",
+    ],
+};
@@ -0,0 +1,75 @@
+//! Native digit data for digit substitution.
+//!
+//! Every script listed here encodes its digits as ten consecutive code
+//! points, so a digit set is identified by its zero.
+
+/// Zero digit for the numeral-system byte of an LCID (`[$-2010401]` → 0x02).
+///
+/// Returns `None` for Western digits (0x00, 0x01) and for unknown systems.
+pub(crate) fn numeral_system_zero(lcid: u32) -> Option<char> {
+    match lcid >> 24 {
+        0x02 => Some('\u{0660}'), // Arabic-Indic
+        0x03 => Some('\u{06F0}'), // Extended Arabic-Indic
+        0x04 => Some('\u{0966}'), // Devanagari
+        0x05 => Some('\u{09E6}'), // Bengali
+        0x06 => Some('\u{0A66}'), // Gurmukhi
+        0x07 => Some('\u{0AE6}'), // Gujarati
+        0x08 => Some('\u{0B66}'), // Oriya
+        0x09 => Some('\u{0BE6}'), // Tamil
+        0x0A => Some('\u{0C66}'), // Telugu
+        0x0B => Some('\u{0CE6}'), // Kannada
+        0x0C => Some('\u{0D66}'), // Malayalam
+        0x0D => Some('\u{0E50}'), // Thai
+        0x0E => Some('\u{0ED0}'), // Lao
+        0x0F => Some('\u{0F20}'), // Tibetan
+        0x10 => Some('\u{1040}'), // Myanmar
+        0x12 => Some('\u{17E0}'), // Khmer
+        0x13 => Some('\u{1810}'), // Mongolian
+        _ => None,
+    }
+}
+
+/// Zero digit of the native digits for the language of an LCID.
+///
+/// Returns `None` for languages that write Western digits natively.
+pub(crate) fn native_zero(lcid: u32) -> Option<char> {
+    match lcid & 0x3FF {
+        0x01 => Some('\u{0660}'),                      // Arabic
+        0x29 | 0x8C => Some('\u{06F0}'),               // Persian, Dari
+        0x39 | 0x4E | 0x61 | 0x57 => Some('\u{0966}'), // Hindi, Marathi, Nepali, Konkani
+        0x45 => Some('\u{09E6}'),                      // Bengali
+        0x46 => Some('\u{0A66}'),                      // Punjabi
+        0x47 => Some('\u{0AE6}'),                      // Gujarati
+        0x48 => Some('\u{0B66}'),                      // Odia
+        0x49 => Some('\u{0BE6}'),                      // Tamil
+        0x4A => Some('\u{0C66}'),                      // Telugu
+        0x4B => Some('\u{0CE6}'),                      // Kannada
+        0x4C => Some('\u{0D66}'),                      // Malayalam
+        0x1E => Some('\u{0E50}'),                      // Thai
+        0x54 => Some('\u{0ED0}'),                      // Lao
+        0x51 => Some('\u{0F20}'),                      // Tibetan
+        0x55 => Some('\u{1040}'),                      // Burmese
+        0x53 => Some('\u{17E0}'),                      // Khmer
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeral_system_zero() {
+        assert_eq!(numeral_system_zero(0x0201_0401), Some('٠'));
+        assert_eq!(numeral_system_zero(0x0D00_041E), Some('๐'));
+        assert_eq!(numeral_system_zero(0x0000_0401), None);
+        assert_eq!(numeral_system_zero(0x0101_0401), None);
+    }
+
+    #[test]
+    fn test_native_zero() {
+        assert_eq!(native_zero(0x0401), Some('٠'));
+        assert_eq!(native_zero(0x0439), Some('०'));
+        assert_eq!(native_zero(0x0409), None);
+    }
+}
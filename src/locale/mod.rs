@@ -1,5 +1,10 @@
 //! Locale support for formatting.
 
 mod builtin;
+#[cfg(feature = "cldr")]
+mod cldr;
+mod data;
+pub(crate) mod digits;
+pub(crate) mod registry;
 
 pub use builtin::Locale;
@@ -0,0 +1,140 @@
+//! Locale registry keyed by Windows locale identifier (LCID) and BCP 47 tag.
+//!
+//! Format codes can carry an LCID in a locale tag such as `[$-411]` or
+//! `[$€-407]`. The low 16 bits are the language/region identifier; higher
+//! bits select calendar and numeral systems (e.g. `[$-2010401]`).
+
+use super::builtin::Locale;
+use super::data::*;
+
+/// Built-in locales as (LCID, BCP 47 tag, data).
+///
+/// The first entry for each language is the one picked for a bare language
+/// tag such as `"de"`.
+static LOCALES: &[(u32, &str, &Locale)] = &[
+    (0x0409, "en-US", &EN_US),
+    (0x0809, "en-GB", &EN_GB),
+    (0x0C09, "en-AU", &EN_AU),
+    (0x1009, "en-CA", &EN_CA),
+    (0x0407, "de-DE", &DE_DE),
+    (0x0C07, "de-AT", &DE_AT),
+    (0x0807, "de-CH", &DE_CH),
+    (0x040C, "fr-FR", &FR_FR),
+    (0x0C0C, "fr-CA", &FR_CA),
+    (0x100C, "fr-CH", &FR_CH),
+    (0x0C0A, "es-ES", &ES_ES),
+    (0x080A, "es-MX", &ES_MX),
+    (0x0410, "it-IT", &IT_IT),
+    (0x0416, "pt-BR", &PT_BR),
+    (0x0816, "pt-PT", &PT_PT),
+    (0x0413, "nl-NL", &NL_NL),
+    (0x0415, "pl-PL", &PL_PL),
+    (0x0419, "ru-RU", &RU_RU),
+    (0x0422, "uk-UA", &UK_UA),
+    (0x0405, "cs-CZ", &CS_CZ),
+    (0x041D, "sv-SE", &SV_SE),
+    (0x0406, "da-DK", &DA_DK),
+    (0x0414, "nb-NO", &NB_NO),
+    (0x040B, "fi-FI", &FI_FI),
+    (0x041F, "tr-TR", &TR_TR),
+    (0x0408, "el-GR", &EL_GR),
+    (0x040E, "hu-HU", &HU_HU),
+    (0x0411, "ja-JP", &JA_JP),
+    (0x0804, "zh-CN", &ZH_CN),
+    (0x0404, "zh-TW", &ZH_TW),
+    (0x0412, "ko-KR", &KO_KR),
+    (0x0401, "ar-SA", &AR_SA),
+    (0x040D, "he-IL", &HE_IL),
+    (0x041E, "th-TH", &TH_TH),
+    (0x0439, "hi-IN", &HI_IN),
+    (0x0421, "id-ID", &ID_ID),
+    (0x042A, "vi-VN", &VI_VN),
+];
+
+/// Look up the built-in locale for an LCID.
+///
+/// Regional variants without their own entry use the language's primary
+/// locale, so `[$-1407]` (German, Liechtenstein) gets German names.
+/// Language-neutral IDs like `0x0007` match nothing.
+pub(crate) fn lookup_lcid(lcid: u32) -> Option<&'static Locale> {
+    let language_id = lcid & 0xFFFF;
+    let primary_language = language_id & 0x3FF;
+    LOCALES
+        .iter()
+        .find(|(id, _, _)| *id == language_id)
+        .or_else(|| {
+            // The sublanguage lives in the upper 6 bits
+            if language_id <= 0x3FF {
+                return None;
+            }
+            LOCALES
+                .iter()
+                .find(|(id, _, _)| id & 0x3FF == primary_language)
+        })
+        .map(|(_, _, locale)| *locale)
+}
+
+/// Find the LCID of the first built-in locale whose data equals `locale`.
+pub(crate) fn lcid_of(locale: &Locale) -> Option<u32> {
+    LOCALES
+        .iter()
+        .find(|(_, _, data)| *data == locale)
+        .map(|(lcid, _, _)| *lcid)
+}
+
+/// Look up the built-in locale for a BCP 47 tag such as `"de-DE"`.
+///
+/// Matching is case-insensitive and accepts `_` as the separator. A bare
+/// language (`"de"`) resolves to that language's primary locale.
+pub(crate) fn lookup_tag(tag: &str) -> Option<&'static Locale> {
+    let tag = tag.replace('_', "-");
+    LOCALES
+        .iter()
+        .find(|(_, t, _)| t.eq_ignore_ascii_case(&tag))
+        .or_else(|| {
+            LOCALES.iter().find(|(_, t, _)| {
+                t.split('-')
+                    .next()
+                    .is_some_and(|lang| lang.eq_ignore_ascii_case(&tag))
+            })
+        })
+        .map(|(_, _, locale)| *locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_lcid_ignores_calendar_bits() {
+        assert_eq!(lookup_lcid(0x409), Some(&EN_US));
+        assert_eq!(lookup_lcid(0x0001_0411), Some(&JA_JP));
+        assert_eq!(lookup_lcid(0x0001), None);
+    }
+
+    #[test]
+    fn test_lookup_lcid_falls_back_to_language() {
+        assert_eq!(lookup_lcid(0x1407), Some(&DE_DE));
+        assert_eq!(lookup_lcid(0x2C0A), Some(&ES_ES));
+        assert_eq!(lookup_lcid(0x0007), None);
+        assert_eq!(lookup_lcid(0x047F), None);
+    }
+
+    #[test]
+    fn test_lookup_tag() {
+        assert_eq!(lookup_tag("de-DE"), Some(&DE_DE));
+        assert_eq!(lookup_tag("pt_br"), Some(&PT_BR));
+        assert_eq!(lookup_tag("fr"), Some(&FR_FR));
+        assert_eq!(lookup_tag("xx-YY"), None);
+    }
+
+    #[test]
+    fn test_table_is_unique() {
+        for (i, (lcid, tag, _)) in LOCALES.iter().enumerate() {
+            for (other_lcid, other_tag, _) in &LOCALES[i + 1..] {
+                assert_ne!(lcid, other_lcid);
+                assert_ne!(tag, other_tag);
+            }
+        }
+    }
+}
@@ -22,11 +22,336 @@ impl DateSystem {
     }
 }
 
+/// How a `*` fill renders when no cell width is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillFallback {
+    /// Drop the fill entirely (the historical behavior)
+    #[default]
+    Omit,
+    /// Emit the fill character once
+    Single,
+}
+
+/// How `_x` skips and `*` fills are measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutMode {
+    /// Every skip renders as one space and widths count characters
+    #[default]
+    Plain,
+    /// Skips reserve the column width of their character (two spaces for
+    /// wide East Asian characters) and widths count terminal columns
+    Monospace,
+}
+
+/// What to show when a value can't be displayed: a date serial outside
+/// Excel's range, or a number wider than `cell_width`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowBehavior {
+    /// Show nothing for out-of-range dates and let wide values overflow the
+    /// cell (the historical behavior)
+    #[default]
+    Empty,
+    /// Show `#` marks as Excel does in a cell: as many as `cell_width` when
+    /// it is set, otherwise this many
+    Hashes(usize),
+    /// Fail with [`FormatError::DateOutOfRange`] or
+    /// [`FormatError::CellOverflow`]; `format` falls back to General
+    ///
+    /// [`FormatError::DateOutOfRange`]: crate::FormatError::DateOutOfRange
+    /// [`FormatError::CellOverflow`]: crate::FormatError::CellOverflow
+    Error,
+}
+
+/// How values exactly halfway between two displayed digits are rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Round halves away from zero: 2.5 → 3, -2.5 → -3 (Excel)
+    #[default]
+    HalfAwayFromZero,
+    /// Round halves to the even neighbor: 2.5 → 2, 3.5 → 4 (banker's rounding)
+    HalfEven,
+}
+
+impl RoundingMode {
+    /// Round `value` to an integer.
+    pub(crate) fn round(self, value: f64) -> f64 {
+        match self {
+            RoundingMode::HalfAwayFromZero => value.round(),
+            RoundingMode::HalfEven => value.round_ties_even(),
+        }
+    }
+
+    /// Whether dropping digits starting with `first_dropped` rounds the
+    /// kept digits up. `rest_nonzero` tells whether any later dropped digit
+    /// is nonzero, and `last_kept` is the last digit kept.
+    pub(crate) fn rounds_up(self, first_dropped: u8, rest_nonzero: bool, last_kept: u8) -> bool {
+        match first_dropped.cmp(&5) {
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => match self {
+                RoundingMode::HalfAwayFromZero => true,
+                RoundingMode::HalfEven => rest_nonzero || last_kept % 2 == 1,
+            },
+        }
+    }
+}
+
+/// How `B2` formats turn Gregorian dates into Hijri dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HijriAlgorithm {
+    /// The arithmetic (Kuwaiti) calendar, which can be a day off the
+    /// observed one
+    #[default]
+    Tabular,
+    /// Saudi Arabia's official Umm al-Qura calendar, for dates from 1900
+    /// to 2100 (others fall back to `Tabular`)
+    UmmAlQura,
+}
+
+/// How a date and time with a time zone becomes a serial number, which
+/// has none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimezonePolicy {
+    /// Use the wall-clock time in the value's own time zone, dropping the
+    /// offset: 09:00+02:00 shows as 09:00
+    #[default]
+    Local,
+    /// Convert to UTC first: 09:00+02:00 shows as 07:00
+    Utc,
+    /// Refuse values with a time zone; `try_format_value` fails with
+    /// [`FormatError::TypeMismatch`] and `format_value` falls back to
+    /// General on the UTC serial
+    ///
+    /// [`FormatError::TypeMismatch`]: crate::FormatError::TypeMismatch
+    Error,
+}
+
+/// What parsing does with sections after the fourth. Excel refuses them
+/// in the "Format Cells" dialog, but they turn up in files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtraSections {
+    /// Keep the first four sections and ignore the rest (the historical
+    /// behavior)
+    #[default]
+    Truncate,
+    /// Fail with [`ParseError::TooManySections`]
+    ///
+    /// [`ParseError::TooManySections`]: crate::ParseError::TooManySections
+    Error,
+}
+
+/// Size limits that parsing enforces with
+/// [`ParseError::FormatTooComplex`], so a hostile workbook cannot make the
+/// parser allocate without bound.
+///
+/// The defaults are far above anything Excel writes; its "Format Cells"
+/// dialog stops at 255 characters.
+///
+/// [`ParseError::FormatTooComplex`]: crate::ParseError::FormatTooComplex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Longest format code, in bytes
+    pub max_length: usize,
+    /// Most parts (placeholders, literals, date codes) in one section
+    pub max_parts_per_section: usize,
+    /// Longest quoted string, in bytes
+    pub max_quoted_length: usize,
+}
+
+impl ParseLimits {
+    /// No limits at all, for format codes from a trusted source.
+    pub const UNLIMITED: ParseLimits = ParseLimits {
+        max_length: usize::MAX,
+        max_parts_per_section: usize::MAX,
+        max_quoted_length: usize::MAX,
+    };
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits {
+            max_length: 4096,
+            max_parts_per_section: 1024,
+            max_quoted_length: 1024,
+        }
+    }
+}
+
+/// Options for parsing format codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseOptions {
+    /// What to do with sections after the fourth.
+    pub extra_sections: ExtraSections,
+    /// Size limits on the format code.
+    pub limits: ParseLimits,
+}
+
 /// Options for formatting values.
+///
+/// Start from [`FormatOptions::default`] or [`FormatOptions::builder`];
+/// the struct is non-exhaustive so options can be added without breaking
+/// callers.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{DateSystem, FormatOptions, Locale, NumberFormat};
+///
+/// let opts = FormatOptions::builder()
+///     .date_system(DateSystem::Date1904)
+///     .locale(Locale::from_tag("fr-FR").unwrap())
+///     .build();
+/// let fmt = NumberFormat::parse("d mmmm yyyy").unwrap();
+/// assert_eq!(fmt.format(0.0, &opts), "1 janvier 1904");
+/// ```
 #[derive(Debug, Clone, Default)]
+#[non_exhaustive]
 pub struct FormatOptions {
     /// The date system to use for serial number conversion.
     pub date_system: DateSystem,
     /// The locale for formatting.
     pub locale: Locale,
+    /// Target cell width in characters. A `*` fill repeats its character
+    /// until the output reaches this width, and General numbers round off
+    /// digits to fit in it.
+    pub cell_width: Option<usize>,
+    /// How a `*` fill renders when `cell_width` is `None`.
+    pub fill_fallback: FillFallback,
+    /// How skips and the cell width are measured.
+    pub layout: LayoutMode,
+    /// How halves are rounded in numbers, percents, scientific mantissas
+    /// and fractional seconds.
+    pub rounding: RoundingMode,
+    /// Keep the minus sign on negative values that display as zero, so
+    /// -0.004 shows as `-0.00` with `0.00`, and sign -0.0. Excel drops it.
+    pub show_negative_zero: bool,
+    /// Show negative values in elapsed time formats as signed durations,
+    /// so -1.5 hours shows as `-1:30` with `[h]:mm`, as in the 1904 date
+    /// system and LibreOffice. Excel shows nothing (`####` in a cell).
+    pub allow_negative_durations: bool,
+    /// What out-of-range dates and numbers wider than `cell_width` show.
+    pub overflow: OverflowBehavior,
+    /// The Hijri calendar used by `B2` formats.
+    pub hijri_algorithm: HijriAlgorithm,
+    /// How dates and times with a time zone are converted to serials.
+    pub timezone_policy: TimezonePolicy,
+    /// Show `/` and `:` in date and time sections as the locale's
+    /// [`date_separator`](Locale::date_separator) and
+    /// [`time_separator`](Locale::time_separator), as Excel does with the
+    /// Windows regional settings, so `m/d/yy` shows `3.15.24` in German.
+    /// Only backslash-escaped characters are kept as written.
+    pub localize_separators: bool,
+}
+
+impl FormatOptions {
+    /// A builder starting from the default options.
+    pub fn builder() -> FormatOptionsBuilder {
+        FormatOptionsBuilder::default()
+    }
+
+    /// Override the decimal and thousands separators, keeping the rest of
+    /// the locale (month and day names, AM/PM strings, currency).
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let opts = FormatOptions::default().with_separators(',', '.');
+    /// let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    /// assert_eq!(fmt.format(1234.56, &opts), "1.234,56");
+    ///
+    /// let fmt = NumberFormat::parse("mmmm d").unwrap();
+    /// assert_eq!(fmt.format(46031.0, &opts), "January 9");
+    /// ```
+    pub fn with_separators(mut self, decimal: char, thousands: char) -> Self {
+        self.locale.decimal_separator = decimal;
+        self.locale.thousands_separator = thousands;
+        self
+    }
+}
+
+/// Builds [`FormatOptions`], from [`FormatOptions::builder`].
+///
+/// Options not set keep their defaults.
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptionsBuilder {
+    options: FormatOptions,
+}
+
+impl FormatOptionsBuilder {
+    /// Set [`FormatOptions::date_system`].
+    pub fn date_system(mut self, date_system: DateSystem) -> Self {
+        self.options.date_system = date_system;
+        self
+    }
+
+    /// Set [`FormatOptions::locale`].
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.options.locale = locale;
+        self
+    }
+
+    /// Set [`FormatOptions::cell_width`].
+    pub fn cell_width(mut self, width: usize) -> Self {
+        self.options.cell_width = Some(width);
+        self
+    }
+
+    /// Set [`FormatOptions::fill_fallback`].
+    pub fn fill_fallback(mut self, fill_fallback: FillFallback) -> Self {
+        self.options.fill_fallback = fill_fallback;
+        self
+    }
+
+    /// Set [`FormatOptions::layout`].
+    pub fn layout(mut self, layout: LayoutMode) -> Self {
+        self.options.layout = layout;
+        self
+    }
+
+    /// Set [`FormatOptions::rounding`].
+    pub fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.options.rounding = rounding;
+        self
+    }
+
+    /// Set [`FormatOptions::show_negative_zero`].
+    pub fn show_negative_zero(mut self, show: bool) -> Self {
+        self.options.show_negative_zero = show;
+        self
+    }
+
+    /// Set [`FormatOptions::allow_negative_durations`].
+    pub fn allow_negative_durations(mut self, allow: bool) -> Self {
+        self.options.allow_negative_durations = allow;
+        self
+    }
+
+    /// Set [`FormatOptions::overflow`].
+    pub fn overflow(mut self, overflow: OverflowBehavior) -> Self {
+        self.options.overflow = overflow;
+        self
+    }
+
+    /// Set [`FormatOptions::hijri_algorithm`].
+    pub fn hijri_algorithm(mut self, hijri_algorithm: HijriAlgorithm) -> Self {
+        self.options.hijri_algorithm = hijri_algorithm;
+        self
+    }
+
+    /// Set [`FormatOptions::timezone_policy`].
+    pub fn timezone_policy(mut self, timezone_policy: TimezonePolicy) -> Self {
+        self.options.timezone_policy = timezone_policy;
+        self
+    }
+
+    /// Set [`FormatOptions::localize_separators`].
+    pub fn localize_separators(mut self, localize: bool) -> Self {
+        self.options.localize_separators = localize;
+        self
+    }
+
+    /// The options.
+    pub fn build(self) -> FormatOptions {
+        self.options
+    }
 }
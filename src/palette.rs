@@ -0,0 +1,163 @@
+//! Resolving section colors to RGB.
+
+use crate::ast::{Color, NamedColor};
+
+/// The 56 colors `[Color1]`-`[Color56]` refer to.
+///
+/// Workbooks may replace Excel's palette: `.xls` files with a `PALETTE`
+/// record of 56 colors, `.xlsx` files with `<indexedColors>` in
+/// `styles.xml`. Named colors are palette entries too (`[Red]` is
+/// `[Color3]`), so they follow a replaced palette as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: [(u8, u8, u8); 56],
+}
+
+impl Palette {
+    /// Excel's default palette.
+    pub const EXCEL: Palette = Palette {
+        colors: [
+            (0x00, 0x00, 0x00),
+            (0xFF, 0xFF, 0xFF),
+            (0xFF, 0x00, 0x00),
+            (0x00, 0xFF, 0x00),
+            (0x00, 0x00, 0xFF),
+            (0xFF, 0xFF, 0x00),
+            (0xFF, 0x00, 0xFF),
+            (0x00, 0xFF, 0xFF),
+            (0x80, 0x00, 0x00),
+            (0x00, 0x80, 0x00),
+            (0x00, 0x00, 0x80),
+            (0x80, 0x80, 0x00),
+            (0x80, 0x00, 0x80),
+            (0x00, 0x80, 0x80),
+            (0xC0, 0xC0, 0xC0),
+            (0x80, 0x80, 0x80),
+            (0x99, 0x99, 0xFF),
+            (0x99, 0x33, 0x66),
+            (0xFF, 0xFF, 0xCC),
+            (0xCC, 0xFF, 0xFF),
+            (0x66, 0x00, 0x66),
+            (0xFF, 0x80, 0x80),
+            (0x00, 0x66, 0xCC),
+            (0xCC, 0xCC, 0xFF),
+            (0x00, 0x00, 0x80),
+            (0xFF, 0x00, 0xFF),
+            (0xFF, 0xFF, 0x00),
+            (0x00, 0xFF, 0xFF),
+            (0x80, 0x00, 0x80),
+            (0x80, 0x00, 0x00),
+            (0x00, 0x80, 0x80),
+            (0x00, 0x00, 0xFF),
+            (0x00, 0xCC, 0xFF),
+            (0xCC, 0xFF, 0xFF),
+            (0xCC, 0xFF, 0xCC),
+            (0xFF, 0xFF, 0x99),
+            (0x99, 0xCC, 0xFF),
+            (0xFF, 0x99, 0xCC),
+            (0xCC, 0x99, 0xFF),
+            (0xFF, 0xCC, 0x99),
+            (0x33, 0x66, 0xFF),
+            (0x33, 0xCC, 0xCC),
+            (0x99, 0xCC, 0x00),
+            (0xFF, 0xCC, 0x00),
+            (0xFF, 0x99, 0x00),
+            (0xFF, 0x66, 0x00),
+            (0x66, 0x66, 0x99),
+            (0x96, 0x96, 0x96),
+            (0x00, 0x33, 0x66),
+            (0x33, 0x99, 0x66),
+            (0x00, 0x33, 0x00),
+            (0x33, 0x33, 0x00),
+            (0x99, 0x33, 0x00),
+            (0x99, 0x33, 0x66),
+            (0x33, 0x33, 0x99),
+            (0x33, 0x33, 0x33),
+        ],
+    };
+
+    /// A palette from the 56 colors of an `.xls` `PALETTE` record.
+    pub fn new(colors: [(u8, u8, u8); 56]) -> Self {
+        Palette { colors }
+    }
+
+    /// A palette from the `<indexedColors>` of an `.xlsx` `styles.xml`.
+    ///
+    /// That list has 64 entries: eight fixed colors, then the 56 the
+    /// palette replaces. Entries past the end of a shorter list keep
+    /// Excel's colors.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::ast::Color;
+    /// use ssfmt::Palette;
+    ///
+    /// let mut indexed = vec![(0, 0, 0); 10];
+    /// indexed[9] = (0x12, 0x34, 0x56);
+    /// let palette = Palette::from_indexed_colors(&indexed);
+    /// assert_eq!(Color::Indexed(2).to_rgb_in(&palette), (0x12, 0x34, 0x56));
+    /// assert_eq!(Color::Indexed(3).to_rgb_in(&palette), (0xFF, 0x00, 0x00));
+    /// ```
+    pub fn from_indexed_colors(indexed_colors: &[(u8, u8, u8)]) -> Self {
+        let mut palette = Palette::EXCEL;
+        for (color, &rgb) in palette.colors.iter_mut().zip(indexed_colors.iter().skip(8)) {
+            *color = rgb;
+        }
+        palette
+    }
+
+    /// The color for `[ColorN]`, or `None` outside 1-56.
+    pub fn get(&self, index: u8) -> Option<(u8, u8, u8)> {
+        let i = usize::from(index).checked_sub(1)?;
+        self.colors.get(i).copied()
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::EXCEL
+    }
+}
+
+impl NamedColor {
+    /// The palette entry the color name stands for.
+    fn palette_index(self) -> u8 {
+        match self {
+            NamedColor::Black => 1,
+            NamedColor::White => 2,
+            NamedColor::Red => 3,
+            NamedColor::Green => 4,
+            NamedColor::Blue => 5,
+            NamedColor::Yellow => 6,
+            NamedColor::Magenta => 7,
+            NamedColor::Cyan => 8,
+        }
+    }
+}
+
+impl Color {
+    /// The color as red, green and blue in Excel's default palette.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::ast::{Color, NamedColor};
+    ///
+    /// assert_eq!(Color::Named(NamedColor::Red).to_rgb(), (0xFF, 0x00, 0x00));
+    /// assert_eq!(Color::Indexed(35).to_rgb(), (0xCC, 0xFF, 0xCC));
+    /// ```
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        self.to_rgb_in(&Palette::EXCEL)
+    }
+
+    /// The color as red, green and blue in a workbook's palette.
+    ///
+    /// The parser only accepts `[Color1]`-`[Color56]`; other indexes, in a
+    /// hand-built [`Color`], resolve to black.
+    pub fn to_rgb_in(&self, palette: &Palette) -> (u8, u8, u8) {
+        let index = match self {
+            Color::Named(name) => name.palette_index(),
+            Color::Indexed(index) => *index,
+        };
+        palette.get(index).unwrap_or((0, 0, 0))
+    }
+}
@@ -0,0 +1,293 @@
+//! Numeric layout analysis for number sections.
+//!
+//! Runs once per section at parse time; the result is stored in
+//! [`SectionMetadata::analysis`](crate::ast::SectionMetadata::analysis).
+
+use std::borrow::Cow;
+
+use crate::ast::{DigitPlaceholder, FormatAnalysis, FormatPart, LocaleCode, Section};
+
+/// The section's precomputed analysis, or a fresh one for sections built
+/// by hand without metadata.
+pub(crate) fn section_analysis(section: &Section) -> Cow<'_, FormatAnalysis> {
+    match &section.metadata.analysis {
+        Some(analysis) => Cow::Borrowed(analysis),
+        None => Cow::Owned(analyze_format(&section.parts)),
+    }
+}
+
+/// Analyze a section's parts to extract its numeric structure.
+pub(crate) fn analyze_format(parts: &[FormatPart]) -> FormatAnalysis {
+    let mut integer_placeholders = Vec::new();
+    let mut decimal_placeholders = Vec::new();
+    let mut has_thousands_separator = false;
+    let mut percent_count = 0;
+    let mut integer_parts = Vec::new();
+    let mut decimal_inline_parts = Vec::new();
+    let mut prefix_parts = Vec::new();
+    let mut suffix_parts = Vec::new();
+
+    // Commas that end a run of digits scale by 1000 each instead of
+    // grouping: those after the last digit or decimal point (skipping
+    // literals, so `0.0,,"M"` shows millions) and those right before the
+    // decimal point (`#,##0,.0`)
+    let mut scaling_commas = vec![false; parts.len()];
+    for (i, part) in parts.iter().enumerate().rev() {
+        match part {
+            FormatPart::ThousandsSeparator => scaling_commas[i] = true,
+            FormatPart::Digit(_) | FormatPart::DecimalPoint => break,
+            _ => {}
+        }
+    }
+    if let Some(point) = parts
+        .iter()
+        .position(|p| matches!(p, FormatPart::DecimalPoint))
+    {
+        let run = parts[..point]
+            .iter()
+            .rev()
+            .take_while(|p| matches!(p, FormatPart::ThousandsSeparator))
+            .count();
+        scaling_commas[point - run..point].fill(true);
+    }
+
+    let mut seen_digit = false;
+    let mut after_decimal = false;
+    let mut after_digits = false;
+
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            FormatPart::Digit(placeholder) => {
+                seen_digit = true;
+                after_digits = false;
+                if after_decimal {
+                    decimal_placeholders.push(*placeholder);
+                } else {
+                    integer_placeholders.push(*placeholder);
+                    integer_parts.push(part.clone());
+                }
+            }
+            FormatPart::DecimalPoint => {
+                after_decimal = true;
+                seen_digit = true;
+                after_digits = true; // Mark that integer digit sequence is complete
+            }
+            FormatPart::ThousandsSeparator => {
+                // Scaling commas don't also turn on grouping
+                if !scaling_commas[i] {
+                    has_thousands_separator = true;
+                    if seen_digit && !after_decimal {
+                        integer_parts.push(part.clone());
+                    }
+                }
+            }
+            FormatPart::Percent
+            | FormatPart::Literal(_)
+            | FormatPart::QuotedLiteral(_)
+            | FormatPart::EscapedLiteral(_)
+            | FormatPart::Locale(LocaleCode {
+                currency: Some(_), ..
+            }) => {
+                // Each `%` scales by 100 and also shows where it was written
+                if matches!(part, FormatPart::Percent) {
+                    percent_count += 1;
+                }
+
+                if !seen_digit {
+                    // Before any digits - prefix
+                    prefix_parts.push(part.clone());
+                } else if after_digits {
+                    // After all digits (after decimal or after digit sequence ended) - suffix
+                    suffix_parts.push(part.clone());
+                } else if after_decimal {
+                    // Among decimal digits - inline literal in decimal part
+                    // Store position from left (index in decimal_placeholders)
+                    decimal_inline_parts.push((decimal_placeholders.len(), part.clone()));
+                } else {
+                    // Among integer digits - inline literal
+                    integer_parts.push(part.clone());
+                }
+            }
+            FormatPart::Locale(loc) if loc.currency.is_none() => {
+                // Locale without currency - treat as before
+                if !seen_digit {
+                    prefix_parts.push(part.clone());
+                } else if after_digits {
+                    suffix_parts.push(part.clone());
+                }
+            }
+            FormatPart::Skip(_) => {
+                // Skip adds space equivalent to character width
+                if !seen_digit {
+                    prefix_parts.push(part.clone());
+                } else {
+                    suffix_parts.push(part.clone());
+                }
+            }
+            FormatPart::Fill(_) => {
+                // Fill adds no text of its own; keep its position for rich output
+                if !seen_digit {
+                    prefix_parts.push(part.clone());
+                } else {
+                    suffix_parts.push(part.clone());
+                }
+            }
+            _ => {
+                // Handle other parts as literals in prefix/suffix
+                if !seen_digit {
+                    prefix_parts.push(part.clone());
+                } else if after_digits {
+                    suffix_parts.push(part.clone());
+                }
+            }
+        }
+    }
+
+    // Ensure we have at least one integer placeholder for output
+    if integer_placeholders.is_empty() && !after_decimal {
+        integer_placeholders.push(DigitPlaceholder::Hash);
+        integer_parts.push(FormatPart::Digit(DigitPlaceholder::Hash));
+    }
+
+    let thousands_scale = scaling_commas.iter().filter(|&&scales| scales).count();
+
+    // Literals after the last integer placeholder render the same as a suffix
+    let mut trailing_literals = Vec::new();
+    if !after_decimal {
+        let run_end = integer_parts
+            .iter()
+            .rposition(|part| matches!(part, FormatPart::Digit(_)))
+            .map_or(0, |i| i + 1);
+        trailing_literals.extend(integer_parts.drain(run_end..));
+    }
+
+    // Likewise for literals after the last decimal placeholder. Keeping these
+    // in the suffix leaves the digit run free of format-code text.
+    let (trailing_decimal_parts, decimal_inline_parts): (Vec<_>, Vec<_>) =
+        decimal_inline_parts.into_iter().partition(|(pos, _)| {
+            !decimal_placeholders.is_empty() && *pos >= decimal_placeholders.len()
+        });
+    trailing_literals.extend(trailing_decimal_parts.into_iter().map(|(_, part)| part));
+    let decimal_inline_literals = decimal_inline_parts
+        .into_iter()
+        .map(|(pos, part)| (pos, literal_text(&part)))
+        .collect();
+    if !trailing_literals.is_empty() {
+        suffix_parts.splice(0..0, trailing_literals);
+    }
+
+    FormatAnalysis {
+        integer_placeholders,
+        decimal_placeholders,
+        has_thousands_separator,
+        percent_count,
+        thousands_scale,
+        integer_parts,
+        decimal_inline_literals,
+        prefix_parts,
+        suffix_parts,
+    }
+}
+
+/// The text a literal-like part shows inline.
+fn literal_text(part: &FormatPart) -> String {
+    match part {
+        FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) | FormatPart::EscapedLiteral(s) => {
+            s.clone()
+        }
+        FormatPart::Locale(loc) => loc.currency.clone().unwrap_or_default(),
+        FormatPart::Percent => "%".to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_simple_integer() {
+        let analysis = analyze_format(&[FormatPart::Digit(DigitPlaceholder::Zero)]);
+
+        assert_eq!(analysis.integer_placeholders.len(), 1);
+        assert_eq!(analysis.decimal_placeholders.len(), 0);
+        assert!(!analysis.has_thousands_separator);
+        assert_eq!(analysis.percent_count, 0);
+    }
+
+    #[test]
+    fn test_analyze_decimal_format() {
+        let analysis = analyze_format(&[
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::DecimalPoint,
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::Digit(DigitPlaceholder::Zero),
+        ]);
+
+        assert_eq!(analysis.integer_placeholders.len(), 1);
+        assert_eq!(analysis.decimal_placeholders.len(), 2);
+    }
+
+    #[test]
+    fn test_analyze_thousands() {
+        let analysis = analyze_format(&[
+            FormatPart::Digit(DigitPlaceholder::Hash),
+            FormatPart::ThousandsSeparator,
+            FormatPart::Digit(DigitPlaceholder::Hash),
+            FormatPart::Digit(DigitPlaceholder::Hash),
+            FormatPart::Digit(DigitPlaceholder::Zero),
+        ]);
+
+        assert!(analysis.has_thousands_separator);
+        assert_eq!(analysis.integer_placeholders.len(), 4);
+    }
+
+    #[test]
+    fn test_analyze_percent() {
+        let analysis = analyze_format(&[
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::Percent,
+        ]);
+
+        assert_eq!(analysis.percent_count, 1);
+        assert_eq!(analysis.suffix_parts.len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_integer_parts() {
+        let analysis = analyze_format(&[
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::Literal("x".into()),
+            FormatPart::ThousandsSeparator,
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::Literal(" kg".into()),
+        ]);
+
+        assert_eq!(
+            analysis.integer_parts,
+            [
+                FormatPart::Digit(DigitPlaceholder::Zero),
+                FormatPart::Literal("x".into()),
+                FormatPart::ThousandsSeparator,
+                FormatPart::Digit(DigitPlaceholder::Zero),
+            ]
+        );
+        assert_eq!(analysis.suffix_parts, [FormatPart::Literal(" kg".into())]);
+    }
+
+    #[test]
+    fn test_analyze_comma_before_decimal_point_scales() {
+        let analysis = analyze_format(&[
+            FormatPart::Digit(DigitPlaceholder::Hash),
+            FormatPart::ThousandsSeparator,
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::ThousandsSeparator,
+            FormatPart::DecimalPoint,
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::ThousandsSeparator,
+        ]);
+
+        assert!(analysis.has_thousands_separator);
+        assert_eq!(analysis.thousands_scale, 2);
+    }
+}
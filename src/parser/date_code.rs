@@ -0,0 +1,121 @@
+//! String-level date detection, ported from SheetJS SSF's `is_date`.
+//!
+//! Readers use this to decide whether a cell holds a date before they try
+//! to format it, so it works on the raw code and never fails: codes that
+//! don't parse are still classified.
+
+/// Returns true if a format code displays a date or time.
+///
+/// Matches SheetJS SSF's `is_date`: quoted text, escaped characters, `_x`
+/// skips, `General`, and bracketed colors or conditions are ignored, while
+/// any date letter (`y`, `m`, `d`, `h`, `s`, `e`, `g`, `b`), `AM/PM`, `A/P`,
+/// `上午/下午`, or elapsed-time bracket (`[h]`, `[mm]`) makes it a date.
+/// SSF only skips `General` when capitalized; here any case is skipped.
+///
+/// Unlike [`NumberFormat::is_date_format`](crate::NumberFormat::is_date_format)
+/// this needs no successful parse.
+///
+/// # Examples
+/// ```
+/// use ssfmt::is_date_format_code;
+///
+/// assert!(is_date_format_code("yyyy-mm-dd"));
+/// assert!(is_date_format_code("[h]:mm:ss"));
+/// assert!(is_date_format_code("[Red]m/d/yy"));
+/// assert!(!is_date_format_code("General"));
+/// assert!(!is_date_format_code("0.00\" days\""));
+/// assert!(!is_date_format_code("[Red]#,##0"));
+/// ```
+pub fn is_date_format_code(format_code: &str) -> bool {
+    let chars: Vec<char> = format_code.chars().collect();
+    let len = chars.len();
+    let at = |i: usize| chars.get(i).copied();
+    let mut i = 0;
+
+    while i < len {
+        let c = chars[i];
+        match c {
+            'G' | 'g' if is_general(&chars, i) => i += 7,
+            'G' => i += 1,
+            '"' => {
+                i += 1;
+                while i < len && chars[i] != '"' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            '\\' | '_' => i += 2,
+            'B' | 'b' | 'M' | 'D' | 'Y' | 'H' | 'S' | 'E' | 'm' | 'd' | 'y' | 'h' | 's' | 'e'
+            | 'g' => return true,
+            'A' | 'a' | '上' => {
+                let rest: String = chars[i..].iter().take(5).collect();
+                let rest = rest.to_uppercase();
+                if rest.starts_with("A/P") || rest.starts_with("AM/PM") || rest == "上午/下午" {
+                    return true;
+                }
+                i += 1;
+            }
+            '[' => {
+                let start = i;
+                i += 1;
+                while i < len && chars[i] != ']' {
+                    i += 1;
+                }
+                if i < len && is_elapsed_bracket(&chars[start + 1..i]) {
+                    return true;
+                }
+                i += 1;
+            }
+            '.' | '0' | '#' => {
+                i += 1;
+                while let Some(c) = at(i) {
+                    let continues = "0#?.,E+-%".contains(c)
+                        || (c == '\\'
+                            && at(i + 1) == Some('-')
+                            && matches!(at(i + 2), Some('0' | '#')));
+                    if !continues {
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            '?' => {
+                i += 1;
+                while at(i) == Some('?') {
+                    i += 1;
+                }
+            }
+            '*' => {
+                i += 1;
+                if matches!(at(i), Some(' ' | '*')) {
+                    i += 1;
+                }
+            }
+            '1'..='9' => {
+                i += 1;
+                while matches!(at(i), Some('0'..='9')) {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    false
+}
+
+/// `General` (any case) starting at `i`.
+fn is_general(chars: &[char], i: usize) -> bool {
+    chars.len() >= i + 7
+        && chars[i..i + 7]
+            .iter()
+            .zip("general".chars())
+            .all(|(a, b)| a.to_ascii_lowercase() == b)
+}
+
+/// Bracket contents made only of elapsed-time letters, including the
+/// Thai hour, minute and second letters.
+fn is_elapsed_bracket(contents: &[char]) -> bool {
+    contents
+        .iter()
+        .all(|c| matches!(c, 'H' | 'h' | 'M' | 'm' | 'S' | 's' | 'ช' | 'น' | 'ท'))
+}
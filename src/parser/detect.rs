@@ -0,0 +1,293 @@
+//! Format detection: suggest format codes for sample strings.
+//!
+//! Candidates come from two places: numeric codes built from the shape of
+//! each sample (currency, grouping, decimals, percent, exponent), and a fixed
+//! list of common date and time codes. Each candidate is scored by how many
+//! samples survive a round trip through [`NumberFormat::parse_value`] and
+//! [`NumberFormat::format`] unchanged.
+
+use crate::ast::NumberFormat;
+use crate::options::FormatOptions;
+use crate::value::Value;
+
+/// A suggested format code for a set of samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatGuess {
+    /// The suggested format code
+    pub format_code: String,
+    /// Share of non-empty samples the code reproduces exactly, from 0.0 to 1.0
+    pub confidence: f64,
+}
+
+/// Common date and time codes, most likely first.
+const DATE_TIME_CODES: &[&str] = &[
+    "yyyy-mm-dd",
+    "m/d/yyyy",
+    "m/d/yy",
+    "mm/dd/yyyy",
+    "d/m/yyyy",
+    "dd/mm/yyyy",
+    "dd.mm.yyyy",
+    "yyyy/mm/dd",
+    "d-mmm-yy",
+    "d-mmm-yyyy",
+    "dd-mmm-yyyy",
+    "mmm d, yyyy",
+    "mmmm d, yyyy",
+    "dddd, mmmm d, yyyy",
+    "yyyy-mm-dd hh:mm",
+    "yyyy-mm-dd hh:mm:ss",
+    "m/d/yyyy h:mm",
+    "m/d/yy h:mm",
+    "h:mm",
+    "hh:mm",
+    "h:mm:ss",
+    "hh:mm:ss",
+    "h:mm AM/PM",
+    "h:mm:ss AM/PM",
+    "[h]:mm:ss",
+];
+
+/// Suggest format codes that reproduce the given samples.
+///
+/// Guesses are sorted by confidence, highest first; codes that reproduce no
+/// sample are left out. Empty samples are ignored. Samples are interpreted
+/// with en-US separators.
+///
+/// # Examples
+/// ```
+/// use ssfmt::detect_format;
+///
+/// let guesses = detect_format(&["$1,234.00", "$56.78"]);
+/// assert_eq!(guesses[0].format_code, "$#,##0.00");
+/// assert_eq!(guesses[0].confidence, 1.0);
+///
+/// let guesses = detect_format(&["2024-01-15", "2024-02-29"]);
+/// assert_eq!(guesses[0].format_code, "yyyy-mm-dd");
+/// ```
+pub fn detect_format(samples: &[&str]) -> Vec<FormatGuess> {
+    let samples: Vec<&str> = samples
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    // One shape covering all compatible numeric samples, then each sample's own
+    let shapes: Vec<NumericShape> = samples.iter().filter_map(|s| NumericShape::of(s)).collect();
+    let mut candidates: Vec<String> = Vec::new();
+    if let Some((first, rest)) = shapes.split_first() {
+        let mut merged = first.clone();
+        if rest.iter().all(|shape| merged.merge(shape)) {
+            candidates.push(merged.code());
+        }
+    }
+    for shape in &shapes {
+        let code = shape.code();
+        if !candidates.contains(&code) {
+            candidates.push(code);
+        }
+    }
+    candidates.extend(DATE_TIME_CODES.iter().map(|code| code.to_string()));
+    candidates.push("General".to_string());
+
+    let opts = FormatOptions::default();
+    let mut guesses: Vec<FormatGuess> = candidates
+        .into_iter()
+        .filter_map(|code| {
+            let fmt = NumberFormat::parse(&code).ok()?;
+            let matched = samples
+                .iter()
+                .filter(|sample| round_trips(&fmt, sample, &opts))
+                .count();
+            (matched > 0).then(|| FormatGuess {
+                format_code: code,
+                confidence: matched as f64 / samples.len() as f64,
+            })
+        })
+        .collect();
+
+    let text_samples = samples
+        .iter()
+        .filter(|sample| !sample.chars().any(|c| c.is_ascii_digit()))
+        .count();
+    if text_samples > 0 {
+        guesses.push(FormatGuess {
+            format_code: "@".to_string(),
+            confidence: text_samples as f64 / samples.len() as f64,
+        });
+    }
+
+    // Stable sort keeps the more specific candidates ahead on ties
+    guesses.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    guesses
+}
+
+/// Whether formatting the parsed sample gives back the sample.
+fn round_trips(fmt: &NumberFormat, sample: &str, opts: &FormatOptions) -> bool {
+    match fmt.parse_value(sample, opts) {
+        Value::Number(value) => fmt.format(value, opts) == sample,
+        _ => false,
+    }
+}
+
+/// The layout of a numeric sample.
+#[derive(Debug, Clone, PartialEq)]
+struct NumericShape {
+    prefix: String,
+    suffix: String,
+    grouped: bool,
+    decimals: usize,
+    percent: usize,
+    exponent_digits: Option<usize>,
+    parenthesized: bool,
+}
+
+impl NumericShape {
+    /// Read the shape of a numeric sample, or `None` if it isn't one.
+    fn of(sample: &str) -> Option<Self> {
+        let (body, parenthesized) = match sample.strip_prefix('(').and_then(|s| s.strip_suffix(')'))
+        {
+            Some(inner) => (inner, true),
+            None => (sample, false),
+        };
+        let body = body.strip_prefix(['-', '+']).unwrap_or(body);
+
+        let start = body.find(|c: char| c.is_ascii_digit())?;
+        let end = body.rfind(|c: char| c.is_ascii_digit())? + 1;
+        let mut prefix = &body[..start];
+        let mut number = &body[start..end];
+        let mut suffix = &body[end..];
+        // A leading decimal point belongs to the number (".5")
+        if let Some(rest) = prefix.strip_suffix('.') {
+            prefix = rest;
+            number = &body[start - 1..end];
+        }
+
+        let mut percent = 0;
+        while let Some(rest) = suffix.strip_suffix('%') {
+            percent += 1;
+            suffix = rest;
+        }
+
+        let (mantissa, exponent_digits) = match number.split_once(['E', 'e']) {
+            Some((mantissa, exponent)) => {
+                let digits = exponent.trim_start_matches(['+', '-']);
+                if mantissa.contains(',')
+                    || digits.is_empty()
+                    || !digits.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return None;
+                }
+                (mantissa, Some(digits.len()))
+            }
+            None => (number, None),
+        };
+
+        let (integer, decimals) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let grouped = integer.contains(',');
+        let valid_integer = if grouped {
+            let mut groups = integer.split(',');
+            let first = groups.next().unwrap_or("");
+            (1..=3).contains(&first.len())
+                && first.bytes().all(|b| b.is_ascii_digit())
+                && groups.all(|g| g.len() == 3 && g.bytes().all(|b| b.is_ascii_digit()))
+        } else {
+            integer.bytes().all(|b| b.is_ascii_digit())
+        };
+        if !valid_integer || !decimals.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        Some(NumericShape {
+            prefix: literal(prefix)?,
+            suffix: literal(suffix)?,
+            grouped,
+            decimals: decimals.len(),
+            percent,
+            exponent_digits,
+            parenthesized,
+        })
+    }
+
+    /// Widen this shape to also cover `other`, if both share their literals.
+    fn merge(&mut self, other: &NumericShape) -> bool {
+        if self.prefix != other.prefix
+            || self.suffix != other.suffix
+            || self.percent != other.percent
+            || self.exponent_digits != other.exponent_digits
+        {
+            return false;
+        }
+        self.grouped |= other.grouped;
+        self.decimals = self.decimals.max(other.decimals);
+        self.parenthesized |= other.parenthesized;
+        true
+    }
+
+    /// The format code for this shape.
+    fn code(&self) -> String {
+        let mut digits = String::from(if self.grouped { "#,##0" } else { "0" });
+        if self.decimals > 0 {
+            digits.push('.');
+            digits.push_str(&"0".repeat(self.decimals));
+        }
+        if let Some(exponent_digits) = self.exponent_digits {
+            digits.push_str("E+");
+            digits.push_str(&"0".repeat(exponent_digits));
+        }
+        let code = format!(
+            "{}{}{}{}",
+            self.prefix,
+            digits,
+            "%".repeat(self.percent),
+            self.suffix
+        );
+        if self.parenthesized {
+            format!("{};({})", code, code)
+        } else {
+            code
+        }
+    }
+}
+
+/// Format-code spelling of literal text around a number.
+fn literal(text: &str) -> Option<String> {
+    if text.chars().any(|c| c.is_ascii_digit() || c == '"') {
+        return None;
+    }
+    Some(match text {
+        "" => String::new(),
+        "$" | "-" | "+" | "(" | ")" | " " => text.to_string(),
+        _ => format!("\"{}\"", text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_shape_code() {
+        let code = |sample| NumericShape::of(sample).map(|shape| shape.code());
+        assert_eq!(code("1,234.50").as_deref(), Some("#,##0.00"));
+        assert_eq!(code("12.5%").as_deref(), Some("0.0%"));
+        assert_eq!(code("-42").as_deref(), Some("0"));
+        assert_eq!(code("1.50E+03").as_deref(), Some("0.00E+00"));
+        assert_eq!(code("12 €").as_deref(), Some("0\" €\""));
+        assert_eq!(code("($1,000)").as_deref(), Some("$#,##0;($#,##0)"));
+        assert_eq!(code("2024-01-15"), None);
+        assert_eq!(code("12,34"), None);
+        assert_eq!(code("abc"), None);
+    }
+
+    #[test]
+    fn test_numeric_shape_merge() {
+        let mut shape = NumericShape::of("1,234").unwrap();
+        assert!(shape.merge(&NumericShape::of("(5.25)").unwrap()));
+        assert_eq!(shape.code(), "#,##0.00;(#,##0.00)");
+        assert!(!shape.merge(&NumericShape::of("5%").unwrap()));
+    }
+}
@@ -21,6 +21,7 @@ const RUN_HASH: u8 = 6;
 const RUN_QUESTION: u8 = 7;
 
 /// A lexer for format code strings.
+#[derive(Clone)]
 pub struct Lexer<'a> {
     /// The input string being tokenized.
     pub(crate) input: &'a str,
@@ -32,6 +33,50 @@ pub struct Lexer<'a> {
     /// When we encounter consecutive same-type chars (e.g., "yyyy"),
     /// we count them once and emit tokens from this counter.
     pending_run: Option<(u8, usize, usize)>,
+    /// Longest quoted string allowed, in bytes.
+    pub(crate) max_quoted_length: usize,
+}
+
+/// Split a format code into tokens, without the final `Eof`.
+///
+/// Never fails, so an editor can highlight code as it is typed: an
+/// unterminated quoted string runs to the end of the input, and a trailing
+/// backslash is left out. Runs such as `yyyy` or `000` give one token per
+/// character. See the [`tokens`](crate::parser::tokens) module for the
+/// stability guarantees.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{tokenize, Token, TokenCategory};
+///
+/// let tokens = tokenize("[Red]0.0\" kg\"");
+/// assert_eq!(tokens[0].token, Token::OpenBracket);
+/// assert_eq!(tokens[5].token.category(), TokenCategory::Placeholder);
+/// let unit = tokens.last().unwrap();
+/// assert_eq!(unit.token.as_str(), "quoted-string");
+/// assert_eq!(unit.span(), 8..13);
+/// ```
+pub fn tokenize(format_code: &str) -> Vec<SpannedToken> {
+    let mut lexer = Lexer::new(format_code);
+    let mut tokens = Vec::new();
+    loop {
+        match lexer.next_token() {
+            Ok(token) if token.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(ParseError::UnterminatedQuote { span, text }) => {
+                tokens.push(SpannedToken {
+                    token: Token::QuotedString(text[1..].to_string()),
+                    start: span.start,
+                    end: span.end,
+                });
+                break;
+            }
+            // A dangling escape is the last character, and quoted strings
+            // are not length-limited here
+            Err(_) => break,
+        }
+    }
+    tokens
 }
 
 impl<'a> Lexer<'a> {
@@ -42,9 +87,16 @@ impl<'a> Lexer<'a> {
             position: 0,
             in_bracket: false,
             pending_run: None,
+            max_quoted_length: usize::MAX,
         }
     }
 
+    /// Moves to the end of the input, so the next token is `Eof`.
+    pub(crate) fn skip_to_end(&mut self) {
+        self.position = self.input.len();
+        self.pending_run = None;
+    }
+
     /// Returns the next token from the input.
     pub fn next_token(&mut self) -> Result<SpannedToken, ParseError> {
         // First, check if we have pending tokens from a run
@@ -93,8 +145,8 @@ impl<'a> Lexer<'a> {
                 }
             }
 
-            // Try to match AM/PM patterns (only if starts with 'A' or 'a')
-            if ch == 'A' || ch == 'a' {
+            // Try to match AM/PM patterns (only if starts with 'A', 'a' or '上')
+            if ch == 'A' || ch == 'a' || ch == '上' {
                 if let Some(am_pm_token) = self.try_match_am_pm() {
                     return Ok(am_pm_token);
                 }
@@ -246,6 +298,16 @@ impl<'a> Lexer<'a> {
                 self.advance();
                 Token::BuddhistYearUpper
             }
+            // Japanese era codes; like SSF, only lowercase `g` is a date code,
+            // and `r` only under a Japanese locale (decided by the parser)
+            'g' if !self.in_bracket => {
+                self.advance();
+                Token::Era
+            }
+            'r' if !self.in_bracket => {
+                self.advance();
+                Token::EraYear
+            }
 
             // Everything else is a literal
             _ => {
@@ -317,14 +379,21 @@ impl<'a> Lexer<'a> {
                     self.advance(); // Skip the closing quote
                     return Ok(Token::QuotedString(content));
                 }
+                Some(_) if content.len() >= self.max_quoted_length => {
+                    return Err(ParseError::FormatTooComplex {
+                        span: start..self.position,
+                        limit: "bytes in a quoted string",
+                        max: self.max_quoted_length,
+                    });
+                }
                 Some(ch) => {
                     content.push(ch);
                     self.advance();
                 }
                 None => {
-                    return Err(ParseError::UnexpectedToken {
-                        position: start,
-                        found: '"',
+                    return Err(ParseError::UnterminatedQuote {
+                        span: start..self.input.len(),
+                        text: self.input[start..].to_string(),
                     });
                 }
             }
@@ -341,9 +410,8 @@ impl<'a> Lexer<'a> {
                 self.advance();
                 Ok(Token::EscapedChar(ch))
             }
-            None => Err(ParseError::UnexpectedToken {
-                position: start,
-                found: '\\',
+            None => Err(ParseError::DanglingEscape {
+                span: start..start + 1,
             }),
         }
     }
@@ -403,6 +471,15 @@ impl<'a> Lexer<'a> {
                 });
             }
         }
+        // Chinese markers, which Excel recognizes as written
+        if remaining.starts_with("上午/下午") {
+            self.position += "上午/下午".len();
+            return Some(SpannedToken {
+                token: Token::AmPm("上午/下午".to_string()),
+                start,
+                end: self.position,
+            });
+        }
         if let Some(prefix) = remaining.get(..3) {
             if prefix.eq_ignore_ascii_case("A/P") {
                 let matched = prefix.to_string();
@@ -1,55 +1,271 @@
 //! Parser for ECMA-376 number format codes.
 
+pub(crate) mod analysis;
+pub mod date_code;
+pub mod detect;
 pub mod lexer;
 pub mod tokens;
 
 use crate::ast::{
-    AmPmStyle, Color, Condition, DatePart, DigitPlaceholder, ElapsedPart, FormatPart, LocaleCode,
-    NamedColor, NumberFormat, Section,
+    AmPmStyle, Color, Condition, DatePart, DigitPlaceholder, ElapsedPart, FormatPart,
+    FractionDenom, LocaleCode, NamedColor, NumberFormat, Section,
 };
-use crate::error::ParseError;
+use crate::error::{ParseError, ParseWarning};
+use crate::highlight::TokenClass;
+use crate::options::{ExtraSections, ParseLimits, ParseOptions};
 use lexer::Lexer;
+use smallvec::SmallVec;
+use std::ops::Range;
 use tokens::{SpannedToken, Token};
 
 /// Parse a format code string into a NumberFormat.
 pub fn parse(format_code: &str) -> Result<NumberFormat, ParseError> {
+    parse_with_warnings(format_code).map(|(format, _)| format)
+}
+
+/// Parse a format code with the given options.
+pub fn parse_with_options(
+    format_code: &str,
+    options: &ParseOptions,
+) -> Result<NumberFormat, ParseError> {
+    parse_with_warnings_and_options(format_code, options).map(|(format, _)| format)
+}
+
+/// Parse a format code, also returning the problems the parser tolerated.
+pub(crate) fn parse_with_warnings(
+    format_code: &str,
+) -> Result<(NumberFormat, Vec<ParseWarning>), ParseError> {
+    parse_with_warnings_and_options(format_code, &ParseOptions::default())
+}
+
+fn parse_with_warnings_and_options(
+    format_code: &str,
+    options: &ParseOptions,
+) -> Result<(NumberFormat, Vec<ParseWarning>), ParseError> {
     if format_code.is_empty() {
         return Err(ParseError::EmptyFormat);
     }
+    check_length(format_code, &options.limits)?;
+    if let Some(format) = parse_general(format_code) {
+        return Ok((format, Vec::new()));
+    }
+
+    let mut parser = Parser::new(format_code, false);
+    parser.extra_sections = options.extra_sections;
+    parser.set_limits(options.limits);
+    let format = parser.parse()?;
+    Ok((format, parser.warnings))
+}
+
+/// Parse a format code, repairing recoverable problems instead of failing.
+pub(crate) fn parse_lenient(format_code: &str) -> (NumberFormat, Vec<ParseWarning>) {
+    if format_code.is_empty() {
+        return (
+            NumberFormat::from_sections(vec![empty_section(None)]),
+            vec![ParseWarning::EmptyFormat],
+        );
+    }
+    let general = || NumberFormat::from_sections(vec![empty_section(None)]);
+    if let Err(err) = check_length(format_code, &ParseLimits::default()) {
+        return (general(), vec![too_complex_warning(err)]);
+    }
+    if let Some(format) = parse_general(format_code) {
+        return (format, Vec::new());
+    }
+
+    let mut parser = Parser::new(format_code, true);
+    parser.set_limits(ParseLimits::default());
+    match parser.parse() {
+        Ok(format) => (format, parser.warnings),
+        // Every other error is recovered from in lenient mode; fall back to
+        // General rather than panic if one slips through
+        Err(err) => {
+            let mut warnings = parser.warnings;
+            if matches!(err, ParseError::FormatTooComplex { .. }) {
+                warnings.push(too_complex_warning(err));
+            }
+            (general(), warnings)
+        }
+    }
+}
+
+/// Classify the text of a format code for [`highlight`](crate::highlight),
+/// one entry per construct the parser reads.
+pub(crate) fn classify(format_code: &str) -> Vec<(Range<usize>, TokenClass)> {
+    let mut parser = Parser::new(format_code, true);
+    parser.highlights = Some(Vec::new());
+    // Lenient parsing without limits does not fail, and the spans up to a
+    // failure would still be right
+    let _ = parser.parse();
+    let mut highlights = parser.highlights.unwrap_or_default();
+    // A dangling backslash at the end is dropped without a token
+    let end = highlights.last().map_or(0, |(span, _)| span.end);
+    if end < format_code.len() {
+        highlights.push((end..format_code.len(), TokenClass::Unknown));
+    }
+    highlights
+}
 
-    // Handle "General" format specially - it's Excel's default format
-    // that displays numbers without unnecessary formatting
-    // Also handle "[Color]General" and similar patterns
-    let general_check = if format_code.eq_ignore_ascii_case("General") {
-        Some(None) // General with no color
-    } else if let Some(bracket_end) = format_code.find(']') {
+/// Fail if the format code is longer than the limits allow.
+fn check_length(format_code: &str, limits: &ParseLimits) -> Result<(), ParseError> {
+    if format_code.len() <= limits.max_length {
+        return Ok(());
+    }
+    let start = (0..=limits.max_length)
+        .rev()
+        .find(|&i| format_code.is_char_boundary(i))
+        .unwrap_or(0);
+    Err(ParseError::FormatTooComplex {
+        span: start..format_code.len(),
+        limit: "bytes",
+        max: limits.max_length,
+    })
+}
+
+fn too_complex_warning(err: ParseError) -> ParseWarning {
+    match err {
+        ParseError::FormatTooComplex { span, limit, max } => {
+            ParseWarning::FormatTooComplex { span, limit, max }
+        }
+        _ => unreachable!("not a FormatTooComplex error"),
+    }
+}
+
+/// Handle "General" format specially - it's Excel's default format
+/// that displays numbers without unnecessary formatting.
+/// Also handle "[Color]General" and similar patterns.
+fn parse_general(format_code: &str) -> Option<NumberFormat> {
+    let color = if format_code.eq_ignore_ascii_case("General") {
+        None // General with no color
+    } else {
         // Check if format is "[...]General"
+        let bracket_end = format_code.find(']').filter(|_| format_code.starts_with('['))?;
+        let bracket_content = &format_code[1..bracket_end];
         let after_bracket = &format_code[bracket_end + 1..];
-        if after_bracket.trim().eq_ignore_ascii_case("General") {
-            // Try to parse the bracket content as a color
-            let bracket_content = &format_code[1..bracket_end];
-            let color = try_parse_color(bracket_content);
-            Some(color)
-        } else {
-            None
+        if !after_bracket.trim().eq_ignore_ascii_case("General")
+            || is_numeral_modifier(bracket_content)
+        {
+            return None;
         }
-    } else {
-        None
+        // Try to parse the bracket content as a color
+        try_parse_color(bracket_content)
     };
 
-    if let Some(color) = general_check {
-        // Create an empty section that will trigger fallback formatting
-        let general_section = Section {
-            condition: None,
-            color,
-            parts: Vec::new(),
-            metadata: crate::ast::SectionMetadata::default(),
-        };
-        return Ok(NumberFormat::from_sections(vec![general_section]));
+    // An empty section triggers fallback formatting
+    Some(NumberFormat::from_sections(vec![empty_section(color)]))
+}
+
+/// Compute section metadata by scanning parts once
+/// Based on SSF's eval_fmt in bits/82_eval.js
+pub(crate) fn section_metadata(parts: &[FormatPart]) -> crate::ast::SectionMetadata {
+    use crate::ast::*;
+
+    let mut has_ampm = false;
+    let mut is_hijri = false;
+    let mut max_subsecond_precision = None;
+    let mut has_elapsed_time = false;
+    let mut has_text = false;
+    let mut smallest_time_unit = TimeUnit::None;
+    let mut format_type = FormatType::General;
+    let mut lcid = None;
+    let mut dbnum = None;
+    let mut natnum = None;
+
+    // Scan parts to gather metadata
+    for part in parts {
+        match part {
+            FormatPart::AmPm(_) => {
+                has_ampm = true;
+            }
+            FormatPart::DatePart(DatePart::BuddhistYear4Alt | DatePart::BuddhistYear2Alt) => {
+                is_hijri = true;
+            }
+            FormatPart::DatePart(DatePart::SubSecond(precision)) => {
+                max_subsecond_precision = Some(max_subsecond_precision.unwrap_or(0).max(*precision));
+                if smallest_time_unit < TimeUnit::Subseconds {
+                    smallest_time_unit = TimeUnit::Subseconds;
+                }
+            }
+            FormatPart::DatePart(DatePart::Second | DatePart::Second2)
+                if smallest_time_unit < TimeUnit::Seconds =>
+            {
+                smallest_time_unit = TimeUnit::Seconds;
+            }
+            FormatPart::DatePart(DatePart::Minute | DatePart::Minute2)
+                if smallest_time_unit < TimeUnit::Minutes =>
+            {
+                smallest_time_unit = TimeUnit::Minutes;
+            }
+            FormatPart::DatePart(DatePart::Hour | DatePart::Hour2)
+                if smallest_time_unit < TimeUnit::Hours =>
+            {
+                smallest_time_unit = TimeUnit::Hours;
+            }
+            FormatPart::Elapsed(_) => {
+                has_elapsed_time = true;
+            }
+            FormatPart::Fraction { .. } => {
+                format_type = FormatType::Fraction;
+            }
+            FormatPart::TextPlaceholder => {
+                has_text = true;
+            }
+            FormatPart::Locale(locale) if lcid.is_none() => {
+                lcid = locale.lcid;
+            }
+            FormatPart::DbNum(style) if dbnum.is_none() => {
+                dbnum = Some(*style);
+            }
+            FormatPart::NatNum(style) if natnum.is_none() => {
+                natnum = Some(*style);
+            }
+            _ => {}
+        }
     }
 
-    let mut parser = Parser::new(format_code);
-    parser.parse()
+    // Determine format type if not already set. A section mixing `@` with
+    // number or date codes formats numbers with those codes; `@` only takes
+    // part when formatting text.
+    if format_type == FormatType::General {
+        let has_date = parts.iter().any(|p| matches!(p, FormatPart::DatePart(_)));
+        let has_number = parts.iter().any(|p| matches!(p, FormatPart::Digit(_) | FormatPart::DecimalPoint));
+
+        if has_date || has_ampm || has_elapsed_time {
+            format_type = FormatType::DateTime;
+        } else if has_number {
+            format_type = FormatType::Number;
+        } else if has_text {
+            format_type = FormatType::Text;
+        }
+    }
+
+    // Number sections get their numeric layout up front
+    let is_scientific = parts.iter().any(|p| matches!(p, FormatPart::Scientific { .. }));
+    let analysis = (matches!(format_type, FormatType::Number | FormatType::General) && !is_scientific)
+        .then(|| analysis::analyze_format(parts));
+
+    SectionMetadata {
+        has_ampm,
+        is_hijri,
+        max_subsecond_precision,
+        has_elapsed_time,
+        smallest_time_unit,
+        format_type,
+        lcid,
+        dbnum,
+        natnum,
+        analysis,
+    }
+}
+
+/// A section with no parts, which formats like General.
+fn empty_section(color: Option<Color>) -> Section {
+    Section {
+        condition: None,
+        color,
+        parts: Vec::new(),
+        metadata: crate::ast::SectionMetadata::default(),
+    }
 }
 
 /// Parser for format code strings.
@@ -57,36 +273,91 @@ struct Parser<'a> {
     lexer: Lexer<'a>,
     /// Current token
     current: SpannedToken,
-    /// Whether we've seen an hour token in the current section (for minute vs month disambiguation)
-    seen_hour: bool,
+    /// Whether the last date code in the section was an hour, so a
+    /// following `m` is a minute
+    after_hour: bool,
+    /// Repair recoverable errors instead of failing
+    lenient: bool,
+    /// What to do with sections after the fourth; lenient parsing always
+    /// truncates
+    extra_sections: ExtraSections,
+    /// Problems found so far; only reported by lenient parsing
+    warnings: Vec<ParseWarning>,
+    /// Most parts allowed in one section
+    max_parts_per_section: usize,
+    /// End of the token before `current`
+    prev_end: usize,
+    /// What each stretch of the code means; only collected for highlighting
+    highlights: Option<Vec<(Range<usize>, TokenClass)>>,
 }
 
 impl<'a> Parser<'a> {
     /// Create a new parser for the given format code.
-    fn new(format_code: &'a str) -> Self {
-        let mut lexer = Lexer::new(format_code);
-        // Get the first token
-        let current = lexer.next_token().unwrap_or(SpannedToken {
-            token: Token::Eof,
-            start: 0,
-            end: 0,
-        });
+    fn new(format_code: &'a str, lenient: bool) -> Self {
         Self {
-            lexer,
-            current,
-            seen_hour: false,
+            lexer: Lexer::new(format_code),
+            current: SpannedToken {
+                token: Token::Eof,
+                start: 0,
+                end: 0,
+            },
+            after_hour: false,
+            lenient,
+            extra_sections: ExtraSections::Truncate,
+            warnings: Vec::new(),
+            max_parts_per_section: usize::MAX,
+            prev_end: 0,
+            highlights: None,
         }
     }
 
+    /// Enforce `limits` on quoted strings and section sizes.
+    fn set_limits(&mut self, limits: ParseLimits) {
+        self.lexer.max_quoted_length = limits.max_quoted_length;
+        self.max_parts_per_section = limits.max_parts_per_section;
+    }
+
     /// Advance to the next token.
     fn advance(&mut self) -> Result<(), ParseError> {
-        self.current = self.lexer.next_token()?;
+        self.prev_end = self.current.end;
+        self.current = match self.lexer.next_token() {
+            Ok(token) => token,
+            Err(err) if self.lenient => self.recover(err)?,
+            Err(err) => return Err(err),
+        };
         Ok(())
     }
 
+    /// Turn a lexer error into a warning and the token to continue with.
+    fn recover(&mut self, err: ParseError) -> Result<SpannedToken, ParseError> {
+        let end = self.lexer.input.len();
+        let token = match err {
+            ParseError::UnterminatedQuote { span, text } => {
+                let content = text[1..].to_string();
+                self.warnings
+                    .push(ParseWarning::UnterminatedQuote { span, text });
+                Token::QuotedString(content)
+            }
+            ParseError::DanglingEscape { span } => {
+                self.warnings.push(ParseWarning::DanglingEscape { span });
+                Token::Eof
+            }
+            err => return Err(err),
+        };
+        // Both errors run to the end of the input
+        self.lexer.skip_to_end();
+        Ok(SpannedToken {
+            token,
+            start: self.current.end,
+            end,
+        })
+    }
+
     /// Parse the format code into a NumberFormat.
     fn parse(&mut self) -> Result<NumberFormat, ParseError> {
-        let mut sections = Vec::new();
+        let mut sections: SmallVec<[Section; 4]> = SmallVec::new();
+        let mut extra_start = None;
+        self.advance()?;
 
         loop {
             let section = self.parse_section()?;
@@ -98,46 +369,82 @@ impl<'a> Parser<'a> {
             }
 
             if matches!(self.current.token, Token::SectionSep) {
+                self.highlight(self.current.start..self.current.end, TokenClass::Separator);
                 self.advance()?;
+                if sections.len() == 4 {
+                    if self.extra_sections == ExtraSections::Error && !self.lenient {
+                        return Err(ParseError::TooManySections);
+                    }
+                    extra_start = Some(self.current.start);
+                }
                 // Continue to next section
             } else {
                 break;
             }
         }
 
-        Ok(NumberFormat::from_sections(sections))
+        // Sections after the fourth are parsed, so errors in them still
+        // count, then dropped by from_sections
+        if let Some(start) = extra_start {
+            self.warnings.push(ParseWarning::TooManySections {
+                span: start..self.lexer.input.len(),
+                ignored: sections.len() - 4,
+            });
+        }
+
+        Ok(NumberFormat::from_section_iter(sections))
     }
 
     /// Parse a single section of the format.
     fn parse_section(&mut self) -> Result<Section, ParseError> {
         let mut builder = SectionBuilder::new();
-        self.seen_hour = false;
+        self.after_hour = false;
 
         loop {
+            if builder.parts.len() > self.max_parts_per_section {
+                return Err(ParseError::FormatTooComplex {
+                    span: self.current.start..self.lexer.input.len(),
+                    limit: "parts in a section",
+                    max: self.max_parts_per_section,
+                });
+            }
+            let start = self.current.start;
+            let parts_before = builder.parts.len();
+            let mut class = None;
+            // Any date code but `m` and `h` ends an hour's hold on `m`
+            if matches!(
+                self.current.token,
+                Token::Year
+                    | Token::Day
+                    | Token::Second
+                    | Token::BuddhistYear
+                    | Token::BuddhistYearUpper
+                    | Token::Era
+                    | Token::EraYear
+                    | Token::ExponentUpper
+                    | Token::ExponentLower
+                    | Token::AmPm(_)
+            ) {
+                self.after_hour = false;
+            }
             match &self.current.token {
                 Token::Eof | Token::SectionSep => break,
 
-                // General format keyword - return empty section to trigger fallback formatting
-                // But only if General is the ONLY content (after color/condition)
+                // General format keyword. It is kept as a part even when it is
+                // the only content, since an empty section hides the value
+                // rather than formatting it like General (`0;General` vs `0;`)
                 Token::General => {
                     self.advance()?;
-                    // Check if there are more format parts after "General"
-                    if matches!(self.current.token, Token::Eof | Token::SectionSep) {
-                        // Truly just "General" - return empty section for fallback formatting
-                        break;
-                    } else {
-                        // "General" followed by more content (like "General ")
-                        // Add GeneralNumber part to signal General formatting should be used
-                        builder.add_part(FormatPart::GeneralNumber);
-                        // Continue parsing the rest as literals
-                    }
+                    builder.add_part(FormatPart::GeneralNumber);
+                    // Any content after "General" (like "General ") is parsed
+                    // as literals
                 }
 
                 // Bracket content - could be color, condition, elapsed time, or locale
                 Token::OpenBracket => {
                     let bracket_start = self.current.start;
                     self.advance()?;
-                    self.parse_bracket_content(&mut builder, bracket_start)?;
+                    class = Some(self.parse_bracket_content(&mut builder, bracket_start)?);
                 }
 
                 // Digit placeholders
@@ -201,21 +508,40 @@ impl<'a> Parser<'a> {
                         let show_plus = matches!(self.current.token, Token::Plus);
                         self.advance()?;
                         let upper = !is_lower;
-                        builder.add_part(FormatPart::Scientific { upper, show_plus });
+
+                        // The digit placeholders that follow belong to the exponent
+                        let mut exponent_digits = Vec::new();
+                        loop {
+                            let placeholder = match self.current.token {
+                                Token::Zero => DigitPlaceholder::Zero,
+                                Token::Hash => DigitPlaceholder::Hash,
+                                Token::Question => DigitPlaceholder::Question,
+                                _ => break,
+                            };
+                            exponent_digits.push(placeholder);
+                            self.advance()?;
+                        }
+                        builder.add_part(FormatPart::Scientific {
+                            upper,
+                            show_plus,
+                            exponent_digits,
+                        });
                     } else {
-                        // Standalone 'e' or 'E' - could be era year (date format)
-                        // Skip consecutive e/E tokens
+                        // Standalone 'e' or 'E' - era year (date format)
+                        let mut count = 1;
                         while matches!(
                             self.current.token,
                             Token::ExponentLower | Token::ExponentUpper
                         ) {
+                            count += 1;
                             self.advance()?;
                         }
-
-                        // Era year format: e, ee, eee, eeee all output 4-digit year
-                        // For Gregorian calendar, era year is same as regular year
-                        // Excel always shows the full year for 'e' format
-                        builder.add_part(FormatPart::DatePart(DatePart::Year4));
+                        let part = if count >= 2 {
+                            DatePart::EraYear2
+                        } else {
+                            DatePart::EraYear
+                        };
+                        builder.add_part(FormatPart::DatePart(part));
                     }
                 }
 
@@ -232,6 +558,7 @@ impl<'a> Parser<'a> {
                 // Fraction
                 Token::Slash => {
                     builder.add_part(FormatPart::Literal("/".to_string()));
+                    class = Some(TokenClass::Separator);
                     self.advance()?;
                 }
 
@@ -248,14 +575,13 @@ impl<'a> Parser<'a> {
                     builder.add_part(FormatPart::DatePart(part));
                 }
                 Token::Month => {
-                    // Check if this should be minute (after hour) or month
-                    // BEFORE consuming tokens, check if seconds follow
-                    let has_seconds_following = self.has_seconds_ahead();
+                    // Like SSF, `m` is a minute when the date code before it
+                    // is an hour or the one after it is a second. Look ahead
+                    // before consuming the run
+                    let is_minute = self.after_hour || self.seconds_ahead();
+                    self.after_hour = false;
                     let count = self.count_consecutive(&Token::Month)?;
-                    // It's a minute if:
-                    // 1. We've seen an hour token, OR
-                    // 2. There are seconds tokens following (mm:ss pattern)
-                    let part = if self.seen_hour || has_seconds_following {
+                    let part = if is_minute {
                         // This is minute
                         if count >= 2 {
                             DatePart::Minute2
@@ -285,7 +611,7 @@ impl<'a> Parser<'a> {
                     builder.add_part(FormatPart::DatePart(part));
                 }
                 Token::Hour => {
-                    self.seen_hour = true;
+                    self.after_hour = true;
                     let count = self.count_consecutive(&Token::Hour)?;
                     let part = if count >= 2 {
                         DatePart::Hour2
@@ -343,8 +669,9 @@ impl<'a> Parser<'a> {
 
                 // Buddhist calendar
                 Token::BuddhistYear => {
+                    // Like SSF, `b` and `bb` show two digits and longer runs four
                     let count = self.count_consecutive(&Token::BuddhistYear)?;
-                    let part = if count >= 4 {
+                    let part = if count >= 3 {
                         DatePart::BuddhistYear4
                     } else {
                         DatePart::BuddhistYear2
@@ -374,7 +701,7 @@ impl<'a> Parser<'a> {
                     } else {
                         // Just 'B' by itself - treat as regular Buddhist year
                         let count = 1 + self.count_consecutive(&Token::BuddhistYearUpper)?;
-                        let part = if count >= 4 {
+                        let part = if count >= 3 {
                             DatePart::BuddhistYear4
                         } else {
                             DatePart::BuddhistYear2
@@ -383,6 +710,29 @@ impl<'a> Parser<'a> {
                     }
                 }
 
+                // Japanese era
+                Token::Era => {
+                    let part = match self.count_consecutive(&Token::Era)? {
+                        1 => DatePart::EraLetter,
+                        2 => DatePart::EraAbbr,
+                        _ => DatePart::EraFull,
+                    };
+                    builder.add_part(FormatPart::DatePart(part));
+                }
+                Token::EraYear => {
+                    let count = self.count_consecutive(&Token::EraYear)?;
+                    if !builder.lcid().is_some_and(crate::era::is_japanese_lcid) {
+                        // Outside Japanese locales `r` is just a letter
+                        builder.add_part(FormatPart::Literal("r".repeat(count)));
+                    } else {
+                        // `rr` is the full era name and year, like `gggee`
+                        if count >= 2 {
+                            builder.add_part(FormatPart::DatePart(DatePart::EraFull));
+                        }
+                        builder.add_part(FormatPart::DatePart(DatePart::EraYear2));
+                    }
+                }
+
                 // AM/PM
                 Token::AmPm(s) => {
                     let style = parse_am_pm_style(s);
@@ -400,40 +750,65 @@ impl<'a> Parser<'a> {
                     self.advance()?;
                 }
                 Token::QuotedString(s) => {
-                    builder.add_part(FormatPart::Literal(s.clone()));
+                    builder.add_part(FormatPart::QuotedLiteral(s.clone()));
                     self.advance()?;
                 }
 
                 Token::CloseBracket => {
                     // Unexpected close bracket - treat as literal
+                    self.warnings.push(ParseWarning::StrayCloseBracket {
+                        span: self.current.start..self.current.end,
+                    });
                     builder.add_part(FormatPart::Literal("]".to_string()));
                     self.advance()?;
                 }
             }
+
+            if self.highlights.is_some() {
+                let class = class
+                    .or_else(|| builder.parts.get(parts_before).map(TokenClass::of_part))
+                    .unwrap_or(TokenClass::Layout);
+                self.highlight(start..self.prev_end, class);
+            }
         }
 
         Ok(builder.build())
     }
 
+    /// Record the class of `span` when highlighting.
+    fn highlight(&mut self, span: Range<usize>, class: TokenClass) {
+        if let Some(highlights) = &mut self.highlights {
+            highlights.push((span, class));
+        }
+    }
+
     /// Parse bracket content: [Red], [>100], [h], [$-409], etc.
     fn parse_bracket_content(
         &mut self,
         builder: &mut SectionBuilder,
         bracket_start: usize,
-    ) -> Result<(), ParseError> {
+    ) -> Result<TokenClass, ParseError> {
         // Collect all content until we hit the close bracket
         let mut content = String::new();
+        let mut bracket_end = self.lexer.input.len();
 
         loop {
             match &self.current.token {
                 Token::CloseBracket => {
+                    bracket_end = self.current.end;
                     self.advance()?;
                     break;
                 }
                 Token::Eof => {
-                    return Err(ParseError::UnterminatedBracket {
-                        position: bracket_start,
-                    });
+                    let input = self.lexer.input;
+                    let span = bracket_start..input.len();
+                    let text = input[bracket_start..].to_string();
+                    if !self.lenient {
+                        return Err(ParseError::UnterminatedBracket { span, text });
+                    }
+                    self.warnings
+                        .push(ParseWarning::UnterminatedBracket { span, text });
+                    break;
                 }
                 Token::Literal(ch) => {
                     content.push(*ch);
@@ -509,33 +884,70 @@ impl<'a> Parser<'a> {
         // Try to parse as color
         if let Some(color) = try_parse_color(content) {
             builder.color = Some(color);
-            return Ok(());
+            return Ok(TokenClass::Color);
         }
 
         // Try to parse as condition
-        if let Some(condition) = try_parse_condition(content) {
-            builder.condition = Some(condition);
-            return Ok(());
+        match try_parse_condition(content) {
+            Some(Ok(condition)) => {
+                builder.condition = Some(condition);
+                return Ok(TokenClass::Condition);
+            }
+            // A comparison without a number; lenient parsing ignores it
+            Some(Err(reason)) if !self.lenient => {
+                return Err(ParseError::InvalidCondition {
+                    span: bracket_start..bracket_end,
+                    text: self.lexer.input[bracket_start..bracket_end].to_string(),
+                    reason,
+                });
+            }
+            _ => {}
         }
 
         // Try to parse as elapsed time
         if let Some(elapsed) = try_parse_elapsed(content) {
             builder.add_part(FormatPart::Elapsed(elapsed));
-            // If this is elapsed hours, set seen_hour so that subsequent 'mm' is parsed as minutes
-            if matches!(elapsed, ElapsedPart::Hours | ElapsedPart::Hours2) {
-                self.seen_hour = true;
-            }
-            return Ok(());
+            // After elapsed hours, `mm` is parsed as minutes
+            self.after_hour = matches!(elapsed, ElapsedPart::Hours | ElapsedPart::Hours2);
+            return Ok(TokenClass::Time);
         }
 
         // Try to parse as locale code
         if let Some(locale) = try_parse_locale(content) {
             builder.add_part(FormatPart::Locale(locale));
-            return Ok(());
+            return Ok(TokenClass::Locale);
         }
 
-        // Unknown bracket content - treat as literal (or ignore)
-        Ok(())
+        // Try to parse as East Asian numeral style
+        if let Some(style) = try_parse_dbnum(content) {
+            builder.add_part(FormatPart::DbNum(style));
+            return Ok(TokenClass::Locale);
+        }
+
+        // Try to parse as native numeral style
+        if let Some(style) = try_parse_natnum(content) {
+            builder.add_part(FormatPart::NatNum(style));
+            return Ok(TokenClass::Locale);
+        }
+
+        // Numeral styles Excel or LibreOffice accept but we don't implement
+        if let Some(feature) = unsupported_numeral_style(content) {
+            if !self.lenient {
+                return Err(ParseError::Unsupported { feature });
+            }
+            self.warnings.push(ParseWarning::Unsupported {
+                span: bracket_start..bracket_end,
+                feature,
+            });
+            return Ok(TokenClass::Unknown);
+        }
+
+        // Unknown bracket content - ignore
+        self.warnings.push(ParseWarning::UnknownBracket {
+            span: bracket_start..bracket_end,
+            text: content.to_string(),
+        });
+        Ok(TokenClass::Unknown)
     }
 
     /// Count consecutive tokens of the same type and advance past them.
@@ -553,31 +965,30 @@ impl<'a> Parser<'a> {
         std::mem::discriminant(&self.current.token) == std::mem::discriminant(token_type)
     }
 
-    /// Check if there are seconds tokens appearing immediately after in a time context.
-    /// Used to disambiguate mm:ss (minutes:seconds) from mm-dd (month-day).
-    /// Returns true if 's' or 'S' appears next (with or without colon), indicating time format.
-    /// Note: Called while current token is a Month token, so need to skip past 'm'/'M' chars.
-    fn has_seconds_ahead(&self) -> bool {
-        // Look ahead in the lexer's remaining input, starting from current position
-        let mut remaining = &self.lexer.input[self.current.start..];
-
-        // Skip past 'm' or 'M' characters (the month/minute tokens we're currently at)
-        remaining = remaining.trim_start_matches(['m', 'M']);
-        remaining = remaining.trim_start();
-
-        // Check for time context patterns:
-        // 1. ":s" or ":ss" - minutes:seconds with colon (e.g., "mm:ss")
-        // 2. "s" or "ss" - minutes+seconds without colon (e.g., "mmss.0")
-        if let Some(after_colon) = remaining.strip_prefix(':') {
-            // Check if 's' or 'S' follows the colon
-            if let Some(first_ch) = after_colon.chars().next() {
-                return first_ch == 's' || first_ch == 'S';
+    /// Whether the next date code in the section, after the current run of
+    /// `m` and any other `m` runs, is a second (`mm:ss`, `m"'"ss`).
+    ///
+    /// Year, day, hour and era-year codes end the search; AM/PM, eras,
+    /// Buddhist years and bracketed codes are skipped, as in SSF.
+    fn seconds_ahead(&self) -> bool {
+        let mut lexer = self.lexer.clone();
+        let mut in_bracket = false;
+        while let Ok(next) = lexer.next_token() {
+            match next.token {
+                Token::OpenBracket => in_bracket = true,
+                Token::CloseBracket => in_bracket = false,
+                Token::Eof => return false,
+                _ if in_bracket => {}
+                Token::Second => return true,
+                Token::Year
+                | Token::Day
+                | Token::Hour
+                | Token::ExponentUpper
+                | Token::ExponentLower
+                | Token::SectionSep => return false,
+                _ => {}
             }
-        } else if let Some(first_ch) = remaining.chars().next() {
-            // Check if 's' or 'S' follows immediately (without colon)
-            return first_ch == 's' || first_ch == 'S';
         }
-
         false
     }
 
@@ -615,7 +1026,7 @@ impl SectionBuilder {
         Self {
             condition: None,
             color: None,
-            parts: Vec::new(),
+            parts: Vec::with_capacity(8),
         }
     }
 
@@ -623,6 +1034,14 @@ impl SectionBuilder {
         self.parts.push(part);
     }
 
+    /// LCID from the first `[$-xxx]` locale tag so far, if any.
+    fn lcid(&self) -> Option<u32> {
+        self.parts.iter().find_map(|part| match part {
+            FormatPart::Locale(locale) => Some(locale.lcid),
+            _ => None,
+        })?
+    }
+
     fn build(mut self) -> Section {
         // Post-process to detect fraction patterns
         self.detect_fractions();
@@ -630,8 +1049,12 @@ impl SectionBuilder {
         // Post-process to detect subsecond patterns in date formats
         self.detect_subseconds();
 
+        // Both passes above look for single-character literals, so merge
+        // runs of them only now
+        self.coalesce_literals();
+
         // Compute metadata by scanning the parts once
-        let metadata = self.compute_metadata();
+        let metadata = section_metadata(&self.parts);
 
         Section {
             condition: self.condition,
@@ -641,260 +1064,106 @@ impl SectionBuilder {
         }
     }
 
-    /// Compute section metadata by scanning parts once
-    /// Based on SSF's eval_fmt in bits/82_eval.js
-    fn compute_metadata(&self) -> crate::ast::SectionMetadata {
-        use crate::ast::*;
+    /// Detect and merge fraction patterns in the parts list.
+    /// Looks for patterns like: [integer digits] [separator] digits "/" digits
+    /// and converts them to a Fraction, keeping the parts around it
+    fn detect_fractions(&mut self) {
+        let is_slash = |part: &FormatPart| {
+            matches!(
+                part,
+                FormatPart::Literal(s)
+                | FormatPart::QuotedLiteral(s)
+                | FormatPart::EscapedLiteral(s)
+                    if s == "/"
+            )
+        };
+        // Most sections have no slash; leave their parts alone
+        if !self.parts.iter().any(is_slash) {
+            return;
+        }
 
-        let mut has_ampm = false;
-        let mut is_hijri = false;
-        let mut max_subsecond_precision = None;
-        let mut has_elapsed_time = false;
-        let mut smallest_time_unit = TimeUnit::None;
-        let mut format_type = FormatType::General;
+        let mut new_parts = Vec::with_capacity(self.parts.len());
+        let mut i = 0;
 
-        // Scan parts to gather metadata
-        for part in &self.parts {
-            match part {
-                FormatPart::AmPm(_) => {
-                    has_ampm = true;
-                }
-                FormatPart::DatePart(DatePart::BuddhistYear4Alt | DatePart::BuddhistYear2Alt) => {
-                    is_hijri = true;
-                }
-                FormatPart::DatePart(DatePart::SubSecond(precision)) => {
-                    max_subsecond_precision = Some(max_subsecond_precision.unwrap_or(0).max(*precision));
-                    if smallest_time_unit < TimeUnit::Subseconds {
-                        smallest_time_unit = TimeUnit::Subseconds;
-                    }
-                }
-                FormatPart::DatePart(DatePart::Second | DatePart::Second2) => {
-                    if smallest_time_unit < TimeUnit::Seconds {
-                        smallest_time_unit = TimeUnit::Seconds;
-                    }
-                }
-                FormatPart::DatePart(DatePart::Minute | DatePart::Minute2) => {
-                    if smallest_time_unit < TimeUnit::Minutes {
-                        smallest_time_unit = TimeUnit::Minutes;
-                    }
-                }
-                FormatPart::DatePart(DatePart::Hour | DatePart::Hour2) => {
-                    if smallest_time_unit < TimeUnit::Hours {
-                        smallest_time_unit = TimeUnit::Hours;
+        while i < self.parts.len() {
+            if is_slash(&self.parts[i]) {
+                if let Some((denominator, space_after_slash, end)) = self.fraction_denominator(i) {
+                    if let Some(fraction) = take_fraction_start(&mut new_parts, denominator, space_after_slash) {
+                        new_parts.push(fraction);
+                        i = end;
+                        continue;
                     }
                 }
-                FormatPart::Elapsed(_) => {
-                    has_elapsed_time = true;
-                }
-                FormatPart::Fraction { .. } => {
-                    format_type = FormatType::Fraction;
-                }
-                FormatPart::TextPlaceholder => {
-                    format_type = FormatType::Text;
-                }
-                _ => {}
             }
+
+            // Not part of a fraction, keep the part as-is
+            new_parts.push(self.parts[i].clone());
+            i += 1;
         }
 
-        // Determine format type if not already set
-        if format_type == FormatType::General {
-            let has_date = self.parts.iter().any(|p| matches!(p, FormatPart::DatePart(_)));
-            let has_number = self.parts.iter().any(|p| matches!(p, FormatPart::Digit(_) | FormatPart::DecimalPoint));
+        self.parts = new_parts;
+    }
 
-            if has_date || has_ampm || has_elapsed_time {
-                format_type = FormatType::DateTime;
-            } else if has_number {
-                format_type = FormatType::Number;
+    /// The denominator after the slash at `slash_pos`: digit placeholders
+    /// (`??`) or a fixed number (`16`), with the spaces between the slash and
+    /// the denominator and the index just past it.
+    fn fraction_denominator(&self, slash_pos: usize) -> Option<(FractionDenom, String, usize)> {
+        // Skip any spaces after the slash
+        let mut space_after_slash = String::new();
+        let mut denom_start = slash_pos + 1;
+        while let Some(FormatPart::Literal(s) | FormatPart::QuotedLiteral(s)) =
+            self.parts.get(denom_start)
+        {
+            if !s.chars().all(|c| c == ' ') {
+                break;
             }
+            space_after_slash.push_str(s);
+            denom_start += 1;
         }
 
-        SectionMetadata {
-            has_ampm,
-            is_hijri,
-            max_subsecond_precision,
-            has_elapsed_time,
-            smallest_time_unit,
-            format_type,
+        let denom_digits = self.collect_digit_placeholders(denom_start);
+        if !denom_digits.is_empty() {
+            let denominator = FractionDenom::UpToDigits(denom_digits.len() as u8);
+            return Some((
+                denominator,
+                space_after_slash,
+                denom_start + denom_digits.len(),
+            ));
         }
-    }
 
-    /// Detect and merge fraction patterns in the parts list.
-    /// Looks for patterns like: [digits] "/" [digits] and converts to Fraction
-    fn detect_fractions(&mut self) {
-        let mut new_parts = Vec::new();
-        let mut i = 0;
-
-        while i < self.parts.len() {
-            // Look for a "/" literal that could be part of a fraction
-            if let Some(slash_pos) = self.find_slash_position(i) {
-                // Check if there are digit placeholders or a fixed number after the slash
-                // Skip any spaces after the slash
-                let mut denom_start = slash_pos + 1;
-                while denom_start < self.parts.len() {
-                    if let FormatPart::Literal(s) = &self.parts[denom_start] {
-                        if s.chars().all(|c| c == ' ') {
-                            denom_start += 1;
-                            continue;
-                        }
-                    }
-                    break;
-                }
-
-                let denom_digits = self.collect_digit_placeholders(denom_start);
-
-                // Also check for fixed denominator (numeric literal)
-                // Need to collect consecutive digit literals/digits to handle multi-digit numbers like "10", "16", etc.
-                let (fixed_denom, fixed_denom_len) = if denom_digits.is_empty() {
-                    let mut num_str = String::new();
-                    let mut count = 0;
-                    for i in denom_start..self.parts.len() {
-                        match &self.parts[i] {
-                            FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) if s.len() == 1 && s.chars().next().unwrap().is_ascii_digit() => {
-                                // Single digit literal like "1", "6"
-                                num_str.push_str(s);
-                                count += 1;
-                            }
-                            FormatPart::Digit(DigitPlaceholder::Zero) => {
-                                // "0" token - can be part of a fixed denominator number
-                                num_str.push('0');
-                                count += 1;
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
-                    }
-                    if !num_str.is_empty() {
-                        (num_str.parse::<u32>().ok(), count)
-                    } else {
-                        (None, 0)
-                    }
-                } else {
-                    (None, 0)
-                };
-
-                if !denom_digits.is_empty() || fixed_denom.is_some() {
-                    // Found denominator, now look for numerator before slash
-                    // Skip any spaces before the slash
-                    let mut num_search_pos = slash_pos;
-                    while num_search_pos > 0 {
-                        if let FormatPart::Literal(s) = &self.parts[num_search_pos - 1] {
-                            if s.chars().all(|c| c == ' ') {
-                                num_search_pos -= 1;
-                                continue;
-                            }
-                        }
-                        break;
-                    }
-
-                    let num_end = num_search_pos;
-                    if num_end > 0 {
-                        let num_digits = self.collect_digit_placeholders_reverse(num_end - 1);
-
-                        if !num_digits.is_empty() {
-                            // Found numerator, now collect any integer part before that
-                            let num_start = num_end - num_digits.len();
-                            let mut int_digits = if num_start > 0 {
-                                self.collect_integer_part(num_start - 1, &mut new_parts)
-                            } else {
-                                Vec::new()
-                            };
-
-                            // Check if this is a mixed fraction or improper fraction
-                            // Mixed fraction: has space between integer and numerator (e.g., "# ??/??")
-                            // Improper fraction: no space, all digits before slash are numerator (e.g., "#0#00??/??")
-                            let has_space_before_numerator = num_start > 0 && {
-                                if let Some(FormatPart::Literal(s) | FormatPart::EscapedLiteral(s)) = self.parts.get(num_start - 1) {
-                                    s == " "
-                                } else {
-                                    false
-                                }
-                            };
-
-                            // For improper fractions, combine int_digits and num_digits into numerator
-                            let (final_int_digits, final_num_digits) = if !has_space_before_numerator && !int_digits.is_empty() {
-                                // Improper fraction: ALL digits before slash are numerator digits
-                                int_digits.extend(num_digits);
-                                (Vec::new(), int_digits)
-                            } else {
-                                // Mixed fraction: keep them separate
-                                (int_digits, num_digits)
-                            };
-
-                            // Check for spaces before slash (between numerator and slash)
-                            let space_before_slash = if num_start > 0 && slash_pos > num_start {
-                                if let FormatPart::Literal(s) = &self.parts[slash_pos - 1] {
-                                    if s.chars().all(|c| c == ' ') {
-                                        s.clone()
-                                    } else {
-                                        String::new()
-                                    }
-                                } else {
-                                    String::new()
-                                }
-                            } else {
-                                String::new()
-                            };
-
-                            // Check for spaces after slash (between slash and denominator)
-                            let space_after_slash = if slash_pos + 1 < denom_start {
-                                if let FormatPart::Literal(s) = &self.parts[slash_pos + 1] {
-                                    if s.chars().all(|c| c == ' ') {
-                                        s.clone()
-                                    } else {
-                                        String::new()
-                                    }
-                                } else {
-                                    String::new()
-                                }
-                            } else {
-                                String::new()
-                            };
-
-                            // Create fraction part
-                            let denominator = if let Some(fixed) = fixed_denom {
-                                crate::ast::FractionDenom::Fixed(fixed)
-                            } else {
-                                crate::ast::FractionDenom::UpToDigits(denom_digits.len() as u8)
-                            };
-
-                            let fraction = FormatPart::Fraction {
-                                integer_digits: final_int_digits,
-                                numerator_digits: final_num_digits,
-                                denominator,
-                                space_before_slash,
-                                space_after_slash,
-                            };
-                            new_parts.push(fraction);
-
-                            // Skip past all the parts we consumed
-                            let skip_count = if fixed_denom.is_some() {
-                                fixed_denom_len // Skip all the fixed denominator literals
-                            } else {
-                                denom_digits.len() // Skip all denominator digit placeholders
-                            };
-                            i = denom_start + skip_count;
-                            continue;
-                        }
-                    }
-                }
-            }
-
-            // Not part of a fraction, keep the part as-is
-            if i < self.parts.len() {
-                new_parts.push(self.parts[i].clone());
-                i += 1;
+        // Fixed denominator: consecutive digit literals and "0" tokens, to handle
+        // multi-digit numbers like "10", "16", etc.
+        let mut num_str = String::new();
+        for part in &self.parts[denom_start..] {
+            match part {
+                FormatPart::Literal(s)
+                | FormatPart::QuotedLiteral(s)
+                | FormatPart::EscapedLiteral(s)
+                    if s.len() == 1 && s.chars().next().unwrap().is_ascii_digit() =>
+                {
+                    num_str.push_str(s);
+                }
+                FormatPart::Digit(DigitPlaceholder::Zero) => num_str.push('0'),
+                _ => break,
             }
         }
-
-        self.parts = new_parts;
+        let fixed = num_str.parse::<u32>().ok()?;
+        Some((
+            FractionDenom::Fixed(fixed),
+            space_after_slash,
+            denom_start + num_str.len(),
+        ))
     }
 
     /// Detect and convert subsecond patterns in date formats.
     /// Looks for DecimalPoint followed by Digit(Zero) placeholders after date/time parts
     /// and converts them to Literal(".") + DatePart::SubSecond(n).
     fn detect_subseconds(&mut self) {
-        let mut new_parts = Vec::new();
+        if !self.parts.iter().any(|p| matches!(p, FormatPart::DecimalPoint)) {
+            return;
+        }
+
+        let mut new_parts = Vec::with_capacity(self.parts.len());
         let mut i = 0;
 
         while i < self.parts.len() {
@@ -932,14 +1201,25 @@ impl SectionBuilder {
         self.parts = new_parts;
     }
 
-    /// Find position of "/" literal starting from index
-    fn find_slash_position(&self, start: usize) -> Option<usize> {
-        for i in start..self.parts.len() {
-            if matches!(&self.parts[i], FormatPart::Literal(s) | FormatPart::EscapedLiteral(s) if s == "/") {
-                return Some(i);
+    /// Merge adjacent literals, such as the characters of an unquoted ` kg`,
+    /// into one part. The `.` before sub-second digits stays on its own,
+    /// since it marks them as sub-seconds when the code is written back.
+    fn coalesce_literals(&mut self) {
+        let mut merged: Vec<FormatPart> = Vec::with_capacity(self.parts.len());
+        let mut parts = std::mem::take(&mut self.parts).into_iter().peekable();
+        while let Some(part) = parts.next() {
+            let before_subsecond = matches!(
+                parts.peek(),
+                Some(FormatPart::DatePart(DatePart::SubSecond(_)))
+            );
+            match (merged.last_mut(), part) {
+                (Some(FormatPart::Literal(prev)), FormatPart::Literal(s)) if !before_subsecond => {
+                    prev.push_str(&s)
+                }
+                (_, part) => merged.push(part),
             }
         }
-        None
+        self.parts = merged;
     }
 
     /// Collect consecutive digit placeholders starting from index
@@ -954,72 +1234,102 @@ impl SectionBuilder {
         }
         digits
     }
+}
 
-    /// Collect consecutive digit placeholders in reverse from index
-    fn collect_digit_placeholders_reverse(&self, end: usize) -> Vec<DigitPlaceholder> {
-        let mut digits = Vec::new();
-        let mut i = end as isize;
-        while i >= 0 {
-            if let Some(FormatPart::Digit(d)) = self.parts.get(i as usize) {
-                digits.push(*d);
-            } else {
-                break;
-            }
-            i -= 1;
-        }
-        digits.reverse();
-        digits
-    }
-
-    /// Collect integer part before numerator (digits before a space typically)
-    fn collect_integer_part(&self, end: usize, new_parts: &mut Vec<FormatPart>) -> Vec<DigitPlaceholder> {
-        let mut int_digits = Vec::new();
-
-        // Scan backwards from end to find digit placeholders
-        let mut i = end as isize;
-        let mut found_space = false;
+/// Take the numerator, and the integer part of a mixed fraction, off the end
+/// of `parts` and build the fraction they start. Returns `None`, leaving
+/// `parts` unchanged, if no numerator digits precede the slash.
+///
+/// The integer part is the run of digit placeholders before the literal text
+/// that separates it from the numerator (`# ?/?`, `0" and "?/?`). Digits right
+/// next to the numerator belong to it (`#0#00??/??` is an improper fraction).
+fn take_fraction_start(
+    parts: &mut Vec<FormatPart>,
+    denominator: FractionDenom,
+    space_after_slash: String,
+) -> Option<FormatPart> {
+    let is_space = |p: &FormatPart| {
+        matches!(
+            p,
+            FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) if s.chars().all(|c| c == ' ')
+        )
+    };
+    let is_literal = |p: &FormatPart| {
+        matches!(
+            p,
+            FormatPart::Literal(_) | FormatPart::QuotedLiteral(_) | FormatPart::EscapedLiteral(_)
+        )
+    };
+    let is_digit = |p: &FormatPart| matches!(p, FormatPart::Digit(_));
+    // Start of the run of parts matching `pred` that ends at `end`
+    let run_start = |parts: &[FormatPart], end: usize, pred: &dyn Fn(&FormatPart) -> bool| {
+        parts[..end]
+            .iter()
+            .rposition(|p| !pred(p))
+            .map_or(0, |i| i + 1)
+    };
 
-        while i >= 0 {
-            match &self.parts.get(i as usize) {
-                Some(FormatPart::Digit(d)) => {
-                    int_digits.push(*d);
-                }
-                Some(FormatPart::Literal(s) | FormatPart::EscapedLiteral(s)) if s == " " => {
-                    // Found a space - this indicates a mixed fraction
-                    found_space = true;
-                    if !int_digits.is_empty() {
-                        // Already collected some integer digits, we're done
-                        break;
-                    }
-                    // Haven't collected integer digits yet, continue scanning backwards
-                }
-                Some(FormatPart::ThousandsSeparator) if !int_digits.is_empty() => {
-                    // Allow thousands separator in integer part
-                }
-                Some(FormatPart::Literal(_) | FormatPart::EscapedLiteral(_)) if int_digits.is_empty() => {
-                    // Haven't started collecting digits yet, and it's not a space, keep this part
-                }
-                _ => {
-                    if !int_digits.is_empty() {
-                        break;
-                    }
-                }
+    let space_start = run_start(parts, parts.len(), &is_space);
+    let num_start = run_start(parts, space_start, &is_digit);
+    if num_start == space_start {
+        return None;
+    }
+    let sep_start = run_start(parts, num_start, &is_literal);
+
+    // Integer digits may contain thousands separators after the first digit
+    let mut int_start = sep_start;
+    while int_start > 0 {
+        match &parts[int_start - 1] {
+            FormatPart::Digit(_) => int_start -= 1,
+            FormatPart::ThousandsSeparator if parts[int_start..sep_start].iter().any(is_digit) => {
+                int_start -= 1
             }
-            i -= 1;
-        }
-
-        // If we found integer digits, remove them from new_parts
-        if !int_digits.is_empty() && found_space {
-            int_digits.reverse();
-            // Remove parts from where integer starts
-            let remove_from = (i + 1) as usize;
-            new_parts.truncate(remove_from);
-        } else {
-            int_digits.clear();
+            _ => break,
         }
-
-        int_digits
     }
+    let is_mixed = sep_start < num_start && int_start < sep_start;
+
+    let literal_text = |parts: &[FormatPart]| -> String {
+        parts
+            .iter()
+            .filter_map(|p| match p {
+                FormatPart::Literal(s)
+                | FormatPart::QuotedLiteral(s)
+                | FormatPart::EscapedLiteral(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect()
+    };
+    let digits = |parts: &[FormatPart]| -> Vec<DigitPlaceholder> {
+        parts
+            .iter()
+            .filter_map(|p| match p {
+                FormatPart::Digit(d) => Some(*d),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let space_before_slash = literal_text(&parts[space_start..]);
+    let numerator_digits = digits(&parts[num_start..space_start]);
+    let (integer_digits, integer_separator) = if is_mixed {
+        (
+            digits(&parts[int_start..sep_start]),
+            literal_text(&parts[sep_start..num_start]),
+        )
+    } else {
+        (Vec::new(), String::new())
+    };
+    parts.truncate(if is_mixed { int_start } else { num_start });
+
+    Some(FormatPart::Fraction {
+        integer_digits,
+        integer_separator,
+        numerator_digits,
+        denominator,
+        space_before_slash,
+        space_after_slash,
+    })
 }
 
 /// Parse AM/PM style from the matched string.
@@ -1031,6 +1341,7 @@ fn parse_am_pm_style(s: &str) -> AmPmStyle {
         "am/p" => AmPmStyle::MalformedLower,
         "A/P" => AmPmStyle::ShortUpper,
         "a/p" => AmPmStyle::ShortLower,
+        "上午/下午" => AmPmStyle::Chinese,
         // Default to upper for mixed case
         _ => {
             if s.len() == 4 {
@@ -1076,43 +1387,56 @@ fn try_parse_color(content: &str) -> Option<Color> {
 }
 
 /// Try to parse bracket content as a condition.
-fn try_parse_condition(content: &str) -> Option<Condition> {
+///
+/// Returns `None` if the content doesn't start with a comparison, and the
+/// reason if the number after it can't be read.
+fn try_parse_condition(content: &str) -> Option<Result<Condition, String>> {
     let content = content.trim();
 
     // Parse conditions like >=, <=, <>, >, <, =
-    if let Some(value_str) = content.strip_prefix(">=") {
-        if let Ok(value) = value_str.trim().parse::<f64>() {
-            return Some(Condition::GreaterOrEqual(value));
-        }
-    } else if let Some(value_str) = content.strip_prefix("<=") {
-        if let Ok(value) = value_str.trim().parse::<f64>() {
-            return Some(Condition::LessOrEqual(value));
-        }
-    } else if let Some(value_str) = content.strip_prefix("<>") {
-        if let Ok(value) = value_str.trim().parse::<f64>() {
-            return Some(Condition::NotEqual(value));
-        }
-    } else if let Some(value_str) = content.strip_prefix('>') {
-        if let Ok(value) = value_str.trim().parse::<f64>() {
-            return Some(Condition::GreaterThan(value));
-        }
-    } else if let Some(value_str) = content.strip_prefix('<') {
-        if let Ok(value) = value_str.trim().parse::<f64>() {
-            return Some(Condition::LessThan(value));
-        }
-    } else if let Some(value_str) = content.strip_prefix('=') {
-        if let Ok(value) = value_str.trim().parse::<f64>() {
-            return Some(Condition::Equal(value));
-        }
-    }
+    let operators = [
+        (">=", Condition::GreaterOrEqual as fn(f64) -> Condition),
+        ("<=", Condition::LessOrEqual),
+        ("<>", Condition::NotEqual),
+        (">", Condition::GreaterThan),
+        ("<", Condition::LessThan),
+        ("=", Condition::Equal),
+    ];
+    let (condition, value_str) = operators.into_iter().find_map(|(operator, condition)| {
+        content
+            .strip_prefix(operator)
+            .map(|value_str| (condition, value_str.trim()))
+    })?;
+    Some(
+        parse_condition_value(value_str)
+            .map(condition)
+            .ok_or_else(|| format!("'{value_str}' is not a number")),
+    )
+}
 
-    None
+/// Parse the number in a condition: a decimal such as `-1.5`, or scientific
+/// notation such as `1e5` or `2.5E-3`.
+///
+/// The decimal separator may also be a comma, as in `[>=1,5]` from codes
+/// written in comma-decimal locales. Condition values have no thousands
+/// separators, so `[>1,500]` means 1.5. Words that `f64::from_str` would
+/// take, such as `inf` and `NaN`, are not numbers here.
+fn parse_condition_value(value_str: &str) -> Option<f64> {
+    let is_numeric = value_str
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b',' | b'+' | b'-' | b'e' | b'E'));
+    if !is_numeric || value_str.contains('.') && value_str.contains(',') {
+        return None;
+    }
+    value_str.replacen(',', ".", 1).parse().ok()
 }
 
 /// Try to parse bracket content as elapsed time.
 fn try_parse_elapsed(content: &str) -> Option<ElapsedPart> {
     let lower = content.to_lowercase();
     match lower.as_str() {
+        "d" => Some(ElapsedPart::Days),
+        "dd" => Some(ElapsedPart::Days2),
         "h" => Some(ElapsedPart::Hours),
         "hh" => Some(ElapsedPart::Hours2),
         "m" => Some(ElapsedPart::Minutes),
@@ -1123,6 +1447,48 @@ fn try_parse_elapsed(content: &str) -> Option<ElapsedPart> {
     }
 }
 
+/// Whether bracket content changes how digits are written, so a following
+/// General must go through the full parser to keep it.
+fn is_numeral_modifier(content: &str) -> bool {
+    try_parse_dbnum(content).is_some()
+        || try_parse_natnum(content).is_some()
+        || unsupported_numeral_style(content).is_some()
+        || try_parse_locale(content).is_some_and(|locale| {
+            locale
+                .lcid
+                .and_then(crate::locale::digits::numeral_system_zero)
+                .is_some()
+        })
+}
+
+/// Try to parse bracket content as a `DBNum1`-`DBNum3` numeral style.
+fn try_parse_dbnum(content: &str) -> Option<u8> {
+    let lower = content.to_lowercase();
+    let style = lower.strip_prefix("dbnum")?.parse::<u8>().ok()?;
+    (1..=3).contains(&style).then_some(style)
+}
+
+/// Try to parse bracket content as a `NatNum0`-`NatNum11` numeral style.
+fn try_parse_natnum(content: &str) -> Option<u8> {
+    let lower = content.to_lowercase();
+    let style = lower.strip_prefix("natnum")?.parse::<u8>().ok()?;
+    (style <= 11).then_some(style)
+}
+
+/// The feature named by a numeral style bracket we recognize but cannot
+/// format: `[DBNum4]`-`[DBNum9]` and LibreOffice's spelled-out
+/// `[NatNum12]`.
+fn unsupported_numeral_style(content: &str) -> Option<&'static str> {
+    let lower = content.to_lowercase();
+    if let Some(style) = lower.strip_prefix("dbnum") {
+        return matches!(style.parse::<u8>(), Ok(4..=9)).then_some("[DBNum4]-[DBNum9]");
+    }
+    let style = lower.strip_prefix("natnum")?;
+    // [NatNum12 cardinal], [NatNum12 ordinal-number] and friends
+    let number = style.split(' ').next().unwrap_or(style);
+    (number == "12").then_some("[NatNum12]")
+}
+
 /// Try to parse bracket content as a locale code.
 fn try_parse_locale(content: &str) -> Option<LocaleCode> {
     // Locale codes start with $ e.g., [$-409], [$€-407]
@@ -1203,32 +1569,57 @@ mod tests {
     fn test_try_parse_condition() {
         assert!(matches!(
             try_parse_condition(">100"),
-            Some(Condition::GreaterThan(n)) if (n - 100.0).abs() < f64::EPSILON
+            Some(Ok(Condition::GreaterThan(n))) if (n - 100.0).abs() < f64::EPSILON
         ));
         assert!(matches!(
             try_parse_condition("<0"),
-            Some(Condition::LessThan(n)) if n.abs() < f64::EPSILON
+            Some(Ok(Condition::LessThan(n))) if n.abs() < f64::EPSILON
         ));
         assert!(matches!(
             try_parse_condition(">=50"),
-            Some(Condition::GreaterOrEqual(n)) if (n - 50.0).abs() < f64::EPSILON
+            Some(Ok(Condition::GreaterOrEqual(n))) if (n - 50.0).abs() < f64::EPSILON
         ));
         assert!(matches!(
             try_parse_condition("<=10"),
-            Some(Condition::LessOrEqual(n)) if (n - 10.0).abs() < f64::EPSILON
+            Some(Ok(Condition::LessOrEqual(n))) if (n - 10.0).abs() < f64::EPSILON
         ));
         assert!(matches!(
             try_parse_condition("=5"),
-            Some(Condition::Equal(n)) if (n - 5.0).abs() < f64::EPSILON
+            Some(Ok(Condition::Equal(n))) if (n - 5.0).abs() < f64::EPSILON
         ));
         assert!(matches!(
             try_parse_condition("<>0"),
-            Some(Condition::NotEqual(n)) if n.abs() < f64::EPSILON
+            Some(Ok(Condition::NotEqual(n))) if n.abs() < f64::EPSILON
         ));
     }
 
+    #[test]
+    fn test_try_parse_condition_value_notation() {
+        assert_eq!(
+            try_parse_condition(">1e5"),
+            Some(Ok(Condition::GreaterThan(100_000.0)))
+        );
+        assert_eq!(
+            try_parse_condition("<=-2.5E-3"),
+            Some(Ok(Condition::LessOrEqual(-0.0025)))
+        );
+        assert_eq!(
+            try_parse_condition(">=1,5"),
+            Some(Ok(Condition::GreaterOrEqual(1.5)))
+        );
+        assert_eq!(
+            try_parse_condition("> inf"),
+            Some(Err("'inf' is not a number".to_string()))
+        );
+        assert!(matches!(try_parse_condition("<1.5,0"), Some(Err(_))));
+        assert!(matches!(try_parse_condition("=abc"), Some(Err(_))));
+        assert_eq!(try_parse_condition("Red"), None);
+    }
+
     #[test]
     fn test_try_parse_elapsed() {
+        assert!(matches!(try_parse_elapsed("d"), Some(ElapsedPart::Days)));
+        assert!(matches!(try_parse_elapsed("DD"), Some(ElapsedPart::Days2)));
         assert!(matches!(try_parse_elapsed("h"), Some(ElapsedPart::Hours)));
         assert!(matches!(try_parse_elapsed("hh"), Some(ElapsedPart::Hours2)));
         assert!(matches!(try_parse_elapsed("m"), Some(ElapsedPart::Minutes)));
@@ -1,7 +1,21 @@
 //! Token types for the format code lexer.
+//!
+//! [`tokenize`](crate::tokenize) splits a format code into these tokens for
+//! tools such as syntax highlighters.
+//!
+//! # Stability
+//!
+//! The names returned by [`Token::as_str`] and [`TokenCategory::as_str`]
+//! are stable. New tokens and categories may be added in minor releases,
+//! so both enums are `#[non_exhaustive]`. Inside brackets, letters other
+//! than `E` lex as [`Token::Literal`], so `[Red]` is a bracket around three
+//! literals; what a bracket means is decided by the parser.
+
+use std::ops::Range;
 
 /// A token in a format code string.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum Token {
     // Literals
     Literal(char),
@@ -41,6 +55,8 @@ pub enum Token {
     Second, // s
     BuddhistYear,      // b (lowercase)
     BuddhistYearUpper, // B (uppercase)
+    Era,               // g (Japanese era)
+    EraYear,           // r (Japanese era year)
 
     // Brackets
     OpenBracket,  // [
@@ -56,10 +72,137 @@ pub enum Token {
     Eof,
 }
 
+impl Token {
+    /// Stable kebab-case name, e.g. `"quoted-string"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Token::Literal(_) => "literal",
+            Token::EscapedChar(_) => "escaped-char",
+            Token::QuotedString(_) => "quoted-string",
+            Token::Zero => "zero",
+            Token::Hash => "hash",
+            Token::Question => "question",
+            Token::DecimalPoint => "decimal-point",
+            Token::ThousandsSep => "thousands-separator",
+            Token::SectionSep => "section-separator",
+            Token::Percent => "percent",
+            Token::At => "at",
+            Token::Asterisk => "asterisk",
+            Token::Underscore => "underscore",
+            Token::ExponentUpper => "exponent-upper",
+            Token::ExponentLower => "exponent-lower",
+            Token::Plus => "plus",
+            Token::Minus => "minus",
+            Token::Slash => "slash",
+            Token::Year => "year",
+            Token::Month => "month",
+            Token::Day => "day",
+            Token::Hour => "hour",
+            Token::Second => "second",
+            Token::BuddhistYear => "buddhist-year",
+            Token::BuddhistYearUpper => "buddhist-year-upper",
+            Token::Era => "era",
+            Token::EraYear => "era-year",
+            Token::OpenBracket => "open-bracket",
+            Token::CloseBracket => "close-bracket",
+            Token::AmPm(_) => "am-pm",
+            Token::General => "general",
+            Token::Eof => "eof",
+        }
+    }
+
+    /// The broad kind of this token, for choosing a highlighting style.
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            Token::Literal(_) | Token::EscapedChar(_) | Token::QuotedString(_) => {
+                TokenCategory::Literal
+            }
+            Token::Zero | Token::Hash | Token::Question | Token::At => TokenCategory::Placeholder,
+            Token::DecimalPoint
+            | Token::ThousandsSep
+            | Token::Percent
+            | Token::Plus
+            | Token::Minus
+            | Token::Slash => TokenCategory::Punctuation,
+            Token::ExponentUpper | Token::ExponentLower => TokenCategory::Exponent,
+            Token::Year
+            | Token::Month
+            | Token::Day
+            | Token::Hour
+            | Token::Second
+            | Token::BuddhistYear
+            | Token::BuddhistYearUpper
+            | Token::Era
+            | Token::EraYear
+            | Token::AmPm(_) => TokenCategory::DateTime,
+            Token::Asterisk | Token::Underscore => TokenCategory::Layout,
+            Token::OpenBracket | Token::CloseBracket => TokenCategory::Bracket,
+            Token::SectionSep => TokenCategory::SectionSeparator,
+            Token::General => TokenCategory::Keyword,
+            Token::Eof => TokenCategory::End,
+        }
+    }
+}
+
+/// The broad kind of a [`Token`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenCategory {
+    /// Text shown as is: quoted strings, escapes and plain characters
+    Literal,
+    /// Digit placeholders `0`, `#` and `?`, and the text placeholder `@`
+    Placeholder,
+    /// `.`, `,`, `%`, `/`, `+` and `-`
+    Punctuation,
+    /// `E` or `e` in scientific notation
+    Exponent,
+    /// Date and time codes, including AM/PM markers (`m` is either month or
+    /// minute; the lexer cannot tell)
+    DateTime,
+    /// `*` (repeat the next character) and `_` (space the width of the next
+    /// character)
+    Layout,
+    /// `[` and `]` around colors, conditions, locales and elapsed time
+    Bracket,
+    /// `;` between sections
+    SectionSeparator,
+    /// `General`
+    Keyword,
+    /// End of input
+    End,
+}
+
+impl TokenCategory {
+    /// Stable kebab-case name, e.g. `"date-time"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenCategory::Literal => "literal",
+            TokenCategory::Placeholder => "placeholder",
+            TokenCategory::Punctuation => "punctuation",
+            TokenCategory::Exponent => "exponent",
+            TokenCategory::DateTime => "date-time",
+            TokenCategory::Layout => "layout",
+            TokenCategory::Bracket => "bracket",
+            TokenCategory::SectionSeparator => "section-separator",
+            TokenCategory::Keyword => "keyword",
+            TokenCategory::End => "end",
+        }
+    }
+}
+
 /// A token with its position in the source.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SpannedToken {
     pub token: Token,
+    /// Byte offset of the first character
     pub start: usize,
+    /// Byte offset just past the last character
     pub end: usize,
 }
+
+impl SpannedToken {
+    /// Byte range of the token in the format code.
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
@@ -0,0 +1,97 @@
+//! Formatting a fixed set of sample values, for "Format Cells" previews.
+
+use crate::ast::{Color, NumberFormat};
+use crate::error::ParseError;
+use crate::options::FormatOptions;
+use crate::value::Value;
+
+/// The values [`preview`] formats: a positive and a negative number, zero,
+/// a fraction, a date and time (2024-03-15 13:30 in the 1900 date system)
+/// and text.
+const SAMPLES: [Value<'static>; 6] = [
+    Value::Number(1234.567),
+    Value::Number(-1234.567),
+    Value::Number(0.0),
+    Value::Number(0.5),
+    Value::Number(45366.5625),
+    Value::Text("text"),
+];
+
+/// Sample values formatted with one format code, from [`preview`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatPreview {
+    /// One entry per sample value, in a fixed order
+    pub samples: Vec<PreviewSample>,
+}
+
+/// One sample value and how a format code shows it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewSample {
+    /// The value that was formatted
+    pub value: Value<'static>,
+    /// The formatted text
+    pub text: String,
+    /// Index of the section that formatted the value; `None` for text
+    /// shown unchanged because the code has no text section
+    pub section: Option<usize>,
+    /// Color of that section, if any
+    pub color: Option<Color>,
+}
+
+impl FormatPreview {
+    /// Indices of the sections used by any sample, in ascending order.
+    pub fn sections_used(&self) -> Vec<usize> {
+        let mut used: Vec<usize> = self.samples.iter().filter_map(|s| s.section).collect();
+        used.sort_unstable();
+        used.dedup();
+        used
+    }
+}
+
+impl NumberFormat {
+    /// Format the standard sample values with this format; see [`preview`].
+    pub fn preview(&self, opts: &FormatOptions) -> FormatPreview {
+        let samples = SAMPLES
+            .into_iter()
+            .map(|value| {
+                let section = match value {
+                    Value::Number(n) => Some(self.section_for(n).0),
+                    _ => self
+                        .text_section_index()
+                        .filter(|&index| !self.sections()[index].parts.is_empty()),
+                };
+                PreviewSample {
+                    text: self.format_value(&value, opts),
+                    color: section.and_then(|index| self.sections()[index].color),
+                    value,
+                    section,
+                }
+            })
+            .collect();
+        FormatPreview { samples }
+    }
+}
+
+/// Format a canonical set of sample values with a format code, for the
+/// preview pane of a format editor.
+///
+/// The samples are 1234.567, -1234.567, 0, 0.5, the date serial 45366.5625
+/// and the text `"text"`, in that order. Each result records the section
+/// that formatted it, so an editor can show which sections a code reaches.
+/// Unlike [`format`](crate::format), the code is not added to the
+/// [`global_cache`](crate::global_cache), since codes being typed are seldom
+/// reused.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{preview, FormatOptions};
+///
+/// let preview = preview("#,##0.00;[Red](#,##0.00)", &FormatOptions::default()).unwrap();
+/// let texts: Vec<_> = preview.samples.iter().map(|s| s.text.as_str()).collect();
+/// assert_eq!(texts, ["1,234.57", "(1,234.57)", "0.00", "0.50", "45,366.56", "text"]);
+/// assert_eq!(preview.samples[1].section, Some(1));
+/// assert_eq!(preview.sections_used(), [0, 1]);
+/// ```
+pub fn preview(format_code: &str, opts: &FormatOptions) -> Result<FormatPreview, ParseError> {
+    Ok(NumberFormat::parse(format_code)?.preview(opts))
+}
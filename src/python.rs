@@ -0,0 +1,187 @@
+//! Python bindings (requires `python` feature).
+//!
+//! A `ssfmt` extension module built with PyO3, for the cells openpyxl
+//! reads but cannot display. Build it with `maturin build --release`,
+//! which picks up the settings in `pyproject.toml`.
+//!
+//! ```python
+//! import ssfmt
+//!
+//! ssfmt.format(1234.5, "#,##0.00")                 # '1,234.50'
+//! ssfmt.format(1234.5, "#,##0.00", locale="de-DE") # '1.234,50'
+//! fmt = ssfmt.NumberFormat("yyyy-mm-dd")
+//! fmt.format(datetime.date(2024, 3, 15))           # '2024-03-15'
+//! ```
+//!
+//! Values may be `float`, `int`, `bool`, `str`, `None`, or a `datetime`,
+//! `date`, `time` or `timedelta`. Datetimes with a `tzinfo` are shown in
+//! their own wall-clock time.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyString, PyTime,
+    PyTimeAccess,
+};
+
+use crate::compiled::CompiledFormat;
+use crate::date_serial::date_to_serial;
+use crate::locale::Locale;
+use crate::options::{DateSystem, FormatOptions};
+use crate::value::Value;
+
+/// A parsed number format code.
+#[pyclass(name = "NumberFormat", module = "ssfmt", frozen)]
+struct PyNumberFormat(CompiledFormat);
+
+#[pymethods]
+impl PyNumberFormat {
+    #[new]
+    fn new(code: &str) -> PyResult<Self> {
+        let format = CompiledFormat::parse(code).map_err(to_value_error)?;
+        Ok(Self(format))
+    }
+
+    /// Format a cell value.
+    #[pyo3(signature = (value, locale=None, date1904=false))]
+    fn format(
+        &self,
+        value: &Bound<'_, PyAny>,
+        locale: Option<&str>,
+        date1904: bool,
+    ) -> PyResult<String> {
+        let opts = options(locale, date1904)?;
+        let cell = cell_value(value, opts.date_system)?;
+        Ok(self.0.format_value(&cell.as_value(), &opts))
+    }
+
+    /// Whether the format shows dates or times.
+    #[getter]
+    fn is_date(&self) -> bool {
+        self.0.is_date_format()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("NumberFormat({:?})", self.0.to_string())
+    }
+}
+
+/// Format a cell value with a format code.
+#[pyfunction]
+#[pyo3(signature = (value, code, locale=None, date1904=false))]
+fn format(
+    value: &Bound<'_, PyAny>,
+    code: &str,
+    locale: Option<&str>,
+    date1904: bool,
+) -> PyResult<String> {
+    let format = crate::cache::get_or_parse(code).map_err(to_value_error)?;
+    let opts = options(locale, date1904)?;
+    let cell = cell_value(value, opts.date_system)?;
+    Ok(format.format_value(&cell.as_value(), &opts))
+}
+
+/// Whether a format code shows dates or times.
+#[pyfunction]
+fn is_date_format(code: &str) -> bool {
+    crate::is_date_format_code(code)
+}
+
+#[pymodule]
+fn ssfmt(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyNumberFormat>()?;
+    module.add_function(wrap_pyfunction!(format, module)?)?;
+    module.add_function(wrap_pyfunction!(is_date_format, module)?)?;
+    Ok(())
+}
+
+fn to_value_error(err: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+fn options(locale: Option<&str>, date1904: bool) -> PyResult<FormatOptions> {
+    let locale = match locale {
+        Some(tag) => Locale::from_tag(tag)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown locale '{tag}'")))?,
+        None => Locale::default(),
+    };
+    Ok(FormatOptions {
+        date_system: if date1904 {
+            DateSystem::Date1904
+        } else {
+            DateSystem::Date1900
+        },
+        locale,
+        ..Default::default()
+    })
+}
+
+/// A Python value converted for formatting, with text copied out of the
+/// Python string.
+enum Cell {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Empty,
+}
+
+impl Cell {
+    fn as_value(&self) -> Value<'_> {
+        match self {
+            Cell::Number(n) => Value::Number(*n),
+            Cell::Text(s) => Value::Text(s),
+            Cell::Bool(b) => Value::Bool(*b),
+            Cell::Empty => Value::Empty,
+        }
+    }
+}
+
+fn cell_value(value: &Bound<'_, PyAny>, system: DateSystem) -> PyResult<Cell> {
+    if value.is_none() {
+        return Ok(Cell::Empty);
+    }
+    // bool is a subclass of int, and datetime of date, so test them first
+    if let Ok(b) = value.cast::<PyBool>() {
+        return Ok(Cell::Bool(b.is_true()));
+    }
+    if let Ok(s) = value.cast::<PyString>() {
+        return Ok(Cell::Text(s.to_str()?.to_string()));
+    }
+    if let Ok(dt) = value.cast::<PyDateTime>() {
+        return Ok(Cell::Number(date_serial(dt, system) + day_fraction(dt)));
+    }
+    if let Ok(d) = value.cast::<PyDate>() {
+        return Ok(Cell::Number(date_serial(d, system)));
+    }
+    if let Ok(t) = value.cast::<PyTime>() {
+        return Ok(Cell::Number(day_fraction(t)));
+    }
+    if let Ok(delta) = value.cast::<PyDelta>() {
+        let seconds = f64::from(delta.get_seconds()) + f64::from(delta.get_microseconds()) / 1e6;
+        let days = f64::from(delta.get_days()) + seconds / 86_400.0;
+        return Ok(Cell::Number(days));
+    }
+    match value.extract::<f64>() {
+        Ok(n) => Ok(Cell::Number(n)),
+        Err(_) => Err(PyTypeError::new_err(format!(
+            "cannot format a value of type '{}'",
+            value.get_type().name()?
+        ))),
+    }
+}
+
+fn date_serial(date: &impl PyDateAccess, system: DateSystem) -> f64 {
+    let (month, day) = (date.get_month().into(), date.get_day().into());
+    date_to_serial(date.get_year(), month, day, system)
+}
+
+fn day_fraction(time: &impl PyTimeAccess) -> f64 {
+    let seconds = f64::from(time.get_hour()) * 3600.0
+        + f64::from(time.get_minute()) * 60.0
+        + f64::from(time.get_second());
+    (seconds + f64::from(time.get_microsecond()) / 1e6) / 86_400.0
+}
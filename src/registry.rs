@@ -0,0 +1,109 @@
+//! Number format IDs for a workbook.
+
+use std::collections::HashMap;
+
+use crate::builtin_formats::format_code_from_id;
+use crate::compiled::CompiledFormat;
+use crate::error::ParseError;
+use crate::options::FormatOptions;
+
+/// Maps a workbook's `numFmtId`s to formats.
+///
+/// An xlsx `styles.xml` lists the workbook's custom formats in `<numFmts>`,
+/// usually with IDs from 164 up; cells refer to them and to the built-in
+/// formats by ID alone. Register the custom formats once, then look up or
+/// format by ID. IDs with no custom format fall back to the built-in
+/// formats of [`format_code_from_id`]. A custom format registered for a
+/// built-in ID replaces it, as it does in Excel.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{FormatOptions, FormatRegistry};
+///
+/// let mut registry = FormatRegistry::new();
+/// registry.register(164, "0.000\" kg\"").unwrap();
+///
+/// let opts = FormatOptions::default();
+/// assert_eq!(registry.format(2.5, 164, &opts).unwrap(), "2.500 kg");
+/// assert_eq!(registry.format(0.5, 9, &opts).unwrap(), "50%");
+/// assert!(registry.format(1.0, 165, &opts).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormatRegistry {
+    custom: HashMap<u32, CustomFormat>,
+}
+
+#[derive(Debug, Clone)]
+struct CustomFormat {
+    code: String,
+    format: CompiledFormat,
+}
+
+impl FormatRegistry {
+    /// Create a registry with no custom formats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the format code for a `numFmtId`, replacing any previous one.
+    ///
+    /// The code is parsed now, so an invalid code is reported here rather
+    /// than when formatting.
+    pub fn register(&mut self, id: u32, format_code: &str) -> Result<(), ParseError> {
+        let format = CompiledFormat::parse(format_code)?;
+        self.custom.insert(
+            id,
+            CustomFormat {
+                code: format_code.to_string(),
+                format,
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove the custom format for an ID, returning its format code.
+    pub fn unregister(&mut self, id: u32) -> Option<String> {
+        self.custom.remove(&id).map(|custom| custom.code)
+    }
+
+    /// The format code for an ID: the custom one if registered, else the built-in one.
+    pub fn format_code(&self, id: u32) -> Option<&str> {
+        match self.custom.get(&id) {
+            Some(custom) => Some(&custom.code),
+            None => format_code_from_id(id),
+        }
+    }
+
+    /// The parsed format for an ID.
+    ///
+    /// Built-in formats are parsed through the [`global_cache`](crate::global_cache).
+    /// Returns [`ParseError::InvalidFormatId`] for an ID that is neither
+    /// registered nor built in.
+    pub fn get(&self, id: u32) -> Result<CompiledFormat, ParseError> {
+        if let Some(custom) = self.custom.get(&id) {
+            return Ok(custom.format.clone());
+        }
+        let format_code = format_code_from_id(id).ok_or(ParseError::InvalidFormatId(id))?;
+        crate::cache::get_or_parse(format_code)
+    }
+
+    /// Format a value with the format for an ID.
+    pub fn format(&self, value: f64, id: u32, opts: &FormatOptions) -> Result<String, ParseError> {
+        Ok(self.get(id)?.format(value, opts))
+    }
+
+    /// Whether a custom format is registered for an ID.
+    pub fn contains(&self, id: u32) -> bool {
+        self.custom.contains_key(&id)
+    }
+
+    /// Number of custom formats.
+    pub fn len(&self) -> usize {
+        self.custom.len()
+    }
+
+    /// Whether no custom formats are registered.
+    pub fn is_empty(&self) -> bool {
+        self.custom.is_empty()
+    }
+}
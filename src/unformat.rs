@@ -0,0 +1,608 @@
+//! Inverse formatting: recover values from formatted text.
+//!
+//! This is the reverse of [`NumberFormat::format`]: given text a user typed or
+//! a spreadsheet displayed, find the value the format code would have shown
+//! that way. Parsing is lenient in the same places Excel's cell entry is
+//! (thousands separators, currency symbols, `%`, parentheses for negatives,
+//! alternative date separators); text that fits neither the format nor a plain
+//! number comes back as [`Value::Text`].
+
+use crate::ast::{AmPmStyle, DatePart, ElapsedPart, FormatPart, NumberFormat, Section};
+use crate::calendar::CalendarSystem;
+use crate::date_serial::{date_to_serial, serial_to_date};
+use crate::locale::Locale;
+use crate::options::FormatOptions;
+use crate::value::Value;
+
+impl NumberFormat {
+    /// Convert formatted text back into a value.
+    ///
+    /// Numbers are matched against each numeric section in turn, so text in
+    /// the negative section's style (e.g. `(500)` for `#,##0;(#,##0)`) comes
+    /// back negative. Date and time formats return the serial number in the
+    /// date system from `opts`. `TRUE`/`FALSE` become booleans, empty text
+    /// becomes [`Value::Empty`], and anything unrecognized is returned as
+    /// [`Value::Text`].
+    ///
+    /// Dates without a year cannot be resolved and are returned as text.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat, Value};
+    ///
+    /// let opts = FormatOptions::default();
+    /// let fmt = NumberFormat::parse("#,##0.00;(#,##0.00)").unwrap();
+    /// assert_eq!(fmt.parse_value("1,234.56", &opts), Value::Number(1234.56));
+    /// assert_eq!(fmt.parse_value("(500.00)", &opts), Value::Number(-500.0));
+    ///
+    /// let fmt = NumberFormat::parse("m/d/yy").unwrap();
+    /// assert_eq!(fmt.parse_value("1/9/26", &opts), Value::Number(46031.0));
+    /// ```
+    pub fn parse_value<'a>(&self, text: &'a str, opts: &FormatOptions) -> Value<'a> {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return Value::Empty;
+        }
+        if trimmed.eq_ignore_ascii_case("TRUE") {
+            return Value::Bool(true);
+        }
+        if trimmed.eq_ignore_ascii_case("FALSE") {
+            return Value::Bool(false);
+        }
+
+        let sections = self.sections();
+        let numeric_sections = sections.len().min(3);
+        for (index, section) in sections[..numeric_sections].iter().enumerate() {
+            if section.has_text_placeholder() {
+                continue;
+            }
+            let parsed = if section.has_date_parts() {
+                parse_date(trimmed, section, opts)
+            } else if index == 2 {
+                parse_zero(trimmed, section, opts)
+            } else {
+                let negate = index == 1 && section.condition.is_none();
+                parse_number(trimmed, section, negate, opts)
+            };
+            if let Some(value) = parsed {
+                return Value::Number(value);
+            }
+        }
+
+        match parse_plain_number(trimmed, opts, false) {
+            Some(value) => Value::Number(value),
+            None => Value::Text(text),
+        }
+    }
+}
+
+/// Parse text against a numeric section: strip the section's literal prefix
+/// and suffix, then read the number in between.
+fn parse_number(text: &str, section: &Section, negate: bool, opts: &FormatOptions) -> Option<f64> {
+    let (prefix, suffix) = affixes(section);
+    let core = text
+        .strip_prefix(prefix.trim())?
+        .strip_suffix(suffix.trim())?
+        .trim();
+    let has_fraction = section
+        .parts
+        .iter()
+        .any(|p| matches!(p, FormatPart::Fraction { .. }));
+    let mut value = parse_plain_number(core, opts, has_fraction)?;
+
+    // Trailing commas scale the displayed value down by 1000 each
+    value *= 1000_f64.powi(trailing_commas(section));
+
+    // The negative section shows the magnitude; its own literals carry the
+    // sign, so a second one makes the text something else
+    if negate {
+        if value < 0.0 || core.starts_with(['-', '(']) {
+            return None;
+        }
+        if value > 0.0 {
+            value = -value;
+        }
+    }
+    Some(value)
+}
+
+/// Parse text against the zero section, which may show zero as text only,
+/// like the `-` of accounting formats.
+fn parse_zero(text: &str, section: &Section, opts: &FormatOptions) -> Option<f64> {
+    let has_number = section.parts.iter().any(|part| {
+        matches!(
+            part,
+            FormatPart::Digit(_)
+                | FormatPart::Scientific { .. }
+                | FormatPart::Fraction { .. }
+                | FormatPart::GeneralNumber
+        )
+    });
+    if has_number {
+        return parse_number(text, section, false, opts);
+    }
+    // Fills and skips show as spaces between the literals
+    let (literals, _) = affixes(section);
+    let shown = |s: &str| s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    (shown(text) == shown(&literals) && !literals.trim().is_empty()).then_some(0.0)
+}
+
+/// Literal text before the first and after the last digit placeholder.
+fn affixes(section: &Section) -> (String, String) {
+    let mut prefix = String::new();
+    let mut suffix = String::new();
+    let mut seen_digit = false;
+    for part in &section.parts {
+        let literal = match part {
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => s.as_str(),
+            FormatPart::Locale(locale) => locale.currency.as_deref().unwrap_or(""),
+            FormatPart::Digit(_)
+            | FormatPart::DecimalPoint
+            | FormatPart::Scientific { .. }
+            | FormatPart::Fraction { .. }
+            | FormatPart::GeneralNumber => {
+                seen_digit = true;
+                suffix.clear();
+                continue;
+            }
+            _ => continue,
+        };
+        if seen_digit {
+            suffix.push_str(literal);
+        } else {
+            prefix.push_str(literal);
+        }
+    }
+    (prefix, suffix)
+}
+
+/// Number of scaling commas after the last digit placeholder.
+fn trailing_commas(section: &Section) -> i32 {
+    let mut count = 0;
+    for part in section.parts.iter().rev() {
+        match part {
+            FormatPart::ThousandsSeparator => count += 1,
+            FormatPart::Digit(_) | FormatPart::DecimalPoint => break,
+            _ => {}
+        }
+    }
+    count
+}
+
+/// Parse a number the way Excel's cell entry does: optional sign or
+/// parentheses, currency symbol, thousands separators, decimal separator,
+/// exponent, and any number of `%` signs.
+///
+/// Only one sign is accepted, so `--5` and `(-5)` are not numbers.
+fn parse_plain_number(text: &str, opts: &FormatOptions, allow_fraction: bool) -> Option<f64> {
+    let locale = &opts.locale;
+    let mut s = text.trim();
+    let mut sign = None;
+
+    if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        sign = Some(true);
+        s = inner.trim();
+    }
+    if let Some((negative, rest)) = strip_sign(s) {
+        if sign.replace(negative).is_some() {
+            return None;
+        }
+        s = rest;
+    }
+
+    let mut percent = 0;
+    while let Some(rest) = s.strip_suffix('%').or_else(|| s.strip_prefix('%')) {
+        percent += 1;
+        s = rest.trim();
+    }
+    s = strip_currency(s, locale);
+    // The sign may also follow the currency symbol, as in `$-5`
+    if let Some(rest) = s.strip_prefix('-') {
+        if sign.replace(true).is_some() {
+            return None;
+        }
+        s = rest.trim_start();
+    }
+    let negative = sign == Some(true);
+
+    let magnitude = if allow_fraction && s.contains('/') {
+        parse_fraction(s)?
+    } else {
+        parse_decimal(s, locale)?
+    };
+
+    let mut value = magnitude / 100_f64.powi(percent);
+    if negative {
+        value = -value;
+    }
+    Some(value)
+}
+
+/// Remove a leading `-` or `+` or a trailing `-`, returning whether it
+/// was a minus.
+fn strip_sign(s: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = s.strip_prefix('-') {
+        Some((true, rest.trim_start()))
+    } else if let Some(rest) = s.strip_prefix('+') {
+        Some((false, rest.trim_start()))
+    } else {
+        s.strip_suffix('-').map(|rest| (true, rest.trim_end()))
+    }
+}
+
+/// Remove a leading or trailing currency symbol.
+fn strip_currency<'a>(s: &'a str, locale: &Locale) -> &'a str {
+    for symbol in [locale.currency_symbol, "$", "€", "£", "¥"] {
+        if symbol.is_empty() {
+            continue;
+        }
+        if let Some(rest) = s.strip_prefix(symbol) {
+            return rest.trim_start();
+        }
+        if let Some(rest) = s.strip_suffix(symbol) {
+            return rest.trim_end();
+        }
+    }
+    s
+}
+
+/// Parse digits with locale separators and an optional exponent.
+fn parse_decimal(s: &str, locale: &Locale) -> Option<f64> {
+    let (mantissa, exponent) = match s.find(['E', 'e']) {
+        Some(pos) => (&s[..pos], Some(&s[pos + 1..])),
+        None => (s, None),
+    };
+
+    let mut normalized = String::with_capacity(s.len());
+    let mut seen_decimal = false;
+    for c in mantissa.chars() {
+        if c.is_ascii_digit() {
+            normalized.push(c);
+        } else if c == locale.decimal_separator && !seen_decimal {
+            seen_decimal = true;
+            normalized.push('.');
+        } else if c == locale.thousands_separator && !seen_decimal {
+            // Grouping is decoration only
+        } else if locale.thousands_separator == '\u{a0}' && c == ' ' && !seen_decimal {
+            // Locales grouping with a no-break space also accept a plain one
+        } else {
+            return None;
+        }
+    }
+    if !normalized.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if let Some(exponent) = exponent {
+        let exponent = exponent.strip_prefix('+').unwrap_or(exponent);
+        let digits = exponent.strip_prefix('-').unwrap_or(exponent);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        normalized.push('e');
+        normalized.push_str(exponent);
+    }
+    // Exponents like 1e400 overflow to infinity, which no cell holds
+    normalized.parse().ok().filter(|value: &f64| value.is_finite())
+}
+
+/// Parse `n/d` or `i n/d`.
+fn parse_fraction(s: &str) -> Option<f64> {
+    let (whole, fraction) = match s.rsplit_once(' ') {
+        Some((whole, fraction)) => (whole.trim().parse::<u64>().ok()? as f64, fraction),
+        None => (0.0, s),
+    };
+    let (numerator, denominator) = fraction.split_once('/')?;
+    let numerator: u64 = numerator.trim().parse().ok()?;
+    let denominator: u64 = denominator.trim().parse().ok()?;
+    if denominator == 0 {
+        return None;
+    }
+    Some(whole + numerator as f64 / denominator as f64)
+}
+
+/// Characters accepted in place of any date or time separator literal.
+const DATE_SEPARATORS: &[char] = &['/', '-', '.', ',', ':', ' '];
+
+/// Parse text against a date/time section, returning its serial number.
+fn parse_date(text: &str, section: &Section, opts: &FormatOptions) -> Option<f64> {
+    let locale = section
+        .metadata
+        .lcid
+        .and_then(crate::locale::registry::lookup_lcid)
+        .unwrap_or(&opts.locale);
+    let mut cursor = Cursor { rest: text };
+
+    let mut year = None;
+    let mut month = None;
+    let mut day = None;
+    let mut hour = 0.0;
+    let mut minute = 0.0;
+    let mut second = 0.0;
+    let mut days = 0.0;
+    let mut elapsed = false;
+    let mut is_pm = None;
+    let era_calendar = section.metadata.lcid.and_then(CalendarSystem::for_eras);
+    let japanese = era_calendar == Some(CalendarSystem::Japanese);
+    let mut era = None;
+    let mut era_year = None;
+
+    for (index, part) in section.parts.iter().enumerate() {
+        let next_is_field = matches!(
+            section.parts.get(index + 1),
+            Some(FormatPart::DatePart(_) | FormatPart::Elapsed(_))
+        );
+        match part {
+            FormatPart::DatePart(date_part) => match date_part {
+                DatePart::Year2 | DatePart::BuddhistYear2 | DatePart::BuddhistYear2Alt => {
+                    let (value, digits) = cursor.number(if next_is_field { 2 } else { 4 })?;
+                    let value = value as i32;
+                    year = Some(match date_part {
+                        _ if digits > 2 => value,
+                        DatePart::Year2 if value < 30 => 2000 + value,
+                        DatePart::Year2 => 1900 + value,
+                        // Buddhist two-digit years are only ever displayed
+                        _ => return None,
+                    });
+                }
+                DatePart::Year3 | DatePart::Year4 => year = Some(cursor.number(4)?.0 as i32),
+                DatePart::BuddhistYear4 => year = Some(cursor.number(4)?.0 as i32 - 543),
+                DatePart::BuddhistYear4Alt => year = Some(cursor.number(4)?.0 as i32 + 582),
+                // Only Japanese era names tell which era the year counts from
+                DatePart::EraLetter | DatePart::EraAbbr | DatePart::EraFull if japanese => {
+                    era = Some(crate::era::era_named(cursor.word())?);
+                }
+                DatePart::EraLetter | DatePart::EraAbbr | DatePart::EraFull
+                    if era_calendar.is_some() =>
+                {
+                    // Other calendars have a single era; longest name first
+                    let names = era_calendar?.single_era_names()?;
+                    if !names.iter().rev().any(|name| cursor.marker(name)) {
+                        return None;
+                    }
+                }
+                DatePart::EraLetter | DatePart::EraAbbr | DatePart::EraFull => {}
+                DatePart::EraYear | DatePart::EraYear2 if era_calendar.is_some() => {
+                    era_year = Some(cursor.number(if next_is_field { 2 } else { 4 })?.0 as i32);
+                }
+                DatePart::EraYear | DatePart::EraYear2 => year = Some(cursor.number(4)?.0 as i32),
+                DatePart::Month | DatePart::Month2 => month = Some(cursor.number(2)?.0 as u32),
+                DatePart::MonthAbbr | DatePart::MonthFull | DatePart::MonthLetter => {
+                    month = Some(month_from_name(cursor.word(), locale)?);
+                }
+                DatePart::Day | DatePart::Day2 => day = Some(cursor.number(2)?.0 as u32),
+                DatePart::DayAbbr | DatePart::DayFull => {
+                    // The weekday follows from the date; just consume it
+                    cursor.word();
+                }
+                DatePart::Hour | DatePart::Hour2 => hour = cursor.number(2)?.0 as f64,
+                DatePart::Minute | DatePart::Minute2 => minute = cursor.number(2)?.0 as f64,
+                DatePart::Second | DatePart::Second2 => second = cursor.number(2)?.0 as f64,
+                DatePart::SubSecond(_) => {
+                    let (value, digits) = cursor.number(9)?;
+                    second += value as f64 / 10_f64.powi(digits as i32);
+                }
+            },
+            FormatPart::Elapsed(elapsed_part) => {
+                elapsed = true;
+                let value = cursor.number(usize::MAX)?.0 as f64;
+                match elapsed_part {
+                    ElapsedPart::Days | ElapsedPart::Days2 => days = value,
+                    ElapsedPart::Hours | ElapsedPart::Hours2 => hour = value,
+                    ElapsedPart::Minutes | ElapsedPart::Minutes2 => minute = value,
+                    ElapsedPart::Seconds | ElapsedPart::Seconds2 => second = value,
+                }
+            }
+            FormatPart::AmPm(style) => {
+                is_pm = Some(parse_ampm(&mut cursor, *style, locale)?);
+            }
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s) => cursor.literal(s),
+            FormatPart::DecimalPoint => cursor.literal(&locale.decimal_separator.to_string()),
+            FormatPart::ThousandsSeparator => cursor.literal(","),
+            _ => {}
+        }
+    }
+    if !cursor.rest.trim().is_empty() {
+        return None;
+    }
+    if let (Some(calendar), Some(era_year)) = (era_calendar, era_year) {
+        year = Some(calendar.gregorian_year(era_year, era)?);
+    }
+
+    if let Some(is_pm) = is_pm {
+        if !(1.0..=12.0).contains(&hour) {
+            return None;
+        }
+        hour %= 12.0;
+        if is_pm {
+            hour += 12.0;
+        }
+    }
+    if !elapsed && (hour >= 24.0 || minute >= 60.0 || second >= 60.0) {
+        return None;
+    }
+    let time = days + (hour * 3600.0 + minute * 60.0 + second) / 86400.0;
+
+    let date = match (year, month, day) {
+        (None, None, None) => 0.0,
+        (Some(year), Some(month), day) => {
+            let day = day.unwrap_or(1);
+            let serial = date_to_serial(year, month, day, opts.date_system);
+            // Reject dates like 2/30 that the serial arithmetic would roll over
+            if serial_to_date(serial, opts.date_system) != Some((year, month, day)) {
+                return None;
+            }
+            serial
+        }
+        _ => return None,
+    };
+    Some(date + time)
+}
+
+/// Month number for a full or abbreviated month name.
+fn month_from_name(word: &str, locale: &Locale) -> Option<u32> {
+    if word.is_empty() {
+        return None;
+    }
+    let lower = word.to_lowercase();
+    locale
+        .month_names_full
+        .iter()
+        .position(|name| name.to_lowercase() == lower)
+        .or_else(|| {
+            locale.month_names_short.iter().position(|name| {
+                name.to_lowercase().trim_end_matches('.') == lower.trim_end_matches('.')
+            })
+        })
+        .map(|index| index as u32 + 1)
+}
+
+/// Read an AM/PM marker, returning whether it means PM.
+fn parse_ampm(cursor: &mut Cursor<'_>, style: AmPmStyle, locale: &Locale) -> Option<bool> {
+    let (am, pm) = match style {
+        AmPmStyle::Chinese => ("上午", "下午"),
+        _ => (locale.am_string, locale.pm_string),
+    };
+    // Designators like "a. m." contain spaces, so match them whole first
+    if cursor.marker(pm) {
+        return Some(true);
+    }
+    if cursor.marker(am) {
+        return Some(false);
+    }
+    match cursor.word().to_lowercase().as_str() {
+        "pm" | "p" => Some(true),
+        "am" | "a" => Some(false),
+        _ => None,
+    }
+}
+
+/// A position in the text being parsed.
+struct Cursor<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    /// Read up to `max` digits, returning the value and the digit count.
+    /// Fails on runs too long for a `u64`.
+    fn number(&mut self, max: usize) -> Option<(u64, usize)> {
+        self.rest = self.rest.trim_start();
+        let len = self
+            .rest
+            .bytes()
+            .take(max)
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if len == 0 {
+            return None;
+        }
+        let value = self.rest[..len].parse().ok()?;
+        self.rest = &self.rest[len..];
+        Some((value, len))
+    }
+
+    /// Read a run of letters (month names, weekdays, AM/PM markers).
+    fn word(&mut self) -> &'a str {
+        self.rest = self.rest.trim_start();
+        let len = self
+            .rest
+            .char_indices()
+            .find(|(_, c)| !c.is_alphabetic() && *c != '.')
+            .map_or(self.rest.len(), |(i, _)| i);
+        let (word, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        word
+    }
+
+    /// Consume `marker` if the text starts with it, ignoring ASCII case.
+    fn marker(&mut self, marker: &str) -> bool {
+        let rest = self.rest.trim_start();
+        let matches = rest
+            .get(..marker.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(marker));
+        if matches {
+            self.rest = &rest[marker.len()..];
+        }
+        matches
+    }
+
+    /// Consume a literal from the format code. Separators are interchangeable,
+    /// so `1-9-26` matches `m/d/yy`; other literals are skipped if absent.
+    fn literal(&mut self, literal: &str) {
+        let trimmed = literal.trim();
+        if trimmed.is_empty() {
+            return;
+        }
+        let rest = self.rest.trim_start();
+        if rest.len() >= trimmed.len()
+            && rest.is_char_boundary(trimmed.len())
+            && rest[..trimmed.len()].eq_ignore_ascii_case(trimmed)
+        {
+            self.rest = &rest[trimmed.len()..];
+        } else if trimmed.chars().all(|c| DATE_SEPARATORS.contains(&c)) {
+            if let Some(c) = rest.chars().next().filter(|c| DATE_SEPARATORS.contains(c)) {
+                self.rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_number() {
+        let opts = FormatOptions::default();
+        assert_eq!(parse_plain_number("1,234.5", &opts, false), Some(1234.5));
+        assert_eq!(parse_plain_number("($1,000)", &opts, false), Some(-1000.0));
+        assert_eq!(parse_plain_number("-$5", &opts, false), Some(-5.0));
+        assert_eq!(parse_plain_number("$-5", &opts, false), Some(-5.0));
+        assert_eq!(parse_plain_number("5-", &opts, false), Some(-5.0));
+        assert_eq!(parse_plain_number("12.5%", &opts, false), Some(0.125));
+        assert_eq!(parse_plain_number("1.5E+03", &opts, false), Some(1500.0));
+        assert_eq!(parse_plain_number("1 1/2", &opts, true), Some(1.5));
+        assert_eq!(parse_plain_number("1/2", &opts, false), None);
+        assert_eq!(parse_plain_number("abc", &opts, false), None);
+        assert_eq!(parse_plain_number("%", &opts, false), None);
+    }
+
+    #[test]
+    fn test_parse_plain_number_rejects_repeated_signs() {
+        let opts = FormatOptions::default();
+        for text in ["--5", "-$-5", "(-5)", "+-5", "-5-", "(5-)", "($-5)"] {
+            assert_eq!(parse_plain_number(text, &opts, false), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_affixes() {
+        let fmt = NumberFormat::parse("\"USD \"#,##0.00\" net\"").unwrap();
+        let (prefix, suffix) = affixes(&fmt.sections()[0]);
+        assert_eq!(prefix, "USD ");
+        assert_eq!(suffix, " net");
+    }
+
+    #[test]
+    fn test_cursor_number_limits_digits() {
+        let mut cursor = Cursor { rest: "20260109" };
+        assert_eq!(cursor.number(4), Some((2026, 4)));
+        assert_eq!(cursor.number(2), Some((1, 2)));
+        assert_eq!(cursor.number(2), Some((9, 2)));
+        assert_eq!(cursor.number(2), None);
+
+        let mut cursor = Cursor { rest: "12345678901:00" };
+        assert_eq!(cursor.number(usize::MAX), Some((12_345_678_901, 11)));
+        let mut cursor = Cursor { rest: "123456789012345678901" };
+        assert_eq!(cursor.number(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_placeholder_only_affixes_are_empty() {
+        let fmt = NumberFormat::parse("0.00").unwrap();
+        assert_eq!(affixes(&fmt.sections()[0]), (String::new(), String::new()));
+    }
+}
@@ -0,0 +1,81 @@
+//! Rejecting format codes that Excel refuses.
+//!
+//! The parser accepts many codes Excel would reject when typed into the
+//! "Format Cells" dialog, since reading workbooks should not fail on them.
+//! Writers want the opposite, so they can refuse bad input before storing it.
+
+use crate::error::{ParseWarning, ValidationError};
+
+/// Check a format code against the rules Excel enforces.
+///
+/// Besides parse errors, this rejects:
+/// - more than 4 sections
+/// - `@` in a section that also has digit placeholders or date codes
+/// - a condition in the third or fourth section
+/// - more than two sections with conditions
+/// - bracket content that is not a color, condition, elapsed time, locale
+///   or numeral modifier
+///
+/// All problems are reported, except that a parse error stops validation.
+///
+/// # Examples
+/// ```
+/// use ssfmt::{validate, ValidationError};
+///
+/// assert!(validate("#,##0.00;[Red](#,##0.00)").is_ok());
+/// assert!(validate("[>100]0;[<-100]0;0").is_ok());
+/// assert_eq!(
+///     validate("0@").unwrap_err(),
+///     [ValidationError::TextMixedWithNumber { section: 0 }]
+/// );
+/// assert_eq!(
+///     validate("0;0;[=0]0").unwrap_err(),
+///     [ValidationError::ConditionNotAllowed { section: 2 }]
+/// );
+/// ```
+pub fn validate(format_code: &str) -> Result<(), Vec<ValidationError>> {
+    let (format, warnings) =
+        crate::parser::parse_with_warnings(format_code).map_err(|err| vec![err.into()])?;
+
+    let mut errors = Vec::new();
+    for warning in warnings {
+        match warning {
+            ParseWarning::TooManySections { span, .. } => {
+                errors.push(ValidationError::TooManySections { span })
+            }
+            ParseWarning::UnknownBracket { span, text } => {
+                errors.push(ValidationError::UnknownBracket { span, text })
+            }
+            _ => {}
+        }
+    }
+
+    for (index, section) in format.sections().iter().enumerate() {
+        if section.has_text_placeholder()
+            && section
+                .parts
+                .iter()
+                .any(|p| p.is_numeric_part() || p.is_date_part())
+        {
+            errors.push(ValidationError::TextMixedWithNumber { section: index });
+        }
+        if index >= 2 && section.condition.is_some() {
+            errors.push(ValidationError::ConditionNotAllowed { section: index });
+        }
+    }
+
+    let conditions = format
+        .sections()
+        .iter()
+        .filter(|s| s.condition.is_some())
+        .count();
+    if conditions > 2 {
+        errors.push(ValidationError::TooManyConditions { count: conditions });
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
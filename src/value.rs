@@ -15,15 +15,31 @@ pub enum Value<'a> {
     /// Use this for integers larger than 2^53 that would lose precision as f64.
     #[cfg(feature = "bigint")]
     BigInt(num_bigint::BigInt),
+    /// An exact decimal number (requires `decimal` feature)
+    /// Use this for monetary values that must not pick up f64 rounding error.
+    #[cfg(feature = "decimal")]
+    Decimal(rust_decimal::Decimal),
     /// A chrono DateTime (requires `chrono` feature)
     #[cfg(feature = "chrono")]
     DateTime(chrono::NaiveDateTime),
+    /// A chrono DateTime with a UTC offset (requires `chrono` feature).
+    /// `FormatOptions::timezone_policy` decides how it becomes a serial.
+    #[cfg(feature = "chrono")]
+    ZonedDateTime(chrono::DateTime<chrono::FixedOffset>),
     /// A chrono Date (requires `chrono` feature)
     #[cfg(feature = "chrono")]
     Date(chrono::NaiveDate),
     /// A chrono Time (requires `chrono` feature)
     #[cfg(feature = "chrono")]
     Time(chrono::NaiveTime),
+    /// A date and time from the `time` crate (requires `time` feature),
+    /// converted to a serial in `FormatOptions::date_system` when formatted
+    #[cfg(feature = "time")]
+    PrimitiveDateTime(time::PrimitiveDateTime),
+    /// A civil date and time from `jiff` (requires `jiff` feature),
+    /// converted to a serial in `FormatOptions::date_system` when formatted
+    #[cfg(feature = "jiff")]
+    CivilDateTime(jiff::civil::DateTime),
 }
 
 impl<'a> From<f64> for Value<'a> {
@@ -89,6 +105,13 @@ impl<'a> From<u128> for Value<'a> {
     }
 }
 
+#[cfg(feature = "decimal")]
+impl<'a> From<rust_decimal::Decimal> for Value<'a> {
+    fn from(d: rust_decimal::Decimal) -> Self {
+        Value::Decimal(d)
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl<'a> From<chrono::NaiveDateTime> for Value<'a> {
     fn from(dt: chrono::NaiveDateTime) -> Self {
@@ -96,6 +119,15 @@ impl<'a> From<chrono::NaiveDateTime> for Value<'a> {
     }
 }
 
+/// Keeps the offset of any time zone, including `chrono::Utc`,
+/// `chrono::Local` and `chrono_tz::Tz` (requires `chrono` feature).
+#[cfg(feature = "chrono")]
+impl<'a, Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Value<'a> {
+    fn from(dt: chrono::DateTime<Tz>) -> Self {
+        Value::ZonedDateTime(dt.fixed_offset())
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl<'a> From<chrono::NaiveDate> for Value<'a> {
     fn from(d: chrono::NaiveDate) -> Self {
@@ -110,9 +142,57 @@ impl<'a> From<chrono::NaiveTime> for Value<'a> {
     }
 }
 
+#[cfg(feature = "time")]
+impl<'a> From<time::PrimitiveDateTime> for Value<'a> {
+    fn from(dt: time::PrimitiveDateTime) -> Self {
+        Value::PrimitiveDateTime(dt)
+    }
+}
+
+/// Converts to midnight of the date (requires `time` feature).
+#[cfg(feature = "time")]
+impl<'a> From<time::Date> for Value<'a> {
+    fn from(d: time::Date) -> Self {
+        time::PrimitiveDateTime::new(d, time::Time::MIDNIGHT).into()
+    }
+}
+
+/// Converts to the fraction of a day, the serial number of a time value
+/// (requires `time` feature).
+#[cfg(feature = "time")]
+impl<'a> From<time::Time> for Value<'a> {
+    fn from(t: time::Time) -> Self {
+        Value::Number(crate::date_serial::time_to_day_fraction(t))
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl<'a> From<jiff::civil::DateTime> for Value<'a> {
+    fn from(dt: jiff::civil::DateTime) -> Self {
+        Value::CivilDateTime(dt)
+    }
+}
+
+/// Converts to midnight of the date (requires `jiff` feature).
+#[cfg(feature = "jiff")]
+impl<'a> From<jiff::civil::Date> for Value<'a> {
+    fn from(d: jiff::civil::Date) -> Self {
+        d.to_datetime(jiff::civil::Time::midnight()).into()
+    }
+}
+
+/// Converts to the fraction of a day, the serial number of a time value
+/// (requires `jiff` feature).
+#[cfg(feature = "jiff")]
+impl<'a> From<jiff::civil::Time> for Value<'a> {
+    fn from(t: jiff::civil::Time) -> Self {
+        Value::Number(crate::date_serial::civil_time_to_day_fraction(t))
+    }
+}
+
 impl<'a> Value<'a> {
     /// Returns the value as a number if possible.
-    /// For BigInt values, returns the f64 representation (may lose precision for large values).
+    /// For BigInt and Decimal values, returns the nearest f64 (may lose precision).
     pub fn as_number(&self) -> Option<f64> {
         match self {
             Value::Number(n) => Some(*n),
@@ -125,6 +205,8 @@ impl<'a> Value<'a> {
                 let float_val = n.to_string().parse::<f64>().unwrap_or(f64::NAN);
                 Some(float_val)
             }
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => rust_decimal::prelude::ToPrimitive::to_f64(d),
             _ => None,
         }
     }
@@ -151,12 +233,20 @@ impl<'a> Value<'a> {
             Value::Empty => "empty",
             #[cfg(feature = "bigint")]
             Value::BigInt(_) => "bigint",
+            #[cfg(feature = "decimal")]
+            Value::Decimal(_) => "decimal",
             #[cfg(feature = "chrono")]
             Value::DateTime(_) => "datetime",
             #[cfg(feature = "chrono")]
+            Value::ZonedDateTime(_) => "zoned datetime",
+            #[cfg(feature = "chrono")]
             Value::Date(_) => "date",
             #[cfg(feature = "chrono")]
             Value::Time(_) => "time",
+            #[cfg(feature = "time")]
+            Value::PrimitiveDateTime(_) => "datetime",
+            #[cfg(feature = "jiff")]
+            Value::CivilDateTime(_) => "datetime",
         }
     }
 
@@ -190,4 +280,13 @@ impl<'a> Value<'a> {
             _ => None,
         }
     }
+
+    /// Returns the decimal value if this is a Decimal.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<&rust_decimal::Decimal> {
+        match self {
+            Value::Decimal(d) => Some(d),
+            _ => None,
+        }
+    }
 }
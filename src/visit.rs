@@ -0,0 +1,240 @@
+//! Walking the parts of a parsed format.
+//!
+//! [`FormatVisitor`] and [`FormatVisitorMut`] have a method per kind of
+//! node, each with a default that does nothing or keeps walking, so tools
+//! override only the nodes they care about and keep compiling when
+//! [`FormatPart`] gains variants.
+//!
+//! ```
+//! use ssfmt::ast::DigitPlaceholder;
+//! use ssfmt::visit::FormatVisitor;
+//! use ssfmt::NumberFormat;
+//!
+//! #[derive(Default)]
+//! struct CountDigits(usize);
+//!
+//! impl FormatVisitor for CountDigits {
+//!     fn visit_digit(&mut self, _placeholder: DigitPlaceholder) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let fmt = NumberFormat::parse("#,##0.00;(#,##0.00)").unwrap();
+//! let mut count = CountDigits::default();
+//! fmt.walk(&mut count);
+//! assert_eq!(count.0, 12);
+//! ```
+
+use crate::ast::{
+    Color, Condition, DatePart, DigitPlaceholder, FormatPart, LocaleCode, NumberFormat, Section,
+};
+
+/// Read-only visitor over a [`NumberFormat`].
+///
+/// The `visit_*` methods that have children call the matching `walk_*`
+/// function by default; an override that still wants the children visited
+/// should call it too.
+pub trait FormatVisitor {
+    /// Visit a section; `index` is its position in the format code.
+    fn visit_section(&mut self, index: usize, section: &Section) {
+        let _ = index;
+        walk_section(self, section);
+    }
+
+    /// Visit a section's condition, such as `[>=100]`.
+    fn visit_condition(&mut self, _condition: &Condition) {}
+
+    /// Visit a section's color, such as `[Red]`.
+    fn visit_color(&mut self, _color: &Color) {}
+
+    /// Visit a part of a section.
+    fn visit_part(&mut self, part: &FormatPart) {
+        walk_part(self, part);
+    }
+
+    /// Visit literal text; `escaped` is true for a backslash-escaped
+    /// character.
+    fn visit_literal(&mut self, _text: &str, _escaped: bool) {}
+
+    /// Visit a digit placeholder, including those of exponents and
+    /// fractions.
+    fn visit_digit(&mut self, _placeholder: DigitPlaceholder) {}
+
+    /// Visit a date or time field.
+    fn visit_date_part(&mut self, _part: DatePart) {}
+
+    /// Visit a `[$...]` locale or currency tag.
+    fn visit_locale(&mut self, _locale: &LocaleCode) {}
+}
+
+/// Visit the condition, color and parts of `section`.
+pub fn walk_section<V: FormatVisitor + ?Sized>(visitor: &mut V, section: &Section) {
+    if let Some(condition) = &section.condition {
+        visitor.visit_condition(condition);
+    }
+    if let Some(color) = &section.color {
+        visitor.visit_color(color);
+    }
+    for part in &section.parts {
+        visitor.visit_part(part);
+    }
+}
+
+/// Visit the contents of `part` with the visitor's specific methods.
+pub fn walk_part<V: FormatVisitor + ?Sized>(visitor: &mut V, part: &FormatPart) {
+    match part {
+        FormatPart::Literal(s) | FormatPart::QuotedLiteral(s) => visitor.visit_literal(s, false),
+        FormatPart::EscapedLiteral(s) => visitor.visit_literal(s, true),
+        FormatPart::Digit(placeholder) => visitor.visit_digit(*placeholder),
+        FormatPart::Scientific {
+            exponent_digits, ..
+        } => {
+            for placeholder in exponent_digits {
+                visitor.visit_digit(*placeholder);
+            }
+        }
+        FormatPart::Fraction {
+            integer_digits,
+            numerator_digits,
+            ..
+        } => {
+            for placeholder in integer_digits.iter().chain(numerator_digits) {
+                visitor.visit_digit(*placeholder);
+            }
+        }
+        FormatPart::DatePart(date_part) => visitor.visit_date_part(*date_part),
+        FormatPart::Locale(locale) => visitor.visit_locale(locale),
+        _ => {}
+    }
+}
+
+/// Visitor that can change a [`NumberFormat`] in place.
+///
+/// Conditions, colors and part lists are passed as the containers that hold
+/// them, so a visitor can remove or insert as well as edit.
+///
+/// # Examples
+/// ```
+/// use ssfmt::ast::{Color, FormatPart, LocaleCode};
+/// use ssfmt::visit::FormatVisitorMut;
+/// use ssfmt::NumberFormat;
+///
+/// // Strip colors and show euros instead of dollars
+/// struct Euros;
+///
+/// impl FormatVisitorMut for Euros {
+///     fn visit_color_mut(&mut self, color: &mut Option<Color>) {
+///         *color = None;
+///     }
+///
+///     fn visit_part_mut(&mut self, part: &mut FormatPart) {
+///         match part {
+///             FormatPart::Literal(s) => *s = s.replace('$', "€"),
+///             FormatPart::Locale(LocaleCode { currency: Some(c), .. }) => *c = "€".to_string(),
+///             _ => {}
+///         }
+///     }
+/// }
+///
+/// let mut fmt = NumberFormat::parse("$#,##0;[Red]-$#,##0").unwrap();
+/// fmt.walk_mut(&mut Euros);
+/// assert_eq!(fmt.to_format_code(), "€#,##0;-€#,##0");
+/// ```
+pub trait FormatVisitorMut {
+    /// Visit a section; `index` is its position in the format code.
+    fn visit_section_mut(&mut self, index: usize, section: &mut Section) {
+        let _ = index;
+        walk_section_mut(self, section);
+    }
+
+    /// Visit a section's condition, if any.
+    fn visit_condition_mut(&mut self, _condition: &mut Option<Condition>) {}
+
+    /// Visit a section's color, if any.
+    fn visit_color_mut(&mut self, _color: &mut Option<Color>) {}
+
+    /// Visit a section's parts; visits each part by default.
+    fn visit_parts_mut(&mut self, parts: &mut Vec<FormatPart>) {
+        for part in parts {
+            self.visit_part_mut(part);
+        }
+    }
+
+    /// Visit a part of a section.
+    fn visit_part_mut(&mut self, _part: &mut FormatPart) {}
+}
+
+/// Visit the condition, color and parts of `section`.
+pub fn walk_section_mut<V: FormatVisitorMut + ?Sized>(visitor: &mut V, section: &mut Section) {
+    visitor.visit_condition_mut(&mut section.condition);
+    visitor.visit_color_mut(&mut section.color);
+    visitor.visit_parts_mut(&mut section.parts);
+}
+
+impl NumberFormat {
+    /// Visit every section of this format in order.
+    pub fn walk<V: FormatVisitor + ?Sized>(&self, visitor: &mut V) {
+        for (index, section) in self.sections().iter().enumerate() {
+            visitor.visit_section(index, section);
+        }
+    }
+
+    /// Visit every section of this format in order, allowing changes.
+    ///
+    /// Each section's metadata is recomputed from its parts afterwards.
+    pub fn walk_mut<V: FormatVisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for (index, section) in self.sections_mut().iter_mut().enumerate() {
+            visitor.visit_section_mut(index, section);
+            section.metadata = crate::parser::section_metadata(&section.parts);
+        }
+    }
+}
+
+/// Rewrites currency symbols for [`NumberFormat::with_currency`].
+struct ReplaceCurrency<'a>(&'a str);
+
+impl FormatVisitorMut for ReplaceCurrency<'_> {
+    fn visit_part_mut(&mut self, part: &mut FormatPart) {
+        match part {
+            FormatPart::Locale(LocaleCode {
+                currency: Some(currency),
+                ..
+            }) => *currency = self.0.to_string(),
+            FormatPart::Literal(s)
+            | FormatPart::QuotedLiteral(s)
+            | FormatPart::EscapedLiteral(s)
+                if s.contains('$') =>
+            {
+                *s = s.replace('$', self.0);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl NumberFormat {
+    /// A copy of this format that shows `symbol` as its currency.
+    ///
+    /// Replaces the symbol of every `[$symbol-lcid]` tag, keeping its
+    /// locale, and every `$` in literal text. Tags without a symbol, such as
+    /// the `[$-409]` of a date format, are left alone.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let opts = FormatOptions::default();
+    /// let usd = NumberFormat::parse("$#,##0.00;($#,##0.00)").unwrap();
+    /// let gbp = usd.with_currency("£");
+    /// assert_eq!(gbp.to_format_code(), "£#,##0.00;(£#,##0.00)");
+    /// assert_eq!(gbp.format(-1234.5, &opts), "(£1,234.50)");
+    ///
+    /// let eur = NumberFormat::parse("[$$-409]#,##0").unwrap().with_currency("€");
+    /// assert_eq!(eur.to_format_code(), "[$€-409]#,##0");
+    /// ```
+    pub fn with_currency(&self, symbol: &str) -> NumberFormat {
+        let mut format = self.clone();
+        format.walk_mut(&mut ReplaceCurrency(symbol));
+        format
+    }
+}
@@ -0,0 +1,33 @@
+//! JavaScript bindings (requires `wasm` feature).
+//!
+//! Exposes an SSF-style API through `wasm-bindgen` so the crate can replace
+//! SheetJS SSF in the browser. Values are formatted with default options
+//! (1900 date system, en-US locale). Errors are thrown as JavaScript `Error`s.
+//!
+//! The format cache is a plain `Mutex`, which `wasm32-unknown-unknown`
+//! supports without threads, and the locale tables are `static` data, so
+//! nothing here needs a thread-enabled build.
+
+use wasm_bindgen::prelude::*;
+
+/// Format a value with a format code, e.g. `format(1234.5, "#,##0.00")`.
+#[wasm_bindgen]
+pub fn format(value: f64, code: &str) -> Result<String, JsError> {
+    crate::format_default(value, code).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Format a value with a built-in format ID, e.g. `formatWithId(0.5, 9)`.
+#[wasm_bindgen(js_name = formatWithId)]
+pub fn format_with_id(value: f64, format_id: u32) -> Result<String, JsError> {
+    crate::format_with_id_default(value, format_id).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Parse a format code and return its AST as JSON.
+///
+/// The JSON has the same shape as the `serde` serialization of
+/// [`NumberFormat`](crate::NumberFormat).
+#[wasm_bindgen]
+pub fn parse(code: &str) -> Result<String, JsError> {
+    let fmt = crate::cache::get_or_parse(code).map_err(|e| JsError::new(&e.to_string()))?;
+    serde_json::to_string(&*fmt).map_err(|e| JsError::new(&e.to_string()))
+}
@@ -0,0 +1,586 @@
+//! Format code writer.
+//!
+//! Regenerates an ECMA-376 format code string from a parsed [`NumberFormat`].
+//! The output is designed so that re-parsing it yields the same AST:
+//! - Quoted literals keep their quotes and escaped literals their backslash
+//! - Other literals are written bare when the lexer would read them back as
+//!   literals, and quoted otherwise
+//! - Date parts that would run together into one token, such as `mm` and
+//!   `m`, are separated with `""`
+//! - Sub-second placeholders are written after a bare `.` so they are
+//!   recognized as date parts again
+
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::ast::{
+    AmPmStyle, Color, Condition, DatePart, DigitPlaceholder, ElapsedPart, FormatPart,
+    FractionDenom, LocaleCode, NamedColor, NumberFormat, Section,
+};
+use crate::options::FormatOptions;
+
+impl NumberFormat {
+    /// Regenerate a format code string from this format.
+    ///
+    /// Sections, colors, conditions, literals with their quoting, fills and
+    /// skips are preserved, so the result can be written back into a
+    /// workbook's styles part.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// let fmt = NumberFormat::parse("#,##0.00;[Red](#,##0.00)").unwrap();
+    /// assert_eq!(fmt.to_format_code(), "#,##0.00;[Red](#,##0.00)");
+    /// ```
+    pub fn to_format_code(&self) -> String {
+        self.to_string()
+    }
+
+    /// Regenerate this format as a canonical format code.
+    ///
+    /// Like [`to_format_code`](Self::to_format_code), which already spells
+    /// date tokens, keywords and colors one way, but quotes literals only
+    /// when needed and drops trailing sections that don't change the
+    /// output: a text section that is just `@`, a zero section that
+    /// repeats the positive one, and a negative section that is `-` and
+    /// the positive one. Two codes that normalize to the same string
+    /// format every value the same way, except that the dropped negative
+    /// section kept the sign of negatives that display as zero (see
+    /// [`equivalent_to`](Self::equivalent_to)).
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// let fmt = NumberFormat::parse("\"$\"#,##0.00;[RED]-\"$\"#,##0.00;\"$\"#,##0.00;@").unwrap();
+    /// assert_eq!(fmt.to_canonical_code(), "$#,##0.00;[Red]-$#,##0.00");
+    /// ```
+    pub fn to_canonical_code(&self) -> String {
+        let sections = self.sections();
+        Canonical(&sections[..canonical_section_count(sections)]).to_string()
+    }
+
+    /// Whether this format shows every value the same way as `other`.
+    ///
+    /// Compares the formats as parsed, so spelling differences such as
+    /// token case (`YYYY` and `yyyy`, `am/pm` and `AM/PM`), quoting (`"$"0`,
+    /// `\$0` and `$0`), literals split across quotes and redundant trailing
+    /// sections are ignored. A negative section that is `-` and the
+    /// positive section counts as redundant, so `0;-0` matches `0`, though
+    /// a value such as -0.4 shows as `-0` with the first and `0` with the
+    /// second unless [`FormatOptions::show_negative_zero`] is set.
+    ///
+    /// Whitespace in literal text is deliberately not ignored: `$ 0` and
+    /// `$0` show different text, and merging their `numFmt` entries would
+    /// change how cells look. To check the outcome
+    /// on real data instead, use [`equivalent_on`](Self::equivalent_on).
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::NumberFormat;
+    ///
+    /// let a = NumberFormat::parse("\"$\"#,##0;[RED]\\-#,##0;$#,##0;@").unwrap();
+    /// let b = NumberFormat::parse("$#,##0;[Red]-#,##0").unwrap();
+    /// assert!(a.equivalent_to(&b));
+    /// assert!(!a.equivalent_to(&NumberFormat::parse("$ #,##0").unwrap()));
+    ///
+    /// let c = NumberFormat::parse("0.00;-0.00").unwrap();
+    /// assert!(c.equivalent_to(&NumberFormat::parse("0.00").unwrap()));
+    /// ```
+    pub fn equivalent_to(&self, other: &NumberFormat) -> bool {
+        let (a, b) = (self.sections(), other.sections());
+        let (a, b) = (
+            &a[..canonical_section_count(a)],
+            &b[..canonical_section_count(b)],
+        );
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_section(a, b))
+    }
+
+    /// Whether this format and `other` give the same text and color for
+    /// each of `values`, and the same text for a text value.
+    ///
+    /// A check on sample data, for formats that are not
+    /// [`equivalent_to`](Self::equivalent_to) each other but may agree on
+    /// the values a workbook holds, such as `0` and `#` for whole numbers
+    /// other than zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use ssfmt::{FormatOptions, NumberFormat};
+    ///
+    /// let opts = FormatOptions::default();
+    /// let a = NumberFormat::parse("0").unwrap();
+    /// let b = NumberFormat::parse("#").unwrap();
+    /// assert!(a.equivalent_on(&b, &[1.0, 42.0, -7.0], &opts));
+    /// assert!(!a.equivalent_on(&b, &[0.0], &opts));
+    /// ```
+    pub fn equivalent_on(
+        &self,
+        other: &NumberFormat,
+        values: &[f64],
+        opts: &FormatOptions,
+    ) -> bool {
+        values.iter().all(|&value| {
+            self.format(value, opts) == other.format(value, opts)
+                && self.section_for(value).1.color == other.section_for(value).1.color
+        }) && self.format_text("text", opts) == other.format_text("text", opts)
+    }
+}
+
+/// The parts of `section` with spelling differences that cannot change the
+/// output removed: escaped and quoted literals become one plain literal,
+/// and `am/pm` becomes `AM/PM`.
+fn semantic_parts(section: &Section) -> Cow<'_, [FormatPart]> {
+    // A lone one-character literal gets a minus sign for negative values
+    // only when it is not escaped, so keep an escaped one as is
+    let lone_quoted = matches!(section.parts[..], [FormatPart::QuotedLiteral(_)]);
+    if section.parts.len() <= 1 && !lone_quoted {
+        return Cow::Borrowed(&section.parts);
+    }
+    let mut parts: Vec<FormatPart> = Vec::with_capacity(section.parts.len());
+    for part in &section.parts {
+        match (part, parts.last_mut()) {
+            (
+                FormatPart::Literal(s)
+                | FormatPart::QuotedLiteral(s)
+                | FormatPart::EscapedLiteral(s),
+                Some(FormatPart::Literal(last)),
+            ) => last.push_str(s),
+            (FormatPart::QuotedLiteral(s) | FormatPart::EscapedLiteral(s), _) => {
+                parts.push(FormatPart::Literal(s.clone()))
+            }
+            (FormatPart::AmPm(AmPmStyle::Lower), _) => {
+                parts.push(FormatPart::AmPm(AmPmStyle::Upper))
+            }
+            _ => parts.push(part.clone()),
+        }
+    }
+    Cow::Owned(parts)
+}
+
+/// Whether two sections format values the same way, ignoring spelling.
+fn same_section(a: &Section, b: &Section) -> bool {
+    a.color == b.color && a.condition == b.condition && semantic_parts(a) == semantic_parts(b)
+}
+
+/// Number of leading sections that determine how values are formatted.
+fn canonical_section_count(sections: &[Section]) -> usize {
+    let is_plain = |s: &Section| s.color.is_none() && s.condition.is_none();
+    let mut len = sections.len();
+    if len == 4 && is_plain(&sections[3]) && sections[3].parts == [FormatPart::TextPlaceholder] {
+        len = 3;
+    }
+    // Without conditions, zero uses the first section once the third is gone
+    if len == 3
+        && sections.iter().all(|s| s.condition.is_none())
+        && same_section(&sections[2], &sections[0])
+    {
+        len = 2;
+    }
+    // A negative section that only adds the minus sign a single section
+    // would add anyway
+    if len == 2
+        && sections[0].color == sections[1].color
+        && sections.iter().all(|s| s.condition.is_none())
+        && is_minus_of(&sections[1], &sections[0])
+    {
+        len = 1;
+    }
+    len
+}
+
+/// Whether `negative` is `-` followed by the number format of `positive`.
+fn is_minus_of(negative: &Section, positive: &Section) -> bool {
+    if positive.has_date_parts()
+        || positive.has_text_placeholder()
+        || !positive.parts.iter().any(|p| p.is_numeric_part())
+    {
+        return false;
+    }
+    let mut expected = vec![FormatPart::Literal("-".to_string())];
+    for part in semantic_parts(positive).iter() {
+        match (part, expected.last_mut()) {
+            (FormatPart::Literal(s), Some(FormatPart::Literal(last))) => last.push_str(s),
+            _ => expected.push(part.clone()),
+        }
+    }
+    semantic_parts(negative)[..] == expected[..]
+}
+
+impl fmt::Display for NumberFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_sections(f, self.sections(), false)
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_section(f, self, false)
+    }
+}
+
+/// Sections written with the minimal quoting of
+/// [`NumberFormat::to_canonical_code`].
+struct Canonical<'a>(&'a [Section]);
+
+impl fmt::Display for Canonical<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_sections(f, self.0, true)
+    }
+}
+
+/// Write `sections` separated by `;`. With `canonical`, quoted literals are
+/// only quoted when needed.
+fn write_sections(
+    f: &mut fmt::Formatter<'_>,
+    sections: &[Section],
+    canonical: bool,
+) -> fmt::Result {
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 {
+            f.write_char(';')?;
+        }
+        // An empty later section (e.g. the middle of "0;;0") is written as
+        // nothing; a lone empty section must be spelled out as General.
+        let is_bare_empty =
+            section.parts.is_empty() && section.color.is_none() && section.condition.is_none();
+        if is_bare_empty && i > 0 {
+            continue;
+        }
+        write_section(f, section, canonical)?;
+    }
+    Ok(())
+}
+
+fn write_section(f: &mut fmt::Formatter<'_>, section: &Section, canonical: bool) -> fmt::Result {
+    if let Some(color) = &section.color {
+        write!(f, "[{}]", color)?;
+    }
+    if let Some(condition) = &section.condition {
+        write!(f, "[{}]", condition)?;
+    }
+
+    // Sections without parts are rendered with the General format
+    if section.parts.is_empty() {
+        return f.write_str("General");
+    }
+
+    for (i, part) in section.parts.iter().enumerate() {
+        // `mm` then `m` written together would read back as `mmm`
+        if let (Some(FormatPart::DatePart(prev)), FormatPart::DatePart(date_part)) =
+            (i.checked_sub(1).map(|j| &section.parts[j]), part)
+        {
+            if date_parts_merge(*prev, *date_part) {
+                f.write_str("\"\"")?;
+            }
+        }
+        let next = section.parts.get(i + 1);
+        match part {
+            FormatPart::QuotedLiteral(s) if canonical => write_literal(f, s)?,
+            _ => write_part(f, part, next)?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Named(named) => f.write_str(match named {
+                NamedColor::Black => "Black",
+                NamedColor::Blue => "Blue",
+                NamedColor::Cyan => "Cyan",
+                NamedColor::Green => "Green",
+                NamedColor::Magenta => "Magenta",
+                NamedColor::Red => "Red",
+                NamedColor::White => "White",
+                NamedColor::Yellow => "Yellow",
+            }),
+            Color::Indexed(index) => write!(f, "Color{}", index),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::GreaterThan(n) => write!(f, ">{}", n),
+            Condition::LessThan(n) => write!(f, "<{}", n),
+            Condition::Equal(n) => write!(f, "={}", n),
+            Condition::GreaterOrEqual(n) => write!(f, ">={}", n),
+            Condition::LessOrEqual(n) => write!(f, "<={}", n),
+            Condition::NotEqual(n) => write!(f, "<>{}", n),
+        }
+    }
+}
+
+impl fmt::Display for LocaleCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('$')?;
+        if let Some(currency) = &self.currency {
+            f.write_str(currency)?;
+        }
+        if let Some(lcid) = self.lcid {
+            write!(f, "-{:X}", lcid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a single format part. `next` is the following part in the section,
+/// used to keep sub-second placeholders attached to their decimal point.
+fn write_part(
+    f: &mut fmt::Formatter<'_>,
+    part: &FormatPart,
+    next: Option<&FormatPart>,
+) -> fmt::Result {
+    match part {
+        FormatPart::Literal(s) => {
+            let before_subsecond =
+                matches!(next, Some(FormatPart::DatePart(DatePart::SubSecond(_))));
+            if s == "." && before_subsecond {
+                f.write_char('.')
+            } else {
+                write_literal(f, s)
+            }
+        }
+        FormatPart::QuotedLiteral(s) => write_quoted(f, s),
+        FormatPart::EscapedLiteral(s) => {
+            for ch in s.chars() {
+                write!(f, "\\{}", ch)?;
+            }
+            Ok(())
+        }
+        FormatPart::Digit(placeholder) => f.write_char(placeholder_char(*placeholder)),
+        FormatPart::DecimalPoint => f.write_char('.'),
+        FormatPart::ThousandsSeparator => f.write_char(','),
+        FormatPart::Percent => f.write_char('%'),
+        FormatPart::Scientific {
+            upper,
+            show_plus,
+            exponent_digits,
+        } => {
+            f.write_char(if *upper { 'E' } else { 'e' })?;
+            f.write_char(if *show_plus { '+' } else { '-' })?;
+            for placeholder in exponent_digits {
+                f.write_char(placeholder_char(*placeholder))?;
+            }
+            Ok(())
+        }
+        FormatPart::Fraction {
+            integer_digits,
+            integer_separator,
+            numerator_digits,
+            denominator,
+            space_before_slash,
+            space_after_slash,
+        } => {
+            if !integer_digits.is_empty() {
+                for placeholder in integer_digits {
+                    f.write_char(placeholder_char(*placeholder))?;
+                }
+                if integer_separator == " " {
+                    f.write_char(' ')?;
+                } else {
+                    write_literal(f, integer_separator)?;
+                }
+            }
+            for placeholder in numerator_digits {
+                f.write_char(placeholder_char(*placeholder))?;
+            }
+            f.write_str(space_before_slash)?;
+            f.write_char('/')?;
+            f.write_str(space_after_slash)?;
+            match denominator {
+                FractionDenom::UpToDigits(n) => {
+                    for _ in 0..*n {
+                        f.write_char('?')?;
+                    }
+                    Ok(())
+                }
+                FractionDenom::Fixed(d) => write!(f, "{}", d),
+            }
+        }
+        FormatPart::DatePart(date_part) => f.write_str(&date_part_code(*date_part)),
+        FormatPart::AmPm(style) => f.write_str(match style {
+            AmPmStyle::Upper => "AM/PM",
+            AmPmStyle::Lower => "am/pm",
+            AmPmStyle::ShortUpper => "A/P",
+            AmPmStyle::ShortLower => "a/p",
+            AmPmStyle::MalformedUpper => "AM/P",
+            AmPmStyle::MalformedLower => "am/p",
+            AmPmStyle::Chinese => "上午/下午",
+        }),
+        FormatPart::Elapsed(elapsed) => f.write_str(match elapsed {
+            ElapsedPart::Days => "[d]",
+            ElapsedPart::Days2 => "[dd]",
+            ElapsedPart::Hours => "[h]",
+            ElapsedPart::Hours2 => "[hh]",
+            ElapsedPart::Minutes => "[m]",
+            ElapsedPart::Minutes2 => "[mm]",
+            ElapsedPart::Seconds => "[s]",
+            ElapsedPart::Seconds2 => "[ss]",
+        }),
+        FormatPart::TextPlaceholder => f.write_char('@'),
+        FormatPart::Fill(ch) => {
+            f.write_char('*')?;
+            write_operand_char(f, *ch)
+        }
+        FormatPart::Skip(ch) => {
+            f.write_char('_')?;
+            write_operand_char(f, *ch)
+        }
+        FormatPart::Locale(locale) => write!(f, "[{}]", locale),
+        FormatPart::GeneralNumber => f.write_str("General"),
+        FormatPart::DbNum(style) => write!(f, "[DBNum{}]", style),
+        FormatPart::NatNum(style) => write!(f, "[NatNum{}]", style),
+    }
+}
+
+fn date_part_code(part: DatePart) -> Cow<'static, str> {
+    Cow::Borrowed(match part {
+        DatePart::Year2 => "yy",
+        DatePart::Year3 => "yyy",
+        DatePart::Year4 => "yyyy",
+        DatePart::Month | DatePart::Minute => "m",
+        DatePart::Month2 | DatePart::Minute2 => "mm",
+        DatePart::MonthAbbr => "mmm",
+        DatePart::MonthFull => "mmmm",
+        DatePart::MonthLetter => "mmmmm",
+        DatePart::Day => "d",
+        DatePart::Day2 => "dd",
+        DatePart::DayAbbr => "ddd",
+        DatePart::DayFull => "dddd",
+        DatePart::Hour => "h",
+        DatePart::Hour2 => "hh",
+        DatePart::Second => "s",
+        DatePart::Second2 => "ss",
+        DatePart::SubSecond(places) => return Cow::Owned("0".repeat(places.into())),
+        DatePart::BuddhistYear2 => "bb",
+        DatePart::BuddhistYear4 => "bbbb",
+        DatePart::BuddhistYear4Alt => "B2yyyy",
+        DatePart::BuddhistYear2Alt => "B2yy",
+        DatePart::EraLetter => "g",
+        DatePart::EraAbbr => "gg",
+        DatePart::EraFull => "ggg",
+        DatePart::EraYear => "e",
+        DatePart::EraYear2 => "ee",
+    })
+}
+
+/// Whether `prev` written right before `next` would lex as one longer
+/// token, such as `yyyy` and `yy` as `yyyyyy`.
+fn date_parts_merge(prev: DatePart, next: DatePart) -> bool {
+    let (prev, next) = (date_part_code(prev), date_part_code(next));
+    match (prev.chars().last(), next.chars().next()) {
+        (Some(a), Some(b)) => a.is_ascii_alphabetic() && a.eq_ignore_ascii_case(&b),
+        _ => false,
+    }
+}
+
+fn placeholder_char(placeholder: DigitPlaceholder) -> char {
+    match placeholder {
+        DigitPlaceholder::Zero => '0',
+        DigitPlaceholder::Hash => '#',
+        DigitPlaceholder::Question => '?',
+    }
+}
+
+/// Write a literal, leaving it bare when the lexer would read it back as the
+/// same literal and quoting it otherwise.
+fn write_literal(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    if s.is_empty() {
+        return f.write_str("\"\"");
+    }
+
+    let mut chars = s.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if is_bare_literal(ch) {
+            return f.write_char(ch);
+        }
+    }
+
+    // Runs of symbols such as `($` are written as they are. Text is quoted,
+    // and so is a slash, which could turn the digits around it into a
+    // fraction or complete 上午/下午.
+    if s
+        .chars()
+        .all(|ch| is_bare_literal(ch) && ch != '/' && !ch.is_alphabetic())
+    {
+        return f.write_str(s);
+    }
+
+    write_quoted(f, s)
+}
+
+/// Write `s` in double quotes. Quoted strings cannot contain a double quote,
+/// so close the string and escape it instead.
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    if s.is_empty() {
+        return f.write_str("\"\"");
+    }
+    let mut in_quotes = false;
+    for ch in s.chars() {
+        if ch == '"' {
+            if in_quotes {
+                f.write_char('"')?;
+                in_quotes = false;
+            }
+            f.write_str("\\\"")?;
+        } else {
+            if !in_quotes {
+                f.write_char('"')?;
+                in_quotes = true;
+            }
+            f.write_char(ch)?;
+        }
+    }
+    if in_quotes {
+        f.write_char('"')?;
+    }
+    Ok(())
+}
+
+/// Write the character operand of a fill (`*`) or skip (`_`), escaping it if
+/// the lexer would otherwise treat it as a token of its own.
+fn write_operand_char(f: &mut fmt::Formatter<'_>, ch: char) -> fmt::Result {
+    if ch.is_ascii_alphabetic() || matches!(ch, '"' | '\\' | '[' | ']' | ';') {
+        f.write_char('\\')?;
+    }
+    f.write_char(ch)
+}
+
+/// Returns true if `ch` written unquoted lexes back to the same literal.
+fn is_bare_literal(ch: char) -> bool {
+    if ch.is_ascii_alphabetic() {
+        // Letters either form date/time tokens or may start a keyword
+        // (General, AM/PM), except for a few that are always literal.
+        return !matches!(
+            ch.to_ascii_lowercase(),
+            'y' | 'm' | 'd' | 'h' | 's' | 'b' | 'e' | 'g' | 'r' | 'a'
+        );
+    }
+    !matches!(
+        ch,
+        '"' | '\\' | '0' | '#' | '?' | '.' | ',' | ';' | '%' | '@' | '*' | '_' | '['
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_bare_literal() {
+        assert!(is_bare_literal(' '));
+        assert!(is_bare_literal('$'));
+        assert!(is_bare_literal('-'));
+        assert!(is_bare_literal('k'));
+        assert!(!is_bare_literal('y'));
+        assert!(!is_bare_literal('E'));
+        assert!(!is_bare_literal('r'));
+        assert!(!is_bare_literal('0'));
+        assert!(!is_bare_literal(','));
+    }
+}
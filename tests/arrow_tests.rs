@@ -0,0 +1,37 @@
+//! Tests for formatting Arrow arrays (`arrow` feature).
+
+#![cfg(feature = "arrow")]
+
+use arrow_array::{Array, Float32Array, Float64Array, UInt8Array};
+use ssfmt::{FormatOptions, NumberFormat};
+
+#[test]
+fn test_format_arrow_matches_format() {
+    let opts = FormatOptions::default();
+    let values = [0.0, -1.5, 45292.25, 1e-7, f64::NAN];
+    let array = Float64Array::from(values.to_vec());
+    for code in ["General", "0.00%", "#,##0;[Red](#,##0)", "yyyy-mm-dd hh:mm", "# ?/?"] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        let strings = fmt.format_arrow(&array, &opts);
+        assert_eq!(strings.len(), values.len());
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(strings.value(i), fmt.format(value, &opts), "code {code}");
+        }
+    }
+}
+
+#[test]
+fn test_format_arrow_nulls_and_types() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("0.0").unwrap();
+
+    let strings = fmt.format_arrow(&Float32Array::from(vec![None, Some(2.5), None]), &opts);
+    assert_eq!(strings.null_count(), 2);
+    assert!(strings.is_null(0));
+    assert_eq!(strings.value(1), "2.5");
+
+    let strings = fmt.format_arrow(&UInt8Array::from(vec![255]), &opts);
+    assert_eq!(strings.value(0), "255.0");
+
+    assert!(fmt.format_arrow(&Float64Array::from(Vec::<f64>::new()), &opts).is_empty());
+}
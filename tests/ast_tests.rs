@@ -1,5 +1,5 @@
-use ssfmt::ast::{Condition, DatePart, DigitPlaceholder, FormatPart, NamedColor, Section};
-use ssfmt::NumberFormat;
+use ssfmt::ast::{Color, Condition, DatePart, DigitPlaceholder, FormatPart, NamedColor, Section};
+use ssfmt::{NumberFormat, Palette};
 
 #[test]
 fn test_named_color_from_str() {
@@ -8,6 +8,23 @@ fn test_named_color_from_str() {
     assert!("invalid".parse::<NamedColor>().is_err());
 }
 
+#[test]
+fn test_color_to_rgb() {
+    let fmt = NumberFormat::parse("[Blue]0;[Color35]-0").unwrap();
+    let colors: Vec<_> = fmt.sections().iter().map(|s| s.color.unwrap().to_rgb()).collect();
+    assert_eq!(colors, [(0x00, 0x00, 0xFF), (0xCC, 0xFF, 0xCC)]);
+    assert_eq!(Color::Indexed(56).to_rgb(), (0x33, 0x33, 0x33));
+    assert_eq!(Palette::EXCEL.get(0), None);
+    assert_eq!(Palette::EXCEL.get(57), None);
+
+    // Named colors are palette entries, so a workbook palette changes them
+    let mut colors = [(0, 0, 0); 56];
+    colors[4] = (0x10, 0x20, 0x30);
+    let palette = Palette::new(colors);
+    assert_eq!(Color::Named(NamedColor::Blue).to_rgb_in(&palette), (0x10, 0x20, 0x30));
+    assert_eq!(Color::Indexed(5).to_rgb_in(&palette), (0x10, 0x20, 0x30));
+}
+
 #[test]
 fn test_condition_evaluate() {
     let cond = Condition::GreaterThan(100.0);
@@ -63,3 +80,31 @@ fn test_number_format_sections_limit() {
     let format = NumberFormat::from_sections(sections);
     assert_eq!(format.sections().len(), 4);
 }
+
+#[test]
+fn test_section_for() {
+    let fmt = NumberFormat::parse("[>=100][Green]0;[<0][Red]0;0.00").unwrap();
+    let (index, section) = fmt.section_for(150.0);
+    assert_eq!(index, 0);
+    assert_eq!(section.color, Some(Color::Named(NamedColor::Green)));
+    assert_eq!(fmt.section_for(-3.0).0, 1);
+    assert_eq!(fmt.section_for(50.0).0, 2);
+
+    let fmt = NumberFormat::parse("0;(0);\"-\";@").unwrap();
+    assert_eq!(fmt.section_for(1.0).0, 0);
+    assert_eq!(fmt.section_for(-1.0).0, 1);
+    assert_eq!(fmt.section_for(0.0).0, 2);
+    assert_eq!(NumberFormat::parse("0").unwrap().section_for(-1.0).0, 0);
+}
+
+#[test]
+fn test_section_matches() {
+    let fmt = NumberFormat::parse("[>=100]0;[<0]0;0.00").unwrap();
+    let sections = fmt.sections();
+    assert!(sections[0].matches(100.0));
+    assert!(!sections[0].matches(99.5));
+    assert!(sections[1].matches(-1.0));
+    // No condition: matches every value
+    assert!(sections[2].matches(-1.0));
+    assert!(sections[2].matches(50.0));
+}
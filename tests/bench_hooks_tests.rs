@@ -0,0 +1,41 @@
+//! Tests for the allocation counters (`bench_hooks` feature).
+
+#![cfg(feature = "bench_hooks")]
+
+use ssfmt::bench_hooks::{allocation_counts, count_allocations, CountingAllocator};
+use ssfmt::{FormatOptions, NumberFormat};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator::new();
+
+#[test]
+fn test_count_allocations() {
+    let (v, counts) = count_allocations(|| vec![0u8; 100]);
+    assert_eq!(v.len(), 100);
+    assert_eq!(counts.allocations, 1);
+    assert_eq!(counts.bytes, 100);
+
+    let (_, counts) = count_allocations(|| 1 + 1);
+    assert_eq!(counts.allocations, 0);
+
+    let before = allocation_counts();
+    let _fmt = NumberFormat::parse("#,##0.00").unwrap();
+    assert!(allocation_counts().allocations > before.allocations);
+}
+
+#[test]
+fn test_allocation_counts_are_repeatable() {
+    let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    let opts = FormatOptions::default();
+    let mut out = String::with_capacity(1024);
+    // Warm up per-thread scratch buffers before measuring
+    fmt.format_to(1.0, &opts, &mut out).unwrap();
+    out.clear();
+
+    let measure =
+        |out: &mut String| count_allocations(|| fmt.format_to(1234.5, &opts, out).unwrap()).1;
+    let first = measure(&mut out);
+    let second = measure(&mut out);
+    assert_eq!(out, "1,234.501,234.50");
+    assert_eq!(first, second);
+}
@@ -1,4 +1,7 @@
-use ssfmt::{format_with_id_default, format_code_from_id};
+use ssfmt::{
+    builtin_format, format_code_from_id, format_code_from_id_with_locale, format_with_id,
+    format_with_id_default, localized_format_code, FormatOptions, Locale, NumberFormat,
+};
 
 /// Test built-in format ID 0 (General)
 #[test]
@@ -128,7 +131,7 @@ fn test_invalid_format_ids() {
 fn test_all_defined_format_ids() {
     let defined_ids = vec![
         0, 1, 2, 3, 4, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
-        37, 38, 39, 40, 45, 46, 47, 48, 49,
+        37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49,
     ];
 
     for id in defined_ids {
@@ -154,3 +157,175 @@ fn test_accounting_formats() {
     let code = format_code_from_id(38).unwrap();
     assert_eq!(code, "#,##0 ;[Red](#,##0)");
 }
+
+/// Excel's accounting formats (IDs 41-44): skips, fills, parenthesized
+/// negatives and a dash for zero working together
+#[test]
+fn test_accounting_builtins_41_to_44() {
+    use ssfmt::{FormatOptions, OverflowBehavior};
+
+    let values = [1234.5, -1234.5, 0.0, -0.001, 1e9];
+    let narrow = FormatOptions::default();
+    let cell = FormatOptions::builder().cell_width(14).build();
+    #[rustfmt::skip]
+    let cases: [(u32, [&str; 5], [&str; 5]); 4] = [
+        (41, [" 1,235 ", " (1,235)", " - ", " (0)", " 1,000,000,000 "],
+             ["        1,235 ", "       (1,235)", "            - ", "           (0)", " 1,000,000,000 "]),
+        (42, [" $1,235 ", " $(1,235)", " $- ", " $(0)", " $1,000,000,000 "],
+             [" $      1,235 ", " $     (1,235)", " $          - ", " $         (0)", " $1,000,000,000 "]),
+        (43, [" 1,234.50 ", " (1,234.50)", " -   ", " (0.00)", " 1,000,000,000.00 "],
+             ["     1,234.50 ", "    (1,234.50)", "          -   ", "        (0.00)", " 1,000,000,000.00 "]),
+        (44, [" $1,234.50 ", " $(1,234.50)", " $-   ", " $(0.00)", " $1,000,000,000.00 "],
+             [" $   1,234.50 ", " $  (1,234.50)", " $        -   ", " $      (0.00)", " $1,000,000,000.00 "]),
+    ];
+    for (id, unfilled, filled) in cases {
+        let fmt = NumberFormat::parse(format_code_from_id(id).unwrap()).unwrap();
+        for ((value, a), b) in values.into_iter().zip(unfilled).zip(filled) {
+            assert_eq!(fmt.format(value, &narrow), a, "ID {} value {}", id, value);
+            assert_eq!(fmt.format(value, &cell), b, "ID {} value {} in cell", id, value);
+        }
+        // Text keeps its padding and spills past the cell instead of filling
+        assert_eq!(fmt.format_text("n/a", &cell), " n/a ");
+
+        // Too wide for the cell: Excel shows hashes
+        let mut hashes = cell.clone();
+        hashes.overflow = OverflowBehavior::Hashes(0);
+        assert_eq!(fmt.format(1e9, &hashes), "#".repeat(14), "ID {}", id);
+    }
+}
+
+/// Locale-specific East Asian IDs resolve by the LCID's language
+#[test]
+fn test_locale_specific_east_asian_ids() {
+    assert_eq!(
+        format_code_from_id_with_locale(27, 0x404),
+        Some("[$-404]e/m/d")
+    );
+    assert_eq!(
+        format_code_from_id_with_locale(32, 0x804),
+        Some("h\"时\"mm\"分\"")
+    );
+    assert_eq!(
+        format_code_from_id_with_locale(57, 0x411),
+        Some("[$-411]ge.m.d")
+    );
+    assert_eq!(
+        format_code_from_id_with_locale(33, 0x412),
+        Some("h\"시\" mm\"분\" ss\"초\"")
+    );
+    // Hong Kong uses the traditional Chinese table, Singapore the simplified one
+    assert_eq!(
+        format_code_from_id_with_locale(32, 0xC04),
+        Some("hh\"時\"mm\"分\"")
+    );
+    assert_eq!(
+        format_code_from_id_with_locale(32, 0x1004),
+        Some("h\"时\"mm\"分\"")
+    );
+
+    for lcid in [0x404, 0x804, 0x411, 0x412] {
+        for id in (27..=36).chain(50..=58) {
+            let code = format_code_from_id_with_locale(id, lcid).unwrap();
+            assert!(
+                NumberFormat::parse(code).is_ok(),
+                "ID {} for LCID {:#x} should parse: {}",
+                id,
+                lcid,
+                code
+            );
+        }
+    }
+}
+
+/// The short date IDs follow the locale, and the rest fall back
+#[test]
+fn test_localized_short_dates() {
+    assert_eq!(localized_format_code(14, 0x407), Some("dd\\.mm\\.yyyy"));
+    assert_eq!(localized_format_code(22, 0x41D), Some("yyyy-mm-dd hh:mm"));
+    // Liechtenstein has no entry of its own and uses German's
+    assert_eq!(localized_format_code(14, 0x1407), Some("dd\\.mm\\.yyyy"));
+    assert_eq!(localized_format_code(14, 0x7F), Some("m/d/yy"));
+    assert_eq!(localized_format_code(20, 0x407), Some("h:mm"));
+    assert_eq!(localized_format_code(32, 0x804), Some("h\"时\"mm\"分\""));
+
+    for lcid in [0x809, 0x407, 0x40C, 0x411, 0x412, 0x40E] {
+        for id in [14, 22] {
+            let code = localized_format_code(id, lcid).unwrap();
+            assert!(NumberFormat::parse(code).is_ok(), "ID {} for LCID {:#x}: {}", id, lcid, code);
+        }
+    }
+
+    let format_in = |id, tag| {
+        let opts = FormatOptions::builder()
+            .locale(Locale::from_tag(tag).unwrap())
+            .build();
+        format_with_id(45366.5, id, &opts).unwrap()
+    };
+    assert_eq!(format_in(14, "en-US"), "3/15/24");
+    assert_eq!(format_in(14, "en-GB"), "15/03/2024");
+    assert_eq!(format_in(14, "de-DE"), "15.03.2024");
+    assert_eq!(format_in(14, "hu-HU"), "2024.03.15.");
+    assert_eq!(format_in(22, "ja-JP"), "2024/3/15 12:00");
+    assert_eq!(format_in(15, "en-GB"), "15-Mar-24");
+    assert_eq!(format_in(31, "ja-JP"), "2024年3月15日");
+}
+
+/// Thai IDs resolve only for Thai locales
+#[test]
+fn test_locale_specific_thai_ids() {
+    assert_eq!(
+        format_code_from_id_with_locale(71, 0x41E),
+        Some("[$-D00041E]d/m/yyyy")
+    );
+    assert_eq!(format_code_from_id_with_locale(81, 0x41E), Some("d/m/bb"));
+    assert_eq!(format_code_from_id_with_locale(63, 0x41E), None);
+    assert_eq!(format_code_from_id_with_locale(71, 0x411), None);
+
+    for id in (59..=62).chain(67..=81) {
+        let code = format_code_from_id_with_locale(id, 0x41E).unwrap();
+        assert!(NumberFormat::parse(code).is_ok(), "ID {} should parse: {}", id, code);
+    }
+}
+
+/// Other IDs and other locales match the en-US view
+#[test]
+fn test_locale_specific_fallback() {
+    for id in 0..=81 {
+        assert_eq!(
+            format_code_from_id_with_locale(id, 0x409),
+            format_code_from_id(id),
+            "ID {}",
+            id
+        );
+    }
+    assert_eq!(format_code_from_id_with_locale(14, 0x411), Some("m/d/yy"));
+    assert_eq!(format_code_from_id_with_locale(49, 0x41E), Some("@"));
+}
+
+/// Built-in locales report their LCID
+#[test]
+fn test_locale_lcid() {
+    let ja = Locale::from_tag("ja-JP").unwrap();
+    assert_eq!(ja.lcid(), Some(0x411));
+    assert_eq!(
+        format_code_from_id_with_locale(31, ja.lcid().unwrap()),
+        Some("yyyy\"年\"m\"月\"d\"日\"")
+    );
+    assert_eq!(Locale::from_lcid(0x407).unwrap().lcid(), Some(0x407));
+}
+
+#[test]
+fn test_builtin_format_preparsed() {
+    for id in 0..=49 {
+        match format_code_from_id(id) {
+            Some(code) => {
+                let fmt = builtin_format(id).unwrap();
+                assert_eq!(*fmt, NumberFormat::parse(code).unwrap(), "id {}", id);
+                assert!(std::ptr::eq(fmt, builtin_format(id).unwrap()));
+            }
+            None => assert!(builtin_format(id).is_none(), "id {}", id),
+        }
+    }
+    assert!(builtin_format(164).is_none());
+    assert!(builtin_format(u32::MAX).is_none());
+}
@@ -0,0 +1,191 @@
+use ssfmt::{
+    global_cache, CompiledFormat, ExtraSections, FormatCache, FormatOptions, NumberFormat,
+    ParseError, ParseLimits, ParseOptions, DEFAULT_CACHE_CAPACITY,
+};
+
+#[test]
+fn test_cache_hits_and_misses() {
+    let cache = FormatCache::new(4);
+    let opts = FormatOptions::default();
+    assert_eq!(cache.format(0.5, "0%", &opts).unwrap(), "50%");
+    assert_eq!(cache.format(0.25, "0%", &opts).unwrap(), "25%");
+    assert_eq!(cache.format(1.0, "0.00", &opts).unwrap(), "1.00");
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.len, 2);
+    assert_eq!(stats.capacity, 4);
+
+    cache.reset_stats();
+    assert_eq!(cache.stats().hits, 0);
+    assert_eq!(cache.stats().len, 2);
+}
+
+#[test]
+fn test_cache_evicts_least_recently_used() {
+    let cache = FormatCache::new(2);
+    cache.get_or_parse("0").unwrap();
+    cache.get_or_parse("0.0").unwrap();
+    cache.get_or_parse("0").unwrap();
+    cache.get_or_parse("0.00").unwrap(); // evicts "0.0"
+    cache.reset_stats();
+
+    cache.get_or_parse("0").unwrap();
+    cache.get_or_parse("0.0").unwrap();
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (1, 1, 2));
+}
+
+#[test]
+fn test_cache_clear_and_resize() {
+    let cache = FormatCache::default();
+    assert_eq!(cache.capacity(), DEFAULT_CACHE_CAPACITY);
+    for code in ["0", "0.0", "0.00", "#,##0"] {
+        cache.get_or_parse(code).unwrap();
+    }
+    cache.set_capacity(2);
+    assert_eq!(cache.stats().len, 2);
+    cache.clear();
+    assert_eq!(cache.stats().len, 0);
+    assert_eq!(cache.stats().misses, 4);
+}
+
+#[test]
+fn test_cache_disabled() {
+    let cache = FormatCache::disabled();
+    cache.get_or_parse("0").unwrap();
+    cache.get_or_parse("0").unwrap();
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 2, 0));
+    assert!(cache.get_or_parse("[Red").is_err());
+}
+
+#[test]
+fn test_cache_per_thread() {
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let cache = FormatCache::new(8);
+                let code = format!("0.{}", "0".repeat(i + 1));
+                for _ in 0..10 {
+                    cache.get_or_parse(&code).unwrap();
+                }
+                cache.stats()
+            })
+        })
+        .collect();
+    for handle in handles {
+        let stats = handle.join().unwrap();
+        assert_eq!((stats.hits, stats.misses), (9, 1));
+    }
+}
+
+#[test]
+fn test_global_cache_opt_out() {
+    let cache = global_cache();
+    cache.set_capacity(0);
+    assert_eq!(ssfmt::format_default(1234.5, "#,##0.0").unwrap(), "1,234.5");
+    assert_eq!(cache.stats().len, 0);
+    cache.set_capacity(DEFAULT_CACHE_CAPACITY);
+    assert_eq!(ssfmt::format_default(1234.5, "#,##0.0").unwrap(), "1,234.5");
+    assert_eq!(cache.stats().len, 1);
+}
+
+#[test]
+fn test_cache_returns_shared_handles() {
+    let cache = FormatCache::new(4);
+    let a = cache.get_or_parse("#,##0.00").unwrap();
+    let b = cache.get_or_parse("#,##0.00").unwrap();
+    assert!(CompiledFormat::ptr_eq(&a, &b));
+    assert_eq!(*a, NumberFormat::parse("#,##0.00").unwrap());
+
+    // Uncached lookups parse a fresh format each time
+    let cache = FormatCache::disabled();
+    let a = cache.get_or_parse("0").unwrap();
+    let b = cache.get_or_parse("0").unwrap();
+    assert!(!CompiledFormat::ptr_eq(&a, &b));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_compiled_format_across_threads() {
+    let fmt = CompiledFormat::parse("0.0%;[Red]-0.0%").unwrap();
+    let results: Vec<String> = std::thread::scope(|scope| {
+        let handles: Vec<_> = [0.5, -0.25]
+            .into_iter()
+            .map(|value| {
+                let fmt = fmt.clone();
+                scope.spawn(move || fmt.format(value, &FormatOptions::default()))
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert_eq!(results, ["50.0%", "-25.0%"]);
+    assert_eq!(
+        CompiledFormat::from(NumberFormat::parse("0").unwrap())
+            .format(3.0, &FormatOptions::default()),
+        "3"
+    );
+}
+
+#[test]
+fn test_cache_keys_include_parse_options() {
+    let cache = FormatCache::new(4);
+    let strict = ParseOptions {
+        extra_sections: ExtraSections::Error,
+        ..Default::default()
+    };
+    let code = "0;-0;0;@;0.00";
+    let lenient = cache.get_or_parse(code).unwrap();
+    assert_eq!(
+        cache.get_or_parse_with_options(code, &strict),
+        Err(ParseError::TooManySections)
+    );
+    let again = cache.get_or_parse(code).unwrap();
+    assert!(CompiledFormat::ptr_eq(&lenient, &again));
+
+    let tight = ParseOptions {
+        limits: ParseLimits {
+            max_length: 4,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(cache.get_or_parse_with_options("0.00", &tight).is_ok());
+    assert!(cache.get_or_parse_with_options("0.000", &tight).is_err());
+    assert!(cache.get_or_parse("0.000").is_ok());
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (1, 5, 3));
+}
+
+#[test]
+fn test_cache_keeps_few_variants_per_code() {
+    let cache = FormatCache::new(4);
+    let limit = |max_length| ParseOptions {
+        limits: ParseLimits {
+            max_length,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    for max_length in 100..110 {
+        cache
+            .get_or_parse_with_options("0.00", &limit(max_length))
+            .unwrap();
+    }
+    let newest = cache
+        .get_or_parse_with_options("0.00", &limit(109))
+        .unwrap();
+    let again = cache
+        .get_or_parse_with_options("0.00", &limit(109))
+        .unwrap();
+    assert!(CompiledFormat::ptr_eq(&newest, &again));
+    cache
+        .get_or_parse_with_options("0.00", &limit(100))
+        .unwrap();
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (2, 11, 1));
+}
@@ -0,0 +1,47 @@
+//! Tests for locale data embedded from CLDR (`cldr` feature).
+//!
+//! Run against the sample data in `tests/fixtures/cldr`, or a full
+//! cldr-json checkout, which has the same values for these locales:
+//!
+//! ```text
+//! SSFMT_CLDR_DIR=tests/fixtures/cldr cargo test --features cldr --test cldr_tests
+//! ```
+
+#![cfg(feature = "cldr")]
+
+use ssfmt::{FormatOptions, Locale, NumberFormat};
+
+#[test]
+fn test_cldr_locale_from_tag() {
+    let is = Locale::from_tag("is").unwrap();
+    assert_eq!(is.decimal_separator, ',');
+    assert_eq!(is.thousands_separator, '.');
+    assert_eq!(is.currency_symbol, "ISK");
+    assert_eq!(is.month_names_full[4], "maí");
+    assert_eq!(is.day_names_short[2], "þri.");
+    assert_eq!(is.am_string, "f.h.");
+    assert_eq!((is.date_separator, is.time_separator), ('.', ':'));
+
+    // Regions without their own data fall back to the language
+    assert_eq!(Locale::from_tag("is_IS"), Some(is.clone()));
+    assert_eq!(Locale::native_zero_for_tag("is"), None);
+
+    let opts = FormatOptions::builder().locale(is).build();
+    let fmt = NumberFormat::parse("dddd d\\. mmmm yyyy").unwrap();
+    assert_eq!(fmt.format(45306.0, &opts), "mánudagur 15. janúar 2024");
+}
+
+#[test]
+fn test_cldr_native_digits() {
+    let ar = Locale::from_tag("ar-EG").unwrap();
+    assert_eq!(ar.decimal_separator, '٫');
+    assert_eq!(ar.pm_string, "م");
+    // The short date pattern has right-to-left marks before each `/`
+    assert_eq!(ar.date_separator, '/');
+    assert_eq!(Locale::native_zero_for_tag("AR-eg"), Some('٠'));
+}
+
+#[test]
+fn test_builtin_data_wins() {
+    assert_eq!(Locale::from_tag("de-DE"), Some(Locale::from_lcid(0x407).unwrap()));
+}
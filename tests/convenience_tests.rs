@@ -1,4 +1,7 @@
-use ssfmt::{format, format_default};
+use ssfmt::{
+    format, format_default, format_duration, format_text, format_value, FormatOptions, Value,
+};
+use std::time::Duration;
 
 #[test]
 fn test_format_convenience() {
@@ -20,3 +23,122 @@ fn test_format_invalid_code() {
     let result = format(42.0, "", &opts);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_format_text_convenience() {
+    let opts = FormatOptions::default();
+    assert_eq!(
+        format_text("abc", "0.00;-0.00;0;[Blue]\"<\"@\">\"", &opts).unwrap(),
+        "<abc>"
+    );
+    // Without a text section the text is shown as-is
+    assert_eq!(format_text("abc", "0.00", &opts).unwrap(), "abc");
+    assert!(format_text("abc", "", &opts).is_err());
+}
+
+#[test]
+fn test_format_value_convenience() {
+    let opts = FormatOptions::default();
+    let code = "#,##0.00;(#,##0.00);\"-\";\"note: \"@";
+    let cases = [
+        (Value::Number(1234.5), "1,234.50"),
+        (Value::Number(-2.0), "(2.00)"),
+        (Value::from(0), "-"),
+        (Value::Text("see below"), "note: see below"),
+        (Value::Bool(false), "note: FALSE"),
+        (Value::Empty, ""),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(
+            format_value(&value, code, &opts).unwrap(),
+            expected,
+            "{:?}",
+            value
+        );
+    }
+}
+
+#[test]
+fn test_format_bool() {
+    let opts = FormatOptions::default();
+    // Number sections never apply to booleans
+    for code in ["0.00", "[>0]\"yes\";\"no\"", "0;-0;\"zero\""] {
+        let fmt = ssfmt::NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format_bool(true, &opts), "TRUE", "{code}");
+        assert_eq!(fmt.format_bool(false, &opts), "FALSE", "{code}");
+    }
+    let fmt = ssfmt::NumberFormat::parse("[Blue]@*-").unwrap();
+    let fr = FormatOptions::builder()
+        .locale(ssfmt::Locale::from_tag("fr-FR").unwrap())
+        .cell_width(6)
+        .build();
+    assert_eq!(fmt.format_bool(false, &fr), "FAUX--");
+}
+
+#[test]
+fn test_format_duration_convenience() {
+    let d = Duration::from_secs(2 * 86_400 + 3 * 3600 + 4 * 60 + 5);
+    assert_eq!(format_duration(d, "[h]:mm:ss").unwrap(), "51:04:05");
+    assert_eq!(format_duration(d, "[mm]").unwrap(), "3064");
+    assert_eq!(format_duration(d, "d \\d\\a\\y\\s h:mm").unwrap(), "2 days 3:04");
+    assert_eq!(
+        format_duration(Duration::from_millis(83_456), "[m]:ss.000").unwrap(),
+        "1:23.456"
+    );
+    assert_eq!(format_duration(Duration::ZERO, "[h]:mm:ss").unwrap(), "0:00:00");
+    assert!(format_duration(d, "").is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_format_value_dates() {
+    use chrono::NaiveDate;
+    use ssfmt::DateSystem;
+
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let opts = FormatOptions::default();
+    assert_eq!(
+        format_value(&Value::Date(date), "yyyy-mm-dd", &opts).unwrap(),
+        "2024-01-01"
+    );
+    let dt = date.and_hms_opt(18, 30, 0).unwrap();
+    assert_eq!(
+        format_value(&Value::DateTime(dt), "d mmm h:mm", &opts).unwrap(),
+        "1 Jan 18:30"
+    );
+    assert_eq!(
+        format_value(&Value::Time(dt.time()), "[h]:mm", &opts).unwrap(),
+        "18:30"
+    );
+    // Serials follow the date system, so the result doesn't change
+    let opts = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
+    assert_eq!(
+        format_value(&Value::Date(date), "yyyy-mm-dd", &opts).unwrap(),
+        "2024-01-01"
+    );
+}
+
+#[test]
+fn test_preview() {
+    use ssfmt::ast::{Color, NamedColor};
+    use ssfmt::preview;
+
+    let opts = FormatOptions::default();
+    let result = preview("yyyy-mm-dd hh:mm", &opts).unwrap();
+    assert_eq!(result.samples[4].value, Value::Number(45366.5625));
+    assert_eq!(result.samples[4].text, "2024-03-15 13:30");
+    assert_eq!(result.samples[5].section, None);
+    assert_eq!(result.sections_used(), [0]);
+
+    let result = preview("0;[Blue]-0;\"zero\";\"<\"@\">\"", &opts).unwrap();
+    let sections: Vec<_> = result.samples.iter().map(|s| s.section).collect();
+    assert_eq!(sections, [Some(0), Some(1), Some(2), Some(0), Some(0), Some(3)]);
+    assert_eq!(result.samples[1].text, "-1235");
+    assert_eq!(result.samples[1].color, Some(Color::Named(NamedColor::Blue)));
+    assert_eq!(result.samples[5].text, "<text>");
+    assert_eq!(result.sections_used(), [0, 1, 2, 3]);
+
+    assert!(preview("0.00\"", &opts).is_err());
+}
@@ -45,3 +45,208 @@ fn test_date_to_serial() {
     let serial = date_to_serial(2026, 1, 9, DateSystem::Date1900);
     assert!((serial - 46031.0).abs() < 0.0001);
 }
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use chrono::{NaiveDate, NaiveDateTime};
+    use ssfmt::date_serial::{naive_datetime_to_serial, serial_to_naive_datetime};
+    use ssfmt::DateSystem;
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_serial_to_naive_datetime_leap_bug() {
+        let system = DateSystem::Date1900;
+        assert_eq!(
+            serial_to_naive_datetime(59.0, system),
+            Some(datetime(1900, 2, 28, 0, 0, 0))
+        );
+        assert_eq!(serial_to_naive_datetime(60.0, system), None);
+        assert_eq!(
+            serial_to_naive_datetime(61.0, system),
+            Some(datetime(1900, 3, 1, 0, 0, 0))
+        );
+        assert_eq!(
+            serial_to_naive_datetime(0.25, system),
+            Some(datetime(1899, 12, 31, 6, 0, 0))
+        );
+        assert_eq!(serial_to_naive_datetime(-1.0, system), None);
+        assert_eq!(serial_to_naive_datetime(f64::NAN, system), None);
+    }
+
+    #[test]
+    fn test_serial_to_naive_datetime_rounding() {
+        // 23:59:59.9999 rounds up to midnight of the next day
+        let serial = 45292.0 + 86_399.999_9 / 86_400.0;
+        assert_eq!(
+            serial_to_naive_datetime(serial, DateSystem::Date1900),
+            Some(datetime(2024, 1, 2, 0, 0, 0))
+        );
+        let dt = serial_to_naive_datetime(45292.0 + 1.5 / 86_400.0, DateSystem::Date1900).unwrap();
+        assert_eq!(dt.and_utc().timestamp_subsec_millis(), 500);
+    }
+
+    #[test]
+    fn test_naive_datetime_round_trip() {
+        for system in [DateSystem::Date1900, DateSystem::Date1904] {
+            for dt in [
+                datetime(1904, 1, 1, 0, 0, 0),
+                datetime(1999, 12, 31, 23, 59, 59),
+                datetime(2024, 2, 29, 12, 30, 15),
+            ] {
+                let serial = naive_datetime_to_serial(dt, system);
+                assert_eq!(
+                    serial_to_naive_datetime(serial, system),
+                    Some(dt),
+                    "{:?}",
+                    system
+                );
+            }
+        }
+        assert_eq!(
+            naive_datetime_to_serial(datetime(1900, 3, 1, 12, 0, 0), DateSystem::Date1900),
+            61.5
+        );
+        assert_eq!(
+            naive_datetime_to_serial(datetime(1904, 1, 1, 0, 0, 0), DateSystem::Date1904),
+            0.0
+        );
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conversions {
+    use ssfmt::date_serial::{primitive_datetime_to_serial, serial_to_primitive_datetime};
+    use ssfmt::{DateSystem, FormatOptions, NumberFormat, Value};
+    use time::macros::{date, datetime, time};
+
+    #[test]
+    fn test_serial_to_primitive_datetime() {
+        let system = DateSystem::Date1900;
+        assert_eq!(
+            serial_to_primitive_datetime(59.5, system),
+            Some(datetime!(1900-02-28 12:00))
+        );
+        assert_eq!(serial_to_primitive_datetime(60.0, system), None);
+        assert_eq!(
+            serial_to_primitive_datetime(61.0, system),
+            Some(datetime!(1900-03-01 0:00))
+        );
+        assert_eq!(
+            serial_to_primitive_datetime(0.0, DateSystem::Date1904),
+            Some(datetime!(1904-01-01 0:00))
+        );
+        assert_eq!(serial_to_primitive_datetime(-0.5, system), None);
+    }
+
+    #[test]
+    fn test_primitive_datetime_round_trip() {
+        for system in [DateSystem::Date1900, DateSystem::Date1904] {
+            for dt in [
+                datetime!(1904-01-01 0:00),
+                datetime!(1999-12-31 23:59:59),
+                datetime!(2024-02-29 12:30:15.250),
+            ] {
+                let serial = primitive_datetime_to_serial(dt, system);
+                assert_eq!(serial_to_primitive_datetime(serial, system), Some(dt));
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_values_format() {
+        let opts = FormatOptions::default();
+        let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm").unwrap();
+        let value = Value::from(datetime!(2024-01-01 18:30));
+        assert_eq!(fmt.format_value(&value, &opts), "2024-01-01 18:30");
+
+        assert_eq!(
+            Value::from(date!(2024 - 01 - 01)),
+            Value::PrimitiveDateTime(datetime!(2024-01-01 0:00))
+        );
+        assert_eq!(Value::from(time!(6:00)), Value::Number(0.25));
+    }
+
+    #[test]
+    fn test_time_values_follow_date_system() {
+        let opts = FormatOptions::builder()
+            .date_system(DateSystem::Date1904)
+            .build();
+        let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm").unwrap();
+        let value = Value::from(date!(2024 - 01 - 15));
+        assert_eq!(fmt.format_value(&value, &opts), "2024-01-15 00:00");
+        let value = Value::from(datetime!(2024-01-15 18:30));
+        assert_eq!(fmt.format_value(&value, &opts), "2024-01-15 18:30");
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_conversions {
+    use jiff::civil::{date, time};
+    use ssfmt::date_serial::{civil_datetime_to_serial, serial_to_civil_datetime};
+    use ssfmt::{DateSystem, FormatOptions, NumberFormat, Value};
+
+    #[test]
+    fn test_serial_to_civil_datetime() {
+        let system = DateSystem::Date1900;
+        assert_eq!(
+            serial_to_civil_datetime(59.5, system),
+            Some(date(1900, 2, 28).at(12, 0, 0, 0))
+        );
+        assert_eq!(serial_to_civil_datetime(60.0, system), None);
+        assert_eq!(
+            serial_to_civil_datetime(61.0, system),
+            Some(date(1900, 3, 1).at(0, 0, 0, 0))
+        );
+        assert_eq!(
+            serial_to_civil_datetime(0.0, DateSystem::Date1904),
+            Some(date(1904, 1, 1).at(0, 0, 0, 0))
+        );
+        assert_eq!(serial_to_civil_datetime(-0.5, system), None);
+    }
+
+    #[test]
+    fn test_civil_datetime_round_trip() {
+        for system in [DateSystem::Date1900, DateSystem::Date1904] {
+            for dt in [
+                date(1904, 1, 1).at(0, 0, 0, 0),
+                date(1999, 12, 31).at(23, 59, 59, 0),
+                date(2024, 2, 29).at(12, 30, 15, 250_000_000),
+            ] {
+                let serial = civil_datetime_to_serial(dt, system);
+                assert_eq!(serial_to_civil_datetime(serial, system), Some(dt));
+            }
+        }
+    }
+
+    #[test]
+    fn test_jiff_values_format() {
+        let opts = FormatOptions::default();
+        let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm").unwrap();
+        let value = Value::from(date(2024, 1, 1).at(18, 30, 0, 0));
+        assert_eq!(fmt.format_value(&value, &opts), "2024-01-01 18:30");
+
+        assert_eq!(
+            Value::from(date(2024, 1, 1)),
+            Value::CivilDateTime(date(2024, 1, 1).at(0, 0, 0, 0))
+        );
+        assert_eq!(Value::from(time(6, 0, 0, 0)), Value::Number(0.25));
+    }
+
+    #[test]
+    fn test_jiff_values_follow_date_system() {
+        let opts = FormatOptions::builder()
+            .date_system(DateSystem::Date1904)
+            .build();
+        let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm").unwrap();
+        let value = Value::from(date(2024, 1, 15));
+        assert_eq!(fmt.format_value(&value, &opts), "2024-01-15 00:00");
+        let value = Value::from(date(2024, 1, 15).at(18, 30, 0, 0));
+        assert_eq!(fmt.format_value(&value, &opts), "2024-01-15 18:30");
+    }
+}
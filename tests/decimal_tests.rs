@@ -0,0 +1,143 @@
+//! Tests for exact decimal formatting (requires `decimal` feature).
+
+#![cfg(feature = "decimal")]
+
+use std::str::FromStr;
+
+use ssfmt::{format_decimal, format_decimal_default, Decimal, FormatOptions, NumberFormat, Value};
+
+fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+}
+
+fn fmt(value: &str, code: &str) -> String {
+    format_decimal_default(&dec(value), code).unwrap()
+}
+
+#[test]
+fn test_sum_is_exact() {
+    let total = dec("0.1") + dec("0.2");
+    assert_eq!(
+        format_decimal_default(&total, "0.0000000000000000000").unwrap(),
+        "0.3000000000000000000"
+    );
+    assert_eq!(format_decimal_default(&total, "General").unwrap(), "0.3");
+}
+
+#[test]
+fn test_digits_beyond_f64_precision() {
+    assert_eq!(
+        fmt("12345678901234567.89", "#,##0.00"),
+        "12,345,678,901,234,567.89"
+    );
+    assert_eq!(
+        fmt("-98765432109876543.21", "#,##0.00"),
+        "-98,765,432,109,876,543.21"
+    );
+    assert_eq!(
+        fmt("0.123456789012345678", "0.000000000000000000"),
+        "0.123456789012345678"
+    );
+}
+
+#[test]
+fn test_rounds_half_away_from_zero() {
+    // 1.005 is 1.00499999999999989... as f64
+    assert_eq!(fmt("1.005", "0.00"), "1.01");
+    assert_eq!(fmt("-1.005", "0.00"), "-1.01");
+    assert_eq!(fmt("2.5", "0"), "3");
+    assert_eq!(fmt("0.995", "0.00"), "1.00");
+}
+
+#[test]
+fn test_matches_f64_path_for_simple_values() {
+    let opts = FormatOptions::default();
+    for code in [
+        "0",
+        "0.00",
+        "#,##0.00",
+        "#.##",
+        "0.0#",
+        "$#,##0.00",
+        "000-00-0000",
+        "0.00%",
+        "#,##0,",
+        "#,##0,.0",
+        "0.0,,\"M\"",
+        "0.00;(0.00)",
+        "0;-0;\"zero\"",
+    ] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        for value in [
+            "0", "1", "-1", "1234.5", "-1234.56", "0.25", "1234567", "12.3",
+        ] {
+            let f = f64::from_str(value).unwrap();
+            assert_eq!(
+                fmt.format_decimal(&dec(value), &opts),
+                fmt.format(f, &opts),
+                "{value} with {code}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_percent_and_scaling() {
+    assert_eq!(
+        fmt("0.123456789012345678", "0.0000000000000000%"),
+        "12.3456789012345678%"
+    );
+    assert_eq!(fmt("12345678901234567890", "#,##0,,"), "12,345,678,901,235");
+    assert_eq!(fmt("1234567", "#,##0,"), "1,235");
+}
+
+#[test]
+fn test_sections() {
+    assert_eq!(fmt("-1234.5", "#,##0.00;(#,##0.00)"), "(1,234.50)");
+    assert_eq!(fmt("0", "0.00;-0.00;\"-\""), "-");
+    assert_eq!(fmt("0.001", "[Red]0.00"), "0.00");
+}
+
+#[test]
+fn test_non_number_sections_use_f64() {
+    assert_eq!(fmt("45658", "yyyy-mm-dd"), "2025-01-01");
+    assert_eq!(fmt("0.5", "# ?/?"), " 1/2");
+    assert_eq!(fmt("12345", "0.00E+00"), "1.23E+04");
+}
+
+#[test]
+fn test_format_decimal_with_options() {
+    let opts = FormatOptions::builder()
+        .locale(ssfmt::Locale::from_tag("de-DE").unwrap())
+        .build();
+    assert_eq!(
+        format_decimal(&dec("1234567.891"), "#,##0.00", &opts).unwrap(),
+        "1.234.567,89"
+    );
+}
+
+#[test]
+fn test_value_decimal() {
+    let value: Value = dec("12.50").into();
+    assert_eq!(value, Value::Decimal(dec("12.5")));
+    assert_eq!(value.as_decimal(), Some(&dec("12.5")));
+    assert_eq!(value.as_number(), Some(12.5));
+    assert_eq!(value.type_name(), "decimal");
+}
+
+#[test]
+fn test_half_even_rounding() {
+    let opts = FormatOptions::builder()
+        .rounding(ssfmt::RoundingMode::HalfEven)
+        .build();
+    assert_eq!(format_decimal(&dec("2.665"), "0.00", &opts).unwrap(), "2.66");
+    assert_eq!(format_decimal(&dec("2.675"), "0.00", &opts).unwrap(), "2.68");
+}
+
+#[test]
+fn test_negative_zero() {
+    assert_eq!(fmt("-0.004", "0.00"), "0.00");
+    assert_eq!(fmt("-0.005", "0.00"), "-0.01");
+    let opts = FormatOptions::builder().show_negative_zero(true).build();
+    assert_eq!(format_decimal(&dec("-0.004"), "0.00", &opts).unwrap(), "-0.00");
+}
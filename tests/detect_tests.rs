@@ -0,0 +1,69 @@
+use ssfmt::{detect_format, FormatGuess};
+
+fn best(samples: &[&str]) -> FormatGuess {
+    detect_format(samples).into_iter().next().unwrap()
+}
+
+#[test]
+fn test_detect_numbers() {
+    assert_eq!(best(&["$1,234.00", "$5.50"]).format_code, "$#,##0.00");
+    assert_eq!(best(&["12.5%", "3.0%"]).format_code, "0.0%");
+    assert_eq!(best(&["1,234", "56"]).format_code, "#,##0");
+    assert_eq!(best(&["1.23E+04"]).format_code, "0.00E+00");
+    assert_eq!(best(&["12 kg", "7 kg"]).format_code, "0\" kg\"");
+}
+
+#[test]
+fn test_detect_accounting_negatives() {
+    let guess = best(&["1,234.00", "(56.00)"]);
+    assert_eq!(guess.format_code, "#,##0.00;(#,##0.00)");
+    assert_eq!(guess.confidence, 1.0);
+}
+
+#[test]
+fn test_detect_dates_and_times() {
+    assert_eq!(
+        best(&["2024-01-15", "2023-12-31"]).format_code,
+        "yyyy-mm-dd"
+    );
+    assert_eq!(best(&["1/15/2024", "12/31/2023"]).format_code, "m/d/yyyy");
+    assert_eq!(
+        best(&["15/01/2024", "31/12/2023"]).format_code,
+        "dd/mm/yyyy"
+    );
+    assert_eq!(best(&["15-Jan-24"]).format_code, "d-mmm-yy");
+    assert_eq!(best(&["9:30 AM", "4:15 PM"]).format_code, "h:mm AM/PM");
+    assert_eq!(
+        best(&["2024-01-15 09:30:00"]).format_code,
+        "yyyy-mm-dd hh:mm:ss"
+    );
+}
+
+#[test]
+fn test_detect_mixed_samples_lower_confidence() {
+    let guesses = detect_format(&["2024-01-15", "2024-02-01", "n/a", "2024-03-10"]);
+    assert_eq!(guesses[0].format_code, "yyyy-mm-dd");
+    assert_eq!(guesses[0].confidence, 0.75);
+    assert!(guesses
+        .iter()
+        .any(|g| g.format_code == "@" && g.confidence == 0.25));
+}
+
+#[test]
+fn test_detect_ranked_by_confidence() {
+    let guesses = detect_format(&["1.5", "2.25", "3"]);
+    assert_eq!(guesses[0].format_code, "General");
+    assert_eq!(guesses[0].confidence, 1.0);
+    for pair in guesses.windows(2) {
+        assert!(pair[0].confidence >= pair[1].confidence);
+    }
+}
+
+#[test]
+fn test_detect_empty_and_text() {
+    assert!(detect_format(&[]).is_empty());
+    assert!(detect_format(&["", "  "]).is_empty());
+    let guess = best(&["apple", "pear"]);
+    assert_eq!(guess.format_code, "@");
+    assert_eq!(guess.confidence, 1.0);
+}
@@ -1,10 +1,10 @@
-use ssfmt::ParseError;
+use ssfmt::{DiagnosticCode, NumberFormat, ParseError};
 
 #[test]
 fn test_parse_error_display() {
     let err = ParseError::UnexpectedToken {
-        position: 5,
-        found: 'x',
+        span: 5..6,
+        found: "x".to_string(),
     };
     let msg = format!("{}", err);
     assert!(msg.contains("position 5"));
@@ -17,3 +17,65 @@ fn test_parse_error_too_many_sections() {
     let msg = format!("{}", err);
     assert!(msg.contains("4"));
 }
+
+#[test]
+fn test_unterminated_bracket_span() {
+    let code = "0.00[Red";
+    let err = NumberFormat::parse(code).unwrap_err();
+    assert_eq!(err.code(), DiagnosticCode::UnterminatedBracket);
+    assert_eq!(err.span(), Some(4..8));
+    assert_eq!(err.offending_text(), Some("[Red"));
+    assert_eq!(
+        err.render_diagnostic(code),
+        "error[unterminated-bracket]: unterminated bracket at position 4\n  |\n  | 0.00[Red\n  |     ^^^^"
+    );
+}
+
+#[test]
+fn test_unterminated_quote_span_is_in_bytes() {
+    let code = "€0\"ab";
+    let err = NumberFormat::parse(code).unwrap_err();
+    assert_eq!(err.code(), DiagnosticCode::UnterminatedQuote);
+    assert_eq!(err.span(), Some(4..7));
+    assert_eq!(&code[err.span().unwrap()], "\"ab");
+    // Carets line up by character, not byte
+    assert!(err.render_diagnostic(code).ends_with("\n  |   ^^^"));
+}
+
+#[test]
+fn test_dangling_escape() {
+    let err = NumberFormat::parse("0\\").unwrap_err();
+    assert_eq!(err.code(), DiagnosticCode::DanglingEscape);
+    assert_eq!(err.span(), Some(1..2));
+    assert_eq!(err.offending_text(), Some("\\"));
+}
+
+#[test]
+fn test_errors_without_location() {
+    let err = NumberFormat::parse("").unwrap_err();
+    assert_eq!(err.code(), DiagnosticCode::EmptyFormat);
+    assert_eq!(err.span(), None);
+    assert_eq!(
+        err.render_diagnostic(""),
+        "error[empty-format]: empty format code"
+    );
+    assert_eq!(
+        DiagnosticCode::InvalidFormatId.to_string(),
+        "invalid-format-id"
+    );
+}
+
+#[test]
+fn test_invalid_condition() {
+    let code = "[>=abc]0;0";
+    let err = NumberFormat::parse(code).unwrap_err();
+    assert_eq!(err.code(), DiagnosticCode::InvalidCondition);
+    assert_eq!(err.span(), Some(0..7));
+    assert_eq!(err.offending_text(), Some("[>=abc]"));
+    assert!(err.to_string().ends_with("'abc' is not a number"));
+
+    // Lenient parsing ignores the bracket instead
+    let (format, warnings) = NumberFormat::parse_lenient(code);
+    assert_eq!(format.sections()[0].condition, None);
+    assert_eq!(warnings.len(), 1);
+}
@@ -0,0 +1,102 @@
+//! Tests for the C ABI (`ffi` feature).
+
+#![cfg(feature = "ffi")]
+
+use std::ffi::{c_char, CStr};
+
+use ssfmt::ffi::*;
+
+fn call(f: impl Fn(*mut c_char, usize) -> isize) -> (isize, String) {
+    let mut buf = [0 as c_char; 64];
+    let len = f(buf.as_mut_ptr(), buf.len());
+    let text = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    (len, text.to_str().unwrap().to_string())
+}
+
+fn last_error() -> String {
+    call(|buf, len| unsafe { ssfmt_last_error(buf, len) }).1
+}
+
+#[test]
+fn test_ffi_format() {
+    let (len, text) =
+        call(|buf, len| unsafe { ssfmt_format(1234.5, c"#,##0.00".as_ptr(), buf, len) });
+    assert_eq!((len, text.as_str()), (8, "1,234.50"));
+
+    let (len, text) = call(|buf, len| unsafe { ssfmt_format_with_id(0.5, 9, buf, len) });
+    assert_eq!((len, text.as_str()), (3, "50%"));
+
+    // Measuring with a null buffer
+    let len = unsafe { ssfmt_format(1.0, c"0.000".as_ptr(), std::ptr::null_mut(), 0) };
+    assert_eq!(len, 5);
+}
+
+#[test]
+fn test_ffi_truncation() {
+    let mut buf = [0x7f as c_char; 4];
+    let len = unsafe { ssfmt_format(1234.5, c"#,##0.00".as_ptr(), buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(len, 8);
+    assert_eq!(
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap(),
+        "1,2"
+    );
+
+    // Never splits a multi-byte character
+    let mut buf = [0x7f as c_char; 4];
+    let len = unsafe { ssfmt_format(5.0, c"\"€€\"0".as_ptr(), buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(len, 7);
+    assert_eq!(
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap(),
+        "€"
+    );
+}
+
+#[test]
+fn test_ffi_errors() {
+    let (len, _) = call(|buf, len| unsafe { ssfmt_format(1.0, c"[Red".as_ptr(), buf, len) });
+    assert_eq!(len, SSFMT_ERR_PARSE);
+    assert!(!last_error().is_empty());
+
+    let (len, _) = call(|buf, len| unsafe { ssfmt_format(1.0, std::ptr::null(), buf, len) });
+    assert_eq!(len, SSFMT_ERR_NULL);
+
+    let invalid = [0xff_u8 as c_char, 0];
+    let (len, _) = call(|buf, len| unsafe { ssfmt_format(1.0, invalid.as_ptr(), buf, len) });
+    assert_eq!(len, SSFMT_ERR_UTF8);
+
+    let (len, _) = call(|buf, len| unsafe { ssfmt_format_with_id(1.0, 999, buf, len) });
+    assert_eq!(len, SSFMT_ERR_PARSE);
+}
+
+#[test]
+fn test_ffi_success_clears_last_error() {
+    let (len, _) = call(|buf, len| unsafe { ssfmt_format(1.0, c"[Red".as_ptr(), buf, len) });
+    assert_eq!(len, SSFMT_ERR_PARSE);
+    assert!(!last_error().is_empty());
+
+    let (len, text) = call(|buf, len| unsafe { ssfmt_format(1.0, c"0.0".as_ptr(), buf, len) });
+    assert_eq!((len, text.as_str()), (3, "1.0"));
+    assert_eq!(last_error(), "");
+
+    assert!(unsafe { ssfmt_parse(c"[Red".as_ptr()) }.is_null());
+    let (len, _) = call(|buf, len| unsafe { ssfmt_format_with_id(0.5, 9, buf, len) });
+    assert_eq!(len, 3);
+    assert_eq!(last_error(), "");
+}
+
+#[test]
+fn test_ffi_compiled_handle() {
+    unsafe {
+        let format = ssfmt_parse(c"0.0%".as_ptr());
+        assert!(!format.is_null());
+        let (len, text) = call(|buf, len| ssfmt_format_compiled(format, 0.125, buf, len));
+        assert_eq!((len, text.as_str()), (5, "12.5%"));
+        ssfmt_free(format);
+
+        assert!(ssfmt_parse(c"[Red".as_ptr()).is_null());
+        assert!(!last_error().is_empty());
+        let (len, _) = call(|buf, len| ssfmt_format_compiled(std::ptr::null(), 1.0, buf, len));
+        assert_eq!(len, SSFMT_ERR_NULL);
+        ssfmt_free(std::ptr::null_mut());
+    }
+}
@@ -1,4 +1,4 @@
-use ssfmt::{FormatOptions, NumberFormat};
+use ssfmt::{DateSystem, FormatOptions, HijriAlgorithm, Locale, NumberFormat};
 
 #[test]
 fn test_format_date_ymd() {
@@ -9,6 +9,69 @@ fn test_format_date_ymd() {
     assert_eq!(fmt.format(46031.0, &opts), "2026-01-09");
 }
 
+#[test]
+fn test_format_year_run_lengths() {
+    let opts = FormatOptions::default();
+    let opts_1904 = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
+    // (code, written back as, 2024-03-15, 1904 system 1904-01-01 + 45366 days)
+    let cases = [
+        ("y", "yy", "24", "28"),
+        ("yy", "yy", "24", "28"),
+        ("yyy", "yyy", "2024", "2028"),
+        ("yyyy", "yyyy", "2024", "2028"),
+        ("yyyyy", "yyyy", "2024", "2028"),
+        ("yyyyyyyyy", "yyyy", "2024", "2028"),
+        ("Y", "yy", "24", "28"),
+        ("YYY", "yyy", "2024", "2028"),
+        ("yYyY", "yyyy", "2024", "2028"),
+        ("YYYYYy", "yyyy", "2024", "2028"),
+        ("d/m/YyY", "d/m/yyy", "15/3/2024", "16/3/2028"),
+    ];
+    for (code, written, expected, expected_1904) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.to_string(), written, "{code}");
+        assert_eq!(fmt.format(45366.0, &opts), expected, "{code}");
+        assert_eq!(fmt.format(45366.0, &opts_1904), expected_1904, "{code}");
+    }
+}
+
+#[test]
+fn test_format_weekday_only() {
+    let opts = FormatOptions::default();
+    let opts_1904 = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
+    for (code, written) in [
+        ("ddd", "ddd"),
+        ("dddd", "dddd"),
+        ("ddddd", "dddd"),
+        ("DdDdDdD", "dddd"),
+    ] {
+        assert_eq!(NumberFormat::parse(code).unwrap().to_string(), written);
+    }
+
+    // Serial 0 is Excel's Saturday, January 0, 1900, and 60 its fictitious
+    // February 29; in the 1904 system serial 0 is Friday, January 1, 1904
+    let fmt = NumberFormat::parse("ddd").unwrap();
+    let long = NumberFormat::parse("ddddd").unwrap();
+    for (serial, day, day_1904) in [
+        (0.0, "Saturday", "Friday"),
+        (0.75, "Saturday", "Friday"),
+        (1.0, "Sunday", "Saturday"),
+        (59.0, "Tuesday", "Monday"),
+        (60.0, "Wednesday", "Tuesday"),
+        (61.0, "Thursday", "Wednesday"),
+        (45366.0, "Friday", "Thursday"),
+    ] {
+        assert_eq!(fmt.format(serial, &opts), day[..3], "{serial}");
+        assert_eq!(fmt.format(serial, &opts_1904), day_1904[..3], "{serial}");
+        assert_eq!(long.format(serial, &opts), day, "{serial}");
+        assert_eq!(long.format(serial, &opts_1904), day_1904, "{serial}");
+    }
+}
+
 #[test]
 fn test_format_date_mdy() {
     let fmt = NumberFormat::parse("m/d/yyyy").unwrap();
@@ -51,3 +114,300 @@ fn test_format_month_name() {
 
     assert_eq!(fmt.format(46031.0, &opts), "January 9, 2026");
 }
+
+#[test]
+fn test_format_date_lcid_names() {
+    let opts = FormatOptions::default();
+
+    // January 9, 2026 is a Friday
+    let fmt = NumberFormat::parse("[$-407]dddd, d mmmm yyyy").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "Freitag, 9 Januar 2026");
+
+    let fmt = NumberFormat::parse("[$-40C]ddd d mmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "ven. 9 janv.");
+
+    let fmt = NumberFormat::parse("[$-411]yyyy\"年\"mmmm d\"日\" dddd").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "2026年1月 9日 金曜日");
+}
+
+#[test]
+fn test_format_date_lcid_overrides_options_locale() {
+    // The format's own locale tag wins over the options' locale for names
+    let opts = FormatOptions::builder()
+        .locale(Locale::from_tag("fr-FR").unwrap())
+        .build();
+    let fmt = NumberFormat::parse("[$-407]dddd, d mmmm yyyy").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "Freitag, 9 Januar 2026");
+
+    // Without a tag the options' locale names are used
+    let fmt = NumberFormat::parse("mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "janvier");
+
+    // Regional variants without their own data use their language's names
+    let fmt = NumberFormat::parse("[$-1407]mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "Januar");
+    let fmt = NumberFormat::parse("[$-2C0A]mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "enero");
+}
+
+#[test]
+fn test_format_time_lcid_ampm() {
+    let fmt = NumberFormat::parse("[$-411]AM/PM h:mm").unwrap();
+    let opts = FormatOptions::default();
+
+    assert_eq!(fmt.format(0.25, &opts), "午前 6:00");
+    assert_eq!(fmt.format(0.75, &opts), "午後 6:00");
+}
+
+#[test]
+fn test_format_ampm_keeps_locale_designators() {
+    let opts = FormatOptions::default();
+
+    // Designators show as the locale writes them, whatever the code's case
+    let fmt = NumberFormat::parse("h:mm am/pm").unwrap();
+    assert_eq!(fmt.format(0.75, &opts), "6:00 PM");
+    let fmt = NumberFormat::parse("[$-C0A]h:mm AM/PM").unwrap();
+    assert_eq!(fmt.format(0.25, &opts), "6:00 a. m.");
+    let fmt = NumberFormat::parse("[$-804]AM/PM h:mm").unwrap();
+    assert_eq!(fmt.format(0.75, &opts), "下午 6:00");
+}
+
+#[test]
+fn test_format_chinese_ampm_marker() {
+    let opts = FormatOptions::default();
+
+    let fmt = NumberFormat::parse("上午/下午h\"时\"mm\"分\"").unwrap();
+    assert_eq!(fmt.format(0.25, &opts), "上午6时00分");
+    assert_eq!(fmt.format(0.75, &opts), "下午6时00分");
+
+    // The marker makes the hour 12-hour whatever the locale
+    let fmt = NumberFormat::parse("[$-409]上午/下午 hh:mm").unwrap();
+    assert_eq!(fmt.format(0.0, &opts), "上午 12:00");
+}
+
+#[test]
+fn test_format_date_unknown_lcid_uses_options_locale() {
+    let opts = FormatOptions::default();
+
+    let fmt = NumberFormat::parse("[$-409]mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "January");
+
+    // Calendar bits in the high word don't affect the language lookup
+    let fmt = NumberFormat::parse("[$-10407]mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "Januar");
+
+    let fmt = NumberFormat::parse("[$-47F]mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "January");
+}
+
+#[test]
+fn test_format_japanese_era() {
+    let opts = FormatOptions::default();
+
+    // June 15, 2024 = serial 45458, Reiwa 6
+    let fmt = NumberFormat::parse("[$-411]ggge\"年\"m\"月\"d\"日\"").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "令和6年6月15日");
+
+    let fmt = NumberFormat::parse("[$-411]gge").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "令6");
+    let fmt = NumberFormat::parse("[$-411]ge.m.d").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "R6.6.15");
+    let fmt = NumberFormat::parse("[$-411]r").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "06");
+
+    // Heisei ended April 30, 2019; Reiwa began May 1
+    let fmt = NumberFormat::parse("[$-411]gggee").unwrap();
+    assert_eq!(fmt.format(43585.0, &opts), "平成31");
+    let fmt = NumberFormat::parse("[$-411]rr").unwrap();
+    assert_eq!(fmt.format(43586.0, &opts), "令和01");
+
+    // January 1, 1900 is Meiji 33
+    let fmt = NumberFormat::parse("[$-411]ggge\"年\"").unwrap();
+    assert_eq!(fmt.format(1.0, &opts), "明治33年");
+}
+
+#[test]
+fn test_format_era_codes_outside_japanese_locale() {
+    let opts = FormatOptions::default();
+
+    // `e` is the Gregorian year and `g` shows nothing
+    let fmt = NumberFormat::parse("e").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "2024");
+    let fmt = NumberFormat::parse("ee/mm").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "2024/06");
+    let fmt = NumberFormat::parse("ggg e").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), " 2024");
+}
+
+#[test]
+fn test_format_roc_calendar() {
+    let opts = FormatOptions::default();
+
+    // Taiwan counts years from 1912, the first year of the Republic
+    let fmt = NumberFormat::parse("[$-404]e\"年\"m\"月\"d\"日\"").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "113年6月15日");
+    let fmt = NumberFormat::parse("[$-404]ggge\"年\"").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "中華民國113年");
+    let fmt = NumberFormat::parse("[$-404]gee").unwrap();
+    assert_eq!(fmt.format(4384.0, &opts), "民國01");
+
+    // `y` stays Gregorian unless the tag names the ROC calendar
+    let fmt = NumberFormat::parse("[$-404]yyyy").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "2024");
+    let fmt = NumberFormat::parse("[$-40404]yyy/m/d").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "113/6/15");
+
+    // Other Chinese locales have no era
+    let fmt = NumberFormat::parse("[$-804]e").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "2024");
+}
+
+#[test]
+fn test_format_thai_buddhist_calendar() {
+    let opts = FormatOptions::default();
+
+    // `b` and `bb` show two digits, longer runs four
+    let fmt = NumberFormat::parse("b bb bbb bbbb").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "67 67 2567 2567");
+
+    // Under a Thai locale `e` is the Buddhist Era year too
+    let fmt = NumberFormat::parse("[$-41E]d/m/e").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "15/6/2567");
+    let fmt = NumberFormat::parse("[$-41E]yyyy").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "2024");
+
+    // The Thai calendar type shows every year in the Buddhist Era, without
+    // adding 543 twice
+    let fmt = NumberFormat::parse("[$-7041E]yyyy bbbb").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "2567 2567");
+    let fmt = NumberFormat::parse("[$-7041E]gg e").unwrap();
+    assert_eq!(fmt.format(45458.0, &opts), "พ.ศ. 2567");
+}
+
+#[test]
+fn test_format_elapsed_days() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("[d] hh:mm").unwrap();
+    assert_eq!(fmt.format(2.75, &opts), "2 18:00");
+    assert_eq!(fmt.format(45.5, &opts), "45 12:00");
+    let fmt = NumberFormat::parse("[dd]\"d\" h\"h\"").unwrap();
+    assert_eq!(fmt.format(0.25, &opts), "00d 6h");
+    // A time that rounds up to midnight carries into the day count
+    let fmt = NumberFormat::parse("[d] hh:mm:ss").unwrap();
+    assert_eq!(fmt.format(1.0 - 0.4 / 86400.0, &opts), "1 00:00:00");
+}
+
+#[test]
+fn test_format_elapsed_subseconds() {
+    let opts = FormatOptions::default();
+    // 1.6 seconds: the whole seconds aren't rounded up past the fraction
+    let fmt = NumberFormat::parse("[ss].0").unwrap();
+    assert_eq!(fmt.format(1.6 / 86400.0, &opts), "01.6");
+    let fmt = NumberFormat::parse("[s].000").unwrap();
+    assert_eq!(fmt.format(61.25 / 86400.0, &opts), "61.250");
+    // Only a fraction that displays as a whole second carries
+    assert_eq!(fmt.format(1.9996 / 86400.0, &opts), "2.000");
+
+    // 59:59.6 stays in hour 0
+    let fmt = NumberFormat::parse("[h]:mm:ss.0").unwrap();
+    assert_eq!(fmt.format(3599.6 / 86400.0, &opts), "0:59:59.6");
+    assert_eq!(fmt.format(90000.25 / 86400.0, &opts), "25:00:00.3");
+}
+
+#[test]
+fn test_format_subsecond_precision() {
+    let opts = FormatOptions::default();
+    let time = (45296.0 + 0.123_456_789) / 86400.0;
+    let cases = [
+        ("hh:mm:ss.0", "12:34:56.1"),
+        ("hh:mm:ss.000", "12:34:56.123"),
+        ("hh:mm:ss.000000", "12:34:56.123457"),
+        ("hh:mm:ss.000000000", "12:34:56.123456789"),
+        ("[ss].000000", "45296.123457"),
+    ];
+    for (code, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(time, &opts), expected, "{code}");
+    }
+    // A date only carries the time to the microsecond
+    let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm:ss.000000").unwrap();
+    assert_eq!(fmt.format(45366.0 + time, &opts), "2024-03-15 12:34:56.123457");
+
+    // Rounding up to a whole second carries into the seconds
+    let fmt = NumberFormat::parse("ss.0").unwrap();
+    assert_eq!(fmt.format(0.001886, &opts), "43.0");
+    let fmt = NumberFormat::parse("ss.000000").unwrap();
+    assert_eq!(fmt.format(41.999_999_6 / 86400.0, &opts), "42.000000");
+    let fmt = NumberFormat::parse("ss.0000000").unwrap();
+    assert_eq!(fmt.format(41.999_999_6 / 86400.0, &opts), "41.9999996");
+}
+
+#[test]
+fn test_format_hebrew_calendar() {
+    let opts = FormatOptions::default();
+    // 2024-03-11 was 1 Adar II 5784, a leap year
+    let fmt = NumberFormat::parse("[$-80409]d mmmm yyyy").unwrap();
+    assert_eq!(fmt.format(45362.0, &opts), "1 Adar II 5784");
+    // 2024-10-03 was Rosh Hashanah 5785
+    assert_eq!(fmt.format(45568.0, &opts), "1 Tishrei 5785");
+    let fmt = NumberFormat::parse("[$-8040D]d mmmm yyyy").unwrap();
+    assert_eq!(fmt.format(45568.0, &opts), "1 תשרי 5785");
+
+    // Months count from Tishrei; weekdays are unchanged
+    let fmt = NumberFormat::parse("[$-80409]m/d/yy dddd").unwrap();
+    assert_eq!(fmt.format(45395.0, &opts), "8/5/84 Saturday");
+
+    // Without the calendar type the date stays Gregorian
+    let fmt = NumberFormat::parse("[$-40D]d/m/yyyy").unwrap();
+    assert_eq!(fmt.format(45568.0, &opts), "3/10/2024");
+}
+
+#[test]
+fn test_format_hijri_umm_al_qura() {
+    let fmt = NumberFormat::parse("B2yyyy-mm-dd").unwrap();
+    let opts = FormatOptions::builder()
+        .hijri_algorithm(HijriAlgorithm::UmmAlQura)
+        .build();
+    // 2024-03-11 was 1 Ramadan 1445 in Saudi Arabia; the tabular calendar
+    // runs ahead
+    assert_eq!(fmt.format(45362.0, &FormatOptions::default()), "1445-09-03");
+    assert_eq!(fmt.format(45362.0, &opts), "1445-09-01");
+    assert_eq!(fmt.format(45361.0, &opts), "1445-08-29");
+    // 2024-07-07 was 1 Muharram 1446
+    assert_eq!(fmt.format(45480.0, &opts), "1446-01-01");
+
+    // Past 2100 the tabular calendar is used
+    assert_eq!(
+        fmt.format(2958465.0, &opts),
+        fmt.format(2958465.0, &FormatOptions::default())
+    );
+}
+
+#[test]
+fn test_standard_formats() {
+    use ssfmt::formats;
+
+    let opts = FormatOptions::default();
+    let serial = 45306.0 + (12.0 * 3600.0 + 34.0 * 60.0 + 56.789) / 86400.0;
+    assert_eq!(formats::ISO_DATE.format(serial, &opts), "2024-01-15");
+    // Like Excel, fields without sub-seconds round to the nearest second
+    assert_eq!(
+        formats::ISO_DATETIME.format(serial, &opts),
+        "2024-01-15T12:34:57"
+    );
+    assert_eq!(
+        formats::RFC3339.format(serial, &opts),
+        "2024-01-15T12:34:56.789Z"
+    );
+    assert_eq!(formats::TIME_HMS.format(serial, &opts), "12:34:57");
+
+    assert_eq!(
+        formats::to_iso_datetime(serial, DateSystem::Date1900).as_deref(),
+        Some("2024-01-15T12:34:57")
+    );
+    assert_eq!(
+        formats::to_iso_date(45306.0, DateSystem::Date1904).as_deref(),
+        Some("2028-01-16")
+    );
+    assert_eq!(formats::to_iso_date(3e6, DateSystem::Date1900), None);
+}
@@ -1,4 +1,4 @@
-use ssfmt::{FormatOptions, NumberFormat};
+use ssfmt::{FillFallback, FormatOptions, NumberFormat};
 
 #[test]
 fn test_format_integer() {
@@ -53,3 +53,646 @@ fn test_format_negative_section() {
     assert_eq!(fmt.format(42.0, &opts), "42");
     assert_eq!(fmt.format(-42.0, &opts), "-42");
 }
+
+#[test]
+fn test_format_fill_to_cell_width() {
+    let fmt = NumberFormat::parse("$* #,##0.00").unwrap();
+    let opts = FormatOptions::builder().cell_width(12).build();
+
+    assert_eq!(fmt.format(1234.5, &opts), "$   1,234.50");
+    assert_eq!(fmt.format(-1234.5, &opts), "-$  1,234.50");
+    // Content wider than the cell gets no fill
+    assert_eq!(fmt.format(123456789.0, &opts), "$123,456,789.00");
+
+    let fmt = NumberFormat::parse("0*-").unwrap();
+    assert_eq!(fmt.format(42.0, &opts), "42----------");
+}
+
+#[test]
+fn test_format_fill_without_width() {
+    let fmt = NumberFormat::parse("$* #,##0.00").unwrap();
+
+    let opts = FormatOptions::default();
+    assert_eq!(fmt.format(1234.5, &opts), "$1,234.50");
+
+    let opts = FormatOptions::builder()
+        .fill_fallback(FillFallback::Single)
+        .build();
+    assert_eq!(fmt.format(1234.5, &opts), "$ 1,234.50");
+}
+
+#[test]
+fn test_format_fill_in_date_and_literal_formats() {
+    let opts = FormatOptions::builder().cell_width(10).build();
+    let fmt = NumberFormat::parse("yyyy*.").unwrap();
+    assert_eq!(fmt.format(45000.0, &opts), "2023......");
+
+    let fmt = NumberFormat::parse("\"N/A\"*_").unwrap();
+    assert_eq!(fmt.format(1.0, &opts), "N/A_______");
+}
+
+#[test]
+fn test_format_dbnum_numerals() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("[DBNum1][$-804]General", 1234.0, "一千二百三十四"),
+        ("[DBNum1][$-411]0", 1234.0, "千二百三十四"),
+        ("[DBNum2][$-804]0", 1234.0, "壹仟贰佰叁拾肆"),
+        ("[DBNum2][$-404]0", 10010.0, "壹萬零壹拾"),
+        ("[DBNum1][$-412]0", 20.0, "二十"),
+        ("[DBNum3][$-411]0", 1234.0, "１２３４"),
+        ("[DBNum1][$-804]0.00", 12.5, "十二.五〇"),
+        ("[DBNum1][$-804]#,##0", 1234.0, "一,二三四"),
+        ("[DBNum1][$-804]0\"元\"", 30.0, "三十元"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "code {}", code);
+    }
+
+    // The modifier applies to its own section only
+    let fmt = NumberFormat::parse("[DBNum1]0;-0").unwrap();
+    assert_eq!(fmt.format(-15.0, &opts), "-15");
+}
+
+#[test]
+fn test_format_dbnum_dates() {
+    let fmt = NumberFormat::parse("[DBNum1][$-411]yyyy\"年\"m\"月\"d\"日\"").unwrap();
+    assert_eq!(
+        fmt.format(45306.0, &FormatOptions::default()),
+        "二〇二四年一月十五日"
+    );
+}
+
+#[test]
+fn test_format_native_digits() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("[$-2010401]0", 123.0, "١٢٣"),
+        ("[$-2010401]#,##0.00", 1234.5, "١,٢٣٤.٥٠"),
+        ("[$-2010401]General", 42.0, "٤٢"),
+        ("[$-D00041E]0", 2567.0, "๒๕๖๗"),
+        ("[NatNum1][$-439]0", 45.0, "४५"),
+        ("[NatNum1][$-401]0.0", 2.5, "٢.٥"),
+        ("[NatNum1][$-804]0", 1234.0, "一二三四"),
+        ("[NatNum2][$-411]0", 12.0, "壱弐"),
+        // No native digits for English; other NatNum styles are ignored
+        ("[NatNum1][$-409]0", 12.0, "12"),
+        ("[NatNum7][$-401]0", 12.0, "12"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "code {}", code);
+    }
+}
+
+#[test]
+fn test_format_to_matches_format() {
+    let opts = FormatOptions::builder().cell_width(12).build();
+    let codes = [
+        "General",
+        "#,##0.00;[Red](#,##0.00)",
+        "0.0%",
+        "# ?/?",
+        "0.00E+00",
+        "yyyy-mm-dd hh:mm",
+        "$* #,##0",
+        "[DBNum1][$-804]0",
+    ];
+    for code in codes {
+        let fmt = NumberFormat::parse(code).unwrap();
+        for value in [0.0, 1234.5678, -98.25, 45306.5, -1e20] {
+            let mut out = String::from(">");
+            fmt.format_to(value, &opts, &mut out).unwrap();
+            assert_eq!(out, format!(">{}", fmt.format(value, &opts)), "code {}", code);
+        }
+    }
+}
+
+#[test]
+fn test_format_to_reentrant_writer() {
+    use std::fmt::{self, Write};
+
+    // A writer that formats another value while being written to
+    struct Nested(String);
+    impl Write for Nested {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let inner = NumberFormat::parse("0.0").unwrap();
+            inner.format_to(1.0, &FormatOptions::default(), &mut self.0)?;
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    let fmt = NumberFormat::parse("#,##0").unwrap();
+    let mut out = Nested(String::new());
+    fmt.format_to(1234.0, &FormatOptions::default(), &mut out).unwrap();
+    assert_eq!(out.0, "1.01,234");
+}
+
+#[test]
+fn test_display_matches_format() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("#,##0.00;[Red](#,##0.00)").unwrap();
+    for value in [0.0, 1234.5678, -98.25, f64::NAN] {
+        assert_eq!(fmt.display(value, &opts).to_string(), fmt.format(value, &opts));
+    }
+
+    let pct = NumberFormat::parse("0%").unwrap();
+    let line = format!("{} of {}", pct.display(0.25, &opts), fmt.display(-8.0, &opts));
+    assert_eq!(line, "25% of (8.00)");
+}
+
+#[test]
+fn test_format_batch_matches_format() {
+    let opts = FormatOptions::default();
+    let values = [0.0, 1.0, -1.0, 1234.5678, -0.004, 1e16, f64::NAN, 45306.25];
+    for code in [
+        "#,##0.00;[Red]-#,##0.00;\"zero\"",
+        "[>=1000]#,##0,\"K\";[<0]\"neg\";0.0",
+        "0.0%",
+        "000-00-0000",
+        "yyyy-mm-dd",
+        "# ?/?",
+    ] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        let expected: Vec<String> = values.iter().map(|&v| fmt.format(v, &opts)).collect();
+        assert_eq!(fmt.format_batch(&values, &opts), expected, "code {}", code);
+        assert_eq!(
+            fmt.format_iter(values, &opts).collect::<Vec<_>>(),
+            expected,
+            "code {}",
+            code
+        );
+    }
+}
+
+#[test]
+fn test_format_iter_size_hint() {
+    let fmt = NumberFormat::parse("0").unwrap();
+    let opts = FormatOptions::default();
+    let iter = fmt.format_iter(vec![1.0, 2.0, 3.0], &opts);
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert!(fmt.format_batch(&[], &opts).is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_format_batch() {
+    let opts = FormatOptions::default();
+    let values: Vec<f64> = (0..20_000).map(|n| (n as f64 - 10_000.0) * 1.37).collect();
+    for code in ["#,##0.00;[Red](#,##0.00)", "0.0E+00", "yyyy-mm-dd", "# ?/?", "General"] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(
+            fmt.par_format_batch(&values, &opts),
+            fmt.format_batch(&values, &opts),
+            "code {}",
+            code
+        );
+    }
+    assert!(NumberFormat::parse("0").unwrap().par_format_batch(&[], &opts).is_empty());
+}
+
+#[test]
+fn test_format_column() {
+    let opts = FormatOptions::default();
+    let values = [1234.5, -0.25, 0.0, 1e20];
+    let fmt = NumberFormat::parse("#,##0.00;(#,##0.00);\"-\"").unwrap();
+    let mut column = ssfmt::format_column(values, "#,##0.00;(#,##0.00);\"-\"", &opts).unwrap();
+    assert_eq!(column.size_hint(), (4, Some(4)));
+    assert_eq!(column.next().unwrap(), fmt.format(values[0], &opts));
+
+    let mut csv = String::new();
+    while let Some(result) = column.next_to(&mut csv) {
+        result.unwrap();
+        csv.push(',');
+    }
+    assert_eq!(csv, "(0.25),-,100,000,000,000,000,000,000.00,");
+    assert!(ssfmt::format_column(values, "[Red", &opts).is_err());
+}
+
+#[test]
+fn test_format_decimal_rounds_like_excel() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("0.00").unwrap();
+
+    // Binary approximations just below the midpoint still round up
+    assert_eq!(fmt.format(2.675, &opts), "2.68");
+    assert_eq!(fmt.format(1.005, &opts), "1.01");
+    assert_eq!(fmt.format(-1.005, &opts), "-1.01");
+    assert_eq!(fmt.format(0.125, &opts), "0.13");
+    assert_eq!(fmt.format(9.995, &opts), "10.00");
+    assert_eq!(fmt.format(0.1 + 0.2, &opts), "0.30");
+
+    let fmt = NumberFormat::parse("0.0%").unwrap();
+    assert_eq!(fmt.format(0.0005, &opts), "0.1%");
+}
+
+#[test]
+fn test_format_decimal_carries_into_integer_part() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("#,##0.00", 999999.996, "1,000,000.00"),
+        ("#.##", 0.999, "1."),
+        ("0.0#", 0.0999, "0.1"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_format_decimal_shows_fifteen_significant_digits() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("0.000000000000000000").unwrap();
+    assert_eq!(fmt.format(0.1 + 0.2, &opts), "0.300000000000000000");
+
+    let fmt = NumberFormat::parse("#,##0.0").unwrap();
+    assert_eq!(fmt.format(1234567890123456.7, &opts), "1,234,567,890,123,460.0");
+    assert_eq!(fmt.format(1e20 + 0.5, &opts), "100,000,000,000,000,000,000.0");
+}
+
+#[test]
+fn test_round_to_display() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("0.00", 2.675, 2.68),
+        ("0.00", -2.675, -2.68),
+        ("0", 2.5, 3.0),
+        ("#,##0.##", 1234.5678, 1234.57),
+        ("0.0%", 0.12345, 0.123),
+        ("0.00,", 1234.5678, 1230.0),
+        ("0.0,,\"M\"", 987_654_321.0, 987_700_000.0),
+        ("##0.0E+0", 123_456.0, 123_500.0),
+        ("0.00;(0.00)", -0.004, 0.0),
+        ("$#,##0.00_);($#,##0.00)", -1234.567, -1234.57),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.round_to_display(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_round_to_display_keeps_unrounded_sections() {
+    let opts = FormatOptions::default();
+    for code in ["General", "yyyy-mm-dd hh:mm", "# ?/?", "@", "\"n/a\""] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.round_to_display(45000.123456, &opts), 45000.123456, "{}", code);
+    }
+    let fmt = NumberFormat::parse("0.00").unwrap();
+    assert!(fmt.round_to_display(f64::NAN, &opts).is_nan());
+}
+
+#[test]
+fn test_round_to_display_formats_the_same() {
+    let opts = FormatOptions::default();
+    for code in ["0.00", "#,##0.0", "0%", "0.00E+00", "#,##0.00;[Red](#,##0.00)"] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        for value in [0.0, 1.005, -2.675, 1234.5678, 0.00049, 98765.4321] {
+            let rounded = fmt.round_to_display(value, &opts);
+            assert_eq!(
+                fmt.format(rounded, &opts),
+                fmt.format(value, &opts),
+                "{} with {}",
+                value,
+                code
+            );
+        }
+    }
+}
+
+#[test]
+fn test_negative_values_rounding_to_zero_drop_the_sign() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("0.00", -0.004, "0.00"),
+        ("0.00", -0.005, "-0.01"),
+        ("0", -0.4, "0"),
+        ("#,##0.0", -0.04, "0.0"),
+        ("0%", -0.001, "0%"),
+        ("0.0%", -0.0004, "0.0%"),
+        ("#,##0.0,", -40.0, "0.0"),
+        ("0.00", -0.0, "0.00"),
+        ("0.00E+00", -0.0, "0.00E+00"),
+        ("0.00E+00", -0.004, "-4.00E-03"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+
+    // The negative section is still picked by the sign of the value
+    let fmt = NumberFormat::parse("0.00;(0.00)").unwrap();
+    assert_eq!(fmt.format(-0.004, &opts), "(0.00)");
+}
+
+#[test]
+fn test_show_negative_zero() {
+    let opts = FormatOptions::builder().show_negative_zero(true).build();
+    let cases = [
+        ("0.00", -0.004, "-0.00"),
+        ("0", -0.4, "-0"),
+        ("0%", -0.001, "-0%"),
+        ("0.00", -0.0, "-0.00"),
+        ("0.00E+00", -0.0, "-0.00E+00"),
+        ("0.00", 0.0, "0.00"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_engineering_notation() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("##0.0E+0", 12345.0, "12.3E+3"),
+        ("##0.0E+0", 123456.0, "123.5E+3"),
+        ("##0.0E+0", 1234567.0, "1.2E+6"),
+        ("##0.0E+0", 0.00012345, "123.5E-6"),
+        ("##0.0E+0", -12345.0, "-12.3E+3"),
+        ("##0.0E+0", 0.0, "0.0E+0"),
+        // The mantissa stays below 1000 when it rounds up
+        ("##0.0E+0", 999.96, "1.0E+3"),
+        ("##0.0E+0", 0.00099996, "1.0E-3"),
+        ("0.00E+00", 9.996, "1.00E+01"),
+        ("#0.0E+0", 99.96, "1.0E+2"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_scientific_exponent_placeholders() {
+    let opts = FormatOptions::default();
+    let cases = [
+        // E- hides the plus sign but not the minus
+        ("0.0E-0", 12345.0, "1.2E4"),
+        ("0.0E-00", 0.00012, "1.2E-04"),
+        ("0.0e+0", 12345.0, "1.2e+4"),
+        ("0.0E+000", 12345.0, "1.2E+004"),
+        ("0.0E+??", 12345.0, "1.2E+ 4"),
+        ("0.0E+??0", 12345.0, "1.2E+  4"),
+        ("0.0E+000", 1e-120, "1.0E-120"),
+        ("0.0E+#", 12345.0, "1.2E+4"),
+        ("0.0E+00", 1e100, "1.0E+100"),
+        ("0.00E+00", 0.0, "0.00E+00"),
+        // Optional mantissa decimals drop trailing zeros
+        ("0.0#E+00", 12000.0, "1.2E+04"),
+        ("0.0#E+00", 12300.0, "1.23E+04"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_question_placeholders_pad_with_spaces() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("??0", 5.0, "  5"),
+        ("??0", 12345.0, "12345"),
+        ("?", 0.0, " "),
+        ("??", 0.0, "  "),
+        ("?.?", 0.5, " .5"),
+        ("??.??", 0.0, "  .  "),
+        ("#.??", 0.0, ".  "),
+        // Separators inside the padding become spaces
+        ("?,??0", 5.0, "    5"),
+        ("?,??0", 12345.0, "12,345"),
+        ("#,??0", 5.0, "  5"),
+        ("?,???", 0.0, "     "),
+        ("0,000", 5.0, "0,005"),
+        ("#,###", 0.0, ""),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_question_placeholders_align_decimal_points() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("?,??0.0??").unwrap();
+    let column: Vec<String> = [1.5, 12.25, 1234.125, 0.0]
+        .iter()
+        .map(|&v| fmt.format(v, &opts))
+        .collect();
+    assert_eq!(column, ["    1.5  ", "   12.25 ", "1,234.125", "    0.0  "]);
+    let points: Vec<usize> = column.iter().map(|s| s.find('.').unwrap()).collect();
+    assert!(points.iter().all(|&p| p == points[0]));
+}
+
+#[test]
+fn test_integer_digits_beyond_placeholders() {
+    let opts = FormatOptions::default();
+    let cases = [
+        // Extra digits all show at the leftmost placeholder
+        ("00", 12345.0, "12345"),
+        ("?0", 12345.0, "12345"),
+        ("#,##0", 1234567.0, "1,234,567"),
+        // Missing digits are padded
+        ("00000", 42.0, "00042"),
+        ("0,000", 5.0, "0,005"),
+        ("0,#00", 5.0, "0,05"),
+        // Inline literals stay attached to their placeholder position
+        ("0\"-\"00", 5.0, "0-05"),
+        ("0\"-\"00", 12345.0, "123-45"),
+        ("#\"-\"00", 5.0, "-05"),
+        ("##0\"k\"00", 1234567.0, "12345k67"),
+        ("#,0\"-\"00", 1234567.0, "1,234,5-67"),
+        ("000\"-\"0000", 5551234.0, "555-1234"),
+        ("000\"-\"0000", 1234.0, "000-1234"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_literals_and_separators_in_digit_runs() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("#,##0\" kg\"", 1234.0, "1,234 kg"),
+        ("0\"x\"000", 1234.0, "1x234"),
+        ("0\"x\"000", 5.0, "0x005"),
+        ("0\\-000", 1234.0, "1-234"),
+        ("#\"x\"#", 0.0, "x"),
+        // Grouping counts digits only; a separator follows its digit
+        ("#,##0\"x\"000", 1234567.0, "1,234,x567"),
+        ("#,0\"-\"00", 1234567.0, "1,234,5-67"),
+        ("0\"x\"0,000", 1234567.0, "1,23x4,567"),
+        // Literals before the decimal point stay in the integer run
+        ("0\"x\".00", 1.5, "1x.50"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_thousands_scaling_rounds() {
+    let opts = FormatOptions::default();
+    let cases = [
+        // Integers scale and then round like any other value
+        ("#,##0,", 1234567.0, "1,235"),
+        ("#,##0,", 1234500.0, "1,235"),
+        ("0,", 499.0, "0"),
+        ("0,", -400.0, "0"),
+        ("0,,", 999999.0, "1"),
+        ("0,,", -1500000.0, "-2"),
+        ("#,##0,,\"M\"", 2500000.0, "3M"),
+        ("0,\"K\"", 1500.0, "2K"),
+        ("0,,,,,,,", 5e15, "0"),
+        // Scaling happens before rounding to the decimal places
+        ("0.0,,\"M\"", 1250000.0, "1.3M"),
+        ("0.00,,", 1234567.0, "1.23"),
+        ("0.0,", 1234567.0, "1234.6"),
+        // Commas right before the decimal point scale too
+        ("0,.0", 1234567.0, "1234.6"),
+        ("#,##0,.00", 1234567.0, "1,234.57"),
+        ("0,,.0,", 123456789012.0, "123.5"),
+        // Percent and scaling combine
+        ("0,%", 1500.0, "150%"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+}
+
+#[test]
+fn test_format_conditional_sections() {
+    let opts = FormatOptions::default();
+    let format = |code: &str, value: f64| NumberFormat::parse(code).unwrap().format(value, &opts);
+
+    // From the SSF oddities corpus: values meeting neither condition use the
+    // third section
+    let code = "[Red][<-25]General;[Blue][>25]General;[Green]General;[Yellow]General";
+    assert_eq!(format(code, 26.0), "26");
+    assert_eq!(format(code, -26.0), "26");
+    assert_eq!(format(code, -1.0), "-1");
+    assert_eq!(format(code, 25.0), "25");
+    assert_eq!(format("[Red][=50]General;[Blue]000", 49.0), "049");
+    assert_eq!(format("[Red][<>50]General;[Blue]000", 50.0), "050");
+
+    // Both conditional with no third section: the first section
+    assert_eq!(format("[>100]\"big\";[<-100]\"small\"", 5.0), "big");
+    // One condition: everything else uses the second section
+    assert_eq!(format("[>100]\"big\";0;\"zero\"", 0.0), "0");
+    assert_eq!(format("[>100]\"big\";0.0;@", 5.0), "5.0");
+    assert_eq!(format("[>100]\"big\";@", 5.0), "big");
+
+    // Conditions past the second section are ignored
+    let code = "0.0;-0.0;[>5]\"cond\";@";
+    assert_eq!(format(code, 0.0), "cond");
+    assert_eq!(format(code, 7.0), "7.0");
+    let code = "0;[Blue]-0;[=0]\"zero\";[>100]\"text\"";
+    assert_eq!(format(code, 150.0), "150");
+}
+
+#[test]
+fn test_fraction_with_literals() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("# ?/?\" lbs\"", 1.5, "1 1/2 lbs"),
+        ("\"$\"# ?/?", -2.25, "-$2 1/4"),
+        ("0\" and \"?/?", 1.5, "1 and 1/2"),
+        ("0 \"and\" ??/??", 2.75, "2 and  3/4 "),
+        ("# ?/?_)", 1.5, "1 1/2 "),
+        ("# ?/?;(# ?/?)", -1.5, "(1 1/2)"),
+        ("# ?/?%", 0.015, "1 1/2%"),
+        ("[$€-407]# ??/16", 3.5, "€3  8/16"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "code {}", code);
+    }
+}
+
+#[test]
+fn test_fraction_fixed_denominators() {
+    use ssfmt::formats;
+
+    let opts = FormatOptions::default();
+    assert_eq!(formats::AS_HALVES.format(1.5, &opts), "1 1/2");
+    assert_eq!(formats::AS_QUARTERS.format(-2.25, &opts), "-2 1/4");
+    assert_eq!(formats::AS_EIGHTHS.format(0.625, &opts), " 5/8");
+    assert_eq!(formats::AS_SIXTEENTHS.format(1.3125, &opts), "1  5/16");
+    assert_eq!(formats::AS_TENTHS.format(1.3, &opts), "1 3/10");
+    assert_eq!(formats::AS_HUNDREDTHS.format(1.25, &opts), "1 25/100");
+
+    // Exact values keep the width of the fraction as alignment spaces
+    assert_eq!(formats::AS_SIXTEENTHS.format(2.0, &opts), "2      ");
+    assert_eq!(formats::AS_QUARTERS.format(1.99, &opts), "2    ");
+
+    // Numerator placeholders pad like digit placeholders elsewhere
+    let cases = [
+        ("# 00/16", 1.25, "1 04/16"),
+        ("# #/16", 1.25, "1 4/16"),
+        ("# 0/4", 2.0, "2 0/4"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "code {}", code);
+    }
+
+    assert_eq!(formats::fraction_of(32).to_string(), "# ??/32");
+    assert_eq!(formats::fraction_of(3).format(1.7, &opts), "1 2/3");
+}
+
+#[test]
+fn test_format_empty_sections() {
+    let opts = FormatOptions::default();
+    let format = |code: &str, value: f64| NumberFormat::parse(code).unwrap().format(value, &opts);
+
+    // An empty section hides the values it applies to
+    assert_eq!(format("0;;", 1.5), "2");
+    assert_eq!(format("0;;", -2.0), "");
+    assert_eq!(format("0;;", 0.0), "");
+    assert_eq!(format("0;-0;", 0.0), "");
+    assert_eq!(format("0;;0", -2.0), "");
+    assert_eq!(format("[Red]0;[Blue]", -2.0), "");
+    for value in [1.5, -2.0, 0.0] {
+        assert_eq!(format(";;;", value), "");
+    }
+
+    // A hidden-number code still shows text
+    let fmt = NumberFormat::parse("0;;;").unwrap();
+    assert_eq!(fmt.format_text("note", &opts), "note");
+
+    // General is not empty
+    assert_eq!(format("0;-0;General", 0.0), "0");
+    assert_eq!(format("0;-0;[Blue]General", 0.0), "0");
+
+    // A missing zero section repeats the first, a missing negative section
+    // too, with a minus sign
+    assert_eq!(format("0.0;(0.0)", 0.0), "0.0");
+    assert_eq!(format("#,##0;@", -1234.0), "-1,234");
+    assert_eq!(format("0.00;\"n/a: \"@", -1.5), "-1.50");
+}
+
+#[test]
+fn test_format_decimal_clamps_to_ten_places() {
+    let opts = FormatOptions::default();
+
+    // ssf oddities.json: places past the tenth are padded, not filled with
+    // float digits
+    let fmt = NumberFormat::parse("0#######0.##0##0######00######0").unwrap();
+    assert_eq!(fmt.format(12.3456789, &opts), "012.3456789000");
+
+    let fmt = NumberFormat::parse("0.00000000000000000000").unwrap();
+    assert_eq!(fmt.format(1.0 / 3.0, &opts), "0.33333333330000000000");
+
+    let fmt = NumberFormat::parse("0.??????????????").unwrap();
+    assert_eq!(fmt.format(0.5, &opts), "0.5             ");
+}
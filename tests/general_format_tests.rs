@@ -80,43 +80,75 @@ fn test_general_format_vs_broken_behavior() {
 }
 
 #[test]
-fn test_general_format_large_integers_no_scientific() {
-    // Excel's General format displays exact integers without scientific notation
-    // These tests verify that large integers are displayed as-is
-
-    // The value from the bug report
-    assert_eq!(format_default(484079807176.0, "General").unwrap(), "484079807176");
-
-    // Values around the old 1e11 threshold
-    assert_eq!(format_default(100000000000.0, "General").unwrap(), "100000000000");
+fn test_general_format_large_integers_scientific() {
+    // Like Excel, General shows at most 11 digits; longer integers switch to
+    // scientific notation
     assert_eq!(format_default(99999999999.0, "General").unwrap(), "99999999999");
-    assert_eq!(format_default(100000000001.0, "General").unwrap(), "100000000001");
-
-    // Larger exact integers (within safe f64 range)
-    assert_eq!(format_default(1000000000000.0, "General").unwrap(), "1000000000000");
-    assert_eq!(format_default(9007199254740991.0, "General").unwrap(), "9007199254740991"); // 2^53 - 1
+    assert_eq!(format_default(100000000000.0, "General").unwrap(), "1E+11");
+    assert_eq!(format_default(100000000001.0, "General").unwrap(), "1E+11");
+    assert_eq!(format_default(484079807176.0, "General").unwrap(), "4.8408E+11");
+    assert_eq!(format_default(1000000000000.0, "General").unwrap(), "1E+12");
+    assert_eq!(format_default(9007199254740991.0, "General").unwrap(), "9.0072E+15");
 
     // Negative large integers
-    assert_eq!(format_default(-484079807176.0, "General").unwrap(), "-484079807176");
-    assert_eq!(format_default(-100000000000.0, "General").unwrap(), "-100000000000");
+    assert_eq!(format_default(-484079807176.0, "General").unwrap(), "-4.8408E+11");
+    assert_eq!(format_default(-100000000000.0, "General").unwrap(), "-1E+11");
+}
+
+#[test]
+fn test_general_format_function() {
+    assert_eq!(ssfmt::general_format(1234.56), "1234.56");
+    assert_eq!(ssfmt::general_format(-0.000012345), "-0.000012345");
+    assert_eq!(ssfmt::general_format(123456789.123), "123456789.1");
+    assert_eq!(ssfmt::general_format(1e21), "1E+21");
 }
 
 #[test]
-fn test_text_format_large_integers_no_scientific() {
-    // The @ format means "text" and should display numbers as-is without scientific notation
+fn test_text_format_shows_numbers_as_general() {
+    // A number in an @ section shows as General, scientific past 11 digits
 
     // The value from the bug report
-    assert_eq!(format_default(484079807176.0, "@").unwrap(), "484079807176");
+    assert_eq!(format_default(484079807176.0, "@").unwrap(), "4.8408E+11");
 
-    // Values around the old 1e11 threshold
-    assert_eq!(format_default(100000000000.0, "@").unwrap(), "100000000000");
+    // Values around the 1e11 threshold
+    assert_eq!(format_default(100000000000.0, "@").unwrap(), "1E+11");
     assert_eq!(format_default(99999999999.0, "@").unwrap(), "99999999999");
-    assert_eq!(format_default(100000000001.0, "@").unwrap(), "100000000001");
-
-    // Larger exact integers
-    assert_eq!(format_default(1000000000000.0, "@").unwrap(), "1000000000000");
+    assert_eq!(format_default(123456789000.0, "@").unwrap(), "1.23457E+11");
 
     // Negative large integers
-    assert_eq!(format_default(-484079807176.0, "@").unwrap(), "-484079807176");
-    assert_eq!(format_default(-100000000000.0, "@").unwrap(), "-100000000000");
+    assert_eq!(format_default(-484079807176.0, "@").unwrap(), "-4.8408E+11");
+    assert_eq!(format_default(-100000000000.0, "@").unwrap(), "-1E+11");
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_general_format_bigint_uses_scientific() {
+    use ssfmt::{format_bigint_default, BigInt};
+
+    // Both sides of f64's safe integer range follow the same rule
+    let safe = BigInt::from(123_456_789_012_345_i64);
+    assert_eq!(format_bigint_default(&safe, "General").unwrap(), "1.23457E+14");
+    let big = BigInt::from(1_i64 << 60);
+    assert_eq!(format_bigint_default(&big, "General").unwrap(), "1.15292E+18");
+    assert_eq!(format_bigint_default(&-big, "General").unwrap(), "-1.15292E+18");
+
+    // Fewer than 12 digits stay as they are
+    let small = BigInt::from(12_345_678_901_i64);
+    assert_eq!(format_bigint_default(&small, "General").unwrap(), "12345678901");
+    // Digit sections keep every digit
+    let big = BigInt::from(1_i64 << 60);
+    assert_eq!(format_bigint_default(&big, "0").unwrap(), "1152921504606846976");
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_general_format_decimal_uses_scientific() {
+    use ssfmt::{format_decimal_default, Decimal};
+
+    let value = Decimal::from(123_456_789_012_345_i64);
+    assert_eq!(format_decimal_default(&value, "General").unwrap(), "1.23457E+14");
+    let value = Decimal::from(1_i64 << 60);
+    assert_eq!(format_decimal_default(&value, "General").unwrap(), "1.15292E+18");
+    let value = Decimal::from(12_345_678_901_i64);
+    assert_eq!(format_decimal_default(&value, "General").unwrap(), "12345678901");
 }
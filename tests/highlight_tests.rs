@@ -0,0 +1,108 @@
+//! Tests for format code highlighting.
+
+use ssfmt::{highlight, TokenClass};
+
+/// The classes of `code`, with the text each one covers.
+fn classes(code: &str) -> Vec<(&str, TokenClass)> {
+    highlight(code)
+        .into_iter()
+        .map(|(span, class)| (&code[span], class))
+        .collect()
+}
+
+#[test]
+fn test_highlight_covers_code() {
+    for code in [
+        "#,##0.00;[Red](#,##0.00)",
+        "_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)",
+        "[$-F800]dddd, mmmm dd, yyyy",
+        "[<=9999999]###-####;(###) ###-####",
+        "0.00E+00",
+        "# ??/??",
+        "\"unterminated",
+        "[Red",
+        "0\\",
+        "",
+    ] {
+        let mut end = 0;
+        for (span, _) in highlight(code) {
+            assert_eq!(span.start, end, "{code}");
+            end = span.end;
+        }
+        assert_eq!(end, code.len(), "{code}");
+    }
+}
+
+#[test]
+fn test_highlight_month_and_minute() {
+    use TokenClass::*;
+
+    assert_eq!(
+        classes("mm:ss"),
+        [("mm", Time), (":", Literal), ("ss", Time)]
+    );
+    assert_eq!(
+        classes("yyyy-mm-dd"),
+        [
+            ("yyyy", Date),
+            ("-", Literal),
+            ("mm", Date),
+            ("-", Literal),
+            ("dd", Date)
+        ]
+    );
+    assert_eq!(
+        classes("[h]:mm AM/PM"),
+        [
+            ("[h]", Time),
+            (":", Literal),
+            ("mm", Time),
+            (" ", Literal),
+            ("AM/PM", Time)
+        ]
+    );
+}
+
+#[test]
+fn test_highlight_brackets_and_layout() {
+    use TokenClass::*;
+
+    assert_eq!(
+        classes("[Blue][>=100]0%;[$€-407]@;General"),
+        [
+            ("[Blue]", Color),
+            ("[>=100]", Condition),
+            ("0", Digit),
+            ("%", Percent),
+            (";", Separator),
+            ("[$€-407]", Locale),
+            ("@", Text),
+            (";", Separator),
+            ("General", Keyword),
+        ]
+    );
+    assert_eq!(
+        classes("* 0.0E+0_)[Foo]"),
+        [
+            ("* ", Layout),
+            ("0", Digit),
+            (".", Separator),
+            ("0", Digit),
+            ("E+0", Exponent),
+            ("_)", Layout),
+            ("[Foo]", Unknown),
+        ]
+    );
+    assert_eq!(
+        classes("# ?/? \"kg\""),
+        [
+            ("#", Digit),
+            (" ", Literal),
+            ("?", Digit),
+            ("/", Separator),
+            ("?", Digit),
+            (" \"kg\"", Literal),
+        ]
+    );
+    assert_eq!(TokenClass::Locale.as_str(), "locale");
+}
@@ -1,6 +1,7 @@
 //! Integration tests for ssfmt - comprehensive tests covering realistic Excel format codes.
 
-use ssfmt::{DateSystem, FormatOptions, NumberFormat};
+use ssfmt::date_serial::{convert_system, date_to_serial};
+use ssfmt::{format_with_id, DateSystem, FormatOptions, LayoutMode, NumberFormat};
 
 // ============================================================================
 // Number Formats
@@ -155,15 +156,70 @@ fn test_1904_date_system() {
     // In 1904 system, day 0 = January 1, 1904
     // day 1 = January 2, 1904
     let fmt = NumberFormat::parse("yyyy-mm-dd").unwrap();
-    let opts = FormatOptions {
-        date_system: DateSystem::Date1904,
-        ..Default::default()
-    };
+    let opts = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
 
     // Serial 1 = January 2, 1904 in the 1904 system
     assert_eq!(fmt.format(1.0, &opts), "1904-01-02");
 }
 
+#[test]
+fn test_1904_date_system_serial_zero() {
+    let fmt = NumberFormat::parse("yyyy-mm-dd").unwrap();
+    let opts = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
+    assert_eq!(fmt.format(0.0, &opts), "1904-01-01");
+    assert_eq!(fmt.format(0.5, &opts), "1904-01-01");
+}
+
+#[test]
+fn test_1904_date_system_builtin_ids() {
+    let opts_1904 = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
+    let opts_1900 = FormatOptions::default();
+
+    // January 2, 2023 at 6:30 AM in each system
+    let serial_1900 = 44928.2708333333;
+    let serial_1904 = convert_system(serial_1900, DateSystem::Date1900, DateSystem::Date1904);
+    for id in (14..=22).chain([45, 47]) {
+        assert_eq!(
+            format_with_id(serial_1904, id, &opts_1904).unwrap(),
+            format_with_id(serial_1900, id, &opts_1900).unwrap(),
+            "ID {}",
+            id
+        );
+    }
+    assert_eq!(format_with_id(serial_1904, 14, &opts_1904).unwrap(), "1/2/23");
+    assert_eq!(format_with_id(serial_1904, 22, &opts_1904).unwrap(), "1/2/23 6:30");
+    assert_eq!(format_with_id(0.0, 15, &opts_1904).unwrap(), "1-Jan-04");
+
+    // Elapsed time and numbers don't depend on the date system
+    assert_eq!(format_with_id(1.5, 46, &opts_1904).unwrap(), "36:00:00");
+    assert_eq!(format_with_id(1.5, 2, &opts_1904).unwrap(), "1.50");
+}
+
+#[test]
+fn test_convert_system_round_trip() {
+    for serial in [1.0, 60.0, 1462.0, 45292.75] {
+        let converted = convert_system(serial, DateSystem::Date1900, DateSystem::Date1904);
+        assert_eq!(
+            convert_system(converted, DateSystem::Date1904, DateSystem::Date1900),
+            serial
+        );
+    }
+    assert_eq!(
+        date_to_serial(2024, 1, 1, DateSystem::Date1904),
+        convert_system(
+            date_to_serial(2024, 1, 1, DateSystem::Date1900),
+            DateSystem::Date1900,
+            DateSystem::Date1904
+        )
+    );
+}
+
 // ============================================================================
 // Colors and Conditions (parsing only)
 // ============================================================================
@@ -237,6 +293,28 @@ fn test_skip_character() {
     assert!(result.contains("42"), "Expected '42' in result: {}", result);
 }
 
+#[test]
+fn test_skip_character_width() {
+    let fmt = NumberFormat::parse("#,##0_);(#,##0)").unwrap();
+    let opts = FormatOptions::default();
+    assert_eq!(fmt.format(1234.0, &opts), "1,234 ");
+
+    // Wide characters reserve two columns in monospace layout
+    let fmt = NumberFormat::parse("0_円").unwrap();
+    assert_eq!(fmt.format(5.0, &opts), "5 ");
+    let opts = FormatOptions::builder()
+        .layout(LayoutMode::Monospace)
+        .build();
+    assert_eq!(fmt.format(5.0, &opts), "5  ");
+}
+
+#[test]
+fn test_skip_character_in_time_format() {
+    let fmt = NumberFormat::parse("h:mm_)").unwrap();
+    let opts = FormatOptions::default();
+    assert_eq!(fmt.format(0.5, &opts), "12:00 ");
+}
+
 #[test]
 fn test_datetime_combined() {
     let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm:ss").unwrap();
@@ -279,3 +357,61 @@ fn test_special_float_values() {
     assert_eq!(fmt.format(f64::INFINITY, &opts), "Infinity");
     assert_eq!(fmt.format(f64::NEG_INFINITY, &opts), "-Infinity");
 }
+
+// ============================================================================
+// Text Values
+// ============================================================================
+
+#[test]
+fn test_text_placeholder_in_single_section() {
+    let opts = FormatOptions::default();
+
+    let fmt = NumberFormat::parse("\"ID-\"@").unwrap();
+    assert_eq!(fmt.format_text("1234", &opts), "ID-1234");
+    // Numbers fall back to General
+    assert_eq!(fmt.format(1234.0, &opts), "1234");
+
+    let fmt = NumberFormat::parse("@\" and \"@").unwrap();
+    assert_eq!(fmt.format_text("this", &opts), "this and this");
+}
+
+#[test]
+fn test_text_placeholder_in_earlier_section() {
+    let opts = FormatOptions::default();
+
+    let fmt = NumberFormat::parse("\"foo\";\"bar\";@").unwrap();
+    assert_eq!(fmt.format_text("sheetjs", &opts), "sheetjs");
+    assert_eq!(fmt.format(1.0, &opts), "foo");
+
+    let fmt = NumberFormat::parse("\"foo\";\"bar\"").unwrap();
+    assert_eq!(fmt.format_text("sheetjs", &opts), "sheetjs");
+}
+
+#[test]
+fn test_text_placeholder_with_digits() {
+    let opts = FormatOptions::default();
+
+    // Numbers use the numeric parts, text uses the `@` parts
+    let fmt = NumberFormat::parse("0.00 \"units\" @").unwrap();
+    assert_eq!(fmt.format(1.5, &opts), "1.50 units ");
+    assert_eq!(fmt.format(-2.0, &opts), "-2.00 units ");
+    assert_eq!(fmt.format_text("abc", &opts), " units abc");
+
+    let fmt = NumberFormat::parse("#,##0;@ \"kg\"").unwrap();
+    assert_eq!(fmt.format(1234.0, &opts), "1,234");
+    assert_eq!(fmt.format_text("n/a", &opts), "n/a kg");
+
+    // Excel refuses the mix, so strict validation does too, while text
+    // wrapped in literals is fine
+    assert!(ssfmt::validate("0.00 \"units\" @").is_err());
+    assert!(ssfmt::validate("\"Haha!\"\\ @\\ \"Yeah!\"").is_ok());
+    let fmt = NumberFormat::parse("\"Haha!\"\\ @\\ \"Yeah!\"").unwrap();
+    assert_eq!(fmt.format_text("abc", &opts), "Haha! abc Yeah!");
+    assert_eq!(fmt.format(1.5, &opts), "1.5");
+}
+
+#[test]
+fn test_text_section_general() {
+    let fmt = NumberFormat::parse("0.00;-0.00;0;[Blue]General").unwrap();
+    assert_eq!(fmt.format_text("note", &FormatOptions::default()), "note");
+}
@@ -0,0 +1,101 @@
+use ssfmt::ast::{Color, NamedColor};
+use ssfmt::{format_code_from_id, FormatKind, NumberFormat};
+
+fn kind(code: &str) -> FormatKind {
+    NumberFormat::parse(code).unwrap().kind()
+}
+
+#[test]
+fn test_builtin_kinds() {
+    let expected = [
+        (0, FormatKind::General),
+        (1, FormatKind::Number),
+        (4, FormatKind::Number),
+        (9, FormatKind::Percentage),
+        (11, FormatKind::Scientific),
+        (12, FormatKind::Fraction),
+        (14, FormatKind::Date),
+        (18, FormatKind::Time),
+        (22, FormatKind::DateTime),
+        (37, FormatKind::Number),
+        (46, FormatKind::Duration),
+        (47, FormatKind::Time),
+        (49, FormatKind::Text),
+    ];
+    for (id, expected) in expected {
+        let code = format_code_from_id(id).unwrap();
+        assert_eq!(kind(code), expected, "ID {} ({})", id, code);
+    }
+}
+
+#[test]
+fn test_custom_kinds() {
+    assert_eq!(kind("_(\"$\"* #,##0_);_(\"$\"* \\(#,##0\\)"), FormatKind::Accounting);
+    assert_eq!(kind("_(* #,##0.00_)"), FormatKind::Accounting);
+    assert_eq!(kind("[$€-407]#,##0.00"), FormatKind::Currency);
+    assert_eq!(kind("#,##0 \"€\""), FormatKind::Currency);
+    assert_eq!(kind("0.00\" kg\""), FormatKind::Custom);
+    assert_eq!(
+        kind("[<=9999999]###-####;(###) ###-####"),
+        FormatKind::Custom
+    );
+    assert_eq!(kind("\"Total\""), FormatKind::Custom);
+    assert_eq!(kind("\"Name: \"@"), FormatKind::Text);
+    assert_eq!(kind("mmm yyyy"), FormatKind::Date);
+    assert_eq!(kind("[mm]:ss"), FormatKind::Duration);
+    assert_eq!(kind("[d] hh:mm"), FormatKind::Duration);
+    assert_eq!(kind("[Red]#,##0;[Blue]-#,##0"), FormatKind::Number);
+}
+
+#[test]
+fn test_format_details() {
+    let fmt = NumberFormat::parse("_(€* #,##0.00_);_(€* (#,##0.00)").unwrap();
+    assert_eq!(fmt.kind(), FormatKind::Accounting);
+    assert_eq!(fmt.currency_symbol(), Some("€"));
+    assert_eq!(fmt.decimal_places(), 2);
+    assert!(fmt.uses_thousands_separator());
+
+    let fmt = NumberFormat::parse("0.0,,\"M\"").unwrap();
+    assert_eq!(fmt.decimal_places(), 1);
+    assert!(!fmt.uses_thousands_separator());
+    assert_eq!(fmt.currency_symbol(), None);
+
+    let fmt = NumberFormat::parse("hh:mm:ss.000").unwrap();
+    assert_eq!(fmt.decimal_places(), 3);
+    assert!(!fmt.uses_thousands_separator());
+
+    let fmt = NumberFormat::parse("# ??/??").unwrap();
+    assert_eq!(fmt.decimal_places(), 0);
+}
+
+#[test]
+fn test_negative_style() {
+    let style = |code: &str| NumberFormat::parse(code).unwrap().negative_style();
+
+    // Excel's four "Negative numbers" choices
+    let minus = style("#,##0.00");
+    assert!(minus.minus_sign && !minus.parentheses && !minus.is_red());
+    let red = style("#,##0.00;[Red]#,##0.00");
+    assert!(!red.minus_sign && !red.parentheses && red.is_red());
+    let parens = style("#,##0.00_);(#,##0.00)");
+    assert!(!parens.minus_sign && parens.parentheses && parens.color.is_none());
+    let red_parens = style("#,##0.00_);[Red](#,##0.00)");
+    assert!(red_parens.parentheses && red_parens.is_red());
+
+    assert!(style("0;[Red]-0").minus_sign);
+    assert_eq!(
+        style("0;[Blue]-0").color,
+        Some(Color::Named(NamedColor::Blue))
+    );
+    assert!(style("0;0.0E+0").minus_sign);
+    assert!(style("0;General").minus_sign);
+    assert!(style("0;@").minus_sign);
+    assert!(style(format_code_from_id(40).unwrap()).parentheses);
+
+    // A condition that -1 meets shows the magnitude
+    assert!(!style("[<0]0;0").minus_sign);
+    // Hidden negatives and dates show neither
+    let hidden = style("0;;0");
+    assert!(!hidden.minus_sign && !hidden.parentheses);
+    assert!(!style("yyyy-mm-dd").minus_sign);
+}
@@ -198,6 +198,17 @@ fn test_lex_am_pm_short() {
     assert_eq!(lexer.next_token().unwrap().token, Token::Eof);
 }
 
+#[test]
+fn test_lex_am_pm_chinese() {
+    let mut lexer = Lexer::new("上午/下午h");
+    assert_eq!(
+        lexer.next_token().unwrap().token,
+        Token::AmPm("上午/下午".into())
+    );
+    assert_eq!(lexer.next_token().unwrap().token, Token::Hour);
+    assert_eq!(lexer.next_token().unwrap().token, Token::Eof);
+}
+
 #[test]
 fn test_lex_elapsed_hours_in_bracket() {
     // Inside brackets, h is treated as literal
@@ -289,3 +300,52 @@ fn test_lex_quoted_string_position() {
     assert_eq!(tok2.start, 4);
     assert_eq!(tok2.end, 5);
 }
+
+#[test]
+fn test_tokenize() {
+    use ssfmt::{tokenize, TokenCategory};
+
+    let tokens = tokenize("[Red]#,##0;\"neg\"@");
+    let names: Vec<_> = tokens.iter().map(|t| t.token.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "open-bracket",
+            "literal",
+            "literal",
+            "literal",
+            "close-bracket",
+            "hash",
+            "thousands-separator",
+            "hash",
+            "hash",
+            "zero",
+            "section-separator",
+            "quoted-string",
+            "at",
+        ]
+    );
+    assert_eq!(tokens[11].span(), 11..16);
+    assert_eq!(tokens[12].token.category().as_str(), "placeholder");
+
+    let categories: Vec<_> = tokenize("hh:mm AM/PM").iter().map(|t| t.token.category()).collect();
+    assert_eq!(
+        categories,
+        [
+            TokenCategory::DateTime,
+            TokenCategory::DateTime,
+            TokenCategory::Literal,
+            TokenCategory::DateTime,
+            TokenCategory::DateTime,
+            TokenCategory::Literal,
+            TokenCategory::DateTime,
+        ]
+    );
+
+    // Unfinished codes still tokenize, for highlighting while typing
+    let tokens = tokenize("0.0\" kg");
+    assert_eq!(tokens.last().unwrap().token, Token::QuotedString(" kg".to_string()));
+    assert_eq!(tokens.last().unwrap().span(), 3..7);
+    assert_eq!(tokenize("0\\").len(), 1);
+    assert!(tokenize("").is_empty());
+}
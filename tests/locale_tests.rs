@@ -0,0 +1,81 @@
+use ssfmt::{FormatOptions, Locale, NumberFormat};
+
+#[test]
+fn test_builtin_locales_by_tag() {
+    let tags = [
+        "en-US", "en-GB", "en-AU", "en-CA", "de-DE", "de-AT", "de-CH", "fr-FR", "fr-CA", "fr-CH",
+        "es-ES", "es-MX", "it-IT", "pt-BR", "pt-PT", "nl-NL", "pl-PL", "ru-RU", "uk-UA", "cs-CZ",
+        "sv-SE", "da-DK", "nb-NO", "fi-FI", "tr-TR", "el-GR", "hu-HU", "ja-JP", "zh-CN", "zh-TW",
+        "ko-KR", "ar-SA", "he-IL", "th-TH", "hi-IN", "id-ID", "vi-VN",
+    ];
+    for tag in tags {
+        assert!(Locale::from_tag(tag).is_some(), "missing locale {}", tag);
+    }
+}
+
+#[test]
+fn test_from_tag_matches_from_lcid() {
+    assert_eq!(Locale::from_tag("de-DE"), Locale::from_lcid(0x407));
+    assert_eq!(Locale::from_tag("ja-JP"), Locale::from_lcid(0x411));
+    assert_eq!(Locale::from_tag("pt-BR"), Locale::from_lcid(0x416));
+    assert_eq!(Locale::from_tag("ko_kr"), Locale::from_lcid(0x412));
+    assert_eq!(Locale::from_tag("en-US"), Some(Locale::default()));
+    assert_eq!(Locale::from_tag("tlh"), None);
+}
+
+#[test]
+fn test_locale_separators_and_currency() {
+    let pt = Locale::from_tag("pt-BR").unwrap();
+    assert_eq!(pt.decimal_separator, ',');
+    assert_eq!(pt.thousands_separator, '.');
+    assert_eq!(pt.currency_symbol, "R$");
+
+    let ch = Locale::from_tag("de-CH").unwrap();
+    assert_eq!(ch.decimal_separator, '.');
+    assert_eq!(ch.thousands_separator, '\'');
+    assert_eq!(ch.currency_symbol, "CHF");
+}
+
+#[test]
+fn test_localize_separators() {
+    let de = Locale::from_tag("de-DE").unwrap();
+    assert_eq!((de.date_separator, de.time_separator), ('.', ':'));
+    let fi = Locale::from_tag("fi-FI").unwrap();
+    assert_eq!((fi.date_separator, fi.time_separator), ('.', '.'));
+
+    let fmt = NumberFormat::parse("m/d/yy h:mm").unwrap();
+    let mut opts = FormatOptions::builder().locale(de).build();
+    assert_eq!(fmt.format(45366.5, &opts), "3/15/24 12:00");
+    opts.localize_separators = true;
+    assert_eq!(fmt.format(45366.5, &opts), "3.15.24 12:00");
+    let scientific = NumberFormat::parse("0.00E+00").unwrap();
+    assert_eq!(scientific.format(12345.0, &opts), "1,23E+04");
+    opts.locale = fi;
+    assert_eq!(fmt.format(45366.5, &opts), "3.15.24 12.00");
+
+    // Escaped characters and number sections are left alone
+    let fmt = NumberFormat::parse("d\\/m;# ?/?").unwrap();
+    assert_eq!(fmt.format(45366.5, &opts), "15/3");
+    assert_eq!(fmt.format(-1.5, &opts), "1 1/2");
+}
+
+#[test]
+fn test_format_with_tag_locale() {
+    let opts = FormatOptions::builder()
+        .locale(Locale::from_tag("it-IT").unwrap())
+        .build();
+    let fmt = NumberFormat::parse("dddd d mmmm yyyy").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "venerdì 9 gennaio 2026");
+
+    let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    assert_eq!(fmt.format(1234.5, &opts), "1.234,50");
+}
+
+#[test]
+fn test_format_lcid_overrides_options_locale() {
+    let opts = FormatOptions::builder()
+        .locale(Locale::from_tag("it-IT").unwrap())
+        .build();
+    let fmt = NumberFormat::parse("[$-419]mmmm").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "январь");
+}
@@ -1,4 +1,6 @@
-use ssfmt::{DateSystem, FormatOptions};
+use ssfmt::{
+    DateSystem, FormatError, FormatOptions, Locale, NumberFormat, OverflowBehavior, RoundingMode,
+};
 
 #[test]
 fn test_default_options() {
@@ -11,3 +13,163 @@ fn test_date_system_epoch() {
     assert_eq!(DateSystem::Date1900.epoch_year(), 1900);
     assert_eq!(DateSystem::Date1904.epoch_year(), 1904);
 }
+
+#[test]
+fn test_with_separators_keeps_locale_names() {
+    let opts = FormatOptions::default().with_separators(',', '.');
+    assert_eq!(opts.locale.decimal_separator, ',');
+    assert_eq!(opts.locale.thousands_separator, '.');
+    assert_eq!(opts.locale.month_names_full[0], "January");
+
+    let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    assert_eq!(fmt.format(-1234567.891, &opts), "-1.234.567,89");
+    let fmt = NumberFormat::parse("0.0%").unwrap();
+    assert_eq!(fmt.format(0.125, &opts), "12,5%");
+    let fmt = NumberFormat::parse("0.00E+00").unwrap();
+    assert_eq!(fmt.format(12345.0, &opts), "1,23E+04");
+    let fmt = NumberFormat::parse("dddd, mmmm d").unwrap();
+    assert_eq!(fmt.format(46031.0, &opts), "Friday, January 9");
+}
+
+#[test]
+fn test_with_separators_on_other_locale() {
+    let opts = FormatOptions::builder()
+        .locale(Locale::from_tag("de-CH").unwrap())
+        .build()
+        .with_separators('.', ' ');
+    let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    assert_eq!(fmt.format(1234.5, &opts), "1 234.50");
+    let fmt = NumberFormat::parse("##0.0E+0").unwrap();
+    assert_eq!(fmt.format(-0.00012345, &opts), "-123.5E-6");
+    let fmt = NumberFormat::parse("mmmm").unwrap();
+    assert_eq!(fmt.format(45047.0, &opts), "Mai");
+}
+
+#[test]
+fn test_rounding_mode_defaults_to_excel() {
+    let opts = FormatOptions::default();
+    assert_eq!(opts.rounding, RoundingMode::HalfAwayFromZero);
+    let fmt = NumberFormat::parse("0").unwrap();
+    assert_eq!(fmt.format(2.5, &opts), "3");
+    assert_eq!(fmt.format(-2.5, &opts), "-3");
+}
+
+#[test]
+fn test_rounding_mode_half_even() {
+    let opts = FormatOptions::builder()
+        .rounding(RoundingMode::HalfEven)
+        .build();
+    let cases = [
+        ("0", 2.5, "2"),
+        ("0", 3.5, "4"),
+        ("0", -2.5, "-2"),
+        ("0", 2.5000001, "3"),
+        ("0.00", 2.675, "2.68"),
+        ("0.00", 2.665, "2.66"),
+        ("#,##0.0", 1234.25, "1,234.2"),
+        ("0%", 0.125, "12%"),
+        ("0.0%", 0.1255, "12.6%"),
+        ("0.0E+00", 1250.0, "1.2E+03"),
+        ("0.0E+00", 1350.0, "1.4E+03"),
+    ];
+    for (code, value, expected) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert_eq!(fmt.format(value, &opts), expected, "{} with {}", value, code);
+    }
+
+    // Fractional seconds: 12:00:00.25
+    let serial = (12.0 * 3600.0 + 0.25) / 86400.0;
+    let fmt = NumberFormat::parse("hh:mm:ss.0").unwrap();
+    assert_eq!(fmt.format(serial, &opts), "12:00:00.2");
+    assert_eq!(fmt.format(serial, &FormatOptions::default()), "12:00:00.3");
+}
+
+#[test]
+fn test_negative_durations() {
+    let fmt = NumberFormat::parse("[h]:mm").unwrap();
+    // Excel shows nothing for negative times
+    assert_eq!(fmt.format(-0.0625, &FormatOptions::default()), "");
+
+    let opts = FormatOptions::builder()
+        .allow_negative_durations(true)
+        .build();
+    assert_eq!(fmt.format(-0.0625, &opts), "-1:30");
+    assert_eq!(fmt.format(-1.5, &opts), "-36:00");
+    assert_eq!(fmt.format(0.0625, &opts), "1:30");
+    let fmt = NumberFormat::parse("[mm]:ss").unwrap();
+    assert_eq!(fmt.format(-0.001, &opts), "-01:26");
+
+    // A negative section supplies its own sign
+    let fmt = NumberFormat::parse("[h]:mm;[Red]([h]:mm)").unwrap();
+    assert_eq!(fmt.format(-0.0625, &opts), "(1:30)");
+
+    // Clock times and dates stay unsigned
+    let fmt = NumberFormat::parse("h:mm").unwrap();
+    assert_eq!(fmt.format(-0.0625, &opts), "");
+}
+
+#[test]
+fn test_overflow_behavior() {
+    let date = NumberFormat::parse("yyyy-mm-dd").unwrap();
+    let number = NumberFormat::parse("#,##0.00").unwrap();
+
+    // Out-of-range dates show nothing, and wide numbers overflow the cell
+    let opts = FormatOptions::builder().cell_width(6).build();
+    assert_eq!(date.format(-1.0, &FormatOptions::default()), "");
+    assert_eq!(number.format(1234.5, &opts), "1,234.50");
+
+    let opts = FormatOptions::builder()
+        .overflow(OverflowBehavior::Hashes(8))
+        .build();
+    assert_eq!(date.format(-1.0, &opts), "########");
+    assert_eq!(date.format(2958466.0, &opts), "########");
+    assert_eq!(date.format(45000.0, &opts), "2023-03-15");
+
+    // With a cell width, the hashes fill the cell
+    let opts = FormatOptions::builder()
+        .cell_width(6)
+        .overflow(OverflowBehavior::Hashes(8))
+        .build();
+    assert_eq!(date.format(-1.0, &opts), "######");
+    assert_eq!(date.format(45000.0, &opts), "######");
+    assert_eq!(number.format(1234.5, &opts), "######");
+    assert_eq!(number.format(12.5, &opts), "12.50");
+    let fill = NumberFormat::parse("* 0.00").unwrap();
+    assert_eq!(fill.format(12.5, &opts), " 12.50");
+    // Text spills over instead
+    let text = NumberFormat::parse("0;0;0;@").unwrap();
+    assert_eq!(text.format_text("overflowing", &opts), "overflowing");
+
+    let opts = FormatOptions::builder()
+        .cell_width(6)
+        .overflow(OverflowBehavior::Error)
+        .build();
+    assert_eq!(
+        date.try_format(-1.0, &opts),
+        Err(FormatError::DateOutOfRange { serial: -1.0 })
+    );
+    assert_eq!(
+        number.try_format(1234.5, &opts),
+        Err(FormatError::CellOverflow {
+            needed: 8,
+            width: 6
+        })
+    );
+    assert_eq!(number.try_format(12.5, &opts).unwrap(), "12.50");
+}
+
+#[test]
+fn test_general_narrows_to_cell_width() {
+    let general = NumberFormat::parse("General").unwrap();
+    let opts = FormatOptions::builder().cell_width(6).build();
+    assert_eq!(general.format(1234.5678, &opts), "1234.6");
+    assert_eq!(general.format(-0.123456, &opts), "-0.123");
+    assert_eq!(general.format(123456789.0, &opts), "1E+08");
+    assert_eq!(general.format(42.0, &opts), "42");
+
+    // Values that can't be narrowed enough overflow as usual
+    let mut opts = FormatOptions::builder().cell_width(4).build();
+    assert_eq!(general.format(123456.0, &opts), "123456");
+    opts.overflow = OverflowBehavior::Hashes(0);
+    assert_eq!(general.format(123456.0, &opts), "####");
+}
@@ -1,6 +1,6 @@
 //! Tests for the format code parser.
 
-use ssfmt::ast::{Color, DatePart, FormatPart, NamedColor};
+use ssfmt::ast::{Color, DatePart, DigitPlaceholder, FormatPart, NamedColor};
 use ssfmt::NumberFormat;
 
 #[test]
@@ -30,6 +30,46 @@ fn test_parse_color() {
     assert_eq!(fmt.sections()[0].color, Some(Color::Named(NamedColor::Red)));
 }
 
+#[test]
+fn test_parse_dbnum() {
+    let fmt = NumberFormat::parse("[DBNum2][$-411]0").unwrap();
+    let section = &fmt.sections()[0];
+    assert_eq!(section.parts[0], FormatPart::DbNum(2));
+    assert_eq!(section.metadata.dbnum, Some(2));
+    assert_eq!(section.metadata.lcid, Some(0x411));
+    assert_eq!(fmt.to_string(), "[DBNum2][$-411]0");
+
+    let fmt = NumberFormat::parse("[dbnum1]General").unwrap();
+    assert_eq!(fmt.sections()[0].metadata.dbnum, Some(1));
+    assert_eq!(NumberFormat::parse("[DBNum99]0").unwrap().sections()[0].metadata.dbnum, None);
+}
+
+#[test]
+fn test_parse_natnum() {
+    let fmt = NumberFormat::parse("[NatNum1][$-401]0").unwrap();
+    let section = &fmt.sections()[0];
+    assert_eq!(section.parts[0], FormatPart::NatNum(1));
+    assert_eq!(section.metadata.natnum, Some(1));
+    assert_eq!(fmt.to_string(), "[NatNum1][$-401]0");
+
+    let fmt = NumberFormat::parse("[$-2010401]General").unwrap();
+    assert_eq!(fmt.sections()[0].metadata.lcid, Some(0x2010401));
+    assert_eq!(NumberFormat::parse("[NatNum99]0").unwrap().sections()[0].metadata.natnum, None);
+}
+
+#[test]
+fn test_parse_precomputes_number_analysis() {
+    let fmt = NumberFormat::parse("#,##0.00,\" K\";yyyy-mm-dd").unwrap();
+    let analysis = fmt.sections()[0].metadata.analysis.as_ref().unwrap();
+    assert_eq!(analysis.integer_placeholders.len(), 4);
+    assert_eq!(analysis.decimal_placeholders.len(), 2);
+    assert!(analysis.has_thousands_separator);
+    assert_eq!(analysis.thousands_scale, 1);
+    assert_eq!(analysis.suffix_parts, [FormatPart::QuotedLiteral(" K".to_string())]);
+    assert!(fmt.sections()[1].metadata.analysis.is_none());
+    assert!(NumberFormat::parse("0.00E+00").unwrap().sections()[0].metadata.analysis.is_none());
+}
+
 #[test]
 fn test_parse_percentage() {
     let fmt = NumberFormat::parse("0%").unwrap();
@@ -66,3 +106,389 @@ fn test_minute_vs_month_disambiguation() {
         .any(|p| matches!(p, FormatPart::DatePart(DatePart::Minute2)));
     assert!(has_minute, "Expected Minute2 after hour");
 }
+
+#[test]
+fn test_minute_vs_month_lookaround() {
+    use DatePart::*;
+
+    // The `m` codes of each section, in order
+    let months_and_minutes = |code| {
+        NumberFormat::parse(code)
+            .unwrap()
+            .sections()
+            .iter()
+            .flat_map(|s| s.parts.clone())
+            .filter_map(|p| match p {
+                FormatPart::DatePart(
+                    part @ (Month | Month2 | MonthAbbr | MonthFull | Minute | Minute2),
+                ) => Some(part),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // A second after `m` makes it a minute, whatever lies between
+    assert_eq!(months_and_minutes("mm:ss.0"), [Minute2]);
+    assert_eq!(months_and_minutes("m\"'\"ss"), [Minute]);
+    assert_eq!(months_and_minutes("mm \"min\" ss \"sec\""), [Minute2]);
+    assert_eq!(months_and_minutes("[h]mm"), [Minute2]);
+    assert_eq!(months_and_minutes("mm[$-409]ss"), [Minute2]);
+    // Only the nearest date code counts
+    assert_eq!(months_and_minutes("h:mm dd/mm"), [Minute2, Month2]);
+    assert_eq!(months_and_minutes("mm/yy ss"), [Month2]);
+    assert_eq!(months_and_minutes("h AM/PM mm"), [Month2]);
+    // Neither an hour nor a second reaches into another section
+    assert_eq!(months_and_minutes("hh;mm"), [Month2]);
+    assert_eq!(months_and_minutes("mm;ss"), [Month2]);
+}
+
+#[test]
+fn test_parse_lenient_repairs() {
+    use ssfmt::{FormatOptions, ParseWarning};
+    let opts = FormatOptions::default();
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("0.00[Blue");
+    assert_eq!(fmt.format(1.5, &opts), "1.50");
+    assert_eq!(
+        warnings,
+        [ParseWarning::UnterminatedBracket {
+            span: 4..9,
+            text: "[Blue".to_string()
+        }]
+    );
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("0]");
+    assert_eq!(fmt.format(7.0, &opts), "7]");
+    assert_eq!(warnings, [ParseWarning::StrayCloseBracket { span: 1..2 }]);
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("[Foo]0");
+    assert_eq!(fmt.format(7.0, &opts), "7");
+    assert_eq!(
+        warnings,
+        [ParseWarning::UnknownBracket {
+            span: 0..5,
+            text: "Foo".to_string()
+        }]
+    );
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("0;-0;\"zero\";@;0.0;0.00");
+    assert_eq!(fmt.sections().len(), 4);
+    assert_eq!(
+        warnings,
+        [ParseWarning::TooManySections {
+            span: 14..22,
+            ignored: 2
+        }]
+    );
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("0\\");
+    assert_eq!(fmt.format(7.0, &opts), "7");
+    assert_eq!(warnings, [ParseWarning::DanglingEscape { span: 1..2 }]);
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("");
+    assert_eq!(fmt.format(1.25, &opts), "1.25");
+    assert_eq!(warnings, [ParseWarning::EmptyFormat]);
+}
+
+#[test]
+fn test_parse_strict_rejects_leading_unterminated_quote() {
+    assert!(NumberFormat::parse("\"abc").is_err());
+    assert!(NumberFormat::parse("\\").is_err());
+    let (fmt, warnings) = NumberFormat::parse_lenient("\"abc");
+    assert_eq!(fmt.format(1.0, &ssfmt::FormatOptions::default()), "abc");
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn test_parse_lenient_matches_strict_for_valid_codes() {
+    for code in ["General", "[Red]General", "#,##0.00;[Red](#,##0.00)", "yyyy-mm-dd", "@"] {
+        let (fmt, warnings) = NumberFormat::parse_lenient(code);
+        assert_eq!(fmt, NumberFormat::parse(code).unwrap(), "{}", code);
+        assert!(warnings.is_empty(), "{}", code);
+    }
+}
+
+#[test]
+fn test_parse_scientific_exponent_digits() {
+    use DigitPlaceholder::{Hash, Question, Zero};
+
+    let exponent = |code| {
+        let fmt = NumberFormat::parse(code).unwrap();
+        fmt.sections()[0]
+            .parts
+            .iter()
+            .find_map(|part| match part {
+                FormatPart::Scientific {
+                    upper,
+                    show_plus,
+                    exponent_digits,
+                } => Some((*upper, *show_plus, exponent_digits.clone())),
+                _ => None,
+            })
+            .unwrap()
+    };
+    assert_eq!(exponent("0.00E+00"), (true, true, vec![Zero, Zero]));
+    assert_eq!(exponent("0.0E+000"), (true, true, vec![Zero, Zero, Zero]));
+    assert_eq!(exponent("##0.0e-#"), (false, false, vec![Hash]));
+    assert_eq!(exponent("0E+??0"), (true, true, vec![Question, Question, Zero]));
+
+    // The exponent digits are no longer separate parts
+    let fmt = NumberFormat::parse("0.0E+00").unwrap();
+    let digits = fmt.sections()[0]
+        .parts
+        .iter()
+        .filter(|p| matches!(p, FormatPart::Digit(_)))
+        .count();
+    assert_eq!(digits, 2);
+}
+
+#[test]
+fn test_parse_japanese_era_codes() {
+    let date_parts = |code: &str| -> Vec<DatePart> {
+        let fmt = NumberFormat::parse(code).unwrap();
+        fmt.sections()[0]
+            .parts
+            .iter()
+            .filter_map(|p| match p {
+                FormatPart::DatePart(part) => Some(*part),
+                _ => None,
+            })
+            .collect()
+    };
+    assert_eq!(
+        date_parts("g gg ggg e ee"),
+        [
+            DatePart::EraLetter,
+            DatePart::EraAbbr,
+            DatePart::EraFull,
+            DatePart::EraYear,
+            DatePart::EraYear2,
+        ]
+    );
+    assert_eq!(date_parts("[$-411]r"), [DatePart::EraYear2]);
+    assert_eq!(
+        date_parts("[$-411]rr"),
+        [DatePart::EraFull, DatePart::EraYear2]
+    );
+    // Outside Japanese locales `r` is a literal
+    assert!(date_parts("r").is_empty());
+    // `E+` is still scientific notation
+    assert!(date_parts("0.0E+0").is_empty());
+}
+
+#[test]
+fn test_parse_buddhist_year_lengths() {
+    let date_parts = |code: &str| -> Vec<DatePart> {
+        let fmt = NumberFormat::parse(code).unwrap();
+        fmt.sections()[0]
+            .parts
+            .iter()
+            .filter_map(|p| match p {
+                FormatPart::DatePart(part) => Some(*part),
+                _ => None,
+            })
+            .collect()
+    };
+    assert_eq!(date_parts("b"), [DatePart::BuddhistYear2]);
+    assert_eq!(date_parts("bb"), [DatePart::BuddhistYear2]);
+    assert_eq!(date_parts("bbb"), [DatePart::BuddhistYear4]);
+    assert_eq!(date_parts("bbbb"), [DatePart::BuddhistYear4]);
+}
+
+#[test]
+fn test_parse_condition_value_notation() {
+    let opts = ssfmt::FormatOptions::default();
+    let fmt = NumberFormat::parse("[>1e5]\"big\";0").unwrap();
+    assert_eq!(fmt.format(200_000.0, &opts), "big");
+    assert_eq!(fmt.format(99_999.0, &opts), "99999");
+
+    // A comma decimal separator, as written in comma-decimal locales
+    let fmt = NumberFormat::parse("[>=1,5]\"high\";0.0").unwrap();
+    assert_eq!(fmt.format(1.5, &opts), "high");
+    assert_eq!(fmt.format(1.4, &opts), "1.4");
+}
+
+#[test]
+fn test_parse_fraction_keeps_surrounding_parts() {
+    use ssfmt::ast::FractionDenom;
+
+    let fmt = NumberFormat::parse("\"$\"0\" and \"??/16\" lbs\"").unwrap();
+    assert_eq!(
+        fmt.sections()[0].parts,
+        vec![
+            FormatPart::QuotedLiteral("$".to_string()),
+            FormatPart::Fraction {
+                integer_digits: vec![DigitPlaceholder::Zero],
+                integer_separator: " and ".to_string(),
+                numerator_digits: vec![DigitPlaceholder::Question; 2],
+                denominator: FractionDenom::Fixed(16),
+                space_before_slash: String::new(),
+                space_after_slash: String::new(),
+            },
+            FormatPart::QuotedLiteral(" lbs".to_string()),
+        ]
+    );
+
+    // Digits next to the numerator make an improper fraction
+    let fmt = NumberFormat::parse("#0??/??").unwrap();
+    assert!(matches!(
+        &fmt.sections()[0].parts[..],
+        [FormatPart::Fraction { integer_digits, numerator_digits, .. }]
+            if integer_digits.is_empty() && numerator_digits.len() == 4
+    ));
+}
+
+#[test]
+fn test_parse_extra_sections() {
+    use ssfmt::{ExtraSections, ParseError, ParseOptions};
+
+    let code = "0;-0;\"zero\";@;0.0;0.00";
+    let fmt = NumberFormat::parse(code).unwrap();
+    assert_eq!(fmt.sections().len(), 4);
+    let fmt = NumberFormat::parse_with_options(code, &ParseOptions::default()).unwrap();
+    assert_eq!(fmt.sections().len(), 4);
+
+    let strict = ParseOptions {
+        extra_sections: ExtraSections::Error,
+        ..Default::default()
+    };
+    assert_eq!(
+        NumberFormat::parse_with_options(code, &strict),
+        Err(ParseError::TooManySections)
+    );
+    assert!(NumberFormat::parse_with_options("0;-0;\"zero\";@", &strict).is_ok());
+}
+
+#[test]
+fn test_parse_unsupported() {
+    use ssfmt::{DiagnosticCode, ParseError, ParseWarning};
+
+    let err = NumberFormat::parse("[DBNum4][$-411]0").unwrap_err();
+    assert_eq!(err, ParseError::Unsupported { feature: "[DBNum4]-[DBNum9]" });
+    assert_eq!(err.code(), DiagnosticCode::Unsupported);
+    assert_eq!(err.to_string(), "unsupported feature: [DBNum4]-[DBNum9]");
+    assert_eq!(
+        NumberFormat::parse("[NatNum12]General").unwrap_err(),
+        ParseError::Unsupported { feature: "[NatNum12]" }
+    );
+    // Malformed codes are not unsupported
+    assert_ne!(NumberFormat::parse("0\"").unwrap_err().code(), DiagnosticCode::Unsupported);
+
+    let (fmt, warnings) = NumberFormat::parse_lenient("[DBNum5]0.0");
+    assert_eq!(fmt.format(7.0, &Default::default()), "7.0");
+    assert_eq!(
+        warnings,
+        [ParseWarning::Unsupported {
+            span: 0..8,
+            feature: "[DBNum4]-[DBNum9]"
+        }]
+    );
+}
+
+#[test]
+fn test_parse_limits() {
+    use ssfmt::{DiagnosticCode, ParseError, ParseLimits, ParseOptions, ParseWarning};
+
+    // A megabyte of placeholders fails before anything is allocated for it
+    let huge = "#".repeat(1 << 20);
+    let err = NumberFormat::parse(&huge).unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::FormatTooComplex {
+            span: 4096..huge.len(),
+            limit: "bytes",
+            max: 4096,
+        }
+    );
+    assert_eq!(err.code(), DiagnosticCode::FormatTooComplex);
+    let (fmt, warnings) = NumberFormat::parse_lenient(&huge);
+    assert_eq!(fmt.format(1.5, &Default::default()), "1.5");
+    assert!(matches!(warnings[..], [ParseWarning::FormatTooComplex { .. }]));
+
+    let limits = ParseLimits {
+        max_length: 64,
+        max_parts_per_section: 8,
+        max_quoted_length: 4,
+    };
+    let options = ParseOptions {
+        limits,
+        ..Default::default()
+    };
+    assert!(NumberFormat::parse_with_options("#,##0.00", &options).is_ok());
+    assert!(NumberFormat::parse_with_options("\"abcd\"0", &options).is_ok());
+    assert_eq!(
+        NumberFormat::parse_with_options("0\"abcde\"", &options),
+        Err(ParseError::FormatTooComplex {
+            span: 1..6,
+            limit: "bytes in a quoted string",
+            max: 4,
+        })
+    );
+    assert!(matches!(
+        NumberFormat::parse_with_options("000000000;0", &options),
+        Err(ParseError::FormatTooComplex {
+            limit: "parts in a section",
+            max: 8,
+            ..
+        })
+    ));
+    // Eight parts per section, in each of two sections
+    assert!(NumberFormat::parse_with_options("00000000;00000000", &options).is_ok());
+    assert!(matches!(
+        NumberFormat::parse_with_options(&"0".repeat(65), &options),
+        Err(ParseError::FormatTooComplex { max: 64, .. })
+    ));
+
+    // Lifting the limits
+    let options = ParseOptions {
+        limits: ParseLimits::UNLIMITED,
+        ..Default::default()
+    };
+    let long = format!("0\"{}\"", "x".repeat(5000));
+    assert!(NumberFormat::parse(&long).is_err());
+    assert!(NumberFormat::parse_with_options(&long, &options).is_ok());
+}
+
+#[test]
+fn test_parse_coalesces_literals() {
+    let fmt = NumberFormat::parse("($#,##0.00)").unwrap();
+    assert_eq!(
+        fmt.sections()[0].parts.first(),
+        Some(&FormatPart::Literal("($".to_string()))
+    );
+    assert_eq!(fmt.to_string(), "($#,##0.00)");
+
+    // Quoted text and escapes stay separate, so they are written back as is
+    let fmt = NumberFormat::parse("0 \"kg\"-\\x").unwrap();
+    assert_eq!(
+        fmt.sections()[0].parts,
+        [
+            FormatPart::Digit(DigitPlaceholder::Zero),
+            FormatPart::Literal(" ".to_string()),
+            FormatPart::QuotedLiteral("kg".to_string()),
+            FormatPart::Literal("-".to_string()),
+            FormatPart::EscapedLiteral("x".to_string()),
+        ]
+    );
+
+    // The decimal point of sub-seconds is kept apart from the text before it
+    let fmt = NumberFormat::parse("ss\" s\".00").unwrap();
+    assert_eq!(
+        fmt.sections()[0].parts,
+        [
+            FormatPart::DatePart(DatePart::Second2),
+            FormatPart::QuotedLiteral(" s".to_string()),
+            FormatPart::Literal(".".to_string()),
+            FormatPart::DatePart(DatePart::SubSecond(2)),
+        ]
+    );
+    assert_eq!(NumberFormat::parse(&fmt.to_string()).unwrap(), fmt);
+}
+
+#[test]
+fn test_from_str_and_try_from() {
+    let fmt: NumberFormat = "0.00;[Red]-0.00".parse().unwrap();
+    assert_eq!(fmt, NumberFormat::parse("0.00;[Red]-0.00").unwrap());
+    assert_eq!(NumberFormat::try_from("0%"), NumberFormat::parse("0%"));
+    assert_eq!("".parse::<NumberFormat>(), Err(ssfmt::ParseError::EmptyFormat));
+}
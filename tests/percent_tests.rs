@@ -0,0 +1,58 @@
+//! Tests for percent signs: each `%` scales by 100 and shows where written.
+
+use ssfmt::{format_default, NumberFormat};
+
+fn fmt(value: f64, code: &str) -> String {
+    format_default(value, code).unwrap()
+}
+
+#[test]
+fn test_suffix_percent() {
+    assert_eq!(fmt(0.5, "0%"), "50%");
+    assert_eq!(fmt(0.1234, "0.00%"), "12.34%");
+    assert_eq!(fmt(12.5, "#,##0%"), "1,250%");
+    assert_eq!(fmt(0.5, "0 %"), "50 %");
+    assert_eq!(fmt(0.5, "0%\" done\""), "50% done");
+    assert_eq!(fmt(-0.25, "0%"), "-25%");
+}
+
+#[test]
+fn test_prefix_percent() {
+    assert_eq!(fmt(0.5, "%0.00"), "%50.00");
+    assert_eq!(fmt(0.5, "\"rate \"%0"), "rate %50");
+}
+
+#[test]
+fn test_inline_percent() {
+    assert_eq!(fmt(0.5, "0%0"), "5%0");
+    assert_eq!(fmt(0.05, "0%0"), "0%5");
+    assert_eq!(fmt(12.34, "#,##0%0"), "1,23%4");
+    assert_eq!(fmt(0.123, "0.0%0"), "12.3%0");
+}
+
+#[test]
+fn test_multiple_percents_each_scale() {
+    assert_eq!(fmt(0.5, "0%%"), "5000%%");
+    assert_eq!(fmt(0.01, "%0%"), "%100%");
+    assert_eq!(fmt(0.5, "0%0%"), "500%0%");
+}
+
+#[test]
+fn test_percent_analysis() {
+    let fmt = NumberFormat::parse("%0%0.0%").unwrap();
+    let analysis = fmt.sections()[0].metadata.analysis.as_ref().unwrap();
+    assert_eq!(analysis.percent_count, 3);
+    assert_eq!(fmt.format(0.0000123, &Default::default()), "%1%2.3%");
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_bigint_percent() {
+    use ssfmt::{format_bigint, BigInt, FormatOptions};
+
+    let big: BigInt = "123456789012345678901234567890".parse().unwrap();
+    assert_eq!(
+        format_bigint(&big, "0%", &FormatOptions::default()).unwrap(),
+        "12345678901234567890123456789000%"
+    );
+}
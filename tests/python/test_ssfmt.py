@@ -0,0 +1,37 @@
+"""Tests for the Python bindings (`python` feature).
+
+Run with `maturin develop && pytest tests/python`.
+"""
+
+import datetime
+
+import pytest
+
+import ssfmt
+
+
+def test_format():
+    assert ssfmt.format(1234.5, "#,##0.00") == "1,234.50"
+    assert ssfmt.format(1234.5, "#,##0.00", locale="de-DE") == "1.234,50"
+    assert ssfmt.format(0, "yyyy", date1904=True) == "1904"
+    assert ssfmt.format(True, "0") == "TRUE"
+    assert ssfmt.format(None, "0") == ""
+    assert ssfmt.format("abc", '"<"@">"') == "<abc>"
+
+
+def test_dates_and_durations():
+    fmt = ssfmt.NumberFormat("yyyy-mm-dd hh:mm:ss")
+    assert fmt.is_date
+    assert fmt.format(datetime.datetime(2024, 3, 15, 9, 30, 5)) == "2024-03-15 09:30:05"
+    assert fmt.format(datetime.date(2024, 3, 15)) == "2024-03-15 00:00:00"
+    assert ssfmt.format(datetime.time(18, 0), "h:mm AM/PM") == "6:00 PM"
+    assert ssfmt.format(datetime.timedelta(days=1, hours=6), "[h]:mm") == "30:00"
+
+
+def test_errors():
+    with pytest.raises(ValueError):
+        ssfmt.NumberFormat("[Red")
+    with pytest.raises(ValueError):
+        ssfmt.format(1, "0", locale="xx-bogus")
+    with pytest.raises(TypeError):
+        ssfmt.format([1], "0")
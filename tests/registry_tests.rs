@@ -0,0 +1,40 @@
+use ssfmt::{FormatOptions, FormatRegistry, ParseError};
+
+#[test]
+fn test_registry_custom_and_builtin() {
+    let mut registry = FormatRegistry::new();
+    registry.register(164, "#,##0.0").unwrap();
+    registry.register(165, "yyyy-mm-dd").unwrap();
+    assert_eq!(registry.len(), 2);
+
+    let opts = FormatOptions::default();
+    assert_eq!(registry.format(1234.56, 164, &opts).unwrap(), "1,234.6");
+    assert_eq!(registry.format(45000.0, 165, &opts).unwrap(), "2023-03-15");
+    assert_eq!(registry.format(45000.0, 14, &opts).unwrap(), "3/15/23");
+    assert_eq!(registry.format_code(164), Some("#,##0.0"));
+    assert_eq!(registry.format_code(4), Some("#,##0.00"));
+}
+
+#[test]
+fn test_registry_override_builtin() {
+    let mut registry = FormatRegistry::new();
+    registry.register(14, "dd/mm/yyyy").unwrap();
+    let opts = FormatOptions::default();
+    assert_eq!(registry.format(45000.0, 14, &opts).unwrap(), "15/03/2023");
+
+    assert_eq!(registry.unregister(14).as_deref(), Some("dd/mm/yyyy"));
+    assert_eq!(registry.format(45000.0, 14, &opts).unwrap(), "3/15/23");
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_registry_errors() {
+    let mut registry = FormatRegistry::new();
+    assert!(registry.register(164, "[Red").is_err());
+    assert!(!registry.contains(164));
+    assert_eq!(
+        registry.get(200).unwrap_err(),
+        ParseError::InvalidFormatId(200)
+    );
+    assert_eq!(registry.format_code(200), None);
+}
@@ -0,0 +1,195 @@
+use ssfmt::ast::{Color, NamedColor};
+use ssfmt::{ColumnFormatter, FormatOptions, FormattedRun, NumberFormat, RichText};
+
+fn rich(code: &str, value: f64) -> RichText {
+    let fmt = NumberFormat::parse(code).unwrap();
+    fmt.format_rich(value, &FormatOptions::default())
+}
+
+fn joined(rich: &RichText) -> String {
+    rich.runs.iter().map(FormattedRun::as_str).collect()
+}
+
+#[test]
+fn test_format_rich_section_color() {
+    let code = "[Blue]#,##0;[Red]-#,##0;[Color10]0";
+    assert_eq!(rich(code, 5.0).color, Some(Color::Named(NamedColor::Blue)));
+    assert_eq!(rich(code, -5.0).color, Some(Color::Named(NamedColor::Red)));
+    assert_eq!(rich(code, 0.0).color, Some(Color::Indexed(10)));
+    assert_eq!(rich("0.00", 5.0).color, None);
+}
+
+#[test]
+fn test_format_rich_text_matches_format() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("#,##0.00", -1234.5),
+        ("\"$\"#,##0.00", 42.0),
+        ("0%", 0.25),
+        ("0.00E+00", 12345.0),
+        ("# ?/?", 1.5),
+        ("yyyy-mm-dd", 45000.0),
+        ("General", 3.25),
+    ];
+    for (code, value) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        let rich = fmt.format_rich(value, &opts);
+        assert_eq!(rich.text, fmt.format(value, &opts), "code {}", code);
+        assert_eq!(joined(&rich), rich.text, "code {}", code);
+    }
+}
+
+#[test]
+fn test_format_rich_number_runs() {
+    let result = rich("\"$\"#,##0.00\" USD\"", 1234.5);
+    assert_eq!(
+        result.runs,
+        vec![
+            FormattedRun::Literal("$".to_string()),
+            FormattedRun::Digits("1,234.50".to_string()),
+            FormattedRun::Literal(" USD".to_string()),
+        ]
+    );
+
+    let result = rich("0%", 0.5);
+    assert_eq!(
+        result.runs,
+        vec![
+            FormattedRun::Digits("50".to_string()),
+            FormattedRun::Literal("%".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_format_rich_negative_sign_is_digits() {
+    let result = rich("\"$\"0", -7.0);
+    assert_eq!(result.text, "-$7");
+    assert_eq!(result.runs[0], FormattedRun::Digits("-".to_string()));
+}
+
+#[test]
+fn test_format_rich_fill_run() {
+    let result = rich("0*-", 12.0);
+    assert_eq!(result.text, "12");
+    assert_eq!(
+        result.runs,
+        vec![
+            FormattedRun::Digits("12".to_string()),
+            FormattedRun::Fill {
+                fill: '-',
+                text: String::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_format_rich_date_runs() {
+    let result = rich("yyyy\"年\"m\"月\"", 45000.0);
+    assert_eq!(result.text, "2023年3月");
+    assert_eq!(
+        result.runs,
+        vec![
+            FormattedRun::Digits("2023".to_string()),
+            FormattedRun::Literal("年".to_string()),
+            FormattedRun::Digits("3".to_string()),
+            FormattedRun::Literal("月".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_format_rich_special_values() {
+    let result = rich("[Red]0", f64::NAN);
+    assert_eq!(result.text, "NaN");
+    assert_eq!(result.color, None);
+    assert_eq!(result.runs, vec![FormattedRun::Digits("NaN".to_string())]);
+}
+
+#[test]
+fn test_format_rich_fill_expanded_to_width() {
+    let fmt = NumberFormat::parse("\"$\"* #,##0").unwrap();
+    let opts = FormatOptions::builder().cell_width(8).build();
+    let result = fmt.format_rich(1234.0, &opts);
+    assert_eq!(result.text, "$  1,234");
+    assert_eq!(
+        result.runs[1],
+        FormattedRun::Fill {
+            fill: ' ',
+            text: "  ".to_string(),
+        }
+    );
+    assert_eq!(joined(&result), result.text);
+}
+
+#[test]
+fn test_format_rich_skip_run() {
+    let result = rich("#,##0_)", 1234.0);
+    assert_eq!(result.text, "1,234 ");
+    assert_eq!(
+        result.runs,
+        vec![
+            FormattedRun::Digits("1,234".to_string()),
+            FormattedRun::Skip {
+                skip: ')',
+                text: " ".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_format_text_rich() {
+    let opts = FormatOptions::default();
+    let fmt = NumberFormat::parse("0;-0;0;[Blue]_(\"Note: \"@").unwrap();
+    let result = fmt.format_text_rich("late", &opts);
+    assert_eq!(result.text, fmt.format_text("late", &opts));
+    assert_eq!(result.color, Some(Color::Named(NamedColor::Blue)));
+    assert_eq!(
+        result.runs,
+        vec![
+            FormattedRun::Skip {
+                skip: '(',
+                text: " ".to_string(),
+            },
+            FormattedRun::Literal("Note: ".to_string()),
+            FormattedRun::Digits("late".to_string()),
+        ]
+    );
+
+    // A single section with @ is the text section
+    let fmt = NumberFormat::parse("[Red]@").unwrap();
+    let result = fmt.format_text_rich("x", &opts);
+    assert_eq!(result.color, Some(Color::Named(NamedColor::Red)));
+
+    // Without a text section the text is shown as-is and uncolored
+    let fmt = NumberFormat::parse("[Red]0").unwrap();
+    let result = fmt.format_text_rich("x", &opts);
+    assert_eq!(result.text, "x");
+    assert_eq!(result.color, None);
+    assert_eq!(result.runs, vec![FormattedRun::Digits("x".to_string())]);
+}
+
+#[test]
+fn test_column_formatter_alignment() {
+    let opts = FormatOptions::builder().cell_width(10).build();
+    let fmt = NumberFormat::parse("$* #,##0.0?_);[Red]$* (#,##0.0?)").unwrap();
+    let cells = ColumnFormatter::new(&fmt, &opts).format(&[1234.5, -0.25, 7.0]);
+
+    let texts: Vec<&str> = cells.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, ["$1,234.5  ", "$   (0.25)", "$    7.0  "]);
+    for cell in &cells {
+        assert_eq!(cell.width, 10);
+        assert_eq!(cell.decimal_offset, Some(6));
+    }
+    assert_eq!(cells[0].skip_width, 1);
+    assert_eq!(cells[1].color, Some(Color::Named(NamedColor::Red)));
+    assert_eq!(cells[1].skip_width, 0);
+    assert_eq!(cells[0].fill_width, 0);
+    assert_eq!(cells[2].fill_width, 4);
+
+    let general = NumberFormat::parse("General").unwrap();
+    let cell = ColumnFormatter::new(&general, &opts).format_cell(42.0);
+    assert_eq!(cell.decimal_offset, None);
+}
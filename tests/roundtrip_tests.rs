@@ -0,0 +1,221 @@
+//! Tests for regenerating format codes from the parsed AST.
+
+use flate2::read::GzDecoder;
+use ssfmt::NumberFormat;
+use std::io::Read;
+
+fn roundtrip(code: &str) -> String {
+    NumberFormat::parse(code).unwrap().to_format_code()
+}
+
+#[test]
+fn test_roundtrip_simple_codes() {
+    assert_eq!(roundtrip("0"), "0");
+    assert_eq!(roundtrip("#,##0.00"), "#,##0.00");
+    assert_eq!(roundtrip("0.00%"), "0.00%");
+    assert_eq!(roundtrip("0.00E+00"), "0.00E+00");
+    assert_eq!(roundtrip("##0.0E-0"), "##0.0E-0");
+    assert_eq!(roundtrip("0.0e+??#"), "0.0e+??#");
+    assert_eq!(roundtrip("@"), "@");
+    assert_eq!(roundtrip("General"), "General");
+}
+
+#[test]
+fn test_roundtrip_sections_colors_conditions() {
+    assert_eq!(roundtrip("#,##0;[Red](#,##0)"), "#,##0;[Red](#,##0)");
+    assert_eq!(roundtrip("[Color10]0"), "[Color10]0");
+    assert_eq!(
+        roundtrip("[>=100][Blue]0;[<0]0.0;0"),
+        "[Blue][>=100]0;[<0]0.0;0"
+    );
+    assert_eq!(roundtrip("[Red]General"), "[Red]General");
+    assert_eq!(roundtrip("0;General"), "0;General");
+    assert_eq!(roundtrip("0;;"), "0;;");
+    assert_eq!(roundtrip("0;;0"), "0;;0");
+}
+
+#[test]
+fn test_roundtrip_literals_fills_and_skips() {
+    assert_eq!(roundtrip("0\" kg\""), "0\" kg\"");
+    assert_eq!(roundtrip("\\$0"), "\\$0");
+    assert_eq!(roundtrip("$0"), "$0");
+    assert_eq!(roundtrip("\"-\"0"), "\"-\"0");
+    assert_eq!(roundtrip("\"$\""), "\"$\"");
+    assert_eq!(roundtrip("\"\"0"), "\"\"0");
+    assert_eq!(
+        roundtrip("_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)"),
+        "_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)"
+    );
+    assert_eq!(roundtrip("0*x"), "0*\\x");
+}
+
+#[test]
+fn test_roundtrip_dates_and_fractions() {
+    assert_eq!(roundtrip("yyyy-mm-dd hh:mm:ss"), "yyyy-mm-dd hh:mm:ss");
+    assert_eq!(roundtrip("[h]:mm:ss.000"), "[h]:mm:ss.000");
+    assert_eq!(roundtrip("[d] hh:mm"), "[d] hh:mm");
+    assert_eq!(roundtrip("h:mm AM/PM"), "h:mm AM/PM");
+    assert_eq!(roundtrip("上午/下午h:mm"), "上午/下午h:mm");
+    assert_eq!(roundtrip("[$-409]mmmm d, yyyy"), "[$-409]mmmm d, yyyy");
+    assert_eq!(roundtrip("[$-411]ggge\"年\""), "[$-411]ggge\"年\"");
+    assert_eq!(roundtrip("[$-411]rr"), "[$-411]gggee");
+    assert_eq!(roundtrip("[$€-407]#,##0.00"), "[$€-407]#,##0.00");
+    assert_eq!(roundtrip("# ??/??"), "# ??/??");
+    assert_eq!(roundtrip("# ?/16"), "# ?/16");
+    assert_eq!(roundtrip("\"$\"# ?/?\" lbs\""), "\"$\"# ?/?\" lbs\"");
+    assert_eq!(roundtrip("0 \"and\" ?/?"), "0\" and \"?/?");
+}
+
+#[test]
+fn test_roundtrip_reparses_to_same_ast() {
+    let compressed = include_bytes!("fixtures/valid.tsv.gz");
+    let mut decoder = GzDecoder::new(&compressed[..]);
+    let mut data = String::new();
+    decoder.read_to_string(&mut data).unwrap();
+
+    for code in data.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(fmt) = NumberFormat::parse(code) else {
+            continue;
+        };
+        let regenerated = fmt.to_format_code();
+        let reparsed = NumberFormat::parse(&regenerated)
+            .unwrap_or_else(|e| panic!("'{}' -> '{}' failed to parse: {}", code, regenerated, e));
+        assert_eq!(
+            fmt, reparsed,
+            "round trip changed the AST: '{}' -> '{}'",
+            code, regenerated
+        );
+    }
+}
+
+#[test]
+fn test_roundtrip_adjacent_date_parts() {
+    use ssfmt::ast::FormatPart;
+    use ssfmt::visit::FormatVisitorMut;
+
+    // Drops the literals between date parts, leaving them side by side
+    struct DropLiterals;
+
+    impl FormatVisitorMut for DropLiterals {
+        fn visit_parts_mut(&mut self, parts: &mut Vec<FormatPart>) {
+            parts.retain(|part| !matches!(part, FormatPart::Literal(_)));
+        }
+    }
+
+    let opts = ssfmt::FormatOptions::default();
+    let mut edited = NumberFormat::parse("dd-ddd-bb-B2yy-hh:h").unwrap();
+    edited.walk_mut(&mut DropLiterals);
+    let formats = ["mm*m", "yyyy*y", "hh*h", "bB", "e_e", "ss.0s"]
+        .map(|code| NumberFormat::parse(code).unwrap())
+        .into_iter()
+        .chain([edited]);
+    for fmt in formats {
+        let regenerated = fmt.to_format_code();
+        let reparsed = NumberFormat::parse(&regenerated).unwrap();
+        for value in [45366.5, 45366.75, 243.125] {
+            assert_eq!(
+                reparsed.format(value, &opts),
+                fmt.format(value, &opts),
+                "'{}' at {}",
+                regenerated,
+                value
+            );
+        }
+    }
+
+    assert_eq!(roundtrip("mm*m"), "mm\"\"m");
+    assert_eq!(roundtrip("bB"), "bb\"\"bb");
+    assert_eq!(roundtrip("yyyy-mm"), "yyyy-mm");
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(ssfmt::normalize("[BLUE]0.00"), "[Blue]0.00");
+    assert_eq!(ssfmt::normalize("HH:MM:SS AM/PM"), "hh:mm:ss AM/PM");
+    assert_eq!(ssfmt::normalize("general"), "General");
+    assert_eq!(ssfmt::normalize("\"-\"0"), "-0");
+    assert_eq!(ssfmt::normalize("\"$\"# ?/?\" lbs\""), "$# ?/?\" lbs\"");
+    assert_eq!(ssfmt::normalize("\\-0\"%\""), "\\-0\"%\"");
+    assert_eq!(ssfmt::normalize("0.0;(0.0);0.0;@"), "0.0;(0.0)");
+    assert_eq!(
+        ssfmt::normalize("0.0;(0.0);0.0;\"t: \"@"),
+        "0.0;(0.0);0.0;\"t: \"@"
+    );
+    assert_eq!(ssfmt::normalize("0;-0;\"zero\""), "0;-0;\"zero\"");
+    assert_eq!(ssfmt::normalize("#,##0;-#,##0;#,##0"), "#,##0");
+    assert_eq!(ssfmt::normalize("[Red]0;[Red]\\-0"), "[Red]0");
+    assert_eq!(ssfmt::normalize("0;[Red]-0"), "0;[Red]-0");
+    assert_eq!(ssfmt::normalize("0;-0.0"), "0;-0.0");
+    assert_eq!(ssfmt::normalize("[<10]0;[<100]0.0;0"), "[<10]0;[<100]0.0;0");
+    assert_eq!(ssfmt::normalize("0;[Red]0;@"), "0;[Red]0;@");
+}
+
+#[test]
+fn test_normalize_preserves_formatting() {
+    // A dropped `-` section keeps the sign of negatives shown as zero
+    let opts = ssfmt::FormatOptions::builder()
+        .show_negative_zero(true)
+        .build();
+    for code in ["0.0;(0.0);0.0;@", "#,##0;-#,##0;#,##0", "[RED]0%;-0%"] {
+        let original = NumberFormat::parse(code).unwrap();
+        let normalized = NumberFormat::parse(&ssfmt::normalize(code)).unwrap();
+        for value in [1.5, -1.5, 0.0, -0.001] {
+            assert_eq!(
+                original.format(value, &opts),
+                normalized.format(value, &opts),
+                "{} at {}",
+                code,
+                value
+            );
+        }
+        assert_eq!(
+            original.format_text("x", &opts),
+            normalized.format_text("x", &opts)
+        );
+    }
+}
+
+#[test]
+fn test_equivalent_to() {
+    let opts = ssfmt::FormatOptions::default();
+    let parse = |code| NumberFormat::parse(code).unwrap();
+    let values = [1234.5, -1234.5, 0.0, 0.25, -0.001, 45366.5625];
+    for (a, b) in [
+        ("YYYY-MM-DD", "yyyy-mm-dd"),
+        ("h:mm am/pm", "h:mm AM/PM"),
+        ("0\" kg\"", "0\\ \\k\\g"),
+        ("\"USD \"0.00", "\"US\"\"D \"0.00"),
+        ("0.0;(0.0);0.0;@", "0.0;(0.0)"),
+        ("[BLUE][>100]0", "[Blue][>100]0"),
+        ("0% ;[Red]\\-0% ", "0% ;[Red]-0% "),
+    ] {
+        let (a, b) = (parse(a), parse(b));
+        assert!(a.equivalent_to(&b), "{a} vs {b}");
+        assert!(b.equivalent_to(&a), "{b} vs {a}");
+        assert!(a.equivalent_on(&b, &values, &opts), "{a} vs {b}");
+    }
+    for (a, b) in [
+        ("0 kg", "0kg"),
+        ("0.00", "0.0"),
+        ("[Red]0", "0"),
+        ("mm:ss", "mm-dd"),
+        // A lone "x" gets a minus sign for negative values, "\x" does not
+        ("x", "\\x"),
+        ("0;-0.0", "0"),
+        ("0;[Red]-0", "0"),
+    ] {
+        let (a, b) = (parse(a), parse(b));
+        assert!(!a.equivalent_to(&b), "{a} vs {b}");
+        assert!(!a.equivalent_on(&b, &values, &opts), "{a} vs {b}");
+    }
+    // Equivalent except for negatives that display as zero
+    let negative_zero = ssfmt::FormatOptions::builder()
+        .show_negative_zero(true)
+        .build();
+    for (a, b) in [("0;-0", "0"), ("\"$\"#,##0.00;-$#,##0.00", "$#,##0.00")] {
+        let (a, b) = (parse(a), parse(b));
+        assert!(a.equivalent_to(&b), "{a} vs {b}");
+        assert!(!a.equivalent_on(&b, &[-0.001], &opts), "{a} vs {b}");
+        assert!(a.equivalent_on(&b, &values, &negative_zero), "{a} vs {b}");
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "serde")]
+
+use ssfmt::ast::{Color, Condition, FormatPart, NamedColor};
+use ssfmt::{FormatOptions, NumberFormat};
+
+const CODES: &[&str] = &[
+    "General",
+    "#,##0.00;[Red](#,##0.00);\"-\";@",
+    "[>=100][Blue]0;[<0]-0.0;0.00",
+    "_($* #,##0_);_($* (#,##0);_($* \"-\"??_);_(@_)",
+    "# ??/16",
+    "0.00E+00",
+    "[$-411]yyyy\"年\"m\"月\"d\"日\" h:mm AM/PM",
+    "[h]:mm:ss.000",
+    "[DBNum1][$-804]General",
+    "[Color10]0*-",
+];
+
+#[test]
+fn test_serde_round_trip() {
+    for code in CODES {
+        let fmt = NumberFormat::parse(code).unwrap();
+        let json = serde_json::to_string(&fmt).unwrap();
+        let back: NumberFormat = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, fmt, "code {}", code);
+        assert_eq!(back.to_string(), fmt.to_string());
+    }
+}
+
+#[test]
+fn test_serde_deserialized_format_formats_identically() {
+    let opts = FormatOptions::default();
+    for code in CODES {
+        let fmt = NumberFormat::parse(code).unwrap();
+        let back: NumberFormat =
+            serde_json::from_str(&serde_json::to_string(&fmt).unwrap()).unwrap();
+        for value in [0.0, -1234.5678, 45306.75, 0.0625] {
+            assert_eq!(back.format(value, &opts), fmt.format(value, &opts));
+        }
+    }
+}
+
+#[test]
+fn test_serde_stable_representation() {
+    assert_eq!(
+        serde_json::to_string(&Color::Named(NamedColor::Red)).unwrap(),
+        r#"{"Named":"Red"}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&Condition::GreaterOrEqual(100.0)).unwrap(),
+        r#"{"GreaterOrEqual":100.0}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&FormatPart::Literal("kg".to_string())).unwrap(),
+        r#"{"Literal":"kg"}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&FormatPart::DecimalPoint).unwrap(),
+        r#""DecimalPoint""#
+    );
+}
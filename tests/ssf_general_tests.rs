@@ -96,10 +96,5 @@ fn test_ssf_general_suite() {
     println!("Failed:  {} ({:.1}%)", failed, 100.0 * failed as f64 / total as f64);
     println!("Skipped: {}", skipped);
 
-    // For now, we'll allow failures and just report them
-    // Once we fix the issues, we can make this assertion stricter
-    if failed > 0 {
-        println!("\nNote: {} tests failed. This is expected during initial integration.", failed);
-        println!("We'll work on fixing these failures to improve Excel compatibility.");
-    }
+    assert_eq!(failed, 0, "{} General format cases failed", failed);
 }
@@ -110,7 +110,5 @@ fn test_ssf_implied_formats() {
     println!("Failed:  {} ({:.1}%)", failed, 100.0 * failed as f64 / total as f64);
     println!("Skipped: {} ({:.1}%)", skipped, 100.0 * skipped as f64 / total as f64);
 
-    if failed > 0 {
-        println!("\nNote: {} tests failed. Working on improving compatibility.", failed);
-    }
+    assert_eq!(failed, 0, "{} implied format cases failed", failed);
 }
@@ -0,0 +1,62 @@
+//! Tests from SheetJS ssf library's is_date.json test suite
+//!
+//! These tests verify that `is_date_format_code` classifies format codes the
+//! same way as SheetJS ssf's `is_date`.
+
+use serde_json::Value;
+use ssfmt::{is_date_format_code, NumberFormat};
+
+fn load_test_cases() -> Vec<(String, bool)> {
+    let json_data = include_str!("fixtures/ssf_is_date.json");
+    let tests: Vec<Value> =
+        serde_json::from_str(json_data).expect("Failed to parse ssf_is_date.json");
+
+    tests
+        .iter()
+        .filter_map(|test| {
+            let arr = test.as_array()?;
+            Some((arr.first()?.as_str()?.to_string(), arr.get(1)?.as_bool()?))
+        })
+        .collect()
+}
+
+#[test]
+fn test_ssf_is_date_suite() {
+    let test_cases = load_test_cases();
+    assert!(!test_cases.is_empty());
+
+    let failures: Vec<_> = test_cases
+        .iter()
+        .filter(|(code, expected)| is_date_format_code(code) != *expected)
+        .collect();
+    assert!(failures.is_empty(), "Mismatches: {:?}", failures);
+}
+
+#[test]
+fn test_is_date_tricky_cases() {
+    assert!(!is_date_format_code("\"yyyy\"0"));
+    assert!(!is_date_format_code("\\d0"));
+    assert!(!is_date_format_code("_y0"));
+    assert!(!is_date_format_code("general"));
+    assert!(!is_date_format_code("[Red][<=100]0;[Blue]0"));
+    assert!(!is_date_format_code("0.00E+00"));
+    assert!(is_date_format_code("[Blue]General;d"));
+    assert!(is_date_format_code("[mm]:ss"));
+    assert!(is_date_format_code("A/P"));
+    assert!(is_date_format_code("B2yyyy"));
+    // Unparseable codes are still classified
+    assert!(is_date_format_code("yyyy\"unterminated"));
+}
+
+#[test]
+fn test_is_duration_format() {
+    for code in ["[h]:mm:ss", "[mm]:ss", "[s]", "0;[h]:mm"] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert!(fmt.is_duration_format(), "{}", code);
+        assert!(fmt.is_date_format(), "{}", code);
+    }
+    for code in ["h:mm:ss", "yyyy-mm-dd", "0.00", "[Red]0"] {
+        let fmt = NumberFormat::parse(code).unwrap();
+        assert!(!fmt.is_duration_format(), "{}", code);
+    }
+}
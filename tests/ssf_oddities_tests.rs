@@ -3,7 +3,7 @@
 //! These tests verify edge cases and unusual formatting behaviors in Excel.
 
 use serde_json::Value;
-use ssfmt::format_default;
+use ssfmt::{format_default, FormatOptions, NumberFormat};
 
 #[derive(Debug)]
 struct OdditiesTestCase {
@@ -76,12 +76,28 @@ fn test_ssf_oddities() {
     println!("\nRunning {} test cases from ssf oddities.json", total);
 
     for (i, test) in test_cases.iter().enumerate() {
-        // Convert value to f64 if it's a number, skip if string
+        // Convert value to f64 if it's a number; format strings as text
         let num_value = match &test.value {
             Value::Number(n) => n.as_f64().unwrap(),
-            Value::String(_) => {
-                // Text formatting not fully implemented yet
-                skipped += 1;
+            Value::String(text) => {
+                let result = NumberFormat::parse(&test.format_code)
+                    .map(|fmt| fmt.format_text(text, &FormatOptions::default()));
+                match result {
+                    Ok(result) if result == test.expected => passed += 1,
+                    _ => {
+                        failed += 1;
+                        if failed <= 20 {
+                            println!(
+                                "FAIL #{}: text={:?}, format='{}', expected='{}', got={:?}",
+                                i + 1,
+                                text,
+                                test.format_code,
+                                test.expected,
+                                result
+                            );
+                        }
+                    }
+                }
                 continue;
             }
             _ => {
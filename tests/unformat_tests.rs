@@ -0,0 +1,170 @@
+use ssfmt::{parse_value, DateSystem, FormatOptions, Locale, NumberFormat, Value};
+
+fn parse<'a>(text: &'a str, code: &str) -> Value<'a> {
+    parse_value(text, code, &FormatOptions::default()).unwrap()
+}
+
+#[test]
+fn test_parse_value_numbers() {
+    assert_eq!(parse("1,234.56", "#,##0.00"), Value::Number(1234.56));
+    assert_eq!(parse("-1,234", "#,##0"), Value::Number(-1234.0));
+    assert_eq!(parse("$42.50", "\"$\"#,##0.00"), Value::Number(42.5));
+    assert_eq!(parse("1.23E+04", "0.00E+00"), Value::Number(12300.0));
+    assert_eq!(parse("7", "General"), Value::Number(7.0));
+}
+
+#[test]
+fn test_parse_value_negative_sections() {
+    let accounting = "_($* #,##0_);_($* (#,##0);_($* \"-\"_);_(@_)";
+    assert_eq!(parse("(500)", accounting), Value::Number(-500.0));
+    assert_eq!(parse("$ (500)", accounting), Value::Number(-500.0));
+    assert_eq!(parse("$ 500", accounting), Value::Number(500.0));
+    assert_eq!(parse("$ -", accounting), Value::Number(0.0));
+
+    // The zero dash of the accounting formats reads back as 0
+    let accounting = "_(* #,##0_);_(* (#,##0);_(* \"-\"_)";
+    let fmt = NumberFormat::parse(accounting).unwrap();
+    let opts = FormatOptions::default();
+    assert_eq!(fmt.parse_value(&fmt.format(0.0, &opts), &opts), Value::Number(0.0));
+    assert_eq!(parse("-", accounting), Value::Number(0.0));
+    assert_eq!(parse("- -", accounting), Value::Text("- -"));
+
+    assert_eq!(parse("500 CR", "0\" DR\";0\" CR\""), Value::Number(-500.0));
+    assert_eq!(parse("500 DR", "0\" DR\";0\" CR\""), Value::Number(500.0));
+}
+
+#[test]
+fn test_parse_value_percent_and_scaling() {
+    assert_eq!(parse("50%", "0%"), Value::Number(0.5));
+    assert_eq!(parse("12.5%", "0.0%"), Value::Number(0.125));
+    assert_eq!(parse("1,235K", "#,##0,\"K\""), Value::Number(1_235_000.0));
+}
+
+#[test]
+fn test_parse_value_fractions() {
+    assert_eq!(parse("1 1/2", "# ?/?"), Value::Number(1.5));
+    assert_eq!(parse("3/4", "# ?/?"), Value::Number(0.75));
+}
+
+#[test]
+fn test_parse_value_dates() {
+    assert_eq!(parse("1/9/26", "m/d/yy"), Value::Number(46031.0));
+    assert_eq!(parse("1-9-2026", "m/d/yy"), Value::Number(46031.0));
+    assert_eq!(parse("2026-01-09", "yyyy-mm-dd"), Value::Number(46031.0));
+    assert_eq!(parse("20260109", "yyyymmdd"), Value::Number(46031.0));
+    assert_eq!(parse("9-Jan-26", "d-mmm-yy"), Value::Number(46031.0));
+    assert_eq!(
+        parse("Friday, January 9, 2026", "dddd, mmmm d, yyyy"),
+        Value::Number(46031.0)
+    );
+    // No February 30th
+    assert_eq!(parse("2/30/26", "m/d/yy"), Value::Text("2/30/26"));
+}
+
+#[test]
+fn test_parse_value_times() {
+    assert_eq!(parse("18:00", "h:mm"), Value::Number(0.75));
+    assert_eq!(parse("6:00 PM", "h:mm AM/PM"), Value::Number(0.75));
+    assert_eq!(
+        parse("12:30 am", "h:mm AM/PM"),
+        Value::Number(30.0 / 1440.0)
+    );
+    assert_eq!(parse("6:00 p. m.", "[$-C0A]h:mm AM/PM"), Value::Number(0.75));
+    assert_eq!(parse("下午6:00", "上午/下午h:mm"), Value::Number(0.75));
+    assert_eq!(parse("36:00:00", "[h]:mm:ss"), Value::Number(1.5));
+    assert_eq!(parse("2 18:00", "[d] hh:mm"), Value::Number(2.75));
+    // Elapsed fields take every digit
+    assert_eq!(parse("2400000000:00", "[h]:mm"), Value::Number(100_000_000.0));
+    assert_eq!(
+        parse("123456789012345678901:00", "[h]:mm"),
+        Value::Text("123456789012345678901:00")
+    );
+    assert_eq!(
+        parse("1/9/26 18:00", "m/d/yy h:mm"),
+        Value::Number(46031.75)
+    );
+}
+
+#[test]
+fn test_parse_value_date_systems_and_locales() {
+    let opts = FormatOptions::builder()
+        .date_system(DateSystem::Date1904)
+        .build();
+    let fmt = NumberFormat::parse("yyyy-mm-dd").unwrap();
+    assert_eq!(fmt.parse_value("1904-01-02", &opts), Value::Number(1.0));
+
+    let opts = FormatOptions::builder()
+        .locale(Locale::from_tag("de-DE").unwrap())
+        .build();
+    let fmt = NumberFormat::parse("#,##0.00").unwrap();
+    assert_eq!(fmt.parse_value("1.234,56", &opts), Value::Number(1234.56));
+    let fmt = NumberFormat::parse("d. mmmm yyyy").unwrap();
+    assert_eq!(
+        fmt.parse_value("9. Januar 2026", &opts),
+        Value::Number(46031.0)
+    );
+}
+
+#[test]
+fn test_parse_value_non_numbers() {
+    assert_eq!(parse("", "0.00"), Value::Empty);
+    assert_eq!(parse("  ", "0.00"), Value::Empty);
+    assert_eq!(parse("TRUE", "0.00"), Value::Bool(true));
+    assert_eq!(parse("false", "General"), Value::Bool(false));
+    assert_eq!(parse("n/a", "0.00"), Value::Text("n/a"));
+    assert_eq!(parse("1e400", "General"), Value::Text("1e400"));
+    assert_eq!(parse("-1E+400", "0.00E+00"), Value::Text("-1E+400"));
+    assert_eq!(parse("hello", "@"), Value::Text("hello"));
+    assert_eq!(parse("1/9", "m/d"), Value::Text("1/9"));
+    assert_eq!(parse("--5", "0"), Value::Text("--5"));
+    assert_eq!(parse("-$-5", "0"), Value::Text("-$-5"));
+    assert_eq!(parse("(-5)", "#,##0;(#,##0)"), Value::Text("(-5)"));
+}
+
+#[test]
+fn test_parse_value_roundtrip() {
+    let opts = FormatOptions::default();
+    let cases = [
+        ("#,##0.00", 1234.5),
+        ("#,##0.00;[Red](#,##0.00)", -98.25),
+        ("0.0%", 0.375),
+        ("\"€\"#,##0", 1500.0),
+        ("yyyy-mm-dd hh:mm:ss", 45000.5),
+        ("0.00E+00", 0.00123),
+    ];
+    for (code, value) in cases {
+        let fmt = NumberFormat::parse(code).unwrap();
+        let text = fmt.format(value, &opts);
+        assert_eq!(
+            fmt.parse_value(&text, &opts),
+            Value::Number(value),
+            "code {} text {}",
+            code,
+            text
+        );
+    }
+}
+
+#[test]
+fn test_parse_value_invalid_format_code() {
+    assert!(parse_value("1", "[Red", &FormatOptions::default()).is_err());
+}
+
+#[test]
+fn test_parse_value_japanese_era() {
+    let code = "[$-411]ggge\"年\"m\"月\"d\"日\"";
+    assert_eq!(parse("令和6年6月15日", code), Value::Number(45458.0));
+    assert_eq!(parse("平成31年4月30日", code), Value::Number(43585.0));
+    assert_eq!(parse("R6.6.15", "[$-411]ge.m.d"), Value::Number(45458.0));
+}
+
+#[test]
+fn test_parse_value_roc_and_buddhist_years() {
+    assert_eq!(
+        parse("中華民國113年6月15日", "[$-404]ggge\"年\"m\"月\"d\"日\""),
+        Value::Number(45458.0)
+    );
+    assert_eq!(parse("113/6/15", "[$-404]e/m/d"), Value::Number(45458.0));
+    assert_eq!(parse("15/6/2567", "[$-41E]d/m/e"), Value::Number(45458.0));
+    assert_eq!(parse("พ.ศ. 2567/6/15", "[$-41E]gg e/m/d"), Value::Number(45458.0));
+}
@@ -0,0 +1,77 @@
+use ssfmt::{format_code_from_id, validate, ParseError, ValidationError};
+
+#[test]
+fn test_builtin_formats_are_valid() {
+    for id in 0..=49 {
+        if let Some(code) = format_code_from_id(id) {
+            assert_eq!(validate(code), Ok(()), "ID {} ({})", id, code);
+        }
+    }
+}
+
+#[test]
+fn test_valid_custom_formats() {
+    for code in [
+        "[Red][<=100]0;[Blue][>100]0.00",
+        "0;-0;\"zero\";\"text: \"@",
+        "@@",
+        "[$€-407]#,##0.00",
+        "[DBNum1][$-804]General",
+        "[h]:mm:ss",
+    ] {
+        assert_eq!(validate(code), Ok(()), "{}", code);
+    }
+}
+
+#[test]
+fn test_text_mixed_with_number() {
+    assert_eq!(
+        validate("0;@0").unwrap_err(),
+        [ValidationError::TextMixedWithNumber { section: 1 }]
+    );
+    assert_eq!(
+        validate("yyyy @").unwrap_err(),
+        [ValidationError::TextMixedWithNumber { section: 0 }]
+    );
+}
+
+#[test]
+fn test_condition_rules() {
+    assert_eq!(
+        validate("[>1]0;[<-1]0;[=0]0").unwrap_err(),
+        [
+            ValidationError::ConditionNotAllowed { section: 2 },
+            ValidationError::TooManyConditions { count: 3 },
+        ]
+    );
+    assert_eq!(
+        validate("0;0;0;[=1]@").unwrap_err(),
+        [ValidationError::ConditionNotAllowed { section: 3 }]
+    );
+}
+
+#[test]
+fn test_structural_errors() {
+    assert_eq!(
+        validate("0;0;0;@;0").unwrap_err(),
+        [ValidationError::TooManySections { span: 8..9 }]
+    );
+    assert_eq!(
+        validate("[Foo]0").unwrap_err(),
+        [ValidationError::UnknownBracket {
+            span: 0..5,
+            text: "Foo".to_string()
+        }]
+    );
+    assert_eq!(
+        validate("").unwrap_err(),
+        [ValidationError::Parse(ParseError::EmptyFormat)]
+    );
+    let errors = validate("0[Red").unwrap_err();
+    assert!(matches!(
+        errors[..],
+        [ValidationError::Parse(
+            ParseError::UnterminatedBracket { .. }
+        )]
+    ));
+}
@@ -23,3 +23,32 @@ fn test_value_from_bool() {
     let v: Value = true.into();
     assert!(matches!(v, Value::Bool(true)));
 }
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_format_zoned_datetime() {
+    use chrono::{FixedOffset, TimeZone, Utc};
+    use ssfmt::{FormatError, FormatOptions, NumberFormat, TimezonePolicy};
+
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let dt = offset.with_ymd_and_hms(2024, 3, 15, 9, 30, 0).unwrap();
+    let value: Value = dt.into();
+    assert!(matches!(value, Value::ZonedDateTime(_)));
+
+    let fmt = NumberFormat::parse("yyyy-mm-dd hh:mm").unwrap();
+    let mut opts = FormatOptions::default();
+    assert_eq!(fmt.format_value(&value, &opts), "2024-03-15 09:30");
+
+    opts.timezone_policy = TimezonePolicy::Utc;
+    assert_eq!(fmt.format_value(&value, &opts), "2024-03-15 07:30");
+    let utc: Value = Utc.with_ymd_and_hms(2024, 3, 15, 7, 30, 0).unwrap().into();
+    assert_eq!(fmt.format_value(&utc, &opts), "2024-03-15 07:30");
+
+    opts.timezone_policy = TimezonePolicy::Error;
+    assert!(matches!(
+        fmt.try_format_value(&value, &opts),
+        Err(FormatError::TypeMismatch { .. })
+    ));
+    assert_eq!(fmt.format_value(&value, &opts), "45366.3125");
+    assert_eq!(fmt.try_format_value(&Value::Number(0.5), &opts).unwrap(), "1900-01-00 12:00");
+}
@@ -0,0 +1,129 @@
+use ssfmt::ast::{Color, Condition, DatePart, FormatPart};
+use ssfmt::visit::{walk_section, FormatVisitor, FormatVisitorMut};
+use ssfmt::{FormatOptions, NumberFormat, Section};
+
+#[derive(Default)]
+struct Collect {
+    sections: Vec<usize>,
+    conditions: Vec<Condition>,
+    colors: Vec<Color>,
+    literals: Vec<(String, bool)>,
+    date_parts: Vec<DatePart>,
+}
+
+impl FormatVisitor for Collect {
+    fn visit_section(&mut self, index: usize, section: &Section) {
+        self.sections.push(index);
+        walk_section(self, section);
+    }
+
+    fn visit_condition(&mut self, condition: &Condition) {
+        self.conditions.push(*condition);
+    }
+
+    fn visit_color(&mut self, color: &Color) {
+        self.colors.push(*color);
+    }
+
+    fn visit_literal(&mut self, text: &str, escaped: bool) {
+        self.literals.push((text.to_string(), escaped));
+    }
+
+    fn visit_date_part(&mut self, part: DatePart) {
+        self.date_parts.push(part);
+    }
+}
+
+#[test]
+fn test_walk_visits_every_node() {
+    let fmt = NumberFormat::parse("[Blue][>=100]0\" pts\";[<0]\\-0;yyyy-mm").unwrap();
+    let mut collect = Collect::default();
+    fmt.walk(&mut collect);
+    assert_eq!(collect.sections, [0, 1, 2]);
+    assert_eq!(
+        collect.conditions,
+        [Condition::GreaterOrEqual(100.0), Condition::LessThan(0.0)]
+    );
+    assert_eq!(collect.colors.len(), 1);
+    assert_eq!(
+        collect.literals,
+        [
+            (" pts".to_string(), false),
+            ("-".to_string(), true),
+            ("-".to_string(), false)
+        ]
+    );
+    assert_eq!(collect.date_parts, [DatePart::Year4, DatePart::Month2]);
+}
+
+/// Drops decimal places, turning `0.00` into `0`.
+struct DropDecimals;
+
+impl FormatVisitorMut for DropDecimals {
+    fn visit_parts_mut(&mut self, parts: &mut Vec<FormatPart>) {
+        if let Some(pos) = parts.iter().position(|p| *p == FormatPart::DecimalPoint) {
+            let end = parts[pos + 1..]
+                .iter()
+                .position(|p| !matches!(p, FormatPart::Digit(_)))
+                .map_or(parts.len(), |n| pos + 1 + n);
+            parts.drain(pos..end);
+        }
+    }
+}
+
+#[test]
+fn test_walk_mut_updates_formatting() {
+    let opts = FormatOptions::default();
+    let mut fmt = NumberFormat::parse("#,##0.00\" kg\";\"-\"0.0").unwrap();
+    fmt.walk_mut(&mut DropDecimals);
+    assert_eq!(fmt.to_format_code(), "#,##0\" kg\";\"-\"0");
+    assert_eq!(fmt.format(1234.56, &opts), "1,235 kg");
+    assert_eq!(fmt.format(-2.5, &opts), "-3");
+    assert_eq!(fmt, NumberFormat::parse("#,##0\" kg\";\"-\"0").unwrap());
+}
+
+#[test]
+fn test_with_currency() {
+    let opts = FormatOptions::default();
+    let accounting =
+        NumberFormat::parse("_(\"$\"* #,##0.00_);_(\"$\"* \\(#,##0.00\\);_(\"$\"* \"-\"??_);_(@_)")
+            .unwrap();
+    let yen = accounting.with_currency("¥");
+    assert_eq!(
+        yen.to_format_code(),
+        "_(\"¥\"* #,##0.00_);_(\"¥\"* \\(#,##0.00\\);_(\"¥\"* \"-\"??_);_(@_)"
+    );
+    assert_eq!(yen.format(-1234.5, &opts), " ¥(1,234.50)");
+
+    let escaped = NumberFormat::parse("\\$#,##0")
+        .unwrap()
+        .with_currency("CHF");
+    assert_eq!(escaped.format(1234.0, &opts), "CHF1,234");
+
+    let tagged = NumberFormat::parse("[$€-407] #,##0.00;[$-409]h:mm").unwrap();
+    assert_eq!(
+        tagged.with_currency("EUR").to_format_code(),
+        "[$EUR-407] #,##0.00;[$-409]h:mm"
+    );
+}
+
+#[test]
+fn test_walk_mut_unsupported_numerals() {
+    use ssfmt::FormatError;
+
+    struct DbNum(u8);
+    impl FormatVisitorMut for DbNum {
+        fn visit_parts_mut(&mut self, parts: &mut Vec<FormatPart>) {
+            parts.insert(0, FormatPart::DbNum(self.0));
+        }
+    }
+
+    let opts = FormatOptions::default();
+    let mut fmt = NumberFormat::parse("#,##0").unwrap();
+    fmt.walk_mut(&mut DbNum(7));
+    assert_eq!(
+        fmt.try_format(1234.0, &opts),
+        Err(FormatError::Unsupported { feature: "[DBNum4]-[DBNum9]" })
+    );
+    assert_eq!(fmt.format(1234.0, &opts), "1234");
+}
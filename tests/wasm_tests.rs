@@ -0,0 +1,22 @@
+#![cfg(feature = "wasm")]
+
+use ssfmt::{wasm, NumberFormat};
+
+#[test]
+fn test_wasm_format() {
+    assert_eq!(wasm::format(1234.5, "#,##0.00").unwrap(), "1,234.50");
+    assert_eq!(wasm::format(45306.0, "yyyy-mm-dd").unwrap(), "2024-01-15");
+}
+
+#[test]
+fn test_wasm_format_with_id() {
+    assert_eq!(wasm::format_with_id(0.5, 9).unwrap(), "50%");
+    assert_eq!(wasm::format_with_id(1234.56, 0).unwrap(), "1234.56");
+}
+
+#[test]
+fn test_wasm_parse_returns_json_ast() {
+    let json = wasm::parse("0.00;[Red]-0.00").unwrap();
+    let fmt: NumberFormat = serde_json::from_str(&json).unwrap();
+    assert_eq!(fmt, NumberFormat::parse("0.00;[Red]-0.00").unwrap());
+}